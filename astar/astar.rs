@@ -18,18 +18,17 @@
 //! the search can report paths rather than just distances.
 //! Labels currently require the `Copy` trait, which is a
 //! bit restrictive, but covers some obvious use cases.
-//! Paths are saved with each state rather than being
-//! reconstructed at the end, which is quite expensive of
-//! memory.
+//! Each state reached records the state it was reached
+//! from, and paths are reconstructed from these at the end.
 //!
 //! [1]: http://en.wikipedia.org/wiki/A*_search_algorithm
 //!
 //! # Examples
 //!
 //! ```rust
-//! # use aoc_astar::*;
+//! # use astar::*;
 //! struct Steps {
-//!     steps: Vec<(usize, Box<Fn(isize) -> isize>)>,
+//!     steps: Vec<(usize, Box<dyn Fn(isize) -> isize>)>,
 //!     goal: isize
 //! }
 //!
@@ -77,53 +76,106 @@
 //! }
 //! ```
 
+mod backtrack;
+pub use backtrack::*;
+
+mod bsearch;
+pub use bsearch::*;
+
+mod grid;
+pub use grid::*;
+
+mod parallel;
+pub use parallel::*;
+
+#[cfg(test)]
+mod testutil;
+
 use std::cmp::*;
-use std::collections::{BTreeSet, BinaryHeap};
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
 
-/// Node with state `S` in A\* search.
-#[derive(Clone, Debug)]
-struct PQElem<S: SearchState> {
-    /// Cost so far.
-    cost: usize,
-    /// Total heuristic cost.
-    fcost: usize,
+use collections::DenseHeap;
+
+/// State reached during a search.
+struct Node<S> {
     /// Actual state.
     state: S,
-    /// Sequence of labels from start to current.
-    path: Option<Vec<S::Label>>,
+    /// Least cost found so far.
+    cost: usize,
+    /// Index of the node this one was reached from, if any.
+    parent: Option<usize>,
+    /// True once the least cost is known.
+    done: bool,
 }
 
-impl<S: SearchState> PartialEq for PQElem<S> {
-    /// From the search point of view, two nodes are
-    /// the same if their heuristic cost and actual
-    /// cost are both the same.
-    fn eq(&self, other: &PQElem<S>) -> bool {
-        other.fcost == self.fcost && other.cost == self.cost
-    }
+/// States reached during a search, numbered in the order
+/// they were first reached so that the queue can hold
+/// numbers rather than states.
+struct Nodes<S> {
+    /// Number of each state.
+    index: BTreeMap<S, usize>,
+    /// Node for each number.
+    nodes: Vec<Node<S>>,
 }
 
-/// It would be nice to just derive this from `PartialEq`,
-/// but Rust derive does the other thing.
-impl<S: SearchState> Eq for PQElem<S> {}
+impl<S> Nodes<S>
+where
+    S: Clone + Ord + SearchState,
+{
+    fn new() -> Self {
+        Nodes {
+            index: BTreeMap::new(),
+            nodes: Vec::new(),
+        }
+    }
 
-/// It would be nice to just derive this from `Ord`,
-/// but Rust derive does the other thing.
-impl<S: SearchState> PartialOrd for PQElem<S> {
-    fn partial_cmp(&self, other: &PQElem<S>) -> Option<Ordering> {
-        Some(self.cmp(other))
+    /// Record that `state` can be reached at `cost` from
+    /// the node numbered `parent`. Returns the number of the
+    /// state if this is the first or a cheaper way to reach
+    /// it, and `None` if its least cost is already known or
+    /// the new way is no cheaper.
+    fn relax(
+        &mut self,
+        state: S,
+        cost: usize,
+        parent: Option<usize>,
+    ) -> Option<usize> {
+        match self.index.entry(state) {
+            Entry::Occupied(e) => {
+                let i = *e.get();
+                let node = &mut self.nodes[i];
+                if node.done || node.cost <= cost {
+                    return None;
+                }
+                node.cost = cost;
+                node.parent = parent;
+                Some(i)
+            }
+            Entry::Vacant(e) => {
+                let i = self.nodes.len();
+                self.nodes.push(Node {
+                    state: e.key().clone(),
+                    cost,
+                    parent,
+                    done: false,
+                });
+                e.insert(i);
+                Some(i)
+            }
+        }
     }
-}
 
-impl<S: SearchState> Ord for PQElem<S> {
-    /// A node is better than another if its heuristic
-    /// cost is smaller. Ties are broken by preferring
-    /// nodes with larger confirmed cost, since these
-    /// are farther along the path to a solution.
-    fn cmp(&self, other: &PQElem<S>) -> Ordering {
-        match other.fcost.cmp(&self.fcost) {
-            Ordering::Equal => self.cost.cmp(&other.cost),
-            c => c,
+    /// The labels of the states on the path from the start
+    /// to the node numbered `i`, inclusive.
+    fn path_to(&self, mut i: usize) -> Vec<S::Label> {
+        let mut path = vec![self.nodes[i].state.label()];
+        while let Some(parent) = self.nodes[i].parent {
+            path.push(self.nodes[parent].state.label());
+            i = parent;
         }
+        path.reverse();
+        path
     }
 }
 
@@ -204,48 +256,346 @@ pub fn a_star<S>(
 where
     S: Clone + PartialEq + Eq + PartialOrd + Ord + SearchState,
 {
-    let mut stop_list = BTreeSet::new();
-    let mut pq = BinaryHeap::new();
-    pq.push(PQElem {
-        state: start.clone(),
-        cost: 0,
-        fcost: start.hcost(global),
-        path: if save_path { Some(Vec::new()) } else { None },
-    });
-    loop {
-        match pq.pop() {
-            Some(PQElem {
-                cost, state, path, ..
-            }) => {
-                let next_path = match path {
-                    None => None,
-                    Some(ref labels) => {
-                        let mut p = labels.clone();
-                        p.push(state.label());
-                        Some(p)
-                    }
-                };
-                if state.is_goal(global) {
-                    return Some((cost, next_path));
-                };
-                if stop_list.insert(state.clone()) {
-                    for nb in state.neighbors(global) {
-                        let (g_cost, ref next_state) = nb;
-                        let h = next_state.hcost(global);
-                        let g = cost + g_cost;
-                        let neighbor = PQElem {
-                            fcost: g + h,
-                            cost: g,
-                            state: (**next_state).clone(),
-                            path: next_path.clone(),
-                        };
-                        pq.push(neighbor);
+    a_star_bounded(global, start, save_path, None)
+}
+
+/// Generic A\* search as with `a_star()`, but abandoning
+/// any state whose heuristic total cost exceeds `max_cost`
+/// when a bound is given. Since the heuristic is
+/// admissible, no goal is lost that could be reached
+/// within the bound, and the search returns `None` quickly
+/// when there is none. This is handy when binary-searching
+/// over some problem parameter.
+pub fn a_star_bounded<S>(
+    global: &S::Global,
+    start: &S,
+    save_path: bool,
+    max_cost: Option<usize>,
+) -> Option<(usize, Option<Vec<S::Label>>)>
+where
+    S: Clone + PartialEq + Eq + PartialOrd + Ord + SearchState,
+{
+    let within = |fcost: usize| match max_cost {
+        Some(m) => fcost <= m,
+        None => true,
+    };
+    // Queue priorities are heuristic total cost, with ties
+    // broken by preferring states with larger confirmed
+    // cost, since these are farther along the path to a
+    // solution. Each state is in the queue at most once.
+    let mut nodes = Nodes::new();
+    let mut pq = DenseHeap::new();
+    let fcost = start.hcost(global);
+    if !within(fcost) {
+        return None;
+    }
+    let i = nodes.relax(start.clone(), 0, None).unwrap();
+    pq.push_or_decrease(i, (fcost, Reverse(0)));
+    while let Some((i, (_, Reverse(cost)))) = pq.pop() {
+        nodes.nodes[i].done = true;
+        let state = nodes.nodes[i].state.clone();
+        if state.is_goal(global) {
+            return Some((cost, save_path.then(|| nodes.path_to(i))));
+        };
+        for (g_cost, next_state) in state.neighbors(global) {
+            let g = cost + g_cost;
+            let h = next_state.hcost(global);
+            if !within(g + h) {
+                continue;
+            }
+            if let Some(j) = nodes.relax(*next_state, g, Some(i)) {
+                pq.push_or_decrease(j, (g + h, Reverse(g)));
+            }
+        }
+    }
+    None
+}
+
+/// Resumable [Dijkstra's Algorithm][1] search from a fixed
+/// start state. The search can be queried repeatedly with
+/// different goals: settled states and the frontier are
+/// retained between queries, so each query picks up where
+/// the last one stopped rather than starting over.
+///
+/// Since goals vary between queries, `SearchState::hcost()`
+/// and `SearchState::is_goal()` are not used here.
+///
+/// [1]: https://en.wikipedia.org/wiki/Dijkstra's_algorithm
+///
+/// # Examples
+///
+/// ```rust
+/// # use astar::*;
+/// #[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+/// struct Count(u8);
+///
+/// impl SearchState for Count {
+///     type Global = ();
+///     type Label = u8;
+///     fn label(&self) -> u8 {
+///         self.0
+///     }
+///     fn is_goal(&self, _: &()) -> bool {
+///         false
+///     }
+///     fn neighbors(&self, _: &()) -> Vec<(usize, Box<Count>)> {
+///         vec![
+///             (1, Box::new(Count(self.0.saturating_add(1)))),
+///             (3, Box::new(Count(self.0.saturating_mul(2)))),
+///         ]
+///     }
+/// }
+///
+/// let mut search = Search::new(&(), &Count(1));
+/// assert_eq!(search.find(|s| s.0 == 4), Some((3, vec![1, 2, 3, 4])));
+/// assert_eq!(search.find(|s| s.0 == 8).map(|(c, _)| c), Some(6));
+/// assert_eq!(search.find_bounded(|s| s.0 == 100, Some(10)), None);
+/// assert_eq!(search.settled_cost(&Count(2)), Some(1));
+/// ```
+pub struct Search<'a, S: SearchState> {
+    /// Global information for the search.
+    global: &'a S::Global,
+    /// States reached so far. Settled states are done.
+    nodes: Nodes<S>,
+    /// Numbers of the states not yet settled, by cost.
+    frontier: DenseHeap<usize>,
+}
+
+impl<'a, S> Search<'a, S>
+where
+    S: Clone + Ord + SearchState,
+{
+    /// Set up a search from the given start state using
+    /// the supplied global data. No searching is done until
+    /// the first query.
+    pub fn new(global: &'a S::Global, start: &S) -> Self {
+        let mut nodes = Nodes::new();
+        let mut frontier = DenseHeap::new();
+        let i = nodes.relax(start.clone(), 0, None).unwrap();
+        frontier.push_or_decrease(i, 0);
+        Search {
+            global,
+            nodes,
+            frontier,
+        }
+    }
+
+    /// Find a least-cost path to some state satisfying
+    /// `goal`. The return value is the cost and the path
+    /// (sequence of labels from start to goal inclusive) if
+    /// a path is found.
+    pub fn find<G>(&mut self, goal: G) -> Option<(usize, Vec<S::Label>)>
+    where
+        G: FnMut(&S) -> bool,
+    {
+        self.find_bounded(goal, None)
+    }
+
+    /// Find a least-cost path as with `Search::find()`, but
+    /// give up once the cost exceeds `max_cost` when a bound
+    /// is given. Giving up leaves the search state intact,
+    /// so a later query with a larger bound resumes from
+    /// here.
+    pub fn find_bounded<G>(
+        &mut self,
+        mut goal: G,
+        max_cost: Option<usize>,
+    ) -> Option<(usize, Vec<S::Label>)>
+    where
+        G: FnMut(&S) -> bool,
+    {
+        let within = |cost: usize| match max_cost {
+            Some(m) => cost <= m,
+            None => true,
+        };
+
+        // Every settled state is at least as cheap as anything
+        // on the frontier, so a settled goal is the answer.
+        let best = self
+            .nodes
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.done && goal(&node.state))
+            .min_by_key(|(_, node)| node.cost)
+            .map(|(i, node)| (node.cost, i));
+        if let Some((cost, i)) = best {
+            if !within(cost) {
+                return None;
+            }
+            return Some((cost, self.nodes.path_to(i)));
+        }
+
+        while let Some((_, &cost)) = self.frontier.peek() {
+            if !within(cost) {
+                return None;
+            }
+            let (i, cost) = self.frontier.pop().unwrap();
+            self.nodes.nodes[i].done = true;
+            let state = self.nodes.nodes[i].state.clone();
+            for (step_cost, next_state) in state.neighbors(self.global) {
+                let next_cost = cost + step_cost;
+                let next = self.nodes.relax(*next_state, next_cost, Some(i));
+                if let Some(j) = next {
+                    self.frontier.push_or_decrease(j, next_cost);
+                }
+            }
+            if goal(&state) {
+                return Some((cost, self.nodes.path_to(i)));
+            }
+        }
+        None
+    }
+
+    /// Least cost of reaching the given state, if it has
+    /// been settled by some previous query.
+    pub fn settled_cost(&self, state: &S) -> Option<usize> {
+        let &i = self.nodes.index.get(state)?;
+        let node = &self.nodes.nodes[i];
+        node.done.then_some(node.cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::testutil::Rng;
+
+    /// Random directed graph with a goal node for fresh
+    /// searches.
+    struct Graph {
+        adj: Vec<Vec<(usize, usize)>>,
+        goal: usize,
+    }
+
+    #[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+    struct Node(usize);
+
+    impl SearchState for Node {
+        type Global = Graph;
+        type Label = usize;
+        fn label(&self) -> usize {
+            self.0
+        }
+        fn is_goal(&self, global: &Graph) -> bool {
+            self.0 == global.goal
+        }
+        fn neighbors(&self, global: &Graph) -> Vec<(usize, Box<Node>)> {
+            global.adj[self.0]
+                .iter()
+                .map(|&(cost, n)| (cost, Box::new(Node(n))))
+                .collect()
+        }
+    }
+
+    fn random_graph(rng: &mut Rng, nnodes: usize, nedges: usize) -> Graph {
+        let mut adj = vec![Vec::new(); nnodes];
+        for _ in 0..nedges {
+            let from = rng.below(nnodes);
+            let to = rng.below(nnodes);
+            let cost = rng.below(10);
+            adj[from].push((cost, to));
+        }
+        Graph { adj, goal: 0 }
+    }
+
+    fn path_cost(graph: &Graph, path: &[usize]) -> usize {
+        path.windows(2)
+            .map(|w| {
+                graph.adj[w[0]]
+                    .iter()
+                    .filter(|&&(_, n)| n == w[1])
+                    .map(|&(c, _)| c)
+                    .min()
+                    .expect("path uses missing edge")
+            })
+            .sum()
+    }
+
+    /// Least cost from node 0 to each node, by Bellman-Ford.
+    fn bellman_ford(graph: &Graph) -> Vec<Option<usize>> {
+        let mut dist = vec![None; graph.adj.len()];
+        dist[0] = Some(0);
+        for _ in 0..graph.adj.len() {
+            for (from, edges) in graph.adj.iter().enumerate() {
+                let Some(d) = dist[from] else { continue };
+                for &(cost, to) in edges {
+                    if dist[to].is_none_or(|old| d + cost < old) {
+                        dist[to] = Some(d + cost);
                     }
-                } else {
-                    continue;
-                };
+                }
+            }
+        }
+        dist
+    }
+
+    #[test]
+    fn matches_bellman_ford() {
+        let mut rng = Rng(0x0123_4567_89ab_cdef);
+        for _ in 0..50 {
+            let mut graph = random_graph(&mut rng, 30, 120);
+            let dist = bellman_ford(&graph);
+            for (goal, &best) in dist.iter().enumerate() {
+                graph.goal = goal;
+                let found = a_star(&graph, &Node(0), true);
+                if let Some((cost, Some(ref path))) = found {
+                    assert_eq!(path.first(), Some(&0));
+                    assert_eq!(path.last(), Some(&goal));
+                    assert_eq!(cost, path_cost(&graph, path));
+                }
+                assert_eq!(found.map(|(c, _)| c), best);
+            }
+        }
+    }
+
+    #[test]
+    fn resumed_matches_fresh() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..50 {
+            let mut graph = random_graph(&mut rng, 40, 100);
+            let mut search = Search::new(&graph, &Node(0));
+            let mut answers = Vec::new();
+            for _ in 0..20 {
+                let goal = rng.below(40);
+                let resumed = search.find(|n| n.0 == goal);
+                if let Some((cost, ref path)) = resumed {
+                    assert_eq!(path.first(), Some(&0));
+                    assert_eq!(path.last(), Some(&goal));
+                    assert_eq!(cost, path_cost(&graph, path));
+                }
+                answers.push((goal, resumed.map(|(c, _)| c)));
+            }
+            for (goal, resumed) in answers {
+                graph.goal = goal;
+                let fresh = a_star(&graph, &Node(0), false);
+                assert_eq!(resumed, fresh.map(|(c, _)| c));
+            }
+        }
+    }
+
+    #[test]
+    fn bounded_matches_unbounded() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..50 {
+            let mut graph = random_graph(&mut rng, 30, 60);
+            graph.goal = rng.below(30);
+            let fresh = a_star(&graph, &Node(0), false).map(|(c, _)| c);
+            for bound in 0..20 {
+                let bounded =
+                    a_star_bounded(&graph, &Node(0), false, Some(bound))
+                        .map(|(c, _)| c);
+                assert_eq!(bounded, fresh.filter(|&c| c <= bound));
+
+                let mut search = Search::new(&graph, &Node(0));
+                let goal = graph.goal;
+                let first =
+                    search.find_bounded(|n| n.0 == goal, Some(bound));
+                let first = first.map(|(c, _)| c);
+                assert_eq!(first, fresh.filter(|&c| c <= bound));
+                let resumed = search.find(|n| n.0 == goal);
+                assert_eq!(resumed.map(|(c, _)| c), fresh);
             }
-            None => return None,
         }
     }
 }
@@ -0,0 +1,369 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Backtracking search for constraint problems.
+//!
+//! To use this, implement the `Backtrack` trait for the
+//! state of a partly-solved problem, then call
+//! `Backtracker::solve()`. The state chooses which variable
+//! to assign next and which values to try, and propagates
+//! the consequences of an assignment itself; the
+//! `Backtracker` does the trying, undoing and bookkeeping.
+//!
+//! Choosing the most-constrained variable (the one with the
+//! fewest candidates) is usually by far the best strategy.
+//!
+//! # Examples
+//!
+//! Three-color the map of a wheel: a hub touching five
+//! regions in a ring.
+//!
+//! ```
+//! # use astar::*;
+//! struct Coloring {
+//!     edges: Vec<(usize, usize)>,
+//!     colors: Vec<Option<u8>>,
+//! }
+//!
+//! impl Backtrack for Coloring {
+//!     type Var = usize;
+//!     type Value = u8;
+//!
+//!     fn choose_variable(&self) -> Option<usize> {
+//!         self.colors.iter().position(Option::is_none)
+//!     }
+//!
+//!     fn candidates(&self, &v: &usize) -> Vec<u8> {
+//!         let other = |&(a, b): &(usize, usize)| match () {
+//!             _ if a == v => Some(b),
+//!             _ if b == v => Some(a),
+//!             _ => None,
+//!         };
+//!         (0..3)
+//!             .filter(|&c| {
+//!                 let mut touching = self.edges.iter().filter_map(other);
+//!                 touching.all(|r| self.colors[r] != Some(c))
+//!             })
+//!             .collect()
+//!     }
+//!
+//!     fn assign(&mut self, &v: &usize, &c: &u8) -> bool {
+//!         self.colors[v] = Some(c);
+//!         true
+//!     }
+//!
+//!     fn unassign(&mut self, &v: &usize, _: &u8) {
+//!         self.colors[v] = None;
+//!     }
+//! }
+//!
+//! let mut edges: Vec<(usize, usize)> = (1..=5).map(|r| (0, r)).collect();
+//! edges.extend((1..=5).map(|r| (r, r % 5 + 1)));
+//! let mut wheel = Coloring { edges, colors: vec![None; 6] };
+//! let mut search = Backtracker::new();
+//! // An odd ring needs three colors, leaving none for the hub.
+//! assert_eq!(search.solve(&mut wheel), Outcome::Unsolvable);
+//! assert!(search.stats().backtracks > 0);
+//! ```
+
+/// The state of a partly-solved constraint problem, for
+/// `Backtracker::solve()`.
+pub trait Backtrack {
+    /// A variable of the problem.
+    type Var;
+    /// A value a variable can take.
+    type Value;
+
+    /// The variable to assign next, or `None` if every
+    /// variable is assigned and the problem is solved.
+    fn choose_variable(&self) -> Option<Self::Var>;
+
+    /// The values to try for `var`, in order.
+    fn candidates(&self, var: &Self::Var) -> Vec<Self::Value>;
+
+    /// Assign `value` to `var`, along with anything that
+    /// follows from it. Returns false if the assignment
+    /// leads to a contradiction. Either way, it will be
+    /// undone by a call to `unassign()` with the same
+    /// arguments before any other variable is assigned
+    /// again.
+    fn assign(&mut self, var: &Self::Var, value: &Self::Value) -> bool;
+
+    /// Undo `assign()` of `value` to `var`, and everything
+    /// it did.
+    fn unassign(&mut self, var: &Self::Var, value: &Self::Value);
+}
+
+/// Result of `Backtracker::solve()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The problem is solved, and the state is left holding
+    /// the solution.
+    Solved,
+    /// The problem has no solution.
+    Unsolvable,
+    /// The node budget ran out first.
+    OutOfBudget,
+}
+
+/// Counts of the work done by a `Backtracker`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BacktrackStats {
+    /// Number of assignments tried.
+    pub nodes: u64,
+    /// Number of assignments undone.
+    pub backtracks: u64,
+    /// Greatest number of variables assigned at once.
+    pub max_depth: usize,
+}
+
+/// Driver for backtracking search over a `Backtrack` state.
+#[derive(Debug, Clone, Default)]
+pub struct Backtracker {
+    budget: Option<u64>,
+    stats: BacktrackStats,
+}
+
+impl Backtracker {
+    /// Make a driver with no node budget.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make a driver that gives up after trying `budget`
+    /// assignments.
+    pub fn with_budget(budget: u64) -> Self {
+        Backtracker {
+            budget: Some(budget),
+            stats: BacktrackStats::default(),
+        }
+    }
+
+    /// Statistics for the searches run so far.
+    pub fn stats(&self) -> BacktrackStats {
+        self.stats
+    }
+
+    /// Search for a complete assignment of `state`'s
+    /// variables. If one is found the state is left holding
+    /// it; otherwise every assignment is undone.
+    pub fn solve<B: Backtrack>(&mut self, state: &mut B) -> Outcome {
+        self.search(state, 0)
+    }
+
+    /// Search from `state`, which has `depth` variables
+    /// assigned.
+    fn search<B>(&mut self, state: &mut B, depth: usize) -> Outcome
+    where
+        B: Backtrack,
+    {
+        self.stats.max_depth = self.stats.max_depth.max(depth);
+        let var = match state.choose_variable() {
+            Some(var) => var,
+            None => return Outcome::Solved,
+        };
+        for value in state.candidates(&var) {
+            if self.budget.is_some_and(|b| self.stats.nodes >= b) {
+                return Outcome::OutOfBudget;
+            }
+            self.stats.nodes += 1;
+            if state.assign(&var, &value) {
+                match self.search(state, depth + 1) {
+                    Outcome::Unsolvable => (),
+                    outcome @ Outcome::Solved => return outcome,
+                    outcome @ Outcome::OutOfBudget => {
+                        state.unassign(&var, &value);
+                        return outcome;
+                    }
+                }
+            }
+            state.unassign(&var, &value);
+            self.stats.backtracks += 1;
+        }
+        Outcome::Unsolvable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// A Sudoku grid in row-major order, with 0 for an empty
+    /// cell.
+    struct Sudoku {
+        cells: [u8; 81],
+    }
+
+    impl Sudoku {
+        fn parse(rows: &[&str]) -> Self {
+            let mut cells = [0; 81];
+            for (r, row) in rows.iter().enumerate() {
+                for (c, ch) in row.chars().enumerate() {
+                    cells[9 * r + c] = ch.to_digit(10).unwrap_or(0) as u8;
+                }
+            }
+            Sudoku { cells }
+        }
+
+        fn peers(i: usize) -> impl Iterator<Item = usize> {
+            let (r, c) = (i / 9, i % 9);
+            let (br, bc) = (r / 3 * 3, c / 3 * 3);
+            (0..81).filter(move |&j| {
+                let (s, t) = (j / 9, j % 9);
+                let same_box = (s / 3 * 3, t / 3 * 3) == (br, bc);
+                j != i && (s == r || t == c || same_box)
+            })
+        }
+
+        fn allowed(&self, i: usize) -> u16 {
+            Self::peers(i).fold(0x3fe, |m, j| m & !(1 << self.cells[j]))
+        }
+    }
+
+    impl Backtrack for Sudoku {
+        type Var = usize;
+        type Value = u8;
+
+        fn choose_variable(&self) -> Option<usize> {
+            (0..81)
+                .filter(|&i| self.cells[i] == 0)
+                .min_by_key(|&i| self.allowed(i).count_ones())
+        }
+
+        fn candidates(&self, &i: &usize) -> Vec<u8> {
+            let allowed = self.allowed(i);
+            (1..=9).filter(|d| allowed >> d & 1 == 1).collect()
+        }
+
+        fn assign(&mut self, &i: &usize, &d: &u8) -> bool {
+            self.cells[i] = d;
+            // Fail early if this leaves a peer with no
+            // candidates.
+            Self::peers(i).all(|j| self.cells[j] != 0 || self.allowed(j) != 0)
+        }
+
+        fn unassign(&mut self, &i: &usize, _: &u8) {
+            self.cells[i] = 0;
+        }
+    }
+
+    #[test]
+    fn sudoku() {
+        let mut puzzle = Sudoku::parse(&[
+            "53..7....",
+            "6..195...",
+            ".98....6.",
+            "8...6...3",
+            "4..8.3..1",
+            "7...2...6",
+            ".6....28.",
+            "...419..5",
+            "....8..79",
+        ]);
+        let solution = Sudoku::parse(&[
+            "534678912",
+            "672195348",
+            "198342567",
+            "859761423",
+            "426853791",
+            "713924856",
+            "961537284",
+            "287419635",
+            "345286179",
+        ]);
+        let mut search = Backtracker::with_budget(10);
+        let before = puzzle.cells;
+        assert_eq!(search.solve(&mut puzzle), Outcome::OutOfBudget);
+        assert_eq!(puzzle.cells, before);
+
+        let mut search = Backtracker::new();
+        assert_eq!(search.solve(&mut puzzle), Outcome::Solved);
+        assert_eq!(puzzle.cells, solution.cells);
+        let stats = search.stats();
+        assert_eq!(stats.max_depth, 51);
+        assert!(stats.nodes >= 51);
+    }
+
+    /// Assignment of ticket fields to positions.
+    struct Fields {
+        /// Positions each field could be at.
+        valid: Vec<Vec<bool>>,
+        /// Position of each field, if assigned.
+        position: Vec<Option<usize>>,
+    }
+
+    impl Fields {
+        fn free(&self, field: usize) -> Vec<usize> {
+            (0..self.valid[field].len())
+                .filter(|&p| self.valid[field][p])
+                .filter(|&p| !self.position.contains(&Some(p)))
+                .collect()
+        }
+    }
+
+    impl Backtrack for Fields {
+        type Var = usize;
+        type Value = usize;
+
+        fn choose_variable(&self) -> Option<usize> {
+            (0..self.position.len())
+                .filter(|&f| self.position[f].is_none())
+                .min_by_key(|&f| self.free(f).len())
+        }
+
+        fn candidates(&self, &field: &usize) -> Vec<usize> {
+            self.free(field)
+        }
+
+        fn assign(&mut self, &field: &usize, &p: &usize) -> bool {
+            self.position[field] = Some(p);
+            true
+        }
+
+        fn unassign(&mut self, &field: &usize, _: &usize) {
+            self.position[field] = None;
+        }
+    }
+
+    #[test]
+    fn ticket_fields() {
+        // 2020 day 16 part 2 example.
+        let rules = [
+            ("class", [(0, 1), (4, 19)]),
+            ("row", [(0, 5), (8, 19)]),
+            ("seat", [(0, 13), (16, 19)]),
+        ];
+        let nearby = [[3, 9, 18], [15, 1, 5], [5, 14, 9]];
+        let valid = rules
+            .iter()
+            .map(|(_, ranges)| {
+                (0..3)
+                    .map(|p| {
+                        nearby.iter().all(|ticket| {
+                            ranges
+                                .iter()
+                                .any(|&(lo, hi)| (lo..=hi).contains(&ticket[p]))
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut fields = Fields {
+            valid,
+            position: vec![None; 3],
+        };
+        let mut search = Backtracker::new();
+        assert_eq!(search.solve(&mut fields), Outcome::Solved);
+        let mine = [11, 12, 13];
+        let values: Vec<(&str, u32)> = rules
+            .iter()
+            .zip(&fields.position)
+            .map(|((name, _), p)| (*name, mine[p.unwrap()]))
+            .collect();
+        assert_eq!(values, [("class", 12), ("row", 11), ("seat", 13)]);
+        assert_eq!(search.stats().backtracks, 0);
+    }
+}
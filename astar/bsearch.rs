@@ -0,0 +1,188 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Binary search over an answer space.
+//!
+//! Many puzzles ask for the least value for which something
+//! works: the least time, the least capacity, the first
+//! fallen byte that blocks a path. When "works" is
+//! monotone — false up to some value and true from then on
+//! — the answer can be found by binary search, and getting
+//! the bounds right is the hard part.
+//!
+//! In debug builds, these all check the predicate at the
+//! ends of the range searched, to catch some predicates that
+//! are not monotone.
+//!
+//! # Examples
+//!
+//! ```
+//! # use astar::*;
+//! // Integer square root: one less than the first number
+//! // whose square is too big.
+//! let n = 1_000_000_007u64;
+//! let root = partition_point_u64(0, n + 1, |x| x * x > n) - 1;
+//! assert_eq!(root, 31622);
+//! assert_eq!(binary_search_max(0, n + 1, |x| x * x <= n), Some(root));
+//! assert_eq!(search_unbounded(0, |x| x * x > n), Some(root + 1));
+//! ```
+
+/// The first value in `lo..hi` for which `pred` is true,
+/// or `hi` if there is none. `pred` must be monotone over
+/// the range: false, then true. Returns `lo` if the range is
+/// empty.
+///
+/// # Panics
+///
+/// In debug builds, panics if `pred` is true at `lo` but
+/// the value returned is later, or false at `hi - 1` but the
+/// value returned is earlier: a sure sign that `pred` is not
+/// monotone.
+pub fn partition_point_u64<F>(lo: u64, hi: u64, mut pred: F) -> u64
+where
+    F: FnMut(u64) -> bool,
+{
+    if lo >= hi {
+        return lo;
+    }
+    let (mut lo, mut hi) = (lo, hi);
+    let (start, end) = (lo, hi);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    // The search itself only looks at a few values, so
+    // check that the ends of the range agree with it.
+    if cfg!(debug_assertions) {
+        assert!(
+            lo == start || !pred(start),
+            "predicate not monotone: true at {} but first found at {}",
+            start,
+            lo,
+        );
+        assert!(
+            lo == end || pred(end - 1),
+            "predicate not monotone: false at {} but first found at {}",
+            end - 1,
+            lo,
+        );
+    }
+    lo
+}
+
+/// The last value in `lo..hi` for which `pred` is true, or
+/// `None` if there is none. `pred` must be monotone over
+/// the range: true, then false.
+///
+/// # Panics
+///
+/// In debug builds, panics if `pred` is found not to be
+/// monotone, as for `partition_point_u64()`.
+pub fn binary_search_max<F>(lo: u64, hi: u64, mut pred: F) -> Option<u64>
+where
+    F: FnMut(u64) -> bool,
+{
+    let first_false = partition_point_u64(lo, hi, |x| !pred(x));
+    (first_false > lo).then(|| first_false - 1)
+}
+
+/// The first value at least `lo` for which `pred` is true,
+/// when no upper bound is known, or `None` if `pred` is
+/// false all the way to `u64::MAX`. `pred` must be monotone:
+/// false, then true.
+///
+/// Steps of doubling size find a value for which `pred` is
+/// true, then binary search finds the first one; so `pred`
+/// is called about twice the log of the distance from `lo`
+/// to the answer.
+///
+/// # Panics
+///
+/// In debug builds, panics if `pred` is found not to be
+/// monotone, as for `partition_point_u64()`.
+pub fn search_unbounded<F>(lo: u64, mut pred: F) -> Option<u64>
+where
+    F: FnMut(u64) -> bool,
+{
+    let mut below = lo;
+    let mut step = 1u64;
+    loop {
+        let probe = below.saturating_add(step - 1);
+        if pred(probe) {
+            return Some(partition_point_u64(below, probe, pred));
+        }
+        if probe == u64::MAX {
+            return None;
+        }
+        below = probe + 1;
+        step = step.saturating_mul(2);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn partition_edges() {
+        // Empty ranges.
+        assert_eq!(partition_point_u64(5, 5, |_| true), 5);
+        assert_eq!(partition_point_u64(7, 3, |_| true), 7);
+        assert_eq!(binary_search_max(5, 5, |_| true), None);
+        assert_eq!(binary_search_max(0, 0, |_| true), None);
+
+        for (lo, hi) in [(0, 1), (0, 2), (3, 10), (0, u64::MAX)] {
+            // Flips at lo: always true.
+            assert_eq!(partition_point_u64(lo, hi, |_| true), lo);
+            assert_eq!(binary_search_max(lo, hi, |_| false), None);
+            // Flips at hi - 1.
+            let last = hi - 1;
+            assert_eq!(partition_point_u64(lo, hi, |x| x >= last), last);
+            let max = binary_search_max(lo, hi, |x| x < last);
+            assert_eq!(max, (last > lo).then(|| last - 1));
+            // Never flips.
+            assert_eq!(partition_point_u64(lo, hi, |_| false), hi);
+            assert_eq!(binary_search_max(lo, hi, |_| true), Some(last));
+        }
+
+        // Every flip point in a small range.
+        for flip in 10..=20 {
+            assert_eq!(partition_point_u64(10, 20, |x| x >= flip), flip);
+            let max = binary_search_max(10, 20, |x| x < flip);
+            assert_eq!(max, (flip > 10).then(|| flip - 1));
+        }
+    }
+
+    #[test]
+    fn unbounded() {
+        for answer in [0, 1, 2, 3, 4, 5, 1000, 1 << 40, u64::MAX] {
+            let mut calls = 0;
+            let found = search_unbounded(0, |x| {
+                calls += 1;
+                x >= answer
+            });
+            assert_eq!(found, Some(answer));
+            assert!(calls <= 2 * 64 + 4, "{} calls", calls);
+        }
+        assert_eq!(search_unbounded(17, |x| x >= 5), Some(17));
+        assert_eq!(search_unbounded(17, |x| x >= 18), Some(18));
+        assert_eq!(search_unbounded(u64::MAX, |_| true), Some(u64::MAX));
+        assert_eq!(search_unbounded(0, |_| false), None);
+        assert_eq!(search_unbounded(u64::MAX - 3, |_| false), None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "not monotone")]
+    fn not_monotone() {
+        // False only at 5: the search lands just past it.
+        partition_point_u64(0, 10, |x| x != 5);
+    }
+}
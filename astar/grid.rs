@@ -0,0 +1,435 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Least-cost paths on a grid of cell costs.
+//!
+//! Many searches are just "move 4-connected on this grid,
+//! paying the cost of each cell entered." The searches here
+//! keep their bookkeeping in flat vectors indexed by cell
+//! rather than in maps of states, which is much faster than
+//! going through `SearchState`.
+//!
+//! # Examples
+//!
+//! ```
+//! # use astar::*;
+//! # use geom::Grid;
+//! let grid = Grid::new(2, 3, vec![1, 9, 1, 1, 1, 1]);
+//! let (cost, path) = grid_shortest_path(&grid, (0, 0), (0, 2)).unwrap();
+//! assert_eq!(cost, 4);
+//! assert_eq!(path, vec![(0, 0), (1, 0), (1, 1), (1, 2), (0, 2)]);
+//! assert_eq!(grid_shortest_path(&grid, (2, 0), (0, 2)), None);
+//! ```
+
+use collections::DenseHeap;
+use geom::{Dirn, Grid, Rot, FACINGS};
+
+/// Grid location as `(row, col)`.
+type Loc = (usize, usize);
+
+/// Moves allowed from a grid search state by a movement
+/// constraint; see `grid_shortest_path_constrained()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Moves {
+    /// Keep moving in the current direction.
+    pub straight: bool,
+    /// Turn 90° in either direction and move.
+    pub turn: bool,
+    /// Stop here, if this is the goal.
+    pub stop: bool,
+}
+
+impl Moves {
+    /// No constraint on movement.
+    pub const ANY: Moves = Moves {
+        straight: true,
+        turn: true,
+        stop: true,
+    };
+}
+
+/// Find a least-cost 4-connected path from `start` to
+/// `goal`, where the cost of a path is the sum of the costs
+/// of the cells entered (not including `start`). The return
+/// value is the cost and the path from `start` to `goal`
+/// inclusive, if a path is found. There is no path if
+/// `start` or `goal` is off the grid.
+pub fn grid_shortest_path(
+    grid: &Grid<u32>,
+    start: Loc,
+    goal: Loc,
+) -> Option<(u32, Vec<Loc>)> {
+    let cols = grid.cols();
+    let clip = grid.grid_box();
+    if !clip.contains(start) || !clip.contains(goal) {
+        return None;
+    }
+    let index = |(r, c): Loc| r * cols + c;
+    let ncells = grid.rows() * cols;
+
+    let mut dist: Vec<Option<u32>> = vec![None; ncells];
+    let mut prev: Vec<Option<usize>> = vec![None; ncells];
+    let mut pq = DenseHeap::with_keys(ncells);
+    dist[index(start)] = Some(0);
+    pq.push_or_decrease(index(start), 0);
+    while let Some((i, cost)) = pq.pop() {
+        let loc = (i / cols, i % cols);
+        if loc == goal {
+            let mut path = vec![loc];
+            let mut cur = i;
+            while let Some(p) = prev[cur] {
+                path.push((p / cols, p % cols));
+                cur = p;
+            }
+            path.reverse();
+            return Some((cost, path));
+        }
+        for dirn in FACINGS {
+            let next = match clip.clip(loc, dirn.disp::<i64>()) {
+                Some(next) => next,
+                None => continue,
+            };
+            let j = index(next);
+            let next_cost = cost + grid[next];
+            if dist[j].map(|d| next_cost < d).unwrap_or(true) {
+                dist[j] = Some(next_cost);
+                prev[j] = Some(i);
+                pq.push_or_decrease(j, next_cost);
+            }
+        }
+    }
+    None
+}
+
+/// Bookkeeping for `grid_shortest_path_constrained()`. A
+/// state is a run length and a slot giving its cell and
+/// direction, numbered `(run - 1) * nslots + slot`: there
+/// is one level of slots per run length up to the maximum,
+/// allocated as runs get longer.
+struct States {
+    /// Number of slots per level.
+    nslots: usize,
+    /// Least cost seen for each state.
+    dist: Vec<Option<u32>>,
+    /// Predecessor state of each state, if any.
+    prev: Vec<Option<usize>>,
+    /// Queue of states to expand, by cost.
+    pq: DenseHeap<u32>,
+}
+
+impl States {
+    /// Record and enqueue the given state if this is the
+    /// cheapest way to reach it so far.
+    fn relax(
+        &mut self,
+        cost: u32,
+        run: usize,
+        slot: usize,
+        from: Option<usize>,
+    ) {
+        let state = (run - 1) * self.nslots + slot;
+        if self.dist.len() <= state {
+            let len = run * self.nslots;
+            self.dist.resize(len, None);
+            self.prev.resize(len, None);
+        }
+        let d = &mut self.dist[state];
+        if d.map(|d| cost < d).unwrap_or(true) {
+            *d = Some(cost);
+            self.prev[state] = from;
+            self.pq.push_or_decrease(state, cost);
+        }
+    }
+
+    /// The run length and slot of a state.
+    fn unstate(&self, state: usize) -> (usize, usize) {
+        (state / self.nslots + 1, state % self.nslots)
+    }
+}
+
+/// Find a least-cost path as with `grid_shortest_path()`,
+/// subject to a movement constraint. The search state is the
+/// position, the current direction, and the length of the
+/// current straight run in that direction. The constraint
+/// is called with these and returns the `Moves` allowed from
+/// there. Reversing direction is never allowed.
+///
+/// Runs longer than `max_run` are counted as `max_run`, so
+/// the constraint must treat all such runs alike. This keeps
+/// the number of search states proportional to `max_run`
+/// rather than to the size of the grid: an unconstrained
+/// search can use a `max_run` of 1.
+///
+/// The first move from `start` may be in any direction, and
+/// begins a run of length 1. If `start` is `goal` the path
+/// holding just `start` is returned without consulting the
+/// constraint. There is no path if `start` or `goal` is off
+/// the grid.
+///
+/// # Panics
+///
+/// Panics if `max_run` is zero.
+///
+/// # Examples
+///
+/// A crucible that must go 4 to 10 cells before turning or
+/// stopping:
+///
+/// ```
+/// # use astar::*;
+/// # use geom::Grid;
+/// let grid = Grid::new(2, 6, vec![1; 12]);
+/// let ultra = |_, _, run| Moves {
+///     straight: run < 10,
+///     turn: run >= 4,
+///     stop: run >= 4,
+/// };
+/// let path =
+///     grid_shortest_path_constrained(&grid, (0, 0), (1, 5), 10, ultra);
+/// assert_eq!(path, None);
+/// let path =
+///     grid_shortest_path_constrained(&grid, (0, 0), (0, 5), 10, ultra);
+/// assert_eq!(path.map(|(cost, _)| cost), Some(5));
+/// ```
+pub fn grid_shortest_path_constrained<F>(
+    grid: &Grid<u32>,
+    start: Loc,
+    goal: Loc,
+    max_run: usize,
+    mut constraint: F,
+) -> Option<(u32, Vec<Loc>)>
+where
+    F: FnMut(Loc, Dirn, usize) -> Moves,
+{
+    assert!(max_run > 0, "maximum run must be positive");
+    let cols = grid.cols();
+    let clip = grid.grid_box();
+    if !clip.contains(start) || !clip.contains(goal) {
+        return None;
+    }
+    if start == goal {
+        return Some((0, vec![start]));
+    }
+    let slot = |(r, c): Loc, dirn: Dirn| {
+        (r * cols + c) * FACINGS.len() + dirn as usize
+    };
+    let unslot = |s: usize| {
+        let cell = s / FACINGS.len();
+        ((cell / cols, cell % cols), FACINGS[s % FACINGS.len()])
+    };
+    let mut states = States {
+        nslots: grid.rows() * cols * FACINGS.len(),
+        dist: Vec::new(),
+        prev: Vec::new(),
+        pq: DenseHeap::new(),
+    };
+
+    for dirn in FACINGS {
+        if let Some(next) = clip.clip(start, dirn.disp::<i64>()) {
+            states.relax(grid[next], 1, slot(next, dirn), None);
+        }
+    }
+    while let Some((state, cost)) = states.pq.pop() {
+        let (run, s) = states.unstate(state);
+        let (loc, dirn) = unslot(s);
+        let moves = constraint(loc, dirn, run);
+        if loc == goal && moves.stop {
+            let mut path = vec![loc];
+            let mut cur = state;
+            while let Some(p) = states.prev[cur] {
+                path.push(unslot(states.unstate(p).1).0);
+                cur = p;
+            }
+            path.push(start);
+            path.reverse();
+            return Some((cost, path));
+        }
+        let back = dirn.turn(Rot::CW, 2);
+        for next_dirn in FACINGS {
+            let next_run = if next_dirn == dirn {
+                if !moves.straight {
+                    continue;
+                }
+                (run + 1).min(max_run)
+            } else {
+                if next_dirn == back || !moves.turn {
+                    continue;
+                }
+                1
+            };
+            if let Some(next) = clip.clip(loc, next_dirn.disp::<i64>()) {
+                let next_cost = cost + grid[next];
+                let next_slot = slot(next, next_dirn);
+                let from = Some(state);
+                states.relax(next_cost, next_run, next_slot, from);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn digit_grid(rows: &[&str]) -> Grid<u32> {
+        let cells = rows
+            .iter()
+            .flat_map(|r| r.chars().map(|c| c.to_digit(10).unwrap()))
+            .collect();
+        Grid::new(rows.len(), rows[0].len(), cells)
+    }
+
+    fn corner(grid: &Grid<u32>) -> Loc {
+        (grid.rows() - 1, grid.cols() - 1)
+    }
+
+    fn path_cost(grid: &Grid<u32>, path: &[Loc]) -> u32 {
+        for w in path.windows(2) {
+            let (r0, c0) = w[0];
+            let (r1, c1) = w[1];
+            assert_eq!(r0.abs_diff(r1) + c0.abs_diff(c1), 1);
+        }
+        path[1..].iter().map(|&loc| grid[loc]).sum()
+    }
+
+    #[test]
+    fn chiton() {
+        // 2021 day 15 example.
+        let grid = digit_grid(&[
+            "1163751742",
+            "1381373672",
+            "2136511328",
+            "3694931569",
+            "7463417111",
+            "1319128137",
+            "1359912421",
+            "3125421639",
+            "1293138521",
+            "2311944581",
+        ]);
+        let (cost, path) =
+            grid_shortest_path(&grid, (0, 0), corner(&grid)).unwrap();
+        assert_eq!(cost, 40);
+        assert_eq!(path_cost(&grid, &path), 40);
+
+        let (rows, cols) = (grid.rows(), grid.cols());
+        let mut cells = Vec::new();
+        for r in 0..5 * rows {
+            for c in 0..5 * cols {
+                let tile = (r / rows + c / cols) as u32;
+                let risk = grid[(r % rows, c % cols)] + tile;
+                cells.push((risk - 1) % 9 + 1);
+            }
+        }
+        let tiled = Grid::new(5 * rows, 5 * cols, cells);
+        let (cost, path) =
+            grid_shortest_path(&tiled, (0, 0), corner(&tiled)).unwrap();
+        assert_eq!(cost, 315);
+        assert_eq!(path_cost(&tiled, &path), 315);
+
+        let unconstrained = |_, _, _| Moves::ANY;
+        let (cost, _) = grid_shortest_path_constrained(
+            &tiled,
+            (0, 0),
+            corner(&tiled),
+            1,
+            unconstrained,
+        )
+        .unwrap();
+        assert_eq!(cost, 315);
+    }
+
+    #[test]
+    fn endpoints() {
+        let grid = Grid::new(2, 2, vec![1; 4]);
+        let any = |_, _, _| Moves::ANY;
+        let found = Some((0, vec![(1, 1)]));
+        assert_eq!(grid_shortest_path(&grid, (1, 1), (1, 1)), found);
+        let path =
+            grid_shortest_path_constrained(&grid, (1, 1), (1, 1), 1, any);
+        assert_eq!(path, found);
+        assert_eq!(grid_shortest_path(&grid, (2, 0), (1, 1)), None);
+        assert_eq!(grid_shortest_path(&grid, (0, 0), (0, 5)), None);
+        let path =
+            grid_shortest_path_constrained(&grid, (0, 7), (1, 1), 1, any);
+        assert_eq!(path, None);
+        let path =
+            grid_shortest_path_constrained(&grid, (0, 0), (3, 0), 1, any);
+        assert_eq!(path, None);
+    }
+
+    #[test]
+    fn big_unconstrained() {
+        // One level of states, however long the runs get.
+        let n = 200;
+        let grid = Grid::new(n, n, vec![1; n * n]);
+        let any = |_, _, _| Moves::ANY;
+        let goal = corner(&grid);
+        let (cost, _) =
+            grid_shortest_path_constrained(&grid, (0, 0), goal, 1, any)
+                .unwrap();
+        assert_eq!(cost, 2 * (n as u32 - 1));
+    }
+
+    #[test]
+    fn crucible() {
+        // 2023 day 17 examples.
+        let grid = digit_grid(&[
+            "2413432311323",
+            "3215453535623",
+            "3255245654254",
+            "3446585845452",
+            "4546657867536",
+            "1438598798454",
+            "4457876987766",
+            "3637877979653",
+            "4654967986887",
+            "4564679986453",
+            "1224686865563",
+            "2546548887735",
+            "4322674655533",
+        ]);
+        let crucible = |_, _, run| Moves {
+            straight: run < 3,
+            ..Moves::ANY
+        };
+        let ultra = |_, _, run| Moves {
+            straight: run < 10,
+            turn: run >= 4,
+            stop: run >= 4,
+        };
+        let goal = corner(&grid);
+        let (cost, path) =
+            grid_shortest_path_constrained(&grid, (0, 0), goal, 3, crucible)
+                .unwrap();
+        assert_eq!(cost, 102);
+        assert_eq!(path_cost(&grid, &path), 102);
+        // A higher cap than needed changes nothing.
+        let (cost, _) =
+            grid_shortest_path_constrained(&grid, (0, 0), goal, 8, crucible)
+                .unwrap();
+        assert_eq!(cost, 102);
+        let (cost, path) =
+            grid_shortest_path_constrained(&grid, (0, 0), goal, 10, ultra)
+                .unwrap();
+        assert_eq!(cost, 94);
+        assert_eq!(path_cost(&grid, &path), 94);
+
+        let grid = digit_grid(&[
+            "111111111111",
+            "999999999991",
+            "999999999991",
+            "999999999991",
+            "999999999991",
+        ]);
+        let goal = corner(&grid);
+        let (cost, _) =
+            grid_shortest_path_constrained(&grid, (0, 0), goal, 10, ultra)
+                .unwrap();
+        assert_eq!(cost, 71);
+    }
+}
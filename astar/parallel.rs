@@ -0,0 +1,153 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Run many independent searches in parallel.
+//!
+//! Some puzzles run the same search from a large number of
+//! starting configurations and aggregate the results. The
+//! searches here share nothing but the (immutable) closure
+//! that runs them, so they can be fanned out over a few
+//! scoped threads without any extra dependencies.
+//!
+//! # Examples
+//!
+//! ```
+//! # use astar::*;
+//! let squares = search_many_threads((0..10u64).collect(), 3, |n| n * n);
+//! assert_eq!(squares, (0..10).map(|n| n * n).collect::<Vec<_>>());
+//! ```
+
+use std::sync::Mutex;
+use std::thread;
+
+/// Environment variable that sets the number of threads used
+/// by `search_many()`.
+pub const THREADS_VAR: &str = "AOC_THREADS";
+
+/// Run `run` on each of the given starts in parallel,
+/// returning the results in the same order as the starts.
+///
+/// The number of threads is taken from the `AOC_THREADS`
+/// environment variable if set, and is otherwise the
+/// available parallelism of the machine.
+///
+/// # Panics
+///
+/// Panics if `AOC_THREADS` is set but is not a positive
+/// number, or if `run` panics.
+pub fn search_many<S, R, F>(starts: Vec<S>, run: F) -> Vec<R>
+where
+    S: Send,
+    R: Send,
+    F: Fn(S) -> R + Sync,
+{
+    let nthreads = match std::env::var(THREADS_VAR) {
+        Ok(n) => n.parse().expect("search_many: bad thread count"),
+        Err(_) => thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1),
+    };
+    search_many_threads(starts, nthreads, run)
+}
+
+/// Run `run` on each of the given starts using `nthreads`
+/// threads, returning the results in the same order as the
+/// starts. Starts are handed out one at a time as threads
+/// become free, so uneven search times balance out.
+///
+/// # Panics
+///
+/// Panics if `nthreads` is 0, or if `run` panics.
+pub fn search_many_threads<S, R, F>(
+    starts: Vec<S>,
+    nthreads: usize,
+    run: F,
+) -> Vec<R>
+where
+    S: Send,
+    R: Send,
+    F: Fn(S) -> R + Sync,
+{
+    assert!(nthreads > 0, "search_many: no threads");
+    let nstarts = starts.len();
+    let jobs = Mutex::new(starts.into_iter().enumerate());
+    let run = &run;
+    let jobs = &jobs;
+    let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..nthreads.min(nstarts))
+            .map(|_| {
+                scope.spawn(move || {
+                    let mut done = Vec::new();
+                    loop {
+                        let job = jobs.lock().unwrap().next();
+                        match job {
+                            Some((i, start)) => done.push((i, run(start))),
+                            None => return done,
+                        }
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|w| w.join().expect("search_many: worker panicked"))
+            .collect()
+    });
+    results.sort_unstable_by_key(|&(i, _)| i);
+    results.into_iter().map(|(_, r)| r).collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::*;
+
+    /// Walk a number line from the start to 0, where a step
+    /// costs 1 and halving (when even) costs 2.
+    #[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+    struct Num(u64);
+
+    impl SearchState for Num {
+        type Global = ();
+        type Label = ();
+        fn label(&self) {}
+        fn is_goal(&self, _: &()) -> bool {
+            self.0 == 0
+        }
+        fn neighbors(&self, _: &()) -> Vec<(usize, Box<Num>)> {
+            let mut result = vec![(1, Box::new(Num(self.0 - 1)))];
+            if self.0.is_multiple_of(2) {
+                result.push((2, Box::new(Num(self.0 / 2))));
+            }
+            result
+        }
+    }
+
+    fn solve(n: u64) -> usize {
+        if n == 0 {
+            return 0;
+        }
+        a_star(&(), &Num(n), false).unwrap().0
+    }
+
+    #[test]
+    fn ordered_results() {
+        let starts: Vec<u64> = (0..64).map(|i| (i * 37) % 101).collect();
+        let expected: Vec<usize> = starts.iter().map(|&n| solve(n)).collect();
+        let results = search_many_threads(starts.clone(), 4, solve);
+        assert_eq!(results, expected);
+
+        let paired = search_many_threads(starts.clone(), 4, |n| (n, solve(n)));
+        let echoed: Vec<u64> = paired.iter().map(|&(n, _)| n).collect();
+        assert_eq!(echoed, starts);
+    }
+
+    #[test]
+    fn more_threads_than_starts() {
+        assert_eq!(search_many_threads(vec![8u64], 16, solve), vec![6]);
+        assert!(search_many_threads(Vec::<u64>::new(), 4, solve).is_empty());
+    }
+}
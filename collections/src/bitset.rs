@@ -0,0 +1,419 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Small sets of indices as bitmasks, and an interner to
+//! turn labels into indices.
+//!
+//! Searches over "which keys have I got" or "which valves
+//! are open" want the set in the search state, where it
+//! should be small, `Copy` and cheap to hash. A `BitSet64`
+//! or `BitSet128` is just that, and an `Interner` gives the
+//! labels of the puzzle their bit positions.
+//!
+//! # Examples
+//!
+//! ```
+//! # use collections::*;
+//! let mut valves = Interner::new();
+//! let aa = valves.intern("AA");
+//! let bb = valves.intern("BB");
+//! let cc = valves.intern("CC");
+//! assert_eq!(valves.intern("BB"), bb);
+//!
+//! let open = BitSet64::singleton(aa) | BitSet64::singleton(cc);
+//! assert!(open.contains(cc) && !open.contains(bb));
+//! let names: Vec<&str> = open.iter().map(|i| *valves.value(i)).collect();
+//! assert_eq!(names, ["AA", "CC"]);
+//! ```
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub,
+    SubAssign,
+};
+
+/// Define a bitset type `$set` over the word type `$word`,
+/// with iterator type `$iter`.
+macro_rules! bitset {
+    ($set:ident, $iter:ident, $word:ty, $bits:expr) => {
+        #[doc = concat!(
+            "A set of indices less than ",
+            stringify!($bits),
+            ", stored as the bits of a `",
+            stringify!($word),
+            "`.",
+        )]
+        ///
+        /// Methods taking an index panic if it is out of
+        /// range.
+        #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $set($word);
+
+        impl $set {
+            /// Number of possible elements: every element is
+            /// less than this.
+            pub const CAPACITY: usize = $bits;
+
+            /// Make an empty set.
+            pub fn new() -> Self {
+                $set(0)
+            }
+
+            /// Make the set of every index less than `n`.
+            pub fn full(n: usize) -> Self {
+                assert!(n <= Self::CAPACITY, "bitset size {} too big", n);
+                if n == Self::CAPACITY {
+                    $set(!0)
+                } else {
+                    $set((1 << n) - 1)
+                }
+            }
+
+            /// Make the set containing just `i`.
+            pub fn singleton(i: usize) -> Self {
+                $set(Self::bit(i))
+            }
+
+            fn bit(i: usize) -> $word {
+                assert!(i < Self::CAPACITY, "bitset index {} out of range", i);
+                1 << i
+            }
+
+            /// Add `i` to the set. Returns true if it was
+            /// not already there.
+            pub fn insert(&mut self, i: usize) -> bool {
+                let old = self.0;
+                self.0 |= Self::bit(i);
+                self.0 != old
+            }
+
+            /// Remove `i` from the set. Returns true if it
+            /// was there.
+            pub fn remove(&mut self, i: usize) -> bool {
+                let old = self.0;
+                self.0 &= !Self::bit(i);
+                self.0 != old
+            }
+
+            /// True if `i` is in the set.
+            pub fn contains(self, i: usize) -> bool {
+                self.0 & Self::bit(i) != 0
+            }
+
+            /// Number of elements of the set.
+            pub fn len(self) -> usize {
+                self.0.count_ones() as usize
+            }
+
+            /// True if the set is empty.
+            pub fn is_empty(self) -> bool {
+                self.0 == 0
+            }
+
+            /// True if every element of this set is in
+            /// `other`.
+            pub fn is_subset(self, other: Self) -> bool {
+                self.0 & !other.0 == 0
+            }
+
+            /// True if the sets have no element in common.
+            pub fn is_disjoint(self, other: Self) -> bool {
+                self.0 & other.0 == 0
+            }
+
+            /// The least element of the set, if any.
+            pub fn first(self) -> Option<usize> {
+                (self.0 != 0).then(|| self.0.trailing_zeros() as usize)
+            }
+
+            /// Iterator over the elements of the set, in
+            /// increasing order.
+            pub fn iter(self) -> $iter {
+                $iter(self.0)
+            }
+        }
+
+        #[doc = concat!(
+            "Iterator over the elements of a `",
+            stringify!($set),
+            "`.",
+        )]
+        #[derive(Debug, Clone)]
+        pub struct $iter($word);
+
+        impl Iterator for $iter {
+            type Item = usize;
+
+            fn next(&mut self) -> Option<usize> {
+                if self.0 == 0 {
+                    return None;
+                }
+                let i = self.0.trailing_zeros() as usize;
+                self.0 &= self.0 - 1;
+                Some(i)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let n = self.0.count_ones() as usize;
+                (n, Some(n))
+            }
+        }
+
+        impl ExactSizeIterator for $iter {}
+
+        impl IntoIterator for $set {
+            type Item = usize;
+            type IntoIter = $iter;
+
+            fn into_iter(self) -> $iter {
+                self.iter()
+            }
+        }
+
+        impl FromIterator<usize> for $set {
+            fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+                let mut set = $set::new();
+                set.extend(iter);
+                set
+            }
+        }
+
+        impl Extend<usize> for $set {
+            fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+                for i in iter {
+                    self.insert(i);
+                }
+            }
+        }
+
+        impl From<$word> for $set {
+            fn from(bits: $word) -> Self {
+                $set(bits)
+            }
+        }
+
+        impl From<$set> for $word {
+            fn from(set: $set) -> Self {
+                set.0
+            }
+        }
+
+        impl fmt::Debug for $set {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.debug_set().entries(self.iter()).finish()
+            }
+        }
+
+        impl BitOr for $set {
+            type Output = Self;
+
+            /// Union.
+            fn bitor(self, other: Self) -> Self {
+                $set(self.0 | other.0)
+            }
+        }
+
+        impl BitAnd for $set {
+            type Output = Self;
+
+            /// Intersection.
+            fn bitand(self, other: Self) -> Self {
+                $set(self.0 & other.0)
+            }
+        }
+
+        impl BitXor for $set {
+            type Output = Self;
+
+            /// Symmetric difference.
+            fn bitxor(self, other: Self) -> Self {
+                $set(self.0 ^ other.0)
+            }
+        }
+
+        impl Sub for $set {
+            type Output = Self;
+
+            /// Difference.
+            fn sub(self, other: Self) -> Self {
+                $set(self.0 & !other.0)
+            }
+        }
+
+        impl BitOrAssign for $set {
+            fn bitor_assign(&mut self, other: Self) {
+                self.0 |= other.0;
+            }
+        }
+
+        impl BitAndAssign for $set {
+            fn bitand_assign(&mut self, other: Self) {
+                self.0 &= other.0;
+            }
+        }
+
+        impl BitXorAssign for $set {
+            fn bitxor_assign(&mut self, other: Self) {
+                self.0 ^= other.0;
+            }
+        }
+
+        impl SubAssign for $set {
+            fn sub_assign(&mut self, other: Self) {
+                self.0 &= !other.0;
+            }
+        }
+    };
+}
+
+bitset!(BitSet64, BitSet64Iter, u64, 64);
+bitset!(BitSet128, BitSet128Iter, u128, 128);
+
+/// Assigns small indices, counting up from 0, to values in
+/// the order they are first seen.
+#[derive(Debug, Clone)]
+pub struct Interner<T> {
+    values: Vec<T>,
+    index: HashMap<T, usize>,
+}
+
+impl<T> Default for Interner<T> {
+    fn default() -> Self {
+        Interner {
+            values: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Clone + Eq + Hash> Interner<T> {
+    /// Make an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The index of `value`, giving it the next one if it
+    /// has none yet.
+    pub fn intern(&mut self, value: T) -> usize {
+        if let Some(&i) = self.index.get(&value) {
+            return i;
+        }
+        let i = self.values.len();
+        self.values.push(value.clone());
+        self.index.insert(value, i);
+        i
+    }
+
+    /// The index of `value`, if it has one.
+    pub fn get<Q>(&self, value: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.index.get(value).copied()
+    }
+
+    /// The value with index `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no value has index `i`.
+    pub fn value(&self, i: usize) -> &T {
+        &self.values[i]
+    }
+
+    /// Number of values interned.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// True if no values have been interned.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The values interned, in index order.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn bitset_operations() {
+        let a: BitSet64 = [5, 1, 63, 9].into_iter().collect();
+        assert_eq!(a.iter().collect::<Vec<_>>(), [1, 5, 9, 63]);
+        assert_eq!(a.iter().len(), 4);
+        assert_eq!(format!("{:?}", a), "{1, 5, 9, 63}");
+        assert_eq!(a.first(), Some(1));
+        let b: BitSet64 = [1, 2, 63].into_iter().collect();
+        let union: Vec<usize> = (a | b).into_iter().collect();
+        assert_eq!(union, [1, 2, 5, 9, 63]);
+        assert_eq!((a & b).iter().collect::<Vec<_>>(), [1, 63]);
+        assert_eq!((a - b).iter().collect::<Vec<_>>(), [5, 9]);
+        assert_eq!((a ^ b).iter().collect::<Vec<_>>(), [2, 5, 9]);
+        assert!((a & b).is_subset(a));
+        assert!(!a.is_subset(b));
+        assert!((a - b).is_disjoint(b));
+
+        let mut c = a;
+        assert!(!c.insert(63));
+        assert!(c.remove(63));
+        assert!(!c.remove(63));
+        c |= b;
+        c -= BitSet64::singleton(2);
+        assert_eq!(c, a);
+        assert_eq!(u64::from(BitSet64::singleton(63)), 1 << 63);
+        assert_eq!(BitSet64::from(0b1010).len(), 2);
+        assert_eq!(BitSet64::full(64).len(), 64);
+        assert!(BitSet64::full(0).is_empty());
+        assert_eq!(BitSet64::new().first(), None);
+    }
+
+    #[test]
+    fn wide_bitset() {
+        let mut set = BitSet128::full(65);
+        assert_eq!(set.len(), 65);
+        assert!(set.contains(63) && set.contains(64));
+        assert!(!set.contains(65));
+        assert!(set.insert(127));
+        assert!(set.remove(0));
+        let tail: Vec<usize> = set.iter().skip(62).collect();
+        assert_eq!(tail, [63, 64, 127]);
+        assert_eq!(BitSet128::full(128).len(), 128);
+        assert_eq!(BitSet128::CAPACITY, 128);
+    }
+
+    #[test]
+    #[should_panic(expected = "bitset index 64 out of range")]
+    fn bitset_range() {
+        BitSet64::new().insert(64);
+    }
+
+    #[test]
+    fn interner() {
+        let labels = ["kh", "tc", "qp", "kh", "ub", "tc"];
+        let mut interner = Interner::new();
+        let ids: Vec<usize> =
+            labels.iter().map(|l| interner.intern(l.to_string())).collect();
+        assert_eq!(ids, [0, 1, 2, 0, 3, 1]);
+        assert_eq!(interner.len(), 4);
+        for (&label, &id) in labels.iter().zip(&ids) {
+            assert_eq!(interner.value(id), label);
+            assert_eq!(interner.get(label), Some(id));
+        }
+        assert_eq!(interner.get("zz"), None);
+        assert_eq!(interner.values(), ["kh", "tc", "qp", "ub"]);
+        let set: BitSet64 = ids.into_iter().collect();
+        assert_eq!(set, BitSet64::full(4));
+    }
+}
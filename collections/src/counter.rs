@@ -0,0 +1,271 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Counting things: a multiset.
+//!
+//! # Examples
+//!
+//! Count lanternfish by age rather than tracking each one
+//! (2021 day 6): there are only nine ages, but hundreds of
+//! billions of fish.
+//!
+//! ```
+//! # use collections::*;
+//! let mut school: Counter<u8> = [3, 4, 3, 1, 2].into_iter().collect();
+//! for _ in 0..256 {
+//!     let mut next = Counter::new();
+//!     for (&age, n) in &school {
+//!         if age == 0 {
+//!             next.add_n(6, n);
+//!             next.add_n(8, n);
+//!         } else {
+//!             next.add_n(age - 1, n);
+//!         }
+//!     }
+//!     school = next;
+//! }
+//! assert_eq!(school.total(), 26_984_457_539);
+//! ```
+
+use std::collections::hash_map::{self, HashMap};
+use std::hash::Hash;
+use std::ops;
+
+/// A multiset: a count of each of some items. Items with a
+/// count of 0 are not stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<T: Eq + Hash> {
+    counts: HashMap<T, usize>,
+    total: usize,
+}
+
+impl<T: Eq + Hash> Default for Counter<T> {
+    fn default() -> Self {
+        Counter {
+            counts: HashMap::new(),
+            total: 0,
+        }
+    }
+}
+
+impl<T: Eq + Hash> Counter<T> {
+    /// Make an empty counter.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one more of `item`.
+    pub fn add(&mut self, item: T) {
+        self.add_n(item, 1);
+    }
+
+    /// Count `n` more of `item`.
+    pub fn add_n(&mut self, item: T, n: usize) {
+        if n > 0 {
+            *self.counts.entry(item).or_insert(0) += n;
+            self.total += n;
+        }
+    }
+
+    /// Count `n` fewer of `item`, stopping at 0. Returns the
+    /// number actually removed.
+    pub fn remove_n(&mut self, item: &T, n: usize) -> usize {
+        let Some(count) = self.counts.get_mut(item) else {
+            return 0;
+        };
+        let removed = n.min(*count);
+        *count -= removed;
+        if *count == 0 {
+            self.counts.remove(item);
+        }
+        self.total -= removed;
+        removed
+    }
+
+    /// Number of `item` counted.
+    pub fn count(&self, item: &T) -> usize {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    /// Total count of all items.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Number of distinct items counted.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// True if nothing has been counted.
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Iterator over the distinct items and their counts, in
+    /// no particular order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(self.counts.iter())
+    }
+
+    /// The `k` items with the highest counts, highest first.
+    /// Items with equal counts are in increasing order of
+    /// item, so the result is deterministic.
+    pub fn most_common(&self, k: usize) -> Vec<(&T, usize)>
+    where
+        T: Ord,
+    {
+        let mut counts: Vec<(&T, usize)> = self.iter().collect();
+        counts.sort_by(|(a, m), (b, n)| n.cmp(m).then_with(|| a.cmp(b)));
+        counts.truncate(k);
+        counts
+    }
+}
+
+/// Iterator over the items of a `Counter` and their counts.
+#[derive(Debug, Clone)]
+pub struct Iter<'a, T>(hash_map::Iter<'a, T, usize>);
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (&'a T, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(item, &n)| (item, n))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, T: Eq + Hash> IntoIterator for &'a Counter<T> {
+    type Item = (&'a T, usize);
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T: Eq + Hash> IntoIterator for Counter<T> {
+    type Item = (T, usize);
+    type IntoIter = hash_map::IntoIter<T, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+impl<T: Eq + Hash> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        for item in items {
+            self.add(item);
+        }
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(items: I) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(items);
+        counter
+    }
+}
+
+/// Counts are summed.
+impl<T: Eq + Hash> ops::AddAssign for Counter<T> {
+    fn add_assign(&mut self, other: Counter<T>) {
+        for (item, n) in other {
+            self.add_n(item, n);
+        }
+    }
+}
+
+/// Counts are summed.
+impl<T: Eq + Hash> ops::Add for Counter<T> {
+    type Output = Counter<T>;
+
+    fn add(mut self, other: Counter<T>) -> Counter<T> {
+        self += other;
+        self
+    }
+}
+
+/// Counts are subtracted, stopping at 0.
+impl<T: Eq + Hash> ops::SubAssign for Counter<T> {
+    fn sub_assign(&mut self, other: Counter<T>) {
+        for (item, n) in other {
+            self.remove_n(&item, n);
+        }
+    }
+}
+
+/// Counts are subtracted, stopping at 0.
+impl<T: Eq + Hash> ops::Sub for Counter<T> {
+    type Output = Counter<T>;
+
+    fn sub(mut self, other: Counter<T>) -> Counter<T> {
+        self -= other;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn counting() {
+        let mut letters: Counter<char> = "abracadabra".chars().collect();
+        assert_eq!(letters.count(&'a'), 5);
+        assert_eq!(letters.count(&'z'), 0);
+        assert_eq!(letters.total(), 11);
+        assert_eq!(letters.len(), 5);
+
+        assert_eq!(letters.remove_n(&'c', 3), 1);
+        assert_eq!(letters.remove_n(&'z', 3), 0);
+        assert_eq!(letters.len(), 4);
+        assert_eq!(letters.total(), 10);
+        letters.add_n('z', 0);
+        assert_eq!(letters.len(), 4);
+        letters.add('z');
+        assert_eq!(letters.count(&'z'), 1);
+        let sum: usize = letters.iter().map(|(_, n)| n).sum();
+        assert_eq!(sum, letters.total());
+    }
+
+    #[test]
+    fn most_common_ties() {
+        let letters: Counter<char> = "mississippi".chars().collect();
+        // 'i' and 's' both appear 4 times; 'i' sorts first.
+        assert_eq!(letters.most_common(3), [(&'i', 4), (&'s', 4), (&'p', 2)]);
+        assert_eq!(letters.most_common(10).len(), 4);
+        assert_eq!(letters.most_common(0), []);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a: Counter<u8> = [1, 1, 1, 2, 3].into_iter().collect();
+        let b: Counter<u8> = [1, 2, 2, 2, 4].into_iter().collect();
+        let sum = a.clone() + b.clone();
+        assert_eq!(sum.count(&1), 4);
+        assert_eq!(sum.count(&2), 4);
+        assert_eq!(sum.total(), 10);
+
+        let diff = a.clone() - b.clone();
+        assert_eq!(diff.count(&1), 2);
+        assert_eq!(diff.count(&2), 0);
+        assert_eq!(diff.count(&3), 1);
+        assert_eq!(diff.count(&4), 0);
+        assert_eq!(diff.len(), 2);
+        assert_eq!(diff.total(), 3);
+
+        let mut none = b.clone();
+        none -= a + b;
+        assert!(none.is_empty());
+        assert_eq!(none, Counter::new());
+    }
+}
@@ -0,0 +1,503 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! A priority queue of keys whose priorities can be lowered
+//! in place.
+//!
+//! The usual trick with `BinaryHeap` is to push a key again
+//! whenever its priority improves and skip the stale
+//! entries as they come out. `IndexedHeap` instead keeps
+//! the position of each key in the heap, so that each key is
+//! in the queue at most once and lowering its priority
+//! takes `O(log n)` time. `DenseHeap` does the same for keys
+//! that are small `usize` indices, without hashing.
+//!
+//! # Examples
+//!
+//! Dijkstra's algorithm on a small graph.
+//!
+//! ```
+//! # use collections::*;
+//! let edges = [(0, 1, 7), (0, 2, 2), (2, 1, 3), (1, 3, 1), (2, 3, 8)];
+//! let mut dist = [None; 4];
+//! let mut queue = IndexedHeap::new();
+//! queue.push_or_decrease(0, 0);
+//! while let Some((node, d)) = queue.pop() {
+//!     dist[node] = Some(d);
+//!     for &(from, to, cost) in &edges {
+//!         if from == node && dist[to].is_none() {
+//!             queue.push_or_decrease(to, d + cost);
+//!         }
+//!     }
+//! }
+//! assert_eq!(dist, [Some(0), Some(5), Some(2), Some(6)]);
+//! ```
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A min-heap of keys with priorities, in which each key
+/// appears at most once. The key with the least priority is
+/// popped first; use `std::cmp::Reverse` priorities to pop
+/// the greatest first. Keys with equal priorities come out
+/// in no particular order.
+#[derive(Debug, Clone)]
+pub struct IndexedHeap<K, P> {
+    /// The heap, ordered by priority.
+    heap: Vec<(K, P)>,
+    /// The index in `heap` of each key.
+    position: HashMap<K, usize>,
+}
+
+impl<K, P> Default for IndexedHeap<K, P> {
+    fn default() -> Self {
+        IndexedHeap {
+            heap: Vec::new(),
+            position: HashMap::new(),
+        }
+    }
+}
+
+impl<K, P> IndexedHeap<K, P>
+where
+    K: Clone + Eq + Hash,
+    P: Ord,
+{
+    /// Make an empty heap.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of keys in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// True if there are no keys in the heap.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// True if `key` is in the heap.
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.position.contains_key(key)
+    }
+
+    /// The priority of `key`, if it is in the heap.
+    pub fn priority_of<Q>(&self, key: &Q) -> Option<&P>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let &i = self.position.get(key)?;
+        Some(&self.heap[i].1)
+    }
+
+    /// Add `key` with the given priority. If `key` is
+    /// already in the heap, its priority is lowered to
+    /// `priority` if that is less, and otherwise left alone.
+    /// Returns true if the heap changed.
+    ///
+    /// A key that has been popped is no longer in the heap,
+    /// so pushing it again adds it back.
+    pub fn push_or_decrease(&mut self, key: K, priority: P) -> bool {
+        let i = match self.position.get(&key) {
+            Some(&i) => {
+                if priority >= self.heap[i].1 {
+                    return false;
+                }
+                self.heap[i].1 = priority;
+                i
+            }
+            None => {
+                self.heap.push((key.clone(), priority));
+                self.heap.len() - 1
+            }
+        };
+        let position = &mut self.position;
+        let i = sift_up(&mut self.heap, i, |k, i| {
+            *position.get_mut(k).unwrap() = i;
+        });
+        position.insert(key, i);
+        true
+    }
+
+    /// The key with the least priority and its priority,
+    /// without removing it.
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.heap.first().map(|(k, p)| (k, p))
+    }
+
+    /// Remove and return the key with the least priority
+    /// and its priority.
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let (key, priority) = self.heap.swap_remove(0);
+        self.position.remove(&key);
+        if !self.heap.is_empty() {
+            let position = &mut self.position;
+            let i = sift_down(&mut self.heap, 0, |k, i| {
+                *position.get_mut(k).unwrap() = i;
+            });
+            *position.get_mut(&self.heap[i].0).unwrap() = i;
+        }
+        Some((key, priority))
+    }
+}
+
+/// A min-heap like `IndexedHeap` whose keys are small
+/// `usize` indices, such as node numbers or flattened grid
+/// cells. The position of each key is kept in a `Vec`
+/// indexed by key rather than in a `HashMap`, which is much
+/// faster; the `Vec` grows to hold the largest key pushed.
+///
+/// # Examples
+///
+/// ```
+/// # use collections::*;
+/// let mut queue = DenseHeap::new();
+/// queue.push_or_decrease(3, "c");
+/// queue.push_or_decrease(1, "b");
+/// queue.push_or_decrease(3, "a");
+/// assert_eq!(queue.pop(), Some((3, "a")));
+/// assert_eq!(queue.pop(), Some((1, "b")));
+/// assert_eq!(queue.pop(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DenseHeap<P> {
+    /// The heap, ordered by priority.
+    heap: Vec<(usize, P)>,
+    /// The index in `heap` of each key, or `ABSENT`.
+    position: Vec<usize>,
+}
+
+/// Position of a key that is not in a `DenseHeap`.
+const ABSENT: usize = usize::MAX;
+
+impl<P> Default for DenseHeap<P> {
+    fn default() -> Self {
+        DenseHeap {
+            heap: Vec::new(),
+            position: Vec::new(),
+        }
+    }
+}
+
+impl<P: Ord> DenseHeap<P> {
+    /// Make an empty heap.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make an empty heap with room for the keys
+    /// `0..nkeys` without reallocating.
+    pub fn with_keys(nkeys: usize) -> Self {
+        DenseHeap {
+            heap: Vec::new(),
+            position: vec![ABSENT; nkeys],
+        }
+    }
+
+    /// Number of keys in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// True if there are no keys in the heap.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// True if `key` is in the heap.
+    pub fn contains(&self, key: usize) -> bool {
+        self.position.get(key).is_some_and(|&i| i != ABSENT)
+    }
+
+    /// The priority of `key`, if it is in the heap.
+    pub fn priority_of(&self, key: usize) -> Option<&P> {
+        match self.position.get(key) {
+            Some(&i) if i != ABSENT => Some(&self.heap[i].1),
+            _ => None,
+        }
+    }
+
+    /// Add `key` with the given priority, or lower its
+    /// priority, as with `IndexedHeap::push_or_decrease()`.
+    /// Returns true if the heap changed.
+    pub fn push_or_decrease(&mut self, key: usize, priority: P) -> bool {
+        if key >= self.position.len() {
+            self.position.resize(key + 1, ABSENT);
+        }
+        let i = match self.position[key] {
+            ABSENT => {
+                self.heap.push((key, priority));
+                self.heap.len() - 1
+            }
+            i => {
+                if priority >= self.heap[i].1 {
+                    return false;
+                }
+                self.heap[i].1 = priority;
+                i
+            }
+        };
+        let position = &mut self.position;
+        let i = sift_up(&mut self.heap, i, |&k, i| position[k] = i);
+        position[key] = i;
+        true
+    }
+
+    /// The key with the least priority and its priority,
+    /// without removing it.
+    pub fn peek(&self) -> Option<(usize, &P)> {
+        self.heap.first().map(|(k, p)| (*k, p))
+    }
+
+    /// Remove and return the key with the least priority
+    /// and its priority.
+    pub fn pop(&mut self) -> Option<(usize, P)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let (key, priority) = self.heap.swap_remove(0);
+        self.position[key] = ABSENT;
+        if !self.heap.is_empty() {
+            let position = &mut self.position;
+            let i = sift_down(&mut self.heap, 0, |&k, i| position[k] = i);
+            position[self.heap[i].0] = i;
+        }
+        Some((key, priority))
+    }
+}
+
+/// Move the entry at `i` up until its parent is no greater,
+/// and return where it ends up. Each parent moved down is
+/// reported to `moved` with its new index; the entry being
+/// moved is not, so that the caller can record its position
+/// just once.
+fn sift_up<K, P: Ord>(
+    heap: &mut [(K, P)],
+    mut i: usize,
+    mut moved: impl FnMut(&K, usize),
+) -> usize {
+    while i > 0 {
+        let parent = (i - 1) / 2;
+        if heap[parent].1 <= heap[i].1 {
+            break;
+        }
+        heap.swap(i, parent);
+        moved(&heap[i].0, i);
+        i = parent;
+    }
+    i
+}
+
+/// Move the entry at `i` down until its children are no
+/// less, and return where it ends up. Moved children are
+/// reported as with `sift_up()`.
+fn sift_down<K, P: Ord>(
+    heap: &mut [(K, P)],
+    mut i: usize,
+    mut moved: impl FnMut(&K, usize),
+) -> usize {
+    let n = heap.len();
+    loop {
+        let mut least = i;
+        for child in [2 * i + 1, 2 * i + 2] {
+            if child < n && heap[child].1 < heap[least].1 {
+                least = child;
+            }
+        }
+        if least == i {
+            return i;
+        }
+        heap.swap(i, least);
+        moved(&heap[i].0, i);
+        i = least;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::testutil::Rng;
+
+    use std::collections::{BTreeMap, BTreeSet};
+
+    /// Slow but obviously correct `IndexedHeap`.
+    #[derive(Default)]
+    struct Reference {
+        priority: BTreeMap<usize, usize>,
+        order: BTreeSet<(usize, usize)>,
+    }
+
+    impl Reference {
+        fn push_or_decrease(&mut self, key: usize, priority: usize) -> bool {
+            if let Some(&old) = self.priority.get(&key) {
+                if priority >= old {
+                    return false;
+                }
+                self.order.remove(&(old, key));
+            }
+            self.priority.insert(key, priority);
+            self.order.insert((priority, key));
+            true
+        }
+
+        fn min_priority(&self) -> Option<usize> {
+            self.order.first().map(|&(p, _)| p)
+        }
+
+        fn remove(&mut self, key: usize) {
+            let priority = self.priority.remove(&key).unwrap();
+            assert!(self.order.remove(&(priority, key)));
+        }
+    }
+
+    /// The operations `against_reference()` checks, for
+    /// both kinds of heap.
+    trait Queue: Default {
+        fn push_or_decrease(&mut self, key: usize, priority: usize) -> bool;
+        fn pop(&mut self) -> Option<(usize, usize)>;
+        fn len(&self) -> usize;
+        fn contains(&self, key: usize) -> bool;
+        fn peek_priority(&self) -> Option<usize>;
+        fn priority_of(&self, key: usize) -> Option<usize>;
+    }
+
+    impl Queue for IndexedHeap<usize, usize> {
+        fn push_or_decrease(&mut self, key: usize, priority: usize) -> bool {
+            self.push_or_decrease(key, priority)
+        }
+        fn pop(&mut self) -> Option<(usize, usize)> {
+            self.pop()
+        }
+        fn len(&self) -> usize {
+            self.len()
+        }
+        fn contains(&self, key: usize) -> bool {
+            self.contains(&key)
+        }
+        fn peek_priority(&self) -> Option<usize> {
+            self.peek().map(|(_, &p)| p)
+        }
+        fn priority_of(&self, key: usize) -> Option<usize> {
+            self.priority_of(&key).copied()
+        }
+    }
+
+    impl Queue for DenseHeap<usize> {
+        fn push_or_decrease(&mut self, key: usize, priority: usize) -> bool {
+            self.push_or_decrease(key, priority)
+        }
+        fn pop(&mut self) -> Option<(usize, usize)> {
+            self.pop()
+        }
+        fn len(&self) -> usize {
+            self.len()
+        }
+        fn contains(&self, key: usize) -> bool {
+            self.contains(key)
+        }
+        fn peek_priority(&self) -> Option<usize> {
+            self.peek().map(|(_, &p)| p)
+        }
+        fn priority_of(&self, key: usize) -> Option<usize> {
+            self.priority_of(key).copied()
+        }
+    }
+
+    fn against_reference<H: Queue>() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..200 {
+            let mut heap = H::default();
+            let mut reference = Reference::default();
+            let nkeys = rng.below(30) + 1;
+            let npriorities = rng.below(50) + 1;
+            for _ in 0..rng.below(300) {
+                if rng.below(3) == 0 {
+                    let popped = heap.pop();
+                    let least = reference.min_priority();
+                    assert_eq!(popped.map(|(_, p)| p), least);
+                    if let Some((key, priority)) = popped {
+                        assert_eq!(reference.priority[&key], priority);
+                        reference.remove(key);
+                        assert!(!heap.contains(key));
+                    }
+                } else {
+                    let key = rng.below(nkeys);
+                    let priority = rng.below(npriorities);
+                    assert_eq!(
+                        heap.push_or_decrease(key, priority),
+                        reference.push_or_decrease(key, priority),
+                    );
+                }
+                assert_eq!(heap.len(), reference.priority.len());
+                assert_eq!(heap.peek_priority(), reference.min_priority());
+                for key in 0..nkeys {
+                    let priority = reference.priority.get(&key).copied();
+                    assert_eq!(heap.priority_of(key), priority);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn indexed_against_reference() {
+        against_reference::<IndexedHeap<usize, usize>>();
+    }
+
+    #[test]
+    fn dense_against_reference() {
+        against_reference::<DenseHeap<usize>>();
+    }
+
+    #[test]
+    fn heap_operations() {
+        let mut heap = IndexedHeap::new();
+        assert!(heap.is_empty());
+        assert_eq!(heap.pop(), None);
+        assert!(heap.push_or_decrease("c", 3));
+        assert!(heap.push_or_decrease("a", 5));
+        assert!(heap.push_or_decrease("b", 4));
+        assert!(!heap.push_or_decrease("b", 4));
+        assert!(!heap.push_or_decrease("c", 6));
+        assert!(heap.push_or_decrease("a", 1));
+        assert_eq!(heap.priority_of("a"), Some(&1));
+        assert_eq!(heap.peek(), Some((&"a", &1)));
+        assert_eq!(heap.len(), 3);
+        let order: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(order, [("a", 1), ("c", 3), ("b", 4)]);
+        assert!(!heap.contains("a"));
+        assert!(heap.push_or_decrease("a", 9));
+        assert_eq!(heap.pop(), Some(("a", 9)));
+    }
+
+    #[test]
+    fn dense_heap_operations() {
+        let mut heap = DenseHeap::with_keys(2);
+        assert_eq!(heap.pop(), None);
+        assert!(!heap.contains(5));
+        assert_eq!(heap.priority_of(5), None);
+        assert!(heap.push_or_decrease(5, 2));
+        assert!(heap.push_or_decrease(0, 3));
+        assert!(!heap.push_or_decrease(5, 2));
+        assert!(heap.push_or_decrease(0, 1));
+        assert_eq!(heap.peek(), Some((0, &1)));
+        assert_eq!(heap.priority_of(5), Some(&2));
+        assert_eq!(heap.pop(), Some((0, 1)));
+        assert!(!heap.contains(0));
+        assert_eq!(heap.pop(), Some((5, 2)));
+        assert!(heap.is_empty());
+    }
+}
@@ -0,0 +1,301 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Maps from non-overlapping ranges of integers to values.
+//!
+//! # Examples
+//!
+//! Push seed ranges through the almanac's layers of offsets
+//! (2023 day 5). Each range is split where the layer's
+//! ranges start and end, so the work depends on the number
+//! of ranges rather than the number of seeds.
+//!
+//! ```
+//! # use collections::*;
+//! # use std::ops::Range;
+//! let layers = [
+//!     vec![(50, 98, 2), (52, 50, 48)],
+//!     vec![(0, 15, 37), (37, 52, 2), (39, 0, 15)],
+//!     vec![(49, 53, 8), (0, 11, 42), (42, 0, 7), (57, 7, 4)],
+//!     vec![(88, 18, 7), (18, 25, 70)],
+//!     vec![(45, 77, 23), (81, 45, 19), (68, 64, 13)],
+//!     vec![(0, 69, 1), (1, 0, 69)],
+//!     vec![(60, 56, 37), (56, 93, 4)],
+//! ];
+//! // Each layer maps a source range to an offset.
+//! let layers: Vec<IntervalMap<i64>> = layers
+//!     .iter()
+//!     .map(|layer| {
+//!         let mut map = IntervalMap::new();
+//!         for &(dest, src, len) in layer {
+//!             map.insert(src..src + len, dest - src).unwrap();
+//!         }
+//!         map
+//!     })
+//!     .collect();
+//!
+//! let mut ranges: Vec<Range<i64>> = vec![79..79 + 14, 55..55 + 13];
+//! for layer in &layers {
+//!     ranges = ranges
+//!         .iter()
+//!         .flat_map(|r| layer.map_range(r.clone()))
+//!         .map(|(r, offset)| {
+//!             let offset = offset.copied().unwrap_or(0);
+//!             r.start + offset..r.end + offset
+//!         })
+//!         .collect();
+//! }
+//! let lowest = ranges.iter().map(|r| r.start).min();
+//! assert_eq!(lowest, Some(46));
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+
+/// What `IntervalMap::insert()` does with a range that
+/// overlaps ranges already in the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overlap {
+    /// Fail with an `OverlapError`, leaving the map alone.
+    #[default]
+    Error,
+    /// Replace the old values where the ranges overlap,
+    /// trimming or splitting the old ranges.
+    Overwrite,
+}
+
+/// Error when inserting a range that overlaps a range
+/// already in an `IntervalMap`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverlapError {
+    /// The range being inserted.
+    pub range: Range<i64>,
+    /// The first range in the map that it overlaps.
+    pub existing: Range<i64>,
+}
+
+impl fmt::Display for OverlapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "range {:?} overlaps existing range {:?}",
+            self.range, self.existing,
+        )
+    }
+}
+
+impl std::error::Error for OverlapError {}
+
+/// A map from non-overlapping half-open ranges of `i64` to
+/// values. Adjacent ranges are kept separate, even if their
+/// values are equal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalMap<V> {
+    /// Each range, by start, with its end and value.
+    ranges: BTreeMap<i64, (i64, V)>,
+    overlap: Overlap,
+}
+
+impl<V> Default for IntervalMap<V> {
+    fn default() -> Self {
+        IntervalMap {
+            ranges: BTreeMap::new(),
+            overlap: Overlap::default(),
+        }
+    }
+}
+
+impl<V> IntervalMap<V> {
+    /// Make an empty map, in which inserting overlapping
+    /// ranges is an error.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make an empty map treating overlapping insertions as
+    /// given.
+    pub fn with_overlap(overlap: Overlap) -> Self {
+        IntervalMap {
+            ranges: BTreeMap::new(),
+            overlap,
+        }
+    }
+
+    /// Number of ranges in the map.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// True if there are no ranges in the map.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The ranges in the map that overlap `range`, in order,
+    /// as start, end and value.
+    fn overlapping(
+        &self,
+        range: Range<i64>,
+    ) -> impl Iterator<Item = (i64, i64, &V)> {
+        let first = match self.ranges.range(..=range.start).next_back() {
+            Some((&s, &(e, _))) if e > range.start => s,
+            _ => range.start,
+        };
+        let end = range.end.max(first);
+        self.ranges
+            .range(first..end)
+            .map(|(&s, (e, v))| (s, *e, v))
+            .filter(move |&(_, e, _)| e > range.start)
+    }
+
+    /// The value for `point`, if it is in a range in the
+    /// map.
+    pub fn get(&self, point: i64) -> Option<&V> {
+        let (_, (end, value)) = self.ranges.range(..=point).next_back()?;
+        (point < *end).then_some(value)
+    }
+
+    /// Split `range` where ranges in the map start and end,
+    /// giving each piece in order with its value, or `None`
+    /// for pieces in no range in the map. The pieces cover
+    /// `range` exactly.
+    pub fn map_range(
+        &self,
+        range: Range<i64>,
+    ) -> Vec<(Range<i64>, Option<&V>)> {
+        let mut pieces = Vec::new();
+        if range.is_empty() {
+            return pieces;
+        }
+        let mut at = range.start;
+        for (s, e, v) in self.overlapping(range.clone()) {
+            let s = s.max(range.start);
+            if at < s {
+                pieces.push((at..s, None));
+            }
+            let e = e.min(range.end);
+            pieces.push((s..e, Some(v)));
+            at = e;
+        }
+        if at < range.end {
+            pieces.push((at..range.end, None));
+        }
+        pieces
+    }
+
+    /// Iterator over the ranges in the map and their values,
+    /// in order.
+    pub fn iter(&self) -> impl Iterator<Item = (Range<i64>, &V)> {
+        self.ranges.iter().map(|(&s, (e, v))| (s..*e, v))
+    }
+}
+
+impl<V: Clone> IntervalMap<V> {
+    /// Map every point of `range` to `value`. If `range`
+    /// overlaps ranges already in the map, this fails or
+    /// overwrites them as the map was made to. Inserting an
+    /// empty range does nothing.
+    pub fn insert(
+        &mut self,
+        range: Range<i64>,
+        value: V,
+    ) -> Result<(), OverlapError> {
+        if range.is_empty() {
+            return Ok(());
+        }
+        let old: Vec<i64> =
+            self.overlapping(range.clone()).map(|(s, _, _)| s).collect();
+        if let (Overlap::Error, Some(&s)) = (self.overlap, old.first()) {
+            let existing = s..self.ranges[&s].0;
+            return Err(OverlapError { range, existing });
+        }
+        for s in old {
+            let (e, v) = self.ranges.remove(&s).unwrap();
+            if e > range.end {
+                self.ranges.insert(range.end, (e, v.clone()));
+            }
+            if s < range.start {
+                self.ranges.insert(s, (range.start, v));
+            }
+        }
+        self.ranges.insert(range.start, (range.end, value));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn firewall() {
+        // Blocked addresses (2016 day 20), among 0..=9.
+        let mut blocked = IntervalMap::with_overlap(Overlap::Overwrite);
+        for (lo, hi) in [(5, 8), (0, 2), (4, 7)] {
+            blocked.insert(lo..hi + 1, ()).unwrap();
+        }
+        let allowed: Vec<Range<i64>> = blocked
+            .map_range(0..10)
+            .into_iter()
+            .filter(|(_, v)| v.is_none())
+            .map(|(r, _)| r)
+            .collect();
+        assert_eq!(allowed, [3..4, 9..10]);
+        assert_eq!(blocked.get(3), None);
+        assert_eq!(blocked.get(7), Some(&()));
+    }
+
+    #[test]
+    fn overwrite_splits() {
+        let mut map = IntervalMap::with_overlap(Overlap::Overwrite);
+        map.insert(0..10, 'a').unwrap();
+        map.insert(20..30, 'b').unwrap();
+        map.insert(5..7, 'c').unwrap();
+        map.insert(8..25, 'd').unwrap();
+        map.insert(3..3, 'z').unwrap();
+        let ranges: Vec<(Range<i64>, char)> =
+            map.iter().map(|(r, &v)| (r, v)).collect();
+        let expected = [
+            (0..5, 'a'),
+            (5..7, 'c'),
+            (7..8, 'a'),
+            (8..25, 'd'),
+            (25..30, 'b'),
+        ];
+        assert_eq!(ranges, expected);
+        let pieces: Vec<(Range<i64>, Option<char>)> = map
+            .map_range(-5..6)
+            .into_iter()
+            .map(|(r, v)| (r, v.copied()))
+            .collect();
+        let expected = [(-5..0, None), (0..5, Some('a')), (5..6, Some('c'))];
+        assert_eq!(pieces, expected);
+        assert_eq!(map.map_range(40..50), [(40..50, None)]);
+        assert_eq!(map.map_range(9..9), []);
+        assert_eq!(map.get(29), Some(&'b'));
+        assert_eq!(map.get(30), None);
+        assert_eq!(map.get(-1), None);
+        assert_eq!(map.len(), 5);
+    }
+
+    #[test]
+    fn overlap_errors() {
+        let mut map = IntervalMap::new();
+        map.insert(0..10, 1).unwrap();
+        map.insert(10..20, 2).unwrap();
+        map.insert(-5..0, 3).unwrap();
+        let err = map.insert(15..25, 4).unwrap_err();
+        assert_eq!(err.existing, 10..20);
+        let message = "range 15..25 overlaps existing range 10..20";
+        assert_eq!(err.to_string(), message);
+        assert_eq!(map.insert(-10..-4, 5).unwrap_err().existing, -5..0);
+        assert_eq!(map.len(), 3);
+        assert!(!map.is_empty());
+        assert_eq!(map.get(0), Some(&1));
+        assert_eq!(map.get(19), Some(&2));
+    }
+}
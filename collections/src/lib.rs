@@ -0,0 +1,35 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Collection types for Advent of Code solutions.
+//!
+//! These wrap the standard collections for jobs that come
+//! up over and over, such as counting things, keeping
+//! insertion order, and caching the values of recursive
+//! functions.
+
+mod bitset;
+mod counter;
+mod heap;
+mod interval;
+mod memo;
+mod ordered;
+mod ranges;
+mod ring;
+mod unionfind;
+mod window;
+pub use bitset::*;
+pub use counter::*;
+pub use heap::*;
+pub use interval::*;
+pub use memo::*;
+pub use ordered::*;
+pub use ranges::*;
+pub use ring::*;
+pub use unionfind::*;
+pub use window::*;
+
+#[cfg(test)]
+mod testutil;
@@ -0,0 +1,304 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Memoizing recursive computations.
+//!
+//! A `Memo` is a cache threaded through a recursive
+//! function by hand; the `memoize!` macro instead wraps a
+//! function with a hidden cache of its own.
+//!
+//! # Examples
+//!
+//! Count the arrangements of damaged springs matching the
+//! group sizes (2023 day 12). The plain recursion takes
+//! exponential time on long rows. The memoized one is keyed
+//! by how much of the row and groups are left, since those
+//! are always suffixes of the originals.
+//!
+//! ```
+//! # use collections::*;
+//! fn arrangements(springs: &[u8], groups: &[usize]) -> u64 {
+//!     let place = |springs: &[u8], groups: &[usize]| {
+//!         let Some((&g, rest)) = groups.split_first() else {
+//!             return 0;
+//!         };
+//!         if springs.len() < g || springs[..g].contains(&b'.') {
+//!             return 0;
+//!         }
+//!         match springs.get(g) {
+//!             None => rest.is_empty() as u64,
+//!             Some(b'#') => 0,
+//!             Some(_) => arrangements(&springs[g + 1..], rest),
+//!         }
+//!     };
+//!     match springs.first() {
+//!         None => groups.is_empty() as u64,
+//!         Some(b'.') => arrangements(&springs[1..], groups),
+//!         Some(b'#') => place(springs, groups),
+//!         Some(_) => {
+//!             arrangements(&springs[1..], groups) + place(springs, groups)
+//!         }
+//!     }
+//! }
+//!
+//! type Cache = Memo<(usize, usize), u64>;
+//!
+//! fn arrangements_memo(
+//!     memo: &mut Cache,
+//!     springs: &[u8],
+//!     groups: &[usize],
+//! ) -> u64 {
+//!     let place = |memo: &mut Cache, springs: &[u8], groups: &[usize]| {
+//!         let Some((&g, rest)) = groups.split_first() else {
+//!             return 0;
+//!         };
+//!         if springs.len() < g || springs[..g].contains(&b'.') {
+//!             return 0;
+//!         }
+//!         match springs.get(g) {
+//!             None => rest.is_empty() as u64,
+//!             Some(b'#') => 0,
+//!             Some(_) => arrangements_memo(memo, &springs[g + 1..], rest),
+//!         }
+//!     };
+//!     memo.get_or_compute((springs.len(), groups.len()), |memo| {
+//!         match springs.first() {
+//!             None => groups.is_empty() as u64,
+//!             Some(b'.') => arrangements_memo(memo, &springs[1..], groups),
+//!             Some(b'#') => place(memo, springs, groups),
+//!             Some(_) => {
+//!                 arrangements_memo(memo, &springs[1..], groups)
+//!                     + place(memo, springs, groups)
+//!             }
+//!         }
+//!     })
+//! }
+//!
+//! let rows = [
+//!     ("???.###", vec![1, 1, 3]),
+//!     (".??..??...?##.", vec![1, 1, 3]),
+//!     ("?#?#?#?#?#?#?#?", vec![1, 3, 1, 6]),
+//!     ("????.#...#...", vec![4, 1, 1]),
+//!     ("????.######..#####.", vec![1, 6, 5]),
+//!     ("?###????????", vec![3, 2, 1]),
+//! ];
+//! let mut plain = 0;
+//! let mut cached = 0;
+//! for (springs, groups) in &rows {
+//!     plain += arrangements(springs.as_bytes(), groups);
+//!     let mut memo = Memo::new();
+//!     cached += arrangements_memo(&mut memo, springs.as_bytes(), groups);
+//! }
+//! assert_eq!((plain, cached), (21, 21));
+//!
+//! // Unfolded, the rows are five times longer: too long to
+//! // count without the cache.
+//! let mut unfolded = 0;
+//! for (springs, groups) in &rows {
+//!     let springs = [*springs; 5].join("?");
+//!     let groups = groups.repeat(5);
+//!     let mut memo = Memo::new();
+//!     unfolded += arrangements_memo(&mut memo, springs.as_bytes(), &groups);
+//! }
+//! assert_eq!(unfolded, 525152);
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A cache of the values of a function, to be passed
+/// through a recursive computation of it.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo {
+            cache: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    /// Make an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The value for `key`: the cached one if any, or else
+    /// the value computed by `f`, which is then cached. The
+    /// cache is passed to `f` for its recursive calls.
+    pub fn get_or_compute<F>(&mut self, key: K, f: F) -> V
+    where
+        F: FnOnce(&mut Self) -> V,
+    {
+        if let Some(value) = self.cache.get(&key) {
+            return value.clone();
+        }
+        let value = f(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// The cached value for `key`, if any.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// Number of values cached.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// True if nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Forget every cached value.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+/// Wrap a function definition so that its values are
+/// cached, keyed by the tuple of its arguments. The function
+/// can call itself recursively, getting the cached version.
+///
+/// The arguments must be owned values that are `Clone`,
+/// `Eq`, `Hash` and `'static`, and the result `Clone`. The
+/// cache is a thread-local private to the function, so it
+/// lasts for the life of the thread: fine for a puzzle
+/// solution, but only pure functions should be memoized.
+///
+/// # Examples
+///
+/// Count stones after blinking (2024 day 11). Every blink
+/// splits stones with an even number of digits, so 75
+/// blinks make far too many stones to count one by one.
+///
+/// ```
+/// # use collections::memoize;
+/// memoize! {
+///     /// Number of stones that `stone` becomes after the
+///     /// given number of blinks.
+///     fn stones(stone: u64, blinks: usize) -> u64 {
+///         if blinks == 0 {
+///             return 1;
+///         }
+///         if stone == 0 {
+///             return stones(1, blinks - 1);
+///         }
+///         let digits = stone.ilog10() + 1;
+///         if digits % 2 == 0 {
+///             let half = 10u64.pow(digits / 2);
+///             stones(stone / half, blinks - 1)
+///                 + stones(stone % half, blinks - 1)
+///         } else {
+///             stones(stone * 2024, blinks - 1)
+///         }
+///     }
+/// }
+///
+/// assert_eq!(stones(125, 25) + stones(17, 25), 55312);
+/// assert_eq!(stones(125, 75) + stones(17, 75), 65601038650482);
+/// ```
+#[macro_export]
+macro_rules! memoize {
+    (
+        $(#[$attr:meta])*
+        $vis:vis fn $name:ident($($arg:ident: $ty:ty),* $(,)?) -> $ret:ty
+        $body:block
+    ) => {
+        $(#[$attr])*
+        $vis fn $name($($arg: $ty),*) -> $ret {
+            // The body as a plain function; its calls to the
+            // outer name reach the cached version.
+            fn uncached($($arg: $ty),*) -> $ret $body
+
+            ::std::thread_local! {
+                static CACHE: ::std::cell::RefCell<
+                    ::std::collections::HashMap<($($ty,)*), $ret>,
+                > = ::std::cell::RefCell::new(
+                    ::std::collections::HashMap::new(),
+                );
+            }
+
+            let key = ($(::std::clone::Clone::clone(&$arg),)*);
+            let cached = CACHE.with(|c| c.borrow().get(&key).cloned());
+            if let Some(value) = cached {
+                return value;
+            }
+            // The cache must not stay borrowed while
+            // computing, since the computation uses it.
+            let value = uncached($($arg),*);
+            CACHE.with(|c| c.borrow_mut().insert(key, value.clone()));
+            value
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use std::cell::Cell;
+
+    thread_local! {
+        static CALLS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    memoize! {
+        fn paths(rows: usize, cols: usize) -> u128 {
+            CALLS.with(|c| c.set(c.get() + 1));
+            if rows == 0 || cols == 0 {
+                1
+            } else {
+                paths(rows - 1, cols) + paths(rows, cols - 1)
+            }
+        }
+    }
+
+    memoize! {
+        pub(crate) fn join(words: Vec<String>, sep: char,) -> String {
+            words.join(&sep.to_string())
+        }
+    }
+
+    #[test]
+    fn macro_caches() {
+        // Lattice paths: C(60, 30), in one call per cell but
+        // the unreachable corner.
+        assert_eq!(paths(30, 30), 118264581564861424);
+        assert_eq!(CALLS.with(Cell::get), 31 * 31 - 1);
+        assert_eq!(paths(30, 30), 118264581564861424);
+        assert_eq!(CALLS.with(Cell::get), 31 * 31 - 1);
+
+        let words = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(join(words.clone(), '-'), "a-b");
+        assert_eq!(join(words, '+'), "a+b");
+    }
+
+    #[test]
+    fn memo_threading() {
+        fn fib(memo: &mut Memo<u32, u64>, n: u32) -> u64 {
+            if n < 2 {
+                return n as u64;
+            }
+            memo.get_or_compute(n, |memo| fib(memo, n - 1) + fib(memo, n - 2))
+        }
+
+        let mut memo = Memo::new();
+        assert_eq!(fib(&mut memo, 90), 2880067194370816120);
+        assert_eq!(memo.len(), 89);
+        assert_eq!(memo.get(&10), Some(&55));
+        assert_eq!(memo.get(&1), None);
+        memo.clear();
+        assert!(memo.is_empty());
+    }
+}
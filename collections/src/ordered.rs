@@ -0,0 +1,454 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Maps and sets that remember insertion order.
+//!
+//! Iterating a `HashMap` visits its entries in a different
+//! order every run, which makes tie-breaking by "first
+//! found" and debugging output unreproducible. An
+//! `OrderedMap` iterates in the order keys were first
+//! inserted, while still looking keys up by hash.
+//!
+//! Removal keeps the order of the remaining entries, at the
+//! cost of taking time linear in the number of entries
+//! after the one removed.
+//!
+//! # Examples
+//!
+//! Lenses in a box (2023 day 15): a replaced lens keeps its
+//! place, and a removed one closes the gap.
+//!
+//! ```
+//! # use collections::*;
+//! let mut lenses = OrderedMap::new();
+//! for step in "rn=1 cm- qp=3 cm=2 qp- pc=4 ot=9 ab=5 pc- pc=6 ot=7"
+//!     .split(' ')
+//! {
+//!     if let Some(label) = step.strip_suffix('-') {
+//!         lenses.remove(label);
+//!     } else {
+//!         let (label, focus) = step.split_once('=').unwrap();
+//!         lenses.insert(label, focus.parse::<u32>().unwrap());
+//!     }
+//! }
+//! let order: Vec<(&str, u32)> =
+//!     lenses.iter().map(|(&l, &f)| (l, f)).collect();
+//! assert_eq!(order, [("rn", 1), ("cm", 2), ("ot", 7), ("ab", 5), ("pc", 6)]);
+//! ```
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops;
+use std::slice;
+use std::vec;
+
+/// A map that iterates in the order its keys were first
+/// inserted. Keys are stored twice, so must be `Clone`.
+#[derive(Debug, Clone)]
+pub struct OrderedMap<K, V> {
+    entries: Vec<(K, V)>,
+    index: HashMap<K, usize>,
+}
+
+impl<K, V> Default for OrderedMap<K, V> {
+    fn default() -> Self {
+        OrderedMap {
+            entries: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, V> OrderedMap<K, V> {
+    /// Make an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map `key` to `value`, returning the old value if
+    /// any. A key already present keeps its place in the
+    /// order.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(&i) = self.index.get(&key) {
+            return Some(std::mem::replace(&mut self.entries[i].1, value));
+        }
+        self.index.insert(key.clone(), self.entries.len());
+        self.entries.push((key, value));
+        None
+    }
+
+    /// The value for `key`, if present.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let &i = self.index.get(key)?;
+        Some(&self.entries[i].1)
+    }
+
+    /// The value for `key`, if present, for changing.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let &i = self.index.get(key)?;
+        Some(&mut self.entries[i].1)
+    }
+
+    /// The value for `key`, first inserting the value made
+    /// by `f` if the key is not present.
+    pub fn get_or_insert_with<F>(&mut self, key: K, f: F) -> &mut V
+    where
+        F: FnOnce() -> V,
+    {
+        let i = match self.index.get(&key) {
+            Some(&i) => i,
+            None => {
+                self.insert(key, f());
+                self.entries.len() - 1
+            }
+        };
+        &mut self.entries[i].1
+    }
+
+    /// True if `key` is present.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.index.contains_key(key)
+    }
+
+    /// Remove `key`, returning its value if it was present.
+    /// The remaining entries keep their order, so this takes
+    /// time proportional to the number of entries after the
+    /// one removed.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let i = self.index.remove(key)?;
+        let (_, value) = self.entries.remove(i);
+        for (k, _) in &self.entries[i..] {
+            *self.index.get_mut::<K>(k).unwrap() -= 1;
+        }
+        Some(value)
+    }
+
+    /// The entry at position `i` in insertion order.
+    pub fn get_index(&self, i: usize) -> Option<(&K, &V)> {
+        self.entries.get(i).map(|(k, v)| (k, v))
+    }
+
+    /// Number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// True if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove every entry.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.index.clear();
+    }
+
+    /// Iterator over the entries in insertion order.
+    pub fn iter(&self) -> OrderedIter<'_, K, V> {
+        OrderedIter(self.entries.iter())
+    }
+
+    /// Iterator over the keys in insertion order.
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    /// Iterator over the values in insertion order of their
+    /// keys.
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> {
+        self.entries.iter().map(|(_, v)| v)
+    }
+}
+
+/// Maps are equal if they have the same entries in the same
+/// order.
+impl<K: PartialEq, V: PartialEq> PartialEq for OrderedMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries == other.entries
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for OrderedMap<K, V> {}
+
+/// # Panics
+///
+/// Panics if the key is not present.
+impl<K, V, Q> ops::Index<&Q> for OrderedMap<K, V>
+where
+    K: Clone + Eq + Hash + Borrow<Q>,
+    Q: Eq + Hash + ?Sized,
+{
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("key not in map")
+    }
+}
+
+/// Iterator over the entries of an `OrderedMap` in
+/// insertion order.
+#[derive(Debug, Clone)]
+pub struct OrderedIter<'a, K, V>(slice::Iter<'a, (K, V)>);
+
+impl<'a, K, V> Iterator for OrderedIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, v)| (k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for OrderedIter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(k, v)| (k, v))
+    }
+}
+
+impl<'a, K: Clone + Eq + Hash, V> IntoIterator for &'a OrderedMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = OrderedIter<'a, K, V>;
+
+    fn into_iter(self) -> OrderedIter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<K, V> IntoIterator for OrderedMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = vec::IntoIter<(K, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<K: Clone + Eq + Hash, V> Extend<(K, V)> for OrderedMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, entries: I) {
+        for (k, v) in entries {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, V> FromIterator<(K, V)> for OrderedMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(entries: I) -> Self {
+        let mut map = OrderedMap::new();
+        map.extend(entries);
+        map
+    }
+}
+
+/// A set that iterates in the order its items were first
+/// inserted. See `OrderedMap`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderedSet<T>(OrderedMap<T, ()>);
+
+impl<T> Default for OrderedSet<T> {
+    fn default() -> Self {
+        OrderedSet(OrderedMap::default())
+    }
+}
+
+impl<T: Clone + Eq + Hash> OrderedSet<T> {
+    /// Make an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `item`, returning true if it was not already
+    /// present. An item already present keeps its place.
+    pub fn insert(&mut self, item: T) -> bool {
+        self.0.insert(item, ()).is_none()
+    }
+
+    /// True if `item` is present.
+    pub fn contains<Q>(&self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.0.contains_key(item)
+    }
+
+    /// Remove `item`, returning true if it was present. As
+    /// for `OrderedMap::remove()`, this takes linear time.
+    pub fn remove<Q>(&mut self, item: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.0.remove(item).is_some()
+    }
+
+    /// The item at position `i` in insertion order.
+    pub fn get_index(&self, i: usize) -> Option<&T> {
+        self.0.get_index(i).map(|(item, _)| item)
+    }
+
+    /// Number of items.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// True if there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Remove every item.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Iterator over the items in insertion order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.0.iter().map(|(item, _)| item)
+    }
+}
+
+impl<T> IntoIterator for OrderedSet<T> {
+    type Item = T;
+    type IntoIter = std::iter::Map<vec::IntoIter<(T, ())>, fn((T, ())) -> T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let item: fn((T, ())) -> T = |(item, _)| item;
+        self.0.into_iter().map(item)
+    }
+}
+
+impl<T: Clone + Eq + Hash> Extend<T> for OrderedSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        for item in items {
+            self.insert(item);
+        }
+    }
+}
+
+impl<T: Clone + Eq + Hash> FromIterator<T> for OrderedSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(items: I) -> Self {
+        let mut set = OrderedSet::new();
+        set.extend(items);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// Pseudo-random keys, for building maps of.
+    fn keys(n: usize) -> Vec<u64> {
+        let mut x = 0x2545_f491_4f6c_dd1d_u64;
+        (0..n)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                x % 1000
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reproducible_order() {
+        let build = || {
+            let mut map = OrderedMap::new();
+            for (i, k) in keys(500).into_iter().enumerate() {
+                *map.get_or_insert_with(k, || 0) += i;
+                if i % 7 == 0 {
+                    map.remove(&((k + 1) % 1000));
+                }
+            }
+            map
+        };
+        let (a, b) = (build(), build());
+        let first: Vec<(u64, usize)> =
+            a.iter().map(|(&k, &v)| (k, v)).collect();
+        let second: Vec<(u64, usize)> = b.into_iter().collect();
+        assert_eq!(first, second);
+
+        // The same as keeping a list of entries and searching
+        // it.
+        let mut list: Vec<(u64, usize)> = Vec::new();
+        for (i, k) in keys(500).into_iter().enumerate() {
+            match list.iter_mut().find(|(l, _)| *l == k) {
+                Some((_, v)) => *v += i,
+                None => list.push((k, i)),
+            }
+            if i % 7 == 0 {
+                list.retain(|&(l, _)| l != (k + 1) % 1000);
+            }
+        }
+        assert_eq!(first, list);
+        for (i, (k, v)) in list.iter().enumerate() {
+            assert_eq!(a.get_index(i), Some((k, v)));
+            assert_eq!(a[k], *v);
+        }
+    }
+
+    #[test]
+    fn map_operations() {
+        let mut map: OrderedMap<String, u32> =
+            [("c", 3), ("a", 1), ("b", 2)]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect();
+        assert_eq!(map.insert("a".to_string(), 10), Some(1));
+        assert_eq!(map.get("a"), Some(&10));
+        assert_eq!(map.get("z"), None);
+        *map.get_mut("b").unwrap() += 1;
+        assert_eq!(map.remove("c"), Some(3));
+        assert_eq!(map.remove("c"), None);
+        map.insert("c".to_string(), 4);
+        let entries: Vec<(&str, u32)> =
+            map.iter().map(|(k, &v)| (k.as_str(), v)).collect();
+        assert_eq!(entries, [("a", 10), ("b", 3), ("c", 4)]);
+        let values: Vec<u32> = map.values().rev().copied().collect();
+        assert_eq!(values, [4, 3, 10]);
+        assert_eq!(map["c"], 4);
+        assert!(map.contains_key("b"));
+        map.clear();
+        assert!(map.is_empty());
+        assert_eq!(map.get_index(0), None);
+    }
+
+    #[test]
+    fn set_operations() {
+        let mut set: OrderedSet<char> = "mississippi".chars().collect();
+        assert_eq!(set.iter().collect::<String>(), "misp");
+        assert!(!set.insert('s'));
+        assert!(set.remove(&'s'));
+        assert!(!set.contains(&'s'));
+        assert!(set.insert('s'));
+        assert_eq!(set.get_index(1), Some(&'i'));
+        assert_eq!(set.len(), 4);
+        assert_eq!(set.clone().into_iter().collect::<String>(), "mips");
+        set.clear();
+        assert!(set.is_empty());
+    }
+}
@@ -0,0 +1,341 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Sets of integers kept as sorted, disjoint ranges.
+//!
+//! # Examples
+//!
+//! Positions on a row that no beacon can occupy (2022
+//! day 15): each sensor rules out a range of the row, and
+//! the known beacon on the row is not ruled out.
+//!
+//! ```
+//! # use collections::*;
+//! let sensors: [((i64, i64), (i64, i64)); 14] = [
+//!     ((2, 18), (-2, 15)), ((9, 16), (10, 16)), ((13, 2), (15, 3)),
+//!     ((12, 14), (10, 16)), ((10, 20), (10, 16)), ((14, 17), (10, 16)),
+//!     ((8, 7), (2, 10)), ((2, 0), (2, 10)), ((0, 11), (2, 10)),
+//!     ((20, 14), (25, 17)), ((17, 20), (21, 22)), ((16, 7), (15, 3)),
+//!     ((14, 3), (15, 3)), ((20, 1), (15, 3)),
+//! ];
+//! let covered = |y: i64| -> Ranges {
+//!     sensors
+//!         .iter()
+//!         .filter_map(|&((sx, sy), (bx, by))| {
+//!             let radius = (sx - bx).abs() + (sy - by).abs();
+//!             let reach = radius - (sy - y).abs();
+//!             (reach >= 0).then(|| sx - reach..=sx + reach)
+//!         })
+//!         .collect()
+//! };
+//! let mut row = covered(10);
+//! row.remove(2..=2);
+//! assert_eq!(row.total_len(), 26);
+//!
+//! // The one place left for the distress beacon.
+//! assert_eq!(covered(11).gaps(0..=20), Ranges::from(14..=14));
+//! ```
+
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+use crate::IntervalMap;
+
+/// A set of `i64` kept as sorted, disjoint, inclusive
+/// ranges. Ranges that overlap or are adjacent are merged,
+/// so the representation of a set is unique.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Ranges {
+    /// Each range, by start, with its end.
+    ranges: BTreeMap<i64, i64>,
+}
+
+impl Ranges {
+    /// Make an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of disjoint ranges in the set.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// True if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Iterator over the ranges of the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<i64>> + '_ {
+        self.ranges.iter().map(|(&s, &e)| s..=e)
+    }
+
+    /// True if `x` is in the set.
+    pub fn contains(&self, x: i64) -> bool {
+        self.ranges
+            .range(..=x)
+            .next_back()
+            .is_some_and(|(_, &e)| e >= x)
+    }
+
+    /// The number of integers in the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the count does not fit in `u64`, which
+    /// takes a set covering all of `i64`.
+    pub fn total_len(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|(&s, &e)| e.abs_diff(s).checked_add(1))
+            .try_fold(0u64, |total, n| total.checked_add(n?))
+            .expect("range set too big to count")
+    }
+
+    /// The starts of the ranges in the set that overlap
+    /// `s..=e`, or are adjacent to it if `adjacent`.
+    fn touching(&self, s: i64, e: i64, adjacent: bool) -> Vec<i64> {
+        let (lo, hi) = if adjacent {
+            (s.saturating_sub(1), e.saturating_add(1))
+        } else {
+            (s, e)
+        };
+        // Ranges are disjoint, so ends decrease along with
+        // starts.
+        self.ranges
+            .range(..=hi)
+            .rev()
+            .take_while(|(_, &end)| end >= lo)
+            .map(|(&start, _)| start)
+            .collect()
+    }
+
+    /// Add every integer in `range` to the set. Adding an
+    /// empty range does nothing.
+    pub fn insert(&mut self, range: RangeInclusive<i64>) {
+        let (mut s, mut e) = range.into_inner();
+        if s > e {
+            return;
+        }
+        for start in self.touching(s, e, true) {
+            let end = self.ranges.remove(&start).unwrap();
+            s = s.min(start);
+            e = e.max(end);
+        }
+        self.ranges.insert(s, e);
+    }
+
+    /// Take every integer in `range` out of the set,
+    /// trimming or splitting the ranges it overlaps.
+    pub fn remove(&mut self, range: RangeInclusive<i64>) {
+        let (s, e) = range.into_inner();
+        if s > e {
+            return;
+        }
+        for start in self.touching(s, e, false) {
+            let end = self.ranges.remove(&start).unwrap();
+            if start < s {
+                self.ranges.insert(start, s - 1);
+            }
+            if end > e {
+                self.ranges.insert(e + 1, end);
+            }
+        }
+    }
+
+    /// Add every integer in `other` to the set.
+    pub fn merge(&mut self, other: &Ranges) {
+        for r in other.iter() {
+            self.insert(r);
+        }
+    }
+
+    /// The integers in both this set and `other`.
+    pub fn intersect(&self, other: &Ranges) -> Ranges {
+        let mut result = Ranges::new();
+        let mut mine = self.iter().peekable();
+        let mut theirs = other.iter().peekable();
+        while let (Some(a), Some(b)) = (mine.peek(), theirs.peek()) {
+            let s = *a.start().max(b.start());
+            let e = *a.end().min(b.end());
+            result.insert(s..=e);
+            if a.end() < b.end() {
+                mine.next();
+            } else {
+                theirs.next();
+            }
+        }
+        result
+    }
+
+    /// The integers in this set but not in `other`.
+    pub fn subtract(&self, other: &Ranges) -> Ranges {
+        let mut result = self.clone();
+        for r in other.iter() {
+            result.remove(r);
+        }
+        result
+    }
+
+    /// The integers in `within` that are not in the set.
+    pub fn gaps(&self, within: RangeInclusive<i64>) -> Ranges {
+        Ranges::from(within).subtract(self)
+    }
+
+    /// Shift each integer in the set by the offset `map`
+    /// gives it, or leave it alone if it has none. Ranges
+    /// are split where the map's ranges start and end.
+    ///
+    /// # Panics
+    ///
+    /// Panics on overflow at the ends of `i64`.
+    pub fn map_offsets(&self, map: &IntervalMap<i64>) -> Ranges {
+        self.iter()
+            .flat_map(|r| map.map_range(*r.start()..*r.end() + 1))
+            .map(|(r, offset)| {
+                let offset = offset.copied().unwrap_or(0);
+                r.start + offset..=r.end - 1 + offset
+            })
+            .collect()
+    }
+}
+
+impl From<RangeInclusive<i64>> for Ranges {
+    fn from(range: RangeInclusive<i64>) -> Self {
+        let mut ranges = Ranges::new();
+        ranges.insert(range);
+        ranges
+    }
+}
+
+impl FromIterator<RangeInclusive<i64>> for Ranges {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = RangeInclusive<i64>>,
+    {
+        let mut ranges = Ranges::new();
+        ranges.extend(iter);
+        ranges
+    }
+}
+
+impl Extend<RangeInclusive<i64>> for Ranges {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = RangeInclusive<i64>>,
+    {
+        for r in iter {
+            self.insert(r);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn ranges(rs: &[(i64, i64)]) -> Ranges {
+        rs.iter().map(|&(s, e)| s..=e).collect()
+    }
+
+    fn pairs(ranges: &Ranges) -> Vec<(i64, i64)> {
+        ranges.iter().map(|r| r.into_inner()).collect()
+    }
+
+    #[test]
+    fn insert_merges() {
+        // Overlapping.
+        assert_eq!(pairs(&ranges(&[(1, 5), (3, 8)])), [(1, 8)]);
+        // Nested.
+        assert_eq!(pairs(&ranges(&[(1, 10), (3, 4)])), [(1, 10)]);
+        assert_eq!(pairs(&ranges(&[(3, 4), (1, 10)])), [(1, 10)]);
+        // Adjacent.
+        assert_eq!(pairs(&ranges(&[(1, 3), (4, 6)])), [(1, 6)]);
+        assert_eq!(pairs(&ranges(&[(4, 6), (1, 3)])), [(1, 6)]);
+        // Disjoint.
+        let set = ranges(&[(10, 12), (1, 3), (5, 6)]);
+        assert_eq!(pairs(&set), [(1, 3), (5, 6), (10, 12)]);
+        assert_eq!(set.len(), 3);
+        // Bridging several.
+        let mut bridged = set.clone();
+        bridged.insert(2..=10);
+        assert_eq!(pairs(&bridged), [(1, 12)]);
+        // Empty.
+        let mut same = set.clone();
+        let (lo, hi) = (8, 7);
+        same.insert(lo..=hi);
+        assert_eq!(same, set);
+        // At the ends of i64.
+        let ends = ranges(&[(i64::MIN, 0), (1, i64::MAX)]);
+        assert_eq!(pairs(&ends), [(i64::MIN, i64::MAX)]);
+    }
+
+    #[test]
+    fn queries() {
+        let set = ranges(&[(1, 3), (5, 6), (-10, -10)]);
+        assert!(set.contains(1));
+        assert!(set.contains(3));
+        assert!(!set.contains(4));
+        assert!(set.contains(-10));
+        assert!(!set.contains(7));
+        assert_eq!(set.total_len(), 6);
+        assert_eq!(Ranges::new().total_len(), 0);
+        assert!(Ranges::new().is_empty());
+        let half = Ranges::from(0..=i64::MAX);
+        assert_eq!(half.total_len(), 1 << 63);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = ranges(&[(0, 10), (20, 30)]);
+        let b = ranges(&[(5, 25), (28, 40)]);
+        assert_eq!(pairs(&a.intersect(&b)), [(5, 10), (20, 25), (28, 30)]);
+        assert_eq!(a.intersect(&b), b.intersect(&a));
+        assert_eq!(pairs(&a.subtract(&b)), [(0, 4), (26, 27)]);
+        assert_eq!(pairs(&b.subtract(&a)), [(11, 19), (31, 40)]);
+        let mut c = a.clone();
+        c.merge(&b);
+        assert_eq!(pairs(&c), [(0, 40)]);
+
+        // Nothing left.
+        assert!(a.subtract(&c).is_empty());
+        assert!(a.intersect(&Ranges::new()).is_empty());
+        assert_eq!(a.subtract(&Ranges::new()), a);
+
+        // Splitting a range in the middle.
+        let mut d = Ranges::from(0..=10);
+        d.remove(4..=6);
+        assert_eq!(pairs(&d), [(0, 3), (7, 10)]);
+        d.remove(-5..=0);
+        d.remove(10..=15);
+        assert_eq!(pairs(&d), [(1, 3), (7, 9)]);
+    }
+
+    #[test]
+    fn gaps() {
+        let set = ranges(&[(2, 3), (6, 8)]);
+        assert_eq!(pairs(&set.gaps(0..=10)), [(0, 1), (4, 5), (9, 10)]);
+        assert_eq!(pairs(&set.gaps(3..=6)), [(4, 5)]);
+        assert!(set.gaps(6..=8).is_empty());
+        assert_eq!(pairs(&Ranges::new().gaps(1..=2)), [(1, 2)]);
+    }
+
+    #[test]
+    fn offsets() {
+        let mut map = IntervalMap::new();
+        map.insert(98..100, -48).unwrap();
+        map.insert(50..98, 2).unwrap();
+        // 2023 day 5 seed ranges through the first layer.
+        let seeds = ranges(&[(79, 92), (55, 67)]);
+        assert_eq!(pairs(&seeds.map_offsets(&map)), [(57, 69), (81, 94)]);
+        // Split at the map's boundaries, and pieces that
+        // land next to each other merge.
+        let set = ranges(&[(45, 99)]);
+        let mapped = set.map_offsets(&map);
+        assert_eq!(pairs(&mapped), [(45, 99)]);
+    }
+}
@@ -0,0 +1,476 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! A circular sequence with a current position.
+//!
+//! A `Ring` is a doubly-linked list kept in a vector of
+//! slots, so that inserting and removing at the current
+//! position take constant time, and rotating takes time
+//! proportional to the distance moved. Each value stays in
+//! the same slot while it is in the ring, so a puzzle can
+//! keep its own index of slots to jump straight to a value.
+//!
+//! # Examples
+//!
+//! The marble game (2018 day 9).
+//!
+//! ```
+//! # use collections::*;
+//! fn high_score(players: usize, last: u64) -> u64 {
+//!     let mut scores = vec![0; players];
+//!     let mut circle: Ring<u64> = [0].into_iter().collect();
+//!     for marble in 1..=last {
+//!         if marble % 23 == 0 {
+//!             circle.rotate(-7);
+//!             let removed = circle.remove_current().unwrap();
+//!             scores[marble as usize % players] += marble + removed;
+//!         } else {
+//!             circle.rotate(1);
+//!             circle.insert_after_current(marble);
+//!             circle.rotate(1);
+//!         }
+//!     }
+//!     scores.into_iter().max().unwrap()
+//! }
+//!
+//! assert_eq!(high_score(9, 25), 32);
+//! assert_eq!(high_score(10, 1618), 8317);
+//! assert_eq!(high_score(30, 5807), 37305);
+//! ```
+
+/// A value in a ring, and its neighbors' slots.
+#[derive(Debug, Clone)]
+struct Slot<T> {
+    value: Option<T>,
+    prev: usize,
+    next: usize,
+}
+
+/// A circular sequence of values with a current position.
+/// Values are kept in numbered slots, and keep their slot
+/// until they are removed; the slots of removed values are
+/// then reused.
+#[derive(Debug, Clone)]
+pub struct Ring<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<usize>,
+    current: Option<usize>,
+    len: usize,
+}
+
+impl<T> Default for Ring<T> {
+    fn default() -> Self {
+        Ring {
+            slots: Vec::new(),
+            free: Vec::new(),
+            current: None,
+            len: 0,
+        }
+    }
+}
+
+impl<T> Ring<T> {
+    /// Make an empty ring.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of values in the ring.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// True if the ring is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The current value, or `None` if the ring is empty.
+    pub fn current(&self) -> Option<&T> {
+        self.slots[self.current?].value.as_ref()
+    }
+
+    /// The current value, for changing.
+    pub fn current_mut(&mut self) -> Option<&mut T> {
+        self.slots[self.current?].value.as_mut()
+    }
+
+    /// The slot of the current value, or `None` if the ring
+    /// is empty.
+    pub fn current_slot(&self) -> Option<usize> {
+        self.current
+    }
+
+    /// Make the value in `slot` current.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no value in the slot.
+    pub fn set_current(&mut self, slot: usize) {
+        assert!(self.get(slot).is_some(), "no value in ring slot {}", slot);
+        self.current = Some(slot);
+    }
+
+    /// The value in `slot`, if any.
+    pub fn get(&self, slot: usize) -> Option<&T> {
+        self.slots.get(slot)?.value.as_ref()
+    }
+
+    /// The slot after `slot` in the ring.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no value in the slot.
+    pub fn next_slot(&self, slot: usize) -> usize {
+        assert!(self.get(slot).is_some(), "no value in ring slot {}", slot);
+        self.slots[slot].next
+    }
+
+    /// The slot before `slot` in the ring.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no value in the slot.
+    pub fn prev_slot(&self, slot: usize) -> usize {
+        assert!(self.get(slot).is_some(), "no value in ring slot {}", slot);
+        self.slots[slot].prev
+    }
+
+    /// Put `value` in a free slot between `prev` and `next`,
+    /// returning the slot.
+    fn link(&mut self, value: T, prev: usize, next: usize) -> usize {
+        let slot = Slot {
+            value: Some(value),
+            prev,
+            next,
+        };
+        let i = match self.free.pop() {
+            Some(i) => {
+                self.slots[i] = slot;
+                i
+            }
+            None => {
+                self.slots.push(slot);
+                self.slots.len() - 1
+            }
+        };
+        self.slots[prev].next = i;
+        self.slots[next].prev = i;
+        self.len += 1;
+        i
+    }
+
+    /// Take the value out of `slot`, joining its neighbors.
+    fn unlink(&mut self, i: usize) -> T {
+        let Slot { prev, next, .. } = self.slots[i];
+        self.slots[prev].next = next;
+        self.slots[next].prev = prev;
+        self.free.push(i);
+        self.len -= 1;
+        self.slots[i].value.take().unwrap()
+    }
+
+    /// Insert `value` into an empty ring, as the current
+    /// value.
+    fn insert_first(&mut self, value: T) -> usize {
+        let i = self.free.pop().unwrap_or(self.slots.len());
+        let slot = Slot {
+            value: Some(value),
+            prev: i,
+            next: i,
+        };
+        if i == self.slots.len() {
+            self.slots.push(slot);
+        } else {
+            self.slots[i] = slot;
+        }
+        self.len = 1;
+        self.current = Some(i);
+        i
+    }
+
+    /// Insert `value` just after the current value, which
+    /// stays current, returning its slot. In an empty ring,
+    /// `value` becomes current.
+    pub fn insert_after_current(&mut self, value: T) -> usize {
+        match self.current {
+            None => self.insert_first(value),
+            Some(c) => {
+                let next = self.slots[c].next;
+                self.link(value, c, next)
+            }
+        }
+    }
+
+    /// Insert `value` just before the current value, which
+    /// stays current, returning its slot: so at the end of
+    /// the ring, as seen from the current value. In an empty
+    /// ring, `value` becomes current.
+    pub fn insert_before_current(&mut self, value: T) -> usize {
+        match self.current {
+            None => self.insert_first(value),
+            Some(c) => {
+                let prev = self.slots[c].prev;
+                self.link(value, prev, c)
+            }
+        }
+    }
+
+    /// Remove and return the current value, making the value
+    /// after it current.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let c = self.current?;
+        let next = self.slots[c].next;
+        self.current = (next != c).then_some(next);
+        Some(self.unlink(c))
+    }
+
+    /// Move the current position `n` values forward, or
+    /// backward if `n` is negative. Moves of the length of
+    /// the ring or more wrap around; the shorter way round
+    /// is taken.
+    pub fn rotate(&mut self, n: i64) {
+        let Some(mut c) = self.current else {
+            return;
+        };
+        let len = self.len as i64;
+        let n = n.rem_euclid(len);
+        if n <= len / 2 {
+            for _ in 0..n {
+                c = self.slots[c].next;
+            }
+        } else {
+            for _ in n..len {
+                c = self.slots[c].prev;
+            }
+        }
+        self.current = Some(c);
+    }
+
+    /// Remove and return the `k` values just after the
+    /// current value, in order. Fewer are returned if there
+    /// are not that many values other than the current one.
+    pub fn splice_out(&mut self, k: usize) -> Vec<T> {
+        let Some(c) = self.current else {
+            return Vec::new();
+        };
+        let k = k.min(self.len - 1);
+        (0..k)
+            .map(|_| {
+                let next = self.slots[c].next;
+                self.unlink(next)
+            })
+            .collect()
+    }
+
+    /// Insert `values` in order just after the current value,
+    /// which stays current, returning their slots. In an
+    /// empty ring the first value becomes current.
+    pub fn splice_in<I>(&mut self, values: I) -> Vec<usize>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut slots: Vec<usize> = Vec::new();
+        for value in values {
+            let slot = match slots.last() {
+                None => self.insert_after_current(value),
+                Some(&prev) => {
+                    let next = self.slots[prev].next;
+                    self.link(value, prev, next)
+                }
+            };
+            slots.push(slot);
+        }
+        slots
+    }
+
+    /// Iterator once around the ring, starting with the
+    /// current value.
+    pub fn iter(&self) -> RingIter<'_, T> {
+        RingIter {
+            ring: self,
+            slot: self.current.unwrap_or(0),
+            left: self.len,
+        }
+    }
+}
+
+/// Iterator once around a `Ring` from its current value.
+#[derive(Debug, Clone)]
+pub struct RingIter<'a, T> {
+    ring: &'a Ring<T>,
+    slot: usize,
+    left: usize,
+}
+
+impl<'a, T> Iterator for RingIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.left == 0 {
+            return None;
+        }
+        let slot = &self.ring.slots[self.slot];
+        self.left -= 1;
+        self.slot = slot.next;
+        slot.value.as_ref()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.left, Some(self.left))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Ring<T> {
+    type Item = &'a T;
+    type IntoIter = RingIter<'a, T>;
+
+    fn into_iter(self) -> RingIter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Values are added at the end of the ring, as seen from
+/// the current value.
+impl<T> Extend<T> for Ring<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        for value in values {
+            self.insert_before_current(value);
+        }
+    }
+}
+
+/// The first value is current, and the rest follow it in
+/// order.
+impl<T> FromIterator<T> for Ring<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(values: I) -> Self {
+        let mut ring = Ring::new();
+        ring.extend(values);
+        ring
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// Play crab cups (2020 day 23) with the given cups
+    /// followed by the rest up to `ncups`, returning the
+    /// ring with the cup labeled 1 current.
+    fn crab_cups(start: &str, ncups: usize, moves: usize) -> Ring<usize> {
+        let labels = start
+            .chars()
+            .map(|c| c.to_digit(10).unwrap() as usize)
+            .chain(start.len() + 1..=ncups);
+        let mut cups: Ring<usize> = labels.collect();
+        // Slot of each cup, by label.
+        let mut slots = vec![0; ncups + 1];
+        for slot in 0..ncups {
+            slots[*cups.get(slot).unwrap()] = slot;
+        }
+        for _ in 0..moves {
+            let here = cups.current_slot().unwrap();
+            let label = *cups.current().unwrap();
+            let held = cups.splice_out(3);
+            let mut dest = label;
+            loop {
+                dest = if dest == 1 { ncups } else { dest - 1 };
+                if !held.contains(&dest) {
+                    break;
+                }
+            }
+            cups.set_current(slots[dest]);
+            let labels = held.clone();
+            for (label, slot) in labels.into_iter().zip(cups.splice_in(held)) {
+                slots[label] = slot;
+            }
+            cups.set_current(cups.next_slot(here));
+        }
+        cups.set_current(slots[1]);
+        cups
+    }
+
+    #[test]
+    fn crab_cups_small() {
+        let order = |ring: Ring<usize>| -> String {
+            ring.iter().skip(1).map(|c| c.to_string()).collect()
+        };
+        assert_eq!(order(crab_cups("389125467", 9, 10)), "92658374");
+        assert_eq!(order(crab_cups("389125467", 9, 100)), "67384529");
+    }
+
+    #[test]
+    #[ignore]
+    fn crab_cups_million() {
+        let cups = crab_cups("389125467", 1_000_000, 10_000_000);
+        let stars: Vec<usize> = cups.iter().skip(1).take(2).copied().collect();
+        assert_eq!(stars[0] * stars[1], 149245887792);
+    }
+
+    /// Mix the numbers (2022 day 20) and sum the grove
+    /// coordinates.
+    fn mix(numbers: &[i64], key: i64, rounds: usize) -> i64 {
+        let mut ring: Ring<i64> = numbers.iter().map(|n| n * key).collect();
+        for _ in 0..rounds {
+            // Slots are reused as soon as freed, so each
+            // number stays in its original slot.
+            for slot in 0..numbers.len() {
+                ring.set_current(slot);
+                let n = ring.remove_current().unwrap();
+                ring.rotate(n - 1);
+                assert_eq!(ring.insert_after_current(n), slot);
+            }
+        }
+        let zero = (0..numbers.len())
+            .find(|&s| ring.get(s) == Some(&0))
+            .unwrap();
+        ring.set_current(zero);
+        let mixed: Vec<i64> = ring.iter().copied().collect();
+        [1000, 2000, 3000]
+            .iter()
+            .map(|i| mixed[i % mixed.len()])
+            .sum()
+    }
+
+    #[test]
+    fn mixing() {
+        let numbers = [1, 2, -3, 3, -2, 0, 4];
+        assert_eq!(mix(&numbers, 1, 1), 3);
+        assert_eq!(mix(&numbers, 811589153, 10), 1623178306);
+    }
+
+    #[test]
+    fn ring_operations() {
+        let mut ring: Ring<char> = Ring::new();
+        assert_eq!(ring.current(), None);
+        assert_eq!(ring.remove_current(), None);
+        assert!(ring.splice_out(2).is_empty());
+        ring.rotate(5);
+        ring.splice_in("abc".chars());
+        assert_eq!(ring.iter().collect::<String>(), "abc");
+        ring.extend("de".chars());
+        assert_eq!(ring.iter().collect::<String>(), "abcde");
+
+        for (n, c) in [(0, 'a'), (2, 'c'), (-1, 'b'), (13, 'e'), (-9, 'a')] {
+            ring.rotate(n);
+            assert_eq!(ring.current(), Some(&c), "{}", n);
+        }
+        assert_eq!(ring.splice_out(2), ['b', 'c']);
+        assert_eq!(ring.iter().collect::<String>(), "ade");
+        *ring.current_mut().unwrap() = 'A';
+        assert_eq!(ring.splice_out(10), ['d', 'e']);
+        assert_eq!(ring.len(), 1);
+        assert_eq!(ring.iter().collect::<String>(), "A");
+        assert_eq!(ring.remove_current(), Some('A'));
+        assert!(ring.is_empty());
+        assert_eq!(ring.iter().count(), 0);
+        ring.insert_before_current('z');
+        assert_eq!(ring.current(), Some(&'z'));
+        let slot = ring.current_slot().unwrap();
+        assert_eq!(ring.next_slot(slot), slot);
+        assert_eq!(ring.prev_slot(slot), slot);
+    }
+}
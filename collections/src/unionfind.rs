@@ -0,0 +1,372 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Disjoint sets, for counting connected components as
+//! connections are found.
+//!
+//! # Examples
+//!
+//! Constellations of points within distance 3 of each other
+//! (2018 day 25).
+//!
+//! ```
+//! # use collections::*;
+//! let points: [[i32; 4]; 8] = [
+//!     [0, 0, 0, 0], [3, 0, 0, 0], [0, 3, 0, 0], [0, 0, 3, 0],
+//!     [0, 0, 0, 3], [0, 0, 0, 6], [9, 0, 0, 0], [12, 0, 0, 0],
+//! ];
+//! let mut constellations = KeyedUnionFind::new();
+//! for (i, p) in points.iter().enumerate() {
+//!     constellations.insert(p);
+//!     for q in &points[..i] {
+//!         let dist: i32 = (0..4).map(|k| (p[k] - q[k]).abs()).sum();
+//!         if dist <= 3 {
+//!             constellations.union(p, q);
+//!         }
+//!     }
+//! }
+//! assert_eq!(constellations.count_sets(), 2);
+//! ```
+
+use std::hash::Hash;
+
+use crate::Interner;
+
+/// Disjoint sets of the indices `0..n`, with path
+/// compression and union by rank. Each index starts in a
+/// set by itself.
+#[derive(Debug, Clone, Default)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    /// Size of the set, valid at roots only.
+    size: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    /// Make `n` sets, one for each index less than `n`.
+    pub fn new(n: usize) -> Self {
+        UnionFind {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            size: vec![1; n],
+            sets: n,
+        }
+    }
+
+    /// Number of indices.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// True if there are no indices.
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Add a new index in a set by itself, returning it.
+    pub fn push(&mut self) -> usize {
+        let i = self.parent.len();
+        self.parent.push(i);
+        self.rank.push(0);
+        self.size.push(1);
+        self.sets += 1;
+        i
+    }
+
+    /// The representative of the set containing `a`. Two
+    /// indices are in the same set exactly when they have
+    /// the same representative, but the representative may
+    /// change with `union()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` is out of range.
+    pub fn find(&mut self, a: usize) -> usize {
+        let mut root = a;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut a = a;
+        while a != root {
+            let next = self.parent[a];
+            self.parent[a] = root;
+            a = next;
+        }
+        root
+    }
+
+    /// Merge the sets containing `a` and `b`. Returns true
+    /// if they were different sets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is out of range.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.rank[a] < self.rank[b] {
+            (a, b) = (b, a);
+        }
+        if self.rank[a] == self.rank[b] {
+            self.rank[a] += 1;
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.sets -= 1;
+        true
+    }
+
+    /// True if `a` and `b` are in the same set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is out of range.
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Size of the set containing `a`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` is out of range.
+    pub fn size_of(&mut self, a: usize) -> usize {
+        let root = self.find(a);
+        self.size[root]
+    }
+
+    /// Number of sets.
+    pub fn count_sets(&self) -> usize {
+        self.sets
+    }
+
+    /// The size of each set, in order of the sets' smallest
+    /// indices.
+    pub fn set_sizes(&mut self) -> Vec<usize> {
+        self.sets().iter().map(Vec::len).collect()
+    }
+
+    /// The members of each set, in increasing order, with
+    /// the sets in order of their smallest indices.
+    pub fn sets(&mut self) -> Vec<Vec<usize>> {
+        let mut which = vec![usize::MAX; self.len()];
+        let mut sets: Vec<Vec<usize>> = Vec::with_capacity(self.sets);
+        for i in 0..self.len() {
+            let root = self.find(i);
+            if which[root] == usize::MAX {
+                which[root] = sets.len();
+                sets.push(Vec::new());
+            }
+            sets[which[root]].push(i);
+        }
+        sets
+    }
+}
+
+/// Disjoint sets of keys, as with `UnionFind`. Each key is
+/// added in a set by itself the first time it is seen.
+#[derive(Debug, Clone)]
+pub struct KeyedUnionFind<K> {
+    keys: Interner<K>,
+    sets: UnionFind,
+}
+
+impl<K> Default for KeyedUnionFind<K> {
+    fn default() -> Self {
+        KeyedUnionFind {
+            keys: Interner::default(),
+            sets: UnionFind::default(),
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash> KeyedUnionFind<K> {
+    /// Make an empty collection of sets.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of keys seen.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// True if no keys have been seen.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// The index of `key` in the underlying `UnionFind`,
+    /// adding it in a set by itself if it is new.
+    pub fn insert(&mut self, key: K) -> usize {
+        let i = self.keys.intern(key);
+        if i == self.sets.len() {
+            self.sets.push();
+        }
+        i
+    }
+
+    /// The representative key of the set containing `key`,
+    /// as with `UnionFind::find()`. A new key is added in a
+    /// set by itself, and so is its own representative.
+    pub fn find(&mut self, key: K) -> &K {
+        let i = self.insert(key);
+        let root = self.sets.find(i);
+        self.keys.value(root)
+    }
+
+    /// Merge the sets containing `a` and `b`, adding either
+    /// key if it is new. Returns true if they were different
+    /// sets.
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let (a, b) = (self.insert(a), self.insert(b));
+        self.sets.union(a, b)
+    }
+
+    /// True if `a` and `b` are in the same set. A new key
+    /// is added in a set by itself, so it is only in the
+    /// same set as itself.
+    pub fn same(&mut self, a: K, b: K) -> bool {
+        let (a, b) = (self.insert(a), self.insert(b));
+        self.sets.same(a, b)
+    }
+
+    /// Size of the set containing `key`. A new key is added
+    /// in a set by itself, of size 1.
+    pub fn size_of(&mut self, key: K) -> usize {
+        let i = self.insert(key);
+        self.sets.size_of(i)
+    }
+
+    /// Number of sets.
+    pub fn count_sets(&self) -> usize {
+        self.sets.count_sets()
+    }
+
+    /// The size of each set, in order of the sets' first
+    /// keys seen.
+    pub fn set_sizes(&mut self) -> Vec<usize> {
+        self.sets.set_sizes()
+    }
+
+    /// The members of each set, in the order the keys were
+    /// first seen, with the sets in order of their first
+    /// keys seen.
+    pub fn sets(&mut self) -> Vec<Vec<K>> {
+        self.sets
+            .sets()
+            .into_iter()
+            .map(|set| {
+                set.into_iter().map(|i| self.keys.value(i).clone()).collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn unions() {
+        let mut uf = UnionFind::new(6);
+        assert_eq!(uf.count_sets(), 6);
+        let steps = [
+            ((0, 1), true, 5),
+            ((2, 3), true, 4),
+            ((1, 0), false, 4),
+            ((1, 3), true, 3),
+            ((0, 2), false, 3),
+            ((4, 4), false, 3),
+            ((5, 4), true, 2),
+        ];
+        for ((a, b), merged, count) in steps {
+            assert_eq!(uf.union(a, b), merged, "union({}, {})", a, b);
+            assert_eq!(uf.count_sets(), count, "union({}, {})", a, b);
+        }
+        assert!(uf.same(0, 3));
+        assert!(!uf.same(0, 4));
+        assert_eq!(uf.find(1), uf.find(2));
+        assert_eq!(uf.size_of(2), 4);
+        assert_eq!(uf.set_sizes(), [4, 2]);
+        assert_eq!(uf.sets(), [vec![0, 1, 2, 3], vec![4, 5]]);
+        assert_eq!(uf.push(), 6);
+        assert_eq!(uf.set_sizes(), [4, 2, 1]);
+        assert!(uf.union(6, 0));
+        assert_eq!(uf.count_sets(), 2);
+
+        // A long chain, which union by rank keeps shallow.
+        let n = 100_000;
+        let mut uf = UnionFind::new(n);
+        for i in 1..n {
+            assert!(uf.union(i - 1, i));
+        }
+        assert_eq!(uf.count_sets(), 1);
+        assert_eq!(uf.size_of(0), n);
+        assert!(UnionFind::new(0).is_empty());
+    }
+
+    #[test]
+    fn constellations() {
+        // 2018 day 25 example.
+        let points: [[i32; 4]; 10] = [
+            [-1, 2, 2, 0],
+            [0, 0, 2, -2],
+            [0, 0, 0, -2],
+            [-1, 2, 0, 0],
+            [-2, -2, -2, 2],
+            [3, 0, 2, -1],
+            [-1, 3, 2, 2],
+            [-1, 0, -1, 0],
+            [0, 2, 1, -2],
+            [3, 0, 0, 0],
+        ];
+        let mut uf = KeyedUnionFind::new();
+        let mut merges = 0;
+        for (i, &p) in points.iter().enumerate() {
+            uf.insert(p);
+            for &q in &points[..i] {
+                let dist: i32 = (0..4).map(|k| (p[k] - q[k]).abs()).sum();
+                if dist <= 3 && uf.union(p, q) {
+                    merges += 1;
+                }
+            }
+        }
+        assert_eq!(uf.count_sets(), 4);
+        assert_eq!(merges, points.len() - 4);
+        assert_eq!(uf.len(), points.len());
+        assert_eq!(uf.set_sizes().iter().sum::<usize>(), points.len());
+        assert!(uf.same(points[0], points[6]));
+        assert!(!uf.same(points[0], points[4]));
+    }
+
+    #[test]
+    fn keyed() {
+        let mut uf = KeyedUnionFind::new();
+        assert!(uf.is_empty());
+        assert!(uf.union("a", "b"));
+        assert!(uf.union("c", "d"));
+        assert!(!uf.union("b", "a"));
+        assert_eq!(uf.count_sets(), 2);
+        assert_eq!(uf.size_of("e"), 1);
+        assert_eq!(uf.count_sets(), 3);
+        assert!(!uf.same("f", "a"));
+        assert_eq!(uf.find("g"), &"g");
+        assert_eq!(uf.count_sets(), 5);
+        assert!(uf.union("d", "a"));
+        let root = *uf.find("c");
+        assert_eq!(uf.find("b"), &root);
+        assert_eq!(
+            uf.sets(),
+            [vec!["a", "b", "c", "d"], vec!["e"], vec!["f"], vec!["g"]],
+        );
+        assert_eq!(uf.set_sizes(), [4, 1, 1, 1]);
+    }
+}
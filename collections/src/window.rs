@@ -0,0 +1,173 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Counting the items in a sliding window.
+//!
+//! The counts are updated as the window slides, adding the
+//! item coming in and removing the one going out, so each
+//! step takes constant time however wide the window.
+//!
+//! # Examples
+//!
+//! Find the start-of-packet marker (2022 day 6).
+//!
+//! ```
+//! # use collections::*;
+//! let signal = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+//! assert_eq!(first_distinct_window(signal.chars(), 4), Some(7));
+//! assert_eq!(first_distinct_window(signal.chars(), 14), Some(19));
+//! ```
+
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+use crate::Counter;
+
+/// The counts of the items in each window of `k`
+/// consecutive items of a sequence. See `windowed_counts()`.
+///
+/// This is not an `Iterator`, since it hands out a borrow
+/// of the same `Counter` each time rather than a copy.
+#[derive(Debug, Clone)]
+pub struct WindowCounts<I>
+where
+    I: Iterator,
+    I::Item: Eq + Hash,
+{
+    items: I,
+    k: usize,
+    window: VecDeque<I::Item>,
+    counts: Counter<I::Item>,
+    end: usize,
+}
+
+/// Make a `WindowCounts` for the windows of `k` consecutive
+/// items of `items`.
+pub fn windowed_counts<I>(items: I, k: usize) -> WindowCounts<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Clone + Eq + Hash,
+{
+    WindowCounts {
+        items: items.into_iter(),
+        k,
+        window: VecDeque::with_capacity(k + 1),
+        counts: Counter::new(),
+        end: 0,
+    }
+}
+
+impl<I> WindowCounts<I>
+where
+    I: Iterator,
+    I::Item: Clone + Eq + Hash,
+{
+    /// Slide to the next window, returning the counts of its
+    /// items, or `None` when the items run out. The first
+    /// window is the first `k` items.
+    pub fn next_window(&mut self) -> Option<&Counter<I::Item>> {
+        loop {
+            let item = self.items.next()?;
+            self.end += 1;
+            self.counts.add(item.clone());
+            self.window.push_back(item);
+            if self.window.len() > self.k {
+                let old = self.window.pop_front().unwrap();
+                self.counts.remove_n(&old, 1);
+            }
+            if self.window.len() == self.k {
+                return Some(&self.counts);
+            }
+        }
+    }
+
+    /// Number of items read so far: the index just past the
+    /// current window.
+    pub fn end(&self) -> usize {
+        self.end
+    }
+}
+
+/// The index just past the first window of `k` consecutive
+/// items that are all different, or `None` if there is no
+/// such window. This is the position the puzzles ask for.
+pub fn first_distinct_window<I>(items: I, k: usize) -> Option<usize>
+where
+    I: IntoIterator,
+    I::Item: Clone + Eq + Hash,
+{
+    if k == 0 {
+        return Some(0);
+    }
+    let mut windows = windowed_counts(items, k);
+    while let Some(counts) = windows.next_window() {
+        if counts.len() == k {
+            return Some(windows.end());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::testutil::Rng;
+
+    use std::collections::HashSet;
+
+    #[test]
+    fn markers() {
+        let examples = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
+        for (signal, packet, message) in examples {
+            assert_eq!(first_distinct_window(signal.chars(), 4), Some(packet));
+            let start = first_distinct_window(signal.chars(), 14);
+            assert_eq!(start, Some(message));
+        }
+        assert_eq!(first_distinct_window("aaaa".chars(), 2), None);
+        assert_eq!(first_distinct_window("abc".chars(), 4), None);
+        assert_eq!(first_distinct_window("".chars(), 0), Some(0));
+    }
+
+    #[test]
+    fn against_naive() {
+        let naive = |items: &[usize], k: usize| {
+            items
+                .windows(k)
+                .position(|w| w.iter().collect::<HashSet<_>>().len() == k)
+                .map(|i| i + k)
+        };
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..500 {
+            let n = rng.below(60);
+            let alphabet = rng.below(12) + 1;
+            let items: Vec<usize> =
+                (0..n).map(|_| rng.below(alphabet)).collect();
+            let k = rng.below(8) + 1;
+            let fast = first_distinct_window(items.iter().copied(), k);
+            assert_eq!(fast, naive(&items, k), "{:?} {}", items, k);
+
+            let mut windows = windowed_counts(items.iter().copied(), k);
+            let mut start = 0;
+            while let Some(counts) = windows.next_window() {
+                let window = &items[start..start + k];
+                assert_eq!(counts.total(), k);
+                for &item in window {
+                    let count = window.iter().filter(|&&i| i == item).count();
+                    assert_eq!(counts.count(&item), count);
+                }
+                assert_eq!(windows.end(), start + k);
+                start += 1;
+            }
+            assert_eq!(start, (n + 1).saturating_sub(k));
+        }
+    }
+}
@@ -3,15 +3,63 @@
 // Please see the file LICENSE in this distribution
 // for license terms.
 
-//! Set operations for Advent of Code 2016 solutions.
+//! Combinatorial operations for Advent of Code solutions.
 //!
-//! These are specifically `std::collections::BTreeSet`
-//! operations, because `std::collections::HashSet` is not
-//! hashable (why?) and thus cannot be contained in a
-//! `HashSet`.
+//! The set operations here are specifically
+//! `std::collections::BTreeSet` operations, because
+//! `std::collections::HashSet` is not hashable (why?) and
+//! thus cannot be contained in a `HashSet`.
 //!
 //! It would nice to have a generic `std::collections::Set`
 //! trait to parameterize the stuff that doesn't care.
+//!
+//! The iterators over combinations and friends work on
+//! slices, and are lazy: nothing is materialized up front.
+
+mod combinations;
+pub use combinations::*;
+
+mod derangements;
+pub use derangements::*;
+
+mod ext;
+pub use ext::*;
+
+mod gray;
+pub use gray::*;
+
+mod lexical;
+pub use lexical::*;
+
+mod multichoose;
+pub use multichoose::*;
+
+mod pairs;
+pub use pairs::*;
+
+mod partitions;
+pub use partitions::*;
+
+mod permutations;
+pub use permutations::*;
+
+mod product;
+pub use product::*;
+
+mod schedules;
+pub use schedules::*;
+
+mod splits;
+pub use splits::*;
+
+mod subsets;
+pub use subsets::*;
+
+mod sums;
+pub use sums::*;
+
+#[cfg(test)]
+mod testutil;
 
 use std::collections::BTreeSet;
 
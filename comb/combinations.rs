@@ -0,0 +1,122 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Combinations: all choices of `k` items from a slice.
+//!
+//! Combinations are generated in lexicographic order of
+//! their item indices.
+//!
+//! # Examples
+//!
+//! ```
+//! # use comb::*;
+//! let items = ['a', 'b', 'c'];
+//! let pairs: Vec<String> = combinations(&items, 2)
+//!     .map(|c| c.into_iter().collect())
+//!     .collect();
+//! assert_eq!(pairs, vec!["ab", "ac", "bc"]);
+//! ```
+
+/// Iterator over the `k`-combinations of the indices
+/// `0..n`, each in increasing order.
+#[derive(Debug, Clone)]
+pub struct CombinationIndices {
+    /// Number of indices to choose from.
+    n: usize,
+    /// Next combination, if any.
+    indices: Option<Vec<usize>>,
+}
+
+impl Iterator for CombinationIndices {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        let result = self.indices.clone()?;
+        let indices = self.indices.as_mut().unwrap();
+        let k = indices.len();
+        // Find the rightmost index that can still advance.
+        match (0..k).rev().find(|&i| indices[i] < self.n - k + i) {
+            Some(i) => {
+                indices[i] += 1;
+                for j in i + 1..k {
+                    indices[j] = indices[j - 1] + 1;
+                }
+            }
+            None => self.indices = None,
+        }
+        Some(result)
+    }
+}
+
+/// Iterate over the ways of choosing `k` of the indices
+/// `0..n`. Choosing 0 gives one empty combination; choosing
+/// more than `n` gives none.
+pub fn combination_indices(n: usize, k: usize) -> CombinationIndices {
+    let indices = if k <= n { Some((0..k).collect()) } else { None };
+    CombinationIndices { n, indices }
+}
+
+/// Iterate over the ways of choosing `k` of the given items,
+/// by reference.
+pub fn combinations<T>(
+    items: &[T],
+    k: usize,
+) -> impl Iterator<Item = Vec<&T>> {
+    combination_indices(items.len(), k)
+        .map(move |ix| ix.into_iter().map(|i| &items[i]).collect())
+}
+
+/// Iterate over the ways of choosing `k` of the given items,
+/// cloning them.
+pub fn combinations_cloned<T: Clone>(
+    items: &[T],
+    k: usize,
+) -> impl Iterator<Item = Vec<T>> + '_ {
+    combination_indices(items.len(), k)
+        .map(move |ix| ix.into_iter().map(|i| items[i].clone()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::testutil::binomial;
+
+    #[test]
+    fn counts_match_binomial() {
+        for n in 0..=10 {
+            for k in 0..=n + 2 {
+                let count = combination_indices(n, k).count() as u64;
+                assert_eq!(count, binomial(n as u64, k as u64), "{} {}", n, k);
+            }
+        }
+    }
+
+    #[test]
+    fn lexicographic_and_distinct() {
+        for n in 0..=8 {
+            for k in 0..=n {
+                let all: Vec<Vec<usize>> = combination_indices(n, k).collect();
+                for c in &all {
+                    assert!(c.windows(2).all(|w| w[0] < w[1]));
+                    assert!(c.iter().all(|&i| i < n));
+                }
+                assert!(all.windows(2).all(|w| w[0] < w[1]));
+            }
+        }
+    }
+
+    #[test]
+    fn edge_cases() {
+        let items = [1, 2, 3];
+        let empty: Vec<Vec<&i32>> = combinations(&items, 0).collect();
+        assert_eq!(empty, vec![Vec::<&i32>::new()]);
+        assert_eq!(combinations(&items, 4).count(), 0);
+        let none: [i32; 0] = [];
+        assert_eq!(combinations(&none, 0).count(), 1);
+        let all: Vec<Vec<i32>> = combinations_cloned(&items, 3).collect();
+        assert_eq!(all, vec![vec![1, 2, 3]]);
+    }
+}
@@ -0,0 +1,240 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Permutations with restrictions on where each item may
+//! go: derangements, which leave no item in place, and
+//! assignments constrained by a compatibility matrix.
+//!
+//! # Examples
+//!
+//! ```
+//! # use comb::*;
+//! let ds: Vec<Vec<usize>> = derangements(3).collect();
+//! assert_eq!(ds, vec![vec![1, 2, 0], vec![2, 0, 1]]);
+//! assert_eq!(count_derangements(5), Some(44));
+//!
+//! // Who can get which gift.
+//! let compat = vec![
+//!     vec![true, true, false],
+//!     vec![true, false, true],
+//!     vec![false, true, true],
+//! ];
+//! assert_eq!(count_perfect_matchings(&compat), 2);
+//! ```
+
+/// Largest number of columns accepted by
+/// `count_perfect_matchings()`. The table of column sets
+/// then takes 8 MB, and every count fits in `u64`: none can
+/// exceed `20!`.
+pub const MAX_MATCHING_COLUMNS: usize = 20;
+
+/// Iterate over the derangements of `0..n`: the
+/// permutations `p` with `p[i] != i` for every `i`, in
+/// lexicographic order. There are `count_derangements(n)`
+/// of them.
+///
+/// Derangements are built one position at a time, never
+/// trying a value at its own position, so the work is
+/// proportional to the number of derangements rather than
+/// the number of permutations.
+pub fn derangements(n: usize) -> impl Iterator<Item = Vec<usize>> {
+    // The derangement built so far, and the values it uses.
+    let mut perm: Vec<usize> = Vec::with_capacity(n);
+    let mut used = vec![false; n];
+    // Smallest value to try next at position `perm.len()`.
+    let mut from = 0;
+    let mut done = false;
+    std::iter::from_fn(move || {
+        if done {
+            return None;
+        }
+        loop {
+            let i = perm.len();
+            let next = if i == n {
+                None
+            } else {
+                (from..n).find(|&v| v != i && !used[v])
+            };
+            if let Some(v) = next {
+                perm.push(v);
+                used[v] = true;
+                from = 0;
+                continue;
+            }
+            let result = (i == n).then(|| perm.clone());
+            // Back up to try the next value one position
+            // earlier.
+            match perm.pop() {
+                Some(v) => {
+                    used[v] = false;
+                    from = v + 1;
+                }
+                None => done = true,
+            }
+            if result.is_some() || done {
+                return result;
+            }
+        }
+    })
+}
+
+/// Number of derangements of `n` items, computed by the
+/// recurrence `D(n) = (n - 1) (D(n - 1) + D(n - 2))`.
+/// Returns `None` if the count does not fit in `u64`, which
+/// is the case for `n > 20`.
+pub fn count_derangements(n: u64) -> Option<u64> {
+    let (mut prev, mut cur) = (1u64, 0u64);
+    if n == 0 {
+        return Some(prev);
+    }
+    for i in 2..=n {
+        let next = prev.checked_add(cur)?.checked_mul(i - 1)?;
+        (prev, cur) = (cur, next);
+    }
+    Some(cur)
+}
+
+/// Number of ways to assign each row a distinct column such
+/// that `compat[row][col]` holds for every assignment. With
+/// as many rows as columns this is the number of perfect
+/// matchings of the bipartite compatibility graph (the
+/// permanent of the matrix).
+///
+/// This is a dynamic program over the sets of columns
+/// used, taking time and space exponential in the number of
+/// columns but only linear in the number of rows.
+///
+/// # Panics
+///
+/// Panics if the rows are of different lengths, or if there
+/// are more than `MAX_MATCHING_COLUMNS` columns.
+pub fn count_perfect_matchings(compat: &[Vec<bool>]) -> u64 {
+    let ncols = compat.first().map(|row| row.len()).unwrap_or(0);
+    assert!(
+        compat.iter().all(|row| row.len() == ncols),
+        "count_perfect_matchings: ragged matrix",
+    );
+    assert!(
+        ncols <= MAX_MATCHING_COLUMNS,
+        "count_perfect_matchings: {} columns is too many (max {})",
+        ncols,
+        MAX_MATCHING_COLUMNS,
+    );
+    let nrows = compat.len();
+    if nrows > ncols {
+        return 0;
+    }
+    // Ways to assign the first `popcount(mask)` rows to
+    // exactly the columns in `mask`. With `k` rows there
+    // are at most `k!` of these, so with at most
+    // `MAX_MATCHING_COLUMNS` columns the sums below cannot
+    // overflow.
+    let mut ways = vec![0u64; 1 << ncols];
+    ways[0] = 1;
+    let mut total = 0;
+    for mask in 0..ways.len() {
+        let w = ways[mask];
+        if w == 0 {
+            continue;
+        }
+        let row = mask.count_ones() as usize;
+        if row == nrows {
+            total += w;
+            continue;
+        }
+        for (col, &ok) in compat[row].iter().enumerate() {
+            if ok && mask & (1 << col) == 0 {
+                ways[mask | (1 << col)] += w;
+            }
+        }
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::permutations::permutations;
+    use crate::testutil::Rng;
+
+    #[test]
+    fn derangement_counts() {
+        let expected = [1, 0, 1, 2, 9, 44, 265, 1854];
+        for (n, &d) in expected.iter().enumerate() {
+            assert_eq!(count_derangements(n as u64), Some(d));
+            assert_eq!(derangements(n).count() as u64, d);
+        }
+        assert_eq!(count_derangements(20), Some(895014631192902121));
+        assert_eq!(count_derangements(21), None);
+        assert_eq!(count_derangements(u64::MAX), None);
+    }
+
+    #[test]
+    fn derangements_in_order() {
+        let cols: Vec<usize> = (0..6).collect();
+        let mut filtered: Vec<Vec<usize>> = permutations(&cols)
+            .filter(|p| p.iter().enumerate().all(|(i, &v)| i != v))
+            .collect();
+        filtered.sort();
+        let direct: Vec<Vec<usize>> = derangements(6).collect();
+        assert_eq!(direct, filtered);
+        assert_eq!(derangements(0).collect::<Vec<_>>(), vec![vec![]]);
+        assert_eq!(derangements(1).next(), None);
+        // Far too many permutations to filter.
+        let first = derangements(20).next().unwrap();
+        let expected = [1, 0, 3, 2, 5, 4, 7, 6, 9, 8];
+        assert_eq!(first[..10], expected);
+        assert_eq!(derangements(12).take(1000).count(), 1000);
+    }
+
+    #[test]
+    fn matchings_match_brute_force() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        let n = 6;
+        for _ in 0..50 {
+            let compat: Vec<Vec<bool>> = (0..n)
+                .map(|_| (0..n).map(|_| rng.below(3) != 0).collect())
+                .collect();
+            let cols: Vec<usize> = (0..n).collect();
+            let brute = permutations(&cols)
+                .filter(|p| (0..n).all(|r| compat[r][p[r]]))
+                .count() as u64;
+            assert_eq!(count_perfect_matchings(&compat), brute);
+        }
+    }
+
+    #[test]
+    fn matchings_edge_cases() {
+        assert_eq!(count_perfect_matchings(&[]), 1);
+        let all = vec![vec![true; 8]; 8];
+        assert_eq!(count_perfect_matchings(&all), 40320);
+        let derange: Vec<Vec<bool>> =
+            (0..7).map(|r| (0..7).map(|c| r != c).collect()).collect();
+        assert_eq!(count_perfect_matchings(&derange), 1854);
+        let wide = vec![vec![true; 4]; 2];
+        assert_eq!(count_perfect_matchings(&wide), 12);
+        let tall = vec![vec![true; 2]; 3];
+        assert_eq!(count_perfect_matchings(&tall), 0);
+    }
+
+    #[test]
+    fn matchings_at_cap() {
+        let n = MAX_MATCHING_COLUMNS;
+        let all = vec![vec![true; n]; n];
+        assert_eq!(count_perfect_matchings(&all), 2_432_902_008_176_640_000);
+        let derange: Vec<Vec<bool>> =
+            (0..n).map(|r| (0..n).map(|c| r != c).collect()).collect();
+        let expected = count_derangements(n as u64).unwrap();
+        assert_eq!(count_perfect_matchings(&derange), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "too many")]
+    fn matchings_over_cap() {
+        let n = MAX_MATCHING_COLUMNS + 1;
+        count_perfect_matchings(&[vec![true; n]]);
+    }
+}
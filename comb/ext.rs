@@ -0,0 +1,168 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Combinatorial adaptors as methods on any iterator.
+//!
+//! The free functions in this crate work on slices and
+//! yield references, which is the fast path. The adaptors
+//! here are a convenience for the end of an iterator chain:
+//! each one buffers what it needs into a `Vec` and yields
+//! clones of the items, using the slice functions
+//! underneath.
+//!
+//! The method names are chosen not to collide with
+//! `itertools`, so both traits can be imported together.
+//!
+//! # Examples
+//!
+//! ```
+//! # use comb::*;
+//! let input = "1721\n979\n366\n299\n675\n1456";
+//! let hits: Vec<(u32, u32, char)> = input
+//!     .lines()
+//!     .map(|line| line.parse::<u32>().unwrap())
+//!     .pairs()
+//!     .cartesian_with(['+', '-'])
+//!     .filter(|&((a, b), op)| match op {
+//!         '+' => a + b == 2020,
+//!         _ => a.abs_diff(b) == 1422,
+//!     })
+//!     .map(|((a, b), op)| (a, b, op))
+//!     .collect();
+//! assert_eq!(hits, vec![(1721, 299, '+'), (1721, 299, '-')]);
+//! ```
+
+use std::rc::Rc;
+
+use crate::combinations::combination_indices;
+use crate::pairs::{pair_indices, triple_indices};
+use crate::permutations::{permutations, Permutations};
+use crate::subsets::{mask_to_items, subset_masks};
+
+/// Extension trait adding combinatorial adaptors to every
+/// iterator with cloneable items.
+pub trait CombIteratorExt: Iterator + Sized
+where
+    Self::Item: Clone,
+{
+    /// Each unordered pair of distinct items, as with
+    /// `pairs()`. Buffers all the items.
+    fn pairs(self) -> impl Iterator<Item = (Self::Item, Self::Item)> {
+        let items: Vec<Self::Item> = self.collect();
+        pair_indices(items.len())
+            .map(move |(i, j)| (items[i].clone(), items[j].clone()))
+    }
+
+    /// Each unordered triple of distinct items, as with
+    /// `triples()`. Buffers all the items.
+    fn triples(
+        self,
+    ) -> impl Iterator<Item = (Self::Item, Self::Item, Self::Item)> {
+        let items: Vec<Self::Item> = self.collect();
+        triple_indices(items.len()).map(move |(i, j, k)| {
+            (items[i].clone(), items[j].clone(), items[k].clone())
+        })
+    }
+
+    /// Each choice of `k` of the items, as with
+    /// `combinations()`. Buffers all the items.
+    fn choose_k(self, k: usize) -> impl Iterator<Item = Vec<Self::Item>> {
+        let items: Vec<Self::Item> = self.collect();
+        combination_indices(items.len(), k).map(move |ix| {
+            ix.into_iter().map(|i| items[i].clone()).collect()
+        })
+    }
+
+    /// Each ordering of the items, as with `permutations()`.
+    /// Buffers all the items.
+    fn arrangements(self) -> Permutations<Self::Item> {
+        let items: Vec<Self::Item> = self.collect();
+        permutations(&items)
+    }
+
+    /// Each subset of the items, as with `subsets()`.
+    /// Buffers all the items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than `MAX_SUBSET_ITEMS`
+    /// items.
+    fn all_subsets(self) -> impl Iterator<Item = Vec<Self::Item>> {
+        let items: Vec<Self::Item> = self.collect();
+        subset_masks(items.len()).map(move |mask| {
+            mask_to_items(mask, &items).into_iter().cloned().collect()
+        })
+    }
+
+    /// Each item paired with each item of `other`, with
+    /// `other` varying fastest. Buffers `other` only: this
+    /// iterator is consumed lazily.
+    fn cartesian_with<J>(
+        self,
+        other: J,
+    ) -> impl Iterator<Item = (Self::Item, J::Item)>
+    where
+        J: IntoIterator,
+        J::Item: Clone,
+    {
+        let other: Rc<Vec<J::Item>> = Rc::new(other.into_iter().collect());
+        self.flat_map(move |a| {
+            let other = Rc::clone(&other);
+            (0..other.len()).map(move |i| (a.clone(), other[i].clone()))
+        })
+    }
+}
+
+impl<I> CombIteratorExt for I
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn matches_slice_functions() {
+        let items = ["a", "b", "c", "d"];
+        let ps: Vec<(&str, &str)> = items.iter().copied().pairs().collect();
+        let expected: Vec<(&str, &str)> =
+            crate::pairs(&items).map(|(a, b)| (*a, *b)).collect();
+        assert_eq!(ps, expected);
+
+        let ts = items.iter().triples().count();
+        assert_eq!(ts, crate::triples(&items).count());
+
+        let cs: Vec<Vec<&str>> = items.iter().copied().choose_k(3).collect();
+        let expected: Vec<Vec<&str>> = combinations_cloned(&items, 3).collect();
+        assert_eq!(cs, expected);
+
+        let perms: Vec<Vec<&str>> =
+            items.iter().copied().arrangements().collect();
+        assert_eq!(perms, permutations(&items).collect::<Vec<_>>());
+
+        let subs: Vec<Vec<&str>> =
+            items.iter().copied().all_subsets().collect();
+        assert_eq!(subs.len(), 16);
+        assert_eq!(subs[0b1010], vec!["b", "d"]);
+    }
+
+    #[test]
+    fn cartesian_is_lazy() {
+        let mut pulled = 0;
+        let first: Vec<(u32, char)> = (0..)
+            .inspect(|_| pulled += 1)
+            .cartesian_with("xy".chars())
+            .take(3)
+            .collect();
+        assert_eq!(first, vec![(0, 'x'), (0, 'y'), (1, 'x')]);
+        assert_eq!(pulled, 2);
+        assert_eq!((0..3).cartesian_with(Vec::<u8>::new()).count(), 0);
+    }
+}
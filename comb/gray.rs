@@ -0,0 +1,146 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Subset masks in [reflected Gray code][1] order, where
+//! each mask differs from the previous one in a single bit.
+//!
+//! Brute-forcing over subsets in this order lets an
+//! objective be updated by adding or removing one item per
+//! step, rather than being recomputed from scratch.
+//!
+//! [1]: https://en.wikipedia.org/wiki/Gray_code
+//!
+//! # Examples
+//!
+//! Keep a running subset sum, counting subsets that sum to
+//! a target.
+//!
+//! ```
+//! # use comb::*;
+//! let items = [20, 15, 10, 5, 5];
+//! let mut sum = 0;
+//! let mut hits = 0;
+//! for (_, bit, set) in gray_code_changes(items.len()) {
+//!     if set {
+//!         sum += items[bit];
+//!     } else {
+//!         sum -= items[bit];
+//!     }
+//!     if sum == 25 {
+//!         hits += 1;
+//!     }
+//! }
+//! assert_eq!(hits, 4);
+//! ```
+
+use crate::subsets::subset_masks;
+
+use std::ops::Range;
+
+/// Iterator over Gray codes. See `gray_codes()`.
+#[derive(Debug, Clone)]
+pub struct GrayCodes {
+    /// Positions of the codes remaining.
+    posns: Range<u64>,
+}
+
+impl Iterator for GrayCodes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let i = self.posns.next()?;
+        Some(i ^ (i >> 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.posns.size_hint()
+    }
+}
+
+/// Iterate over all `2^n` masks of `n` bits in reflected
+/// Gray code order, starting with 0.
+///
+/// # Panics
+///
+/// Panics if `n` is greater than `MAX_SUBSET_ITEMS`.
+pub fn gray_codes(n: usize) -> GrayCodes {
+    GrayCodes {
+        posns: subset_masks(n),
+    }
+}
+
+/// Iterator over Gray code changes. See
+/// `gray_code_changes()`.
+#[derive(Debug, Clone)]
+pub struct GrayCodeChanges {
+    /// Positions of the codes remaining.
+    posns: Range<u64>,
+}
+
+impl Iterator for GrayCodeChanges {
+    type Item = (u64, usize, bool);
+
+    fn next(&mut self) -> Option<(u64, usize, bool)> {
+        let i = self.posns.next()?;
+        let code = i ^ (i >> 1);
+        let bit = i.trailing_zeros() as usize;
+        Some((code, bit, code & (1 << bit) != 0))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.posns.size_hint()
+    }
+}
+
+/// Iterate over the steps of `gray_codes(n)` after the
+/// initial 0. Each step is `(code, bit, set)`: the new code,
+/// the index of the bit that changed to get there, and
+/// whether that bit was set (rather than cleared).
+///
+/// # Panics
+///
+/// Panics if `n` is greater than `MAX_SUBSET_ITEMS`.
+pub fn gray_code_changes(n: usize) -> GrayCodeChanges {
+    let posns = subset_masks(n);
+    GrayCodeChanges {
+        posns: 1..posns.end,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn single_bit_steps() {
+        for n in 0..=10 {
+            let codes: Vec<u64> = gray_codes(n).collect();
+            assert_eq!(codes.len(), 1 << n);
+            assert_eq!(codes[0], 0);
+            assert!(codes
+                .windows(2)
+                .all(|w| (w[0] ^ w[1]).count_ones() == 1));
+            let mut sorted = codes.clone();
+            sorted.sort_unstable();
+            assert!(sorted.into_iter().eq(0..1 << n));
+
+            let changes: Vec<(u64, usize, bool)> =
+                gray_code_changes(n).collect();
+            assert_eq!(changes.len(), codes.len() - 1);
+            for (w, &(code, bit, set)) in codes.windows(2).zip(&changes) {
+                assert_eq!(code, w[1]);
+                assert_eq!(w[0] ^ w[1], 1 << bit);
+                assert_eq!(set, w[1] > w[0]);
+            }
+        }
+    }
+
+    #[test]
+    fn first_codes() {
+        let codes: Vec<u64> = gray_codes(3).collect();
+        assert_eq!(codes, vec![0, 1, 3, 2, 6, 7, 5, 4]);
+    }
+}
@@ -0,0 +1,253 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Stepping through permutations in lexicographic order, in
+//! place, and jumping straight to the `n`-th one. Stepping
+//! skips repeated arrangements, so it also gives the
+//! distinct permutations of a multiset.
+//!
+//! # Examples
+//!
+//! ```
+//! # use comb::*;
+//! let mut items = [1, 2, 2];
+//! let mut all = vec![items];
+//! while next_permutation(&mut items) {
+//!     all.push(items);
+//! }
+//! assert_eq!(all, vec![[1, 2, 2], [2, 1, 2], [2, 2, 1]]);
+//! assert_eq!(items, [1, 2, 2]);
+//!
+//! assert_eq!(nth_permutation(&['a', 'b', 'c'], 3), vec!['b', 'c', 'a']);
+//!
+//! let word: Vec<char> = "aabb".chars().collect();
+//! assert_eq!(count_multiset_permutations(&word), 6);
+//! let words: Vec<String> = multiset_permutations(&word)
+//!     .map(|p| p.into_iter().collect())
+//!     .collect();
+//! assert_eq!(words, ["aabb", "abab", "abba", "baab", "baba", "bbaa"]);
+//! ```
+
+use crate::multichoose::multiplicity;
+
+/// Rearrange `items` into the lexicographically next
+/// permutation, returning true. If `items` is already the
+/// last permutation (sorted in decreasing order), instead
+/// sort it into the first (increasing order) and return
+/// false.
+///
+/// This is the same algorithm as C++
+/// `std::next_permutation`: with repeated items, only
+/// distinct arrangements are produced.
+pub fn next_permutation<T: Ord>(items: &mut [T]) -> bool {
+    // Find the last ascent.
+    let i = match items.windows(2).rposition(|w| w[0] < w[1]) {
+        Some(i) => i,
+        None => {
+            items.reverse();
+            return false;
+        }
+    };
+    // Swap its head with the rightmost larger item, then
+    // make the (decreasing) suffix increasing.
+    let j = items.iter().rposition(|x| *x > items[i]).unwrap();
+    items.swap(i, j);
+    items[i + 1..].reverse();
+    true
+}
+
+/// Rearrange `items` into the lexicographically previous
+/// permutation, returning true. If `items` is already the
+/// first permutation (sorted in increasing order), instead
+/// sort it into the last (decreasing order) and return
+/// false. This is the inverse of `next_permutation()`.
+pub fn prev_permutation<T: Ord>(items: &mut [T]) -> bool {
+    let i = match items.windows(2).rposition(|w| w[0] > w[1]) {
+        Some(i) => i,
+        None => {
+            items.reverse();
+            return false;
+        }
+    };
+    let j = items.iter().rposition(|x| *x < items[i]).unwrap();
+    items.swap(i, j);
+    items[i + 1..].reverse();
+    true
+}
+
+/// The `n`-th (counting from 0) permutation of the given
+/// items in lexicographic order of their positions: the
+/// 0-th permutation is the items as given. This is computed
+/// directly via the [factorial number system][1] rather than
+/// by stepping. Repeated items are treated as distinct.
+///
+/// [1]: https://en.wikipedia.org/wiki/Factorial_number_system
+///
+/// # Panics
+///
+/// Panics if `n` is not less than the number of
+/// permutations.
+pub fn nth_permutation<T: Clone>(items: &[T], n: u64) -> Vec<T> {
+    let len = items.len();
+    // Factorials up to (len - 1)!, saturating: a saturated
+    // factorial exceeds any u64 index.
+    let mut factorials = vec![1u64; len.max(1)];
+    for i in 1..len {
+        factorials[i] = factorials[i - 1].saturating_mul(i as u64);
+    }
+    let total = factorials[len.max(1) - 1].saturating_mul(len.max(1) as u64);
+    assert!(
+        n < total || (len == 0 && n == 0),
+        "nth_permutation: index {} out of range",
+        n,
+    );
+    let mut remaining: Vec<T> = items.to_vec();
+    let mut result = Vec::with_capacity(len);
+    let mut n = n;
+    for i in (0..len).rev() {
+        let digit = n / factorials[i];
+        n %= factorials[i];
+        result.push(remaining.remove(digit as usize));
+    }
+    result
+}
+
+/// Iterator over the distinct permutations of a multiset.
+/// See `multiset_permutations()`.
+#[derive(Debug, Clone)]
+pub struct MultisetPermutations<T> {
+    next: Option<Vec<T>>,
+}
+
+impl<T: Ord + Clone> Iterator for MultisetPermutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let mut next = self.next.take()?;
+        let result = next.clone();
+        if next_permutation(&mut next) {
+            self.next = Some(next);
+        }
+        Some(result)
+    }
+}
+
+/// Iterate over the distinct permutations of the given
+/// items in lexicographic order, yielding each arrangement
+/// of repeated items exactly once. There are
+/// `count_multiset_permutations(items)` of them.
+pub fn multiset_permutations<T>(items: &[T]) -> MultisetPermutations<T>
+where
+    T: Ord + Clone,
+{
+    let mut first = items.to_vec();
+    first.sort();
+    MultisetPermutations { next: Some(first) }
+}
+
+/// Number of distinct permutations of the given items: the
+/// multinomial coefficient `n! / ∏ kᵢ!`, where the `kᵢ` are
+/// the counts of each distinct item.
+///
+/// # Panics
+///
+/// Panics if the count does not fit in `u64`.
+pub fn count_multiset_permutations<T: Ord>(items: &[T]) -> u64 {
+    let mut sorted: Vec<&T> = items.iter().collect();
+    sorted.sort();
+    multiplicity(&sorted)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut items = [1, 2, 3, 4, 5];
+        let mut all = vec![items];
+        while next_permutation(&mut items) {
+            all.push(items);
+        }
+        assert_eq!(all.len(), 120);
+        assert!(all.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+
+        let mut items = [5, 4, 3, 2, 1];
+        let mut back = vec![items];
+        while prev_permutation(&mut items) {
+            back.push(items);
+        }
+        back.reverse();
+        assert_eq!(back, all);
+        assert_eq!(items, [5, 4, 3, 2, 1]);
+
+        for (i, p) in all.iter().enumerate() {
+            assert_eq!(&nth_permutation(&[1, 2, 3, 4, 5], i as u64), p);
+            let mut q = *p;
+            if next_permutation(&mut q) {
+                assert!(prev_permutation(&mut q));
+                assert_eq!(&q, p);
+            }
+        }
+    }
+
+    #[test]
+    fn duplicates() {
+        let mut items = ['a', 'a', 'b', 'b'];
+        let mut count = 1;
+        while next_permutation(&mut items) {
+            count += 1;
+        }
+        assert_eq!(count, 6);
+        let mut items: [u8; 0] = [];
+        assert!(!next_permutation(&mut items));
+        assert!(!prev_permutation(&mut [7]));
+    }
+
+    #[test]
+    fn multiset_counts() {
+        for word in ["", "a", "aabb", "banana", "mississippi", "abcdef"] {
+            let letters: Vec<char> = word.chars().collect();
+            let perms: Vec<Vec<char>> =
+                multiset_permutations(&letters).collect();
+            let count = count_multiset_permutations(&letters);
+            assert_eq!(perms.len() as u64, count);
+            assert!(perms.windows(2).all(|w| w[0] < w[1]));
+        }
+        assert_eq!(count_multiset_permutations(&[1, 1, 2, 2]), 6);
+        assert_eq!(count_multiset_permutations(&[0; 20]), 1);
+        let letters: Vec<char> = "mississippi".chars().collect();
+        assert_eq!(count_multiset_permutations(&letters), 34650);
+        // 21! / 3! fits in u64, though 21! does not.
+        let mut items: Vec<u32> = (0..18).collect();
+        items.extend([99, 99, 99]);
+        let count = count_multiset_permutations(&items);
+        assert_eq!(count, 8_515_157_028_618_240_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow")]
+    fn multiset_count_overflow() {
+        let items: Vec<u32> = (0..21).collect();
+        let _ = count_multiset_permutations(&items);
+    }
+
+    #[test]
+    fn millionth() {
+        let digits: Vec<u8> = (0..10).collect();
+        let p = nth_permutation(&digits, 999_999);
+        assert_eq!(p, vec![2, 7, 8, 3, 9, 1, 5, 4, 6, 0]);
+        let empty: Vec<u8> = nth_permutation(&[], 0);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn nth_out_of_range() {
+        let _ = nth_permutation(&[1, 2, 3], 6);
+    }
+}
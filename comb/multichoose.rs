@@ -0,0 +1,165 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Combinations with replacement ("multichoose"): choices of
+//! `k` items where repetition is allowed and order doesn't
+//! matter.
+//!
+//! # Examples
+//!
+//! The distribution of sums of three three-sided dice, as in
+//! the 2021 day 21 Dirac dice, without rolling all 27
+//! ordered outcomes.
+//!
+//! ```
+//! # use comb::*;
+//! let mut weights = [0u64; 10];
+//! for (roll, count) in weighted_outcomes(&[1, 2, 3], 3) {
+//!     let sum: usize = roll.into_iter().sum();
+//!     weights[sum] += count;
+//! }
+//! assert_eq!(weights[3..], [1, 3, 6, 7, 6, 3, 1]);
+//! ```
+
+/// Iterator over the non-decreasing length-`k` sequences of
+/// indices `0..n`, in lexicographic order.
+#[derive(Debug, Clone)]
+pub struct MultichooseIndices {
+    /// Number of indices to choose from.
+    n: usize,
+    /// Next sequence, if any.
+    indices: Option<Vec<usize>>,
+}
+
+impl Iterator for MultichooseIndices {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        let result = self.indices.clone()?;
+        let indices = self.indices.as_mut().unwrap();
+        match indices.iter().rposition(|&i| i + 1 < self.n) {
+            Some(i) => {
+                let next = indices[i] + 1;
+                for j in &mut indices[i..] {
+                    *j = next;
+                }
+            }
+            None => self.indices = None,
+        }
+        Some(result)
+    }
+}
+
+/// Iterate over the ways of choosing `k` of the indices
+/// `0..n` with repetition, as non-decreasing index
+/// sequences. There are `C(n + k - 1, k)` of them.
+pub fn multichoose_indices(n: usize, k: usize) -> MultichooseIndices {
+    let indices = if n > 0 || k == 0 {
+        Some(vec![0; k])
+    } else {
+        None
+    };
+    MultichooseIndices { n, indices }
+}
+
+/// Iterate over the multisets of `k` of the given items.
+pub fn combinations_with_replacement<T>(
+    items: &[T],
+    k: usize,
+) -> impl Iterator<Item = Vec<&T>> {
+    multichoose_indices(items.len(), k)
+        .map(move |ix| ix.into_iter().map(|i| &items[i]).collect())
+}
+
+/// Number of distinct orderings of a sorted sequence: the
+/// multinomial coefficient `k! / ∏ mᵢ!` where the `mᵢ` are
+/// the repeat counts.
+///
+/// # Panics
+///
+/// Panics if the result does not fit in `u64`.
+pub(crate) fn multiplicity<T: PartialEq>(indices: &[T]) -> u64 {
+    // Each step multiplies in one more binomial factor
+    // exactly. The partial results only grow, so each fits
+    // if the final one does, but the product taken before
+    // dividing needs the room of a `u128`.
+    let mut result = 1u64;
+    let mut total = 0u128;
+    for run in indices.chunk_by(|a, b| a == b) {
+        for i in 1..=run.len() as u128 {
+            total += 1;
+            let next = u128::from(result) * total / i;
+            result = u64::try_from(next).expect("multiplicity overflow");
+        }
+    }
+    result
+}
+
+/// Iterate over the multisets of `k` of the given items, as
+/// with `combinations_with_replacement()`, each paired with
+/// its multiplicity: the number of ordered length-`k`
+/// sequences that are arrangements of it. The
+/// multiplicities sum to `n^k`.
+///
+/// # Panics
+///
+/// The iterator panics if a multiplicity does not fit in
+/// `u64`.
+pub fn weighted_outcomes<T>(
+    items: &[T],
+    k: usize,
+) -> impl Iterator<Item = (Vec<&T>, u64)> {
+    multichoose_indices(items.len(), k).map(move |ix| {
+        let count = multiplicity(&ix);
+        (ix.into_iter().map(|i| &items[i]).collect(), count)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::testutil::binomial;
+
+    #[test]
+    fn counts_match_binomial() {
+        for n in 0..=8u64 {
+            for k in 0..=6u64 {
+                let count =
+                    multichoose_indices(n as usize, k as usize).count();
+                let expected = if n == 0 && k == 0 {
+                    1
+                } else if n == 0 {
+                    0
+                } else {
+                    binomial(n + k - 1, k)
+                };
+                assert_eq!(count as u64, expected, "{} {}", n, k);
+            }
+        }
+    }
+
+    #[test]
+    fn sequences() {
+        let all: Vec<Vec<usize>> = multichoose_indices(3, 2).collect();
+        let expected = vec![
+            vec![0, 0], vec![0, 1], vec![0, 2],
+            vec![1, 1], vec![1, 2], vec![2, 2],
+        ];
+        assert_eq!(all, expected);
+    }
+
+    #[test]
+    fn multiplicities_sum() {
+        for n in 1..=5usize {
+            for k in 0..=5u32 {
+                let items: Vec<usize> = (0..n).collect();
+                let total: u64 =
+                    weighted_outcomes(&items, k as usize).map(|(_, c)| c).sum();
+                assert_eq!(total, (n as u64).pow(k));
+            }
+        }
+    }
+}
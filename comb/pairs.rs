@@ -0,0 +1,173 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Unordered pairs, triples and `K`-tuples of distinct
+//! items from a slice.
+//!
+//! Tuples are produced in lexicographic order of their item
+//! indices, so the order is predictable and easy to split
+//! up.
+//!
+//! # Examples
+//!
+//! Sum the distances between all pairs of galaxies in the
+//! 2023 day 11 example, after expansion.
+//!
+//! ```
+//! # use comb::*;
+//! let image = [
+//!     "...#......",
+//!     ".......#..",
+//!     "#.........",
+//!     "..........",
+//!     "......#...",
+//!     ".#........",
+//!     ".........#",
+//!     "..........",
+//!     ".......#..",
+//!     "#...#.....",
+//! ];
+//! let galaxies: Vec<(usize, usize)> = image
+//!     .iter()
+//!     .enumerate()
+//!     .flat_map(|(r, row)| {
+//!         row.match_indices('#').map(move |(c, _)| (r, c))
+//!     })
+//!     .collect();
+//! let empty_rows = |r0: usize, r1: usize| {
+//!     (r0..r1).filter(|&r| galaxies.iter().all(|g| g.0 != r)).count()
+//! };
+//! let empty_cols = |c0: usize, c1: usize| {
+//!     (c0..c1).filter(|&c| galaxies.iter().all(|g| g.1 != c)).count()
+//! };
+//! let total: usize = pairs(&galaxies)
+//!     .map(|(&(r0, c0), &(r1, c1))| {
+//!         let (r0, r1) = (r0.min(r1), r0.max(r1));
+//!         let (c0, c1) = (c0.min(c1), c0.max(c1));
+//!         r1 - r0 + c1 - c0 + empty_rows(r0, r1) + empty_cols(c0, c1)
+//!     })
+//!     .sum();
+//! assert_eq!(total, 374);
+//! ```
+
+use crate::combinations::combination_indices;
+
+/// Iterator over the index pairs `(i, j)` with
+/// `i < j < n`. See `pair_indices()`.
+#[derive(Debug, Clone)]
+pub struct PairIndices {
+    /// Number of indices.
+    n: usize,
+    /// Next pair.
+    next: (usize, usize),
+}
+
+impl Iterator for PairIndices {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        let (i, j) = self.next;
+        if j >= self.n {
+            return None;
+        }
+        self.next = if j + 1 < self.n { (i, j + 1) } else { (i + 1, i + 2) };
+        Some((i, j))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (i, j) = self.next;
+        let n = self.n;
+        let left = if j >= n {
+            0
+        } else {
+            // The rest of this row, then all later rows.
+            let rest = n - i - 1;
+            (n - j) + rest * (rest - 1) / 2
+        };
+        (left, Some(left))
+    }
+}
+
+impl ExactSizeIterator for PairIndices {}
+
+/// Iterate over all index pairs `(i, j)` with `i < j < n`,
+/// in lexicographic order.
+pub fn pair_indices(n: usize) -> PairIndices {
+    PairIndices { n, next: (0, 1) }
+}
+
+/// Iterate over each unordered pair of distinct items once,
+/// in the order given by `pair_indices()`.
+pub fn pairs<T>(items: &[T]) -> impl Iterator<Item = (&T, &T)> {
+    pair_indices(items.len()).map(move |(i, j)| (&items[i], &items[j]))
+}
+
+/// Iterate over all index triples `(i, j, k)` with
+/// `i < j < k < n`, in lexicographic order.
+pub fn triple_indices(
+    n: usize,
+) -> impl Iterator<Item = (usize, usize, usize)> {
+    combination_indices(n, 3).map(|ix| (ix[0], ix[1], ix[2]))
+}
+
+/// Iterate over each unordered triple of distinct items
+/// once, in the order given by `triple_indices()`.
+pub fn triples<T>(items: &[T]) -> impl Iterator<Item = (&T, &T, &T)> {
+    triple_indices(items.len())
+        .map(move |(i, j, k)| (&items[i], &items[j], &items[k]))
+}
+
+/// Iterate over each unordered `K`-tuple of distinct items
+/// once, as arrays, in lexicographic order of indices.
+///
+/// ```
+/// # use comb::*;
+/// let quads: Vec<[&u8; 4]> = tuples::<_, 4>(&[1, 2, 3, 4, 5]).collect();
+/// assert_eq!(quads.len(), 5);
+/// assert_eq!(quads[1], [&1, &2, &3, &5]);
+/// ```
+pub fn tuples<T, const K: usize>(
+    items: &[T],
+) -> impl Iterator<Item = [&T; K]> {
+    combination_indices(items.len(), K)
+        .map(move |ix| std::array::from_fn(|i| &items[ix[i]]))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn pair_order() {
+        let ps: Vec<(usize, usize)> = pair_indices(4).collect();
+        assert_eq!(ps, vec![(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+        for n in 0..10 {
+            let mut it = pair_indices(n);
+            let expected = n * n.saturating_sub(1) / 2;
+            assert_eq!(it.len(), expected);
+            assert_eq!(it.by_ref().count(), expected);
+            assert_eq!(it.len(), 0);
+            let ts: Vec<[usize; 2]> =
+                combination_indices(n, 2).map(|c| [c[0], c[1]]).collect();
+            let ps: Vec<[usize; 2]> =
+                pair_indices(n).map(|(i, j)| [i, j]).collect();
+            assert_eq!(ps, ts);
+        }
+    }
+
+    #[test]
+    fn matches_combinations() {
+        let items: Vec<u32> = (0..7).collect();
+        let ts: Vec<[&u32; 3]> =
+            triples(&items).map(|(a, b, c)| [a, b, c]).collect();
+        assert_eq!(ts, tuples(&items).collect::<Vec<[&u32; 3]>>());
+        assert_eq!(ts.len(), 35);
+        let ps: Vec<[&u32; 2]> = pairs(&items).map(|(a, b)| [a, b]).collect();
+        assert_eq!(ps, tuples::<_, 2>(&items).collect::<Vec<_>>());
+        assert_eq!(tuples::<_, 8>(&items).count(), 0);
+        assert_eq!(tuples::<_, 0>(&items).count(), 1);
+    }
+}
@@ -0,0 +1,397 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Integer partitions, set partitions, and equal-sum splits.
+//!
+//! # Examples
+//!
+//! ```
+//! # use comb::*;
+//! let parts: Vec<Vec<u64>> = partitions_of_integer(4).collect();
+//! assert_eq!(
+//!     parts,
+//!     vec![vec![4], vec![3, 1], vec![2, 2], vec![2, 1, 1], vec![1, 1, 1, 1]],
+//! );
+//!
+//! let splits: Vec<Vec<Vec<&char>>> =
+//!     set_partitions(&['a', 'b', 'c'], 2).collect();
+//! assert_eq!(splits.len(), 3);
+//! assert_eq!(splits[0], vec![vec![&'a', &'b'], vec![&'c']]);
+//! ```
+
+/// Iterator over the partitions of an integer.
+#[derive(Debug, Clone)]
+pub struct IntegerPartitions {
+    /// Next partition, if any.
+    parts: Option<Vec<u64>>,
+}
+
+impl Iterator for IntegerPartitions {
+    type Item = Vec<u64>;
+
+    fn next(&mut self) -> Option<Vec<u64>> {
+        let result = self.parts.clone()?;
+        let parts = self.parts.as_mut().unwrap();
+        // Take the last part bigger than 1 down by one, and
+        // redistribute it and the trailing 1s after it as
+        // greedily as possible.
+        match parts.iter().rposition(|&p| p > 1) {
+            Some(i) => {
+                let mut rest = (parts.len() - i) as u64;
+                parts[i] -= 1;
+                parts.truncate(i + 1);
+                let size = parts[i];
+                while rest > 0 {
+                    let p = rest.min(size);
+                    parts.push(p);
+                    rest -= p;
+                }
+            }
+            None => self.parts = None,
+        }
+        Some(result)
+    }
+}
+
+/// Iterate over the partitions of `n`: the ways of writing it
+/// as a sum of positive parts, each given as a non-increasing
+/// list of parts. Partitions are produced in reverse
+/// lexicographic order, starting with `[n]`. The one
+/// partition of 0 is empty.
+pub fn partitions_of_integer(n: u64) -> IntegerPartitions {
+    let parts = if n == 0 { Vec::new() } else { vec![n] };
+    IntegerPartitions { parts: Some(parts) }
+}
+
+/// Iterator over the partitions of a set into a fixed number
+/// of blocks, as restricted growth strings: position `i`
+/// holds the block number of item `i`, and each block number
+/// first appears after all smaller ones.
+#[derive(Debug, Clone)]
+pub struct SetPartitionBlocks {
+    /// Number of blocks.
+    k: usize,
+    /// Next block assignment, if any.
+    blocks: Option<Vec<usize>>,
+}
+
+impl SetPartitionBlocks {
+    /// Least valid completion of `blocks` after position
+    /// `i`, given the largest block number used so far.
+    fn complete(blocks: &mut [usize], i: usize, max: usize, k: usize) {
+        let n = blocks.len();
+        let need = k - 1 - max;
+        for b in &mut blocks[i + 1..n - need] {
+            *b = 0;
+        }
+        for (j, b) in blocks[n - need..].iter_mut().enumerate() {
+            *b = max + 1 + j;
+        }
+    }
+}
+
+impl Iterator for SetPartitionBlocks {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        let result = self.blocks.clone()?;
+        let blocks = self.blocks.as_mut().unwrap();
+        let n = blocks.len();
+        let mut prefix_max = Vec::with_capacity(n);
+        let mut max = 0;
+        for &b in blocks.iter() {
+            max = max.max(b);
+            prefix_max.push(max);
+        }
+        for i in (1..n).rev() {
+            let v = blocks[i] + 1;
+            let max = prefix_max[i - 1].max(v);
+            if v <= prefix_max[i - 1] + 1
+                && v < self.k
+                && self.k - 1 - max <= n - 1 - i
+            {
+                blocks[i] = v;
+                Self::complete(blocks, i, max, self.k);
+                return Some(result);
+            }
+        }
+        self.blocks = None;
+        Some(result)
+    }
+}
+
+/// Iterate over the ways of splitting `n` items into exactly
+/// `k` unlabeled non-empty blocks, as block assignments
+/// (restricted growth strings) in lexicographic order. There
+/// are `S(n, k)` of them, a Stirling number of the second
+/// kind.
+pub fn set_partition_blocks(n: usize, k: usize) -> SetPartitionBlocks {
+    let blocks = if n == 0 && k == 0 {
+        Some(Vec::new())
+    } else if k == 0 || k > n {
+        None
+    } else {
+        let mut blocks = vec![0; n];
+        SetPartitionBlocks::complete(&mut blocks, 0, 0, k);
+        Some(blocks)
+    };
+    SetPartitionBlocks { k, blocks }
+}
+
+/// Iterate over the ways of splitting the given items into
+/// exactly `k` unlabeled non-empty groups. Groups are listed
+/// in order of their first item, and items within a group
+/// keep their original order.
+pub fn set_partitions<T>(
+    items: &[T],
+    k: usize,
+) -> impl Iterator<Item = Vec<Vec<&T>>> {
+    set_partition_blocks(items.len(), k).map(move |blocks| {
+        let mut groups = vec![Vec::new(); k];
+        for (item, b) in items.iter().zip(blocks) {
+            groups[b].push(item);
+        }
+        groups
+    })
+}
+
+/// Find the subsets of exactly `size` of the `weights`
+/// (sorted in decreasing order) that sum to `target`, as
+/// index lists, calling `f` on each.
+pub(crate) fn subsets_of_size_with_sum<F>(
+    weights: &[u64],
+    size: usize,
+    target: u64,
+    f: &mut F,
+) where
+    F: FnMut(&[usize]),
+{
+    fn go<F: FnMut(&[usize])>(
+        weights: &[u64],
+        suffix_sums: &[u64],
+        start: usize,
+        size: usize,
+        target: u64,
+        chosen: &mut Vec<usize>,
+        f: &mut F,
+    ) {
+        if size == 0 {
+            if target == 0 {
+                f(chosen);
+            }
+            return;
+        }
+        for i in start..weights.len() {
+            if weights.len() - i < size {
+                return;
+            }
+            // Weights are decreasing: if the largest remaining
+            // weights can't reach the target, nothing later can.
+            if suffix_sums[i] - suffix_sums[i + size] < target {
+                return;
+            }
+            if weights[i] > target {
+                continue;
+            }
+            chosen.push(i);
+            let rest = target - weights[i];
+            go(weights, suffix_sums, i + 1, size - 1, rest, chosen, f);
+            chosen.pop();
+        }
+    }
+    let mut suffix_sums = vec![0; weights.len() + 1];
+    for i in (0..weights.len()).rev() {
+        suffix_sums[i] = suffix_sums[i + 1] + weights[i];
+    }
+    go(weights, &suffix_sums, 0, size, target, &mut Vec::new(), f);
+}
+
+/// Try to split the given weights (sorted in decreasing
+/// order) into `groups` groups each summing to `target`,
+/// returning the groups if possible.
+fn fill_groups(
+    weights: &[u64],
+    groups: usize,
+    target: u64,
+) -> Option<Vec<Vec<u64>>> {
+    fn go(
+        weights: &[u64],
+        i: usize,
+        loads: &mut [u64],
+        target: u64,
+        assignment: &mut Vec<usize>,
+    ) -> bool {
+        if i == weights.len() {
+            return loads.iter().all(|&l| l == target);
+        }
+        for g in 0..loads.len() {
+            // Empty groups are interchangeable: only try the
+            // first one.
+            if loads[g] == 0 && loads[..g].contains(&0) {
+                break;
+            }
+            if loads[g] + weights[i] > target {
+                continue;
+            }
+            loads[g] += weights[i];
+            assignment.push(g);
+            if go(weights, i + 1, loads, target, assignment) {
+                return true;
+            }
+            assignment.pop();
+            loads[g] -= weights[i];
+        }
+        false
+    }
+    if weights.iter().sum::<u64>() != groups as u64 * target {
+        return None;
+    }
+    let mut loads = vec![0; groups];
+    let mut assignment = Vec::with_capacity(weights.len());
+    if !go(weights, 0, &mut loads, target, &mut assignment) {
+        return None;
+    }
+    let mut result = vec![Vec::new(); groups];
+    for (&w, g) in weights.iter().zip(assignment) {
+        result[g].push(w);
+    }
+    Some(result)
+}
+
+/// Split the given items into `groups` groups each summing
+/// to `target`, if possible. This is tailored to the 2015
+/// day 24 sleigh-balancing puzzle: the first group returned
+/// has as few items as possible, and among those the least
+/// product ("quantum entanglement"). Each group is listed in
+/// decreasing order.
+///
+/// Candidate first groups are enumerated by increasing size
+/// with sum-based pruning, and only the best candidates are
+/// checked for a split of the remaining items, so this is
+/// fast on puzzle inputs of 30 or so items.
+pub fn split_into_groups_with_sum(
+    items: &[u64],
+    groups: usize,
+    target: u64,
+) -> Option<Vec<Vec<u64>>> {
+    if groups == 0 {
+        return if items.is_empty() { Some(Vec::new()) } else { None };
+    }
+    if items.iter().sum::<u64>() != groups as u64 * target {
+        return None;
+    }
+    let mut weights = items.to_vec();
+    weights.sort_unstable_by(|a, b| b.cmp(a));
+    for size in 1..=weights.len() {
+        let mut candidates: Vec<Vec<usize>> = Vec::new();
+        subsets_of_size_with_sum(&weights, size, target, &mut |ix| {
+            candidates.push(ix.to_vec());
+        });
+        let product = |ix: &[usize]| -> u128 {
+            ix.iter().map(|&i| weights[i] as u128).product()
+        };
+        candidates.sort_by_cached_key(|ix| product(ix));
+        for ix in candidates {
+            let first: Vec<u64> = ix.iter().map(|&i| weights[i]).collect();
+            let rest: Vec<u64> = weights
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !ix.contains(i))
+                .map(|(_, &w)| w)
+                .collect();
+            if let Some(others) = fill_groups(&rest, groups - 1, target) {
+                let mut result = vec![first];
+                result.extend(others);
+                return Some(result);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn partition_counts() {
+        // OEIS A000041.
+        let p = [1, 1, 2, 3, 5, 7, 11, 15, 22, 30, 42, 56, 77];
+        for (n, &count) in p.iter().enumerate() {
+            let parts: Vec<Vec<u64>> =
+                partitions_of_integer(n as u64).collect();
+            assert_eq!(parts.len(), count);
+            for part in &parts {
+                assert_eq!(part.iter().sum::<u64>(), n as u64);
+                assert!(part.windows(2).all(|w| w[0] >= w[1]));
+            }
+            assert!(parts.windows(2).all(|w| w[0] > w[1]));
+        }
+    }
+
+    #[test]
+    fn stirling_and_bell() {
+        // Stirling numbers of the second kind, S(n, k).
+        let stirling: &[&[usize]] = &[
+            &[1],
+            &[0, 1],
+            &[0, 1, 1],
+            &[0, 1, 3, 1],
+            &[0, 1, 7, 6, 1],
+            &[0, 1, 15, 25, 10, 1],
+            &[0, 1, 31, 90, 65, 15, 1],
+            &[0, 1, 63, 301, 350, 140, 21, 1],
+        ];
+        let bell = [1, 1, 2, 5, 15, 52, 203, 877];
+        for (n, row) in stirling.iter().enumerate() {
+            let mut total = 0;
+            for (k, &count) in row.iter().enumerate() {
+                let blocks: Vec<Vec<usize>> =
+                    set_partition_blocks(n, k).collect();
+                assert_eq!(blocks.len(), count, "S({}, {})", n, k);
+                assert!(blocks.windows(2).all(|w| w[0] < w[1]));
+                total += blocks.len();
+            }
+            assert_eq!(set_partition_blocks(n, n + 1).count(), 0);
+            assert_eq!(total, bell[n]);
+        }
+    }
+
+    #[test]
+    fn sleigh_balancing() {
+        // 2015 day 24 example.
+        let items = [1, 2, 3, 4, 5, 7, 8, 9, 10, 11];
+        let qe = |groups: usize| {
+            let target = items.iter().sum::<u64>() / groups as u64;
+            let split =
+                split_into_groups_with_sum(&items, groups, target).unwrap();
+            assert_eq!(split.len(), groups);
+            for g in &split {
+                assert_eq!(g.iter().sum::<u64>(), target);
+            }
+            split[0].iter().product::<u64>()
+        };
+        assert_eq!(qe(3), 99);
+        assert_eq!(qe(4), 44);
+        assert_eq!(split_into_groups_with_sum(&items, 3, 19), None);
+        assert_eq!(split_into_groups_with_sum(&[5, 5, 2], 2, 6), None);
+    }
+
+    #[test]
+    fn sleigh_balancing_large() {
+        // A puzzle-sized input: 29 distinct weights.
+        let items = [
+            1, 2, 3, 7, 11, 13, 17, 19, 23, 31, 37, 41, 43, 47, 53, 59,
+            61, 67, 71, 73, 79, 83, 89, 97, 101, 103, 107, 109, 113,
+        ];
+        let target = items.iter().sum::<u64>() / 3;
+        let split = split_into_groups_with_sum(&items, 3, target).unwrap();
+        assert_eq!(split[0], vec![113, 109, 107, 101, 89, 1]);
+        let target = items.iter().sum::<u64>() / 4;
+        let split = split_into_groups_with_sum(&items, 4, target).unwrap();
+        assert_eq!(split[0], vec![113, 109, 107, 61]);
+    }
+}
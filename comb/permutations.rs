@@ -0,0 +1,181 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Permutations of a slice via [Heap's Algorithm][1].
+//!
+//! Heap's Algorithm produces each permutation from the
+//! previous one by a single swap, which makes it very cheap.
+//! The price is that the permutations are **not** produced
+//! in lexicographic order: the order is that of the
+//! algorithm, starting with the items as given.
+//!
+//! [1]: https://en.wikipedia.org/wiki/Heap%27s_algorithm
+//!
+//! # Examples
+//!
+//! ```
+//! # use comb::*;
+//! let perms: Vec<String> = permutations(&['a', 'b', 'c'])
+//!     .map(|p| p.into_iter().collect())
+//!     .collect();
+//! assert_eq!(perms, vec!["abc", "bac", "cab", "acb", "bca", "cba"]);
+//! ```
+
+/// State of an iterative Heap's Algorithm run over `n`
+/// items.
+#[derive(Debug, Clone)]
+struct HeapState {
+    /// Loop counters simulating the recursion.
+    c: Vec<usize>,
+    /// Current recursion level.
+    i: usize,
+}
+
+impl HeapState {
+    fn new(n: usize) -> Self {
+        HeapState {
+            c: vec![0; n],
+            i: 1,
+        }
+    }
+
+    /// Return the pair of positions to swap to get the next
+    /// permutation, or `None` when there are no more.
+    fn next_swap(&mut self) -> Option<(usize, usize)> {
+        while self.i < self.c.len() {
+            let i = self.i;
+            if self.c[i] < i {
+                let j = if i.is_multiple_of(2) { 0 } else { self.c[i] };
+                self.c[i] += 1;
+                self.i = 1;
+                return Some((j, i));
+            }
+            self.c[i] = 0;
+            self.i += 1;
+        }
+        None
+    }
+}
+
+/// Iterator over the permutations of some items.
+#[derive(Debug, Clone)]
+pub struct Permutations<T> {
+    /// Current permutation.
+    items: Vec<T>,
+    /// Algorithm state.
+    state: HeapState,
+    /// True until the initial permutation is returned.
+    first: bool,
+}
+
+impl<T: Clone> Iterator for Permutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.first {
+            self.first = false;
+        } else {
+            let (i, j) = self.state.next_swap()?;
+            self.items.swap(i, j);
+        }
+        Some(self.items.clone())
+    }
+}
+
+/// Iterate over all permutations of the given items, in
+/// Heap's Algorithm order. There are `n!` of them; for no
+/// items there is one empty permutation.
+pub fn permutations<T: Clone>(items: &[T]) -> Permutations<T> {
+    Permutations {
+        items: items.to_vec(),
+        state: HeapState::new(items.len()),
+        first: true,
+    }
+}
+
+/// Call `f` on each permutation of `items` in turn, in Heap's
+/// Algorithm order, permuting the slice in place. Nothing is
+/// allocated per permutation.
+///
+/// When this returns, `items` has been restored to its
+/// original arrangement.
+pub fn for_each_permutation<T, F>(items: &mut [T], mut f: F)
+where
+    F: FnMut(&[T]),
+{
+    let n = items.len();
+    // Original position of the item now at each position.
+    let mut origin: Vec<usize> = (0..n).collect();
+    let mut state = HeapState::new(n);
+    f(items);
+    while let Some((i, j)) = state.next_swap() {
+        items.swap(i, j);
+        origin.swap(i, j);
+        f(items);
+    }
+    // Put each item back, a cycle at a time.
+    for i in 0..n {
+        while origin[i] != i {
+            let j = origin[i];
+            items.swap(i, j);
+            origin.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::collections::HashSet;
+
+    fn factorial(n: usize) -> usize {
+        (1..=n).product()
+    }
+
+    #[test]
+    fn counts() {
+        for n in 0..=7 {
+            let items: Vec<usize> = (0..n).collect();
+            assert_eq!(permutations(&items).count(), factorial(n));
+            let mut items = items;
+            let mut count = 0;
+            for_each_permutation(&mut items, |_| count += 1);
+            assert_eq!(count, factorial(n));
+        }
+    }
+
+    #[test]
+    fn distinct() {
+        let items: Vec<u8> = (0..6).collect();
+        let perms: HashSet<Vec<u8>> = permutations(&items).collect();
+        assert_eq!(perms.len(), 720);
+        for p in &perms {
+            let mut sorted = p.clone();
+            sorted.sort();
+            assert_eq!(sorted, items);
+        }
+
+        let mut in_place = HashSet::new();
+        let mut items = items;
+        for_each_permutation(&mut items, |p| {
+            in_place.insert(p.to_vec());
+        });
+        assert_eq!(in_place, perms);
+    }
+
+    #[test]
+    fn same_order_and_restored() {
+        for n in 0..=6 {
+            let original: Vec<char> = ('a'..).take(n).collect();
+            let mut items = original.clone();
+            let mut seen = Vec::new();
+            for_each_permutation(&mut items, |p| seen.push(p.to_vec()));
+            assert_eq!(items, original);
+            let iterated: Vec<Vec<char>> = permutations(&original).collect();
+            assert_eq!(seen, iterated);
+        }
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Cartesian products, generated lazily in "odometer" order:
+//! the last position changes fastest.
+//!
+//! # Examples
+//!
+//! Brute-force the 2024 day 7 calibration equations, first
+//! with two operators and then with three.
+//!
+//! ```
+//! # use comb::*;
+//! #[derive(Clone, Copy)]
+//! enum Op {
+//!     Add,
+//!     Mul,
+//!     Cat,
+//! }
+//! use Op::*;
+//!
+//! let equations: &[(u64, &[u64])] = &[
+//!     (190, &[10, 19]),
+//!     (3267, &[81, 40, 27]),
+//!     (83, &[17, 5]),
+//!     (156, &[15, 6]),
+//!     (7290, &[6, 8, 6, 15]),
+//!     (161011, &[16, 10, 13]),
+//!     (192, &[17, 8, 14]),
+//!     (21037, &[9, 7, 18, 13]),
+//!     (292, &[11, 6, 16, 20]),
+//! ];
+//! let calibrate = |ops: &[Op]| -> u64 {
+//!     equations
+//!         .iter()
+//!         .filter(|&&(target, nums)| {
+//!             repeat_product(ops, nums.len() - 1).any(|choice| {
+//!                 let mut acc = nums[0];
+//!                 for (op, &n) in choice.iter().zip(&nums[1..]) {
+//!                     acc = match op {
+//!                         Add => acc + n,
+//!                         Mul => acc * n,
+//!                         Cat => acc * 10u64.pow(n.ilog10() + 1) + n,
+//!                     };
+//!                 }
+//!                 acc == target
+//!             })
+//!         })
+//!         .map(|&(target, _)| target)
+//!         .sum()
+//! };
+//! assert_eq!(calibrate(&[Add, Mul]), 3749);
+//! assert_eq!(calibrate(&[Add, Mul, Cat]), 11387);
+//! ```
+
+/// Iterator over all index vectors `v` with `v[i] <
+/// sizes[i]`, in odometer order.
+#[derive(Debug, Clone)]
+pub struct ProductIndices {
+    /// Number of choices at each position.
+    sizes: Vec<usize>,
+    /// Next index vector, if any.
+    indices: Option<Vec<usize>>,
+}
+
+impl Iterator for ProductIndices {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        let result = self.indices.clone()?;
+        let indices = self.indices.as_mut().unwrap();
+        let mut i = indices.len();
+        loop {
+            if i == 0 {
+                self.indices = None;
+                break;
+            }
+            i -= 1;
+            indices[i] += 1;
+            if indices[i] < self.sizes[i] {
+                break;
+            }
+            indices[i] = 0;
+        }
+        Some(result)
+    }
+}
+
+/// Iterate over the index vectors of the cartesian product
+/// of sets with the given sizes. An empty list of sizes
+/// gives one empty index vector; a size of 0 anywhere gives
+/// none.
+pub fn product_indices(sizes: &[usize]) -> ProductIndices {
+    let indices = if sizes.contains(&0) {
+        None
+    } else {
+        Some(vec![0; sizes.len()])
+    };
+    ProductIndices {
+        sizes: sizes.to_vec(),
+        indices,
+    }
+}
+
+/// Iterate over the cartesian product of the given sets:
+/// every vector taking its `i`-th item from `sets[i]`.
+pub fn cartesian_product<T: Clone>(
+    sets: &[Vec<T>],
+) -> impl Iterator<Item = Vec<T>> + '_ {
+    let sizes: Vec<usize> = sets.iter().map(|s| s.len()).collect();
+    product_indices(&sizes).map(move |ix| {
+        ix.into_iter()
+            .zip(sets)
+            .map(|(i, s)| s[i].clone())
+            .collect()
+    })
+}
+
+/// Iterate over the cartesian product of `k` copies of the
+/// given set: every length-`k` sequence of its items.
+pub fn repeat_product<T: Clone>(
+    set: &[T],
+    k: usize,
+) -> impl Iterator<Item = Vec<T>> + '_ {
+    product_indices(&vec![set.len(); k])
+        .map(move |ix| ix.into_iter().map(|i| set[i].clone()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn odometer_order() {
+        let all: Vec<Vec<usize>> = product_indices(&[2, 3]).collect();
+        let expected = vec![
+            vec![0, 0], vec![0, 1], vec![0, 2],
+            vec![1, 0], vec![1, 1], vec![1, 2],
+        ];
+        assert_eq!(all, expected);
+    }
+
+    #[test]
+    fn products() {
+        let sets = vec![vec!['a', 'b'], vec!['x'], vec!['1', '2']];
+        let all: Vec<String> = cartesian_product(&sets)
+            .map(|v| v.into_iter().collect())
+            .collect();
+        assert_eq!(all, vec!["ax1", "ax2", "bx1", "bx2"]);
+        assert_eq!(repeat_product(&[0, 1, 2], 4).count(), 81);
+    }
+
+    #[test]
+    fn edge_cases() {
+        let none: Vec<Vec<u8>> = Vec::new();
+        let all: Vec<Vec<u8>> = cartesian_product(&none).collect();
+        assert_eq!(all, vec![Vec::<u8>::new()]);
+        assert_eq!(repeat_product(&[1, 2], 0).count(), 1);
+        let sets = vec![vec![1, 2], vec![]];
+        assert_eq!(cartesian_product(&sets).count(), 0);
+        assert_eq!(repeat_product::<u8>(&[], 3).count(), 0);
+        assert_eq!(repeat_product::<u8>(&[], 0).count(), 1);
+    }
+}
@@ -0,0 +1,236 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Systematic pairings and selections for game-style
+//! puzzles: round-robin tournament schedules, and loadouts
+//! built by picking items for a set of equipment slots.
+//!
+//! # Examples
+//!
+//! ```
+//! # use comb::*;
+//! let rounds = round_robin_pairs(4);
+//! assert_eq!(rounds, vec![
+//!     vec![(0, 3), (1, 2)],
+//!     vec![(0, 2), (3, 1)],
+//!     vec![(0, 1), (2, 3)],
+//! ]);
+//!
+//! let hats = ["fez", "cap"];
+//! let shoes = ["boots", "clogs", "sandals"];
+//! let slots = [Slot::optional(&hats), Slot::up_to(&shoes, 2)];
+//! assert_eq!(all_loadouts(&slots).count(), 3 * 7);
+//! ```
+
+use crate::combinations::combination_indices;
+use crate::product::product_indices;
+
+/// A schedule of rounds for a round-robin tournament among
+/// players `0..n`, made by the [circle method][1]. Each pair
+/// of players meets exactly once, and no player appears
+/// twice in a round. There are `n - 1` rounds of `n / 2`
+/// pairs for even `n`; for odd `n` there are `n` rounds, in
+/// each of which one player sits out.
+///
+/// [1]: https://en.wikipedia.org/wiki/Round-robin_tournament#Circle_method
+pub fn round_robin_pairs(n: usize) -> Vec<Vec<(usize, usize)>> {
+    if n < 2 {
+        return Vec::new();
+    }
+    // With an odd number of players, pair with a dummy
+    // player `n` to sit out.
+    let m = n + n % 2;
+    // Player 0 stays put; the rest rotate around the circle.
+    let mut circle: Vec<usize> = (0..m).collect();
+    let mut rounds = Vec::with_capacity(m - 1);
+    for _ in 0..m - 1 {
+        let round = (0..m / 2)
+            .map(|i| (circle[i], circle[m - 1 - i]))
+            .filter(|&(a, b)| a < n && b < n)
+            .collect();
+        rounds.push(round);
+        circle[1..].rotate_right(1);
+    }
+    rounds
+}
+
+/// An equipment slot for `all_loadouts()`: a set of items of
+/// which between `min` and `max` distinct ones must be
+/// picked.
+#[derive(Debug, Clone, Copy)]
+pub struct Slot<'a, T> {
+    /// Items available for this slot.
+    pub items: &'a [T],
+    /// Fewest items to pick.
+    pub min: usize,
+    /// Most items to pick.
+    pub max: usize,
+}
+
+impl<'a, T> Slot<'a, T> {
+    /// Slot picking between `min` and `max` of the items.
+    pub fn new(items: &'a [T], min: usize, max: usize) -> Self {
+        Slot { items, min, max }
+    }
+
+    /// Slot picking exactly one of the items.
+    pub fn exactly_one(items: &'a [T]) -> Self {
+        Slot::new(items, 1, 1)
+    }
+
+    /// Slot picking at most one of the items.
+    pub fn optional(items: &'a [T]) -> Self {
+        Slot::new(items, 0, 1)
+    }
+
+    /// Slot picking up to `max` distinct items.
+    pub fn up_to(items: &'a [T], max: usize) -> Self {
+        Slot::new(items, 0, max)
+    }
+
+    /// The ways of filling this slot, as item lists.
+    fn choices(&self) -> Vec<Vec<&'a T>> {
+        let items = self.items;
+        (self.min..=self.max)
+            .flat_map(|k| combination_indices(items.len(), k))
+            .map(|ix| ix.into_iter().map(|i| &items[i]).collect())
+            .collect()
+    }
+}
+
+/// Iterate over every loadout that fills each of the given
+/// slots according to its rules. A loadout lists the items
+/// picked, slot by slot. Within a slot, smaller picks come
+/// first; the last slot varies fastest.
+///
+/// # Examples
+///
+/// The 2015 day 21 shop: exactly one weapon, optional armor,
+/// and up to two distinct rings.
+///
+/// ```
+/// # use comb::*;
+/// let weapons = [8, 10, 25, 40, 74];
+/// let armor = [13, 31, 53, 75, 102];
+/// let rings = [25, 50, 100, 20, 40, 80];
+/// let slots = [
+///     Slot::exactly_one(&weapons),
+///     Slot::optional(&armor),
+///     Slot::up_to(&rings, 2),
+/// ];
+/// assert_eq!(all_loadouts(&slots).count(), 5 * 6 * 22);
+/// ```
+pub fn all_loadouts<'a, T>(
+    slots: &[Slot<'a, T>],
+) -> impl Iterator<Item = Vec<&'a T>> {
+    let choices: Vec<Vec<Vec<&'a T>>> =
+        slots.iter().map(|s| s.choices()).collect();
+    let sizes: Vec<usize> = choices.iter().map(|c| c.len()).collect();
+    product_indices(&sizes).map(move |ix| {
+        ix.into_iter()
+            .zip(&choices)
+            .flat_map(|(i, c)| c[i].iter().copied())
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn every_pair_once() {
+        for n in 0..=12 {
+            let rounds = round_robin_pairs(n);
+            let nrounds = if n < 2 { 0 } else { n - 1 + n % 2 };
+            assert_eq!(rounds.len(), nrounds);
+            let mut met = vec![vec![false; n]; n];
+            for round in &rounds {
+                assert_eq!(round.len(), n / 2);
+                let mut busy = vec![false; n];
+                for &(a, b) in round {
+                    assert!(!busy[a] && !busy[b]);
+                    busy[a] = true;
+                    busy[b] = true;
+                    assert!(!met[a][b]);
+                    met[a][b] = true;
+                    met[b][a] = true;
+                }
+            }
+            for (a, row) in met.iter().enumerate() {
+                for (b, &m) in row.iter().enumerate() {
+                    assert_eq!(m, a != b);
+                }
+            }
+        }
+    }
+
+    /// Shop item as `(cost, damage, armor)`.
+    type Item = (u32, u32, u32);
+
+    #[test]
+    fn rpg_shop() {
+        // 2015 day 21 shop, against a boss with 104 hit
+        // points, 8 damage and 1 armor.
+        let weapons: [Item; 5] =
+            [(8, 4, 0), (10, 5, 0), (25, 6, 0), (40, 7, 0), (74, 8, 0)];
+        let armor: [Item; 5] =
+            [(13, 0, 1), (31, 0, 2), (53, 0, 3), (75, 0, 4), (102, 0, 5)];
+        let rings: [Item; 6] = [
+            (25, 1, 0),
+            (50, 2, 0),
+            (100, 3, 0),
+            (20, 0, 1),
+            (40, 0, 2),
+            (80, 0, 3),
+        ];
+        let slots = [
+            Slot::exactly_one(&weapons),
+            Slot::optional(&armor),
+            Slot::up_to(&rings, 2),
+        ];
+        let wins = |damage: u32, armor: u32| {
+            let hits = |hp: u32, dmg: u32| hp.div_ceil(dmg.max(1));
+            hits(104, damage.saturating_sub(1)) <= hits(100, 8 - armor.min(7))
+        };
+        let mut cheapest_win = u32::MAX;
+        let mut dearest_loss = 0;
+        let mut count = 0;
+        for loadout in all_loadouts(&slots) {
+            count += 1;
+            let cost: u32 = loadout.iter().map(|i| i.0).sum();
+            let damage = loadout.iter().map(|i| i.1).sum();
+            let armor = loadout.iter().map(|i| i.2).sum();
+            if wins(damage, armor) {
+                cheapest_win = cheapest_win.min(cost);
+            } else {
+                dearest_loss = dearest_loss.max(cost);
+            }
+        }
+        assert_eq!(count, 660);
+        assert_eq!(cheapest_win, 78);
+        assert_eq!(dearest_loss, 148);
+    }
+
+    #[test]
+    fn slot_rules() {
+        let items = [1, 2, 3];
+        let picks: Vec<Vec<&i32>> =
+            all_loadouts(&[Slot::new(&items, 2, 3)]).collect();
+        assert_eq!(
+            picks,
+            vec![
+                vec![&1, &2],
+                vec![&1, &3],
+                vec![&2, &3],
+                vec![&1, &2, &3],
+            ],
+        );
+        assert_eq!(all_loadouts::<i32>(&[]).count(), 1);
+        let none = Slot::new(&items, 4, 5);
+        assert_eq!(all_loadouts(&[Slot::optional(&items), none]).count(), 0);
+    }
+}
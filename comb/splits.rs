@@ -0,0 +1,144 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Ways of cutting a slice into contiguous non-empty chunks.
+//!
+//! The chunks are subslices of the original, so nothing is
+//! copied.
+//!
+//! # Examples
+//!
+//! Find the ways of putting `+` between some of the digits
+//! of 123456 to make 174.
+//!
+//! ```
+//! # use comb::*;
+//! let digits: Vec<u64> = (1..=6).collect();
+//! let value = |chunk: &[u64]| chunk.iter().fold(0, |n, d| 10 * n + d);
+//! let hits: Vec<Vec<u64>> = all_splits(&digits)
+//!     .map(|chunks| chunks.into_iter().map(value).collect::<Vec<_>>())
+//!     .filter(|terms| terms.iter().sum::<u64>() == 174)
+//!     .collect();
+//! assert_eq!(hits, vec![vec![123, 45, 6]]);
+//! ```
+
+use crate::combinations::combination_indices;
+use crate::subsets::subset_masks;
+
+/// Cut `items` just before each of the given increasing
+/// positions.
+fn cut_at<'a, T>(items: &'a [T], cuts: &[usize]) -> Vec<&'a [T]> {
+    let mut chunks = Vec::with_capacity(cuts.len() + 1);
+    let mut start = 0;
+    for &cut in cuts {
+        chunks.push(&items[start..cut]);
+        start = cut;
+    }
+    chunks.push(&items[start..]);
+    chunks
+}
+
+/// Iterate over the ways of cutting `items` into exactly `k`
+/// contiguous non-empty chunks, in order. For `n` items
+/// there are `C(n - 1, k - 1)` of them, produced in
+/// lexicographic order of the cut positions. An empty slice
+/// has one cutting into 0 chunks.
+pub fn compositions_of_slice<T>(
+    items: &[T],
+    k: usize,
+) -> impl Iterator<Item = Vec<&[T]>> {
+    let n = items.len();
+    let empty = (n == 0 && k == 0).then(Vec::new);
+    let cuts = (n > 0 && k > 0).then(|| combination_indices(n - 1, k - 1));
+    empty.into_iter().chain(cuts.into_iter().flatten().map(move |ix| {
+        let cuts: Vec<usize> = ix.into_iter().map(|i| i + 1).collect();
+        cut_at(items, &cuts)
+    }))
+}
+
+/// Iterate over all `2^(n - 1)` ways of cutting `n` items
+/// into contiguous non-empty chunks, in order. Bit `i` of a
+/// cutting's position in the sequence says whether there is
+/// a cut after item `i`, so the unbroken slice comes first.
+/// An empty slice has one cutting into 0 chunks.
+///
+/// # Panics
+///
+/// Panics if there are more than `MAX_SUBSET_ITEMS + 1`
+/// items.
+pub fn all_splits<T>(items: &[T]) -> impl Iterator<Item = Vec<&[T]>> {
+    let n = items.len();
+    let empty = (n == 0).then(Vec::new);
+    let masks = subset_masks(n.saturating_sub(1));
+    let masks = if n == 0 { 0..0 } else { masks };
+    empty.into_iter().chain(masks.map(move |mask| {
+        let cuts: Vec<usize> = (1..n)
+            .filter(|&i| mask & (1 << (i - 1)) != 0)
+            .collect();
+        cut_at(items, &cuts)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::testutil::binomial;
+
+    #[test]
+    fn composition_counts() {
+        for n in 1..=8usize {
+            let items: Vec<usize> = (0..n).collect();
+            let mut total = 0;
+            for k in 0..=n + 1 {
+                let cs: Vec<Vec<&[usize]>> =
+                    compositions_of_slice(&items, k).collect();
+                let expected = if k == 0 {
+                    0
+                } else {
+                    binomial(n as u64 - 1, k as u64 - 1)
+                };
+                assert_eq!(cs.len() as u64, expected);
+                for c in &cs {
+                    assert_eq!(c.len(), k);
+                    assert!(c.iter().all(|chunk| !chunk.is_empty()));
+                    assert_eq!(c.concat(), items);
+                }
+                total += cs.len();
+            }
+            assert_eq!(total, all_splits(&items).count());
+            assert_eq!(total, 1 << (n - 1));
+        }
+        let empty: [u8; 0] = [];
+        assert_eq!(compositions_of_slice(&empty, 0).count(), 1);
+        assert_eq!(compositions_of_slice(&empty, 1).count(), 0);
+        let splits: Vec<Vec<&[u8]>> = all_splits(&empty).collect();
+        assert_eq!(splits, vec![Vec::<&[u8]>::new()]);
+    }
+
+    #[test]
+    fn split_order() {
+        let items = ['a', 'b', 'c'];
+        let words: Vec<Vec<String>> = all_splits(&items)
+            .map(|s| s.into_iter().map(|c| c.iter().collect()).collect())
+            .collect();
+        assert_eq!(
+            words,
+            vec![
+                vec!["abc"],
+                vec!["a", "bc"],
+                vec!["ab", "c"],
+                vec!["a", "b", "c"],
+            ],
+        );
+        let twos: Vec<Vec<&[char]>> =
+            compositions_of_slice(&items, 2).collect();
+        let expected = vec![
+            vec![&items[..1], &items[1..]],
+            vec![&items[..2], &items[2..]],
+        ];
+        assert_eq!(twos, expected);
+    }
+}
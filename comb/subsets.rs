@@ -0,0 +1,175 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Subsets of a slice, and their bitmask representation.
+//!
+//! A subset of `n ≤ 63` items is represented as a `u64` mask
+//! whose bit `i` is set when item `i` is present. Working
+//! with the masks directly is often much faster than
+//! building subsets.
+//!
+//! # Examples
+//!
+//! ```
+//! # use comb::*;
+//! let items = ["a", "b", "c"];
+//! assert_eq!(subsets(&items).count(), 8);
+//! assert_eq!(mask_to_items(0b101, &items), vec![&"a", &"c"]);
+//! let subs: Vec<u64> = submasks_of(0b101).collect();
+//! assert_eq!(subs, vec![0b101, 0b100, 0b001, 0b000]);
+//! ```
+
+use std::ops::Range;
+
+/// Largest number of items whose subsets can be enumerated.
+pub const MAX_SUBSET_ITEMS: usize = 63;
+
+/// Iterate over the masks of all subsets of `n` items, in
+/// increasing numeric order.
+///
+/// # Panics
+///
+/// Panics if `n` is greater than `MAX_SUBSET_ITEMS`.
+pub fn subset_masks(n: usize) -> Range<u64> {
+    assert!(
+        n <= MAX_SUBSET_ITEMS,
+        "subset_masks: {} items is too many (max {})",
+        n,
+        MAX_SUBSET_ITEMS,
+    );
+    0..1u64 << n
+}
+
+/// The items of the subset with the given mask.
+///
+/// # Panics
+///
+/// Panics if there are more than 64 items, or if the mask
+/// has a bit set beyond the last item.
+pub fn mask_to_items<T>(mask: u64, items: &[T]) -> Vec<&T> {
+    assert!(
+        items.len() <= 64,
+        "mask_to_items: {} items is too many for a mask (max 64)",
+        items.len(),
+    );
+    // A shift by 64 leaves nothing beyond the last item.
+    let beyond = mask.checked_shr(items.len() as u32).unwrap_or(0);
+    assert!(
+        beyond == 0,
+        "mask_to_items: mask {:#x} has bits beyond {} items",
+        mask,
+        items.len(),
+    );
+    items
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| mask & (1 << i) != 0)
+        .map(|(_, item)| item)
+        .collect()
+}
+
+/// Iterate over all `2^n` subsets of the given items, in the
+/// order of their masks.
+///
+/// # Panics
+///
+/// Panics if there are more than `MAX_SUBSET_ITEMS` items.
+pub fn subsets<T>(items: &[T]) -> impl Iterator<Item = Vec<&T>> {
+    subset_masks(items.len()).map(move |mask| mask_to_items(mask, items))
+}
+
+/// Iterator over the submasks of a mask.
+#[derive(Debug, Clone)]
+pub struct Submasks {
+    /// Mask whose submasks are produced.
+    mask: u64,
+    /// Next submask, if any.
+    next: Option<u64>,
+}
+
+impl Iterator for Submasks {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let s = self.next?;
+        self.next = if s == 0 {
+            None
+        } else {
+            Some((s - 1) & self.mask)
+        };
+        Some(s)
+    }
+}
+
+/// Iterate over all submasks of the given mask, including
+/// the mask itself and 0, in decreasing numeric order. This
+/// uses the standard `(s - 1) & mask` trick, so it takes
+/// time proportional to the number of submasks.
+pub fn submasks_of(mask: u64) -> Submasks {
+    Submasks {
+        mask,
+        next: Some(mask),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn counts() {
+        for n in 0..=10 {
+            let items: Vec<usize> = (0..n).collect();
+            assert_eq!(subsets(&items).count(), 1 << n);
+        }
+        let all: Vec<Vec<&char>> = subsets(&['x', 'y']).collect();
+        let expected = vec![vec![], vec![&'x'], vec![&'y'], vec![&'x', &'y']];
+        assert_eq!(all, expected);
+    }
+
+    #[test]
+    fn submasks_brute_force() {
+        for mask in 0..1u64 << 8 {
+            let fast: Vec<u64> = submasks_of(mask).collect();
+            let slow: Vec<u64> =
+                (0..=mask).rev().filter(|s| s & !mask == 0).collect();
+            assert_eq!(fast, slow);
+            assert_eq!(fast.len(), 1 << mask.count_ones());
+        }
+        assert_eq!(submasks_of(1 << 63).count(), 2);
+    }
+
+    #[test]
+    fn max_items() {
+        assert_eq!(subset_masks(63).next_back(), Some(u64::MAX >> 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "too many")]
+    fn too_many_items() {
+        let _ = subset_masks(64);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mask_too_big() {
+        let _ = mask_to_items(0b100, &[1, 2]);
+    }
+
+    #[test]
+    fn full_mask() {
+        let items: Vec<usize> = (0..64).collect();
+        let all = mask_to_items(u64::MAX, &items);
+        assert_eq!(all.len(), 64);
+        assert_eq!(mask_to_items(1 << 63, &items), [&63]);
+    }
+
+    #[test]
+    #[should_panic(expected = "too many")]
+    fn mask_too_many_items() {
+        let _ = mask_to_items(1, &[0; 65]);
+    }
+}
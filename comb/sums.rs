@@ -0,0 +1,207 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Subsets of weights with a given exact sum.
+//!
+//! Subsets are given as increasing lists of indices into the
+//! weights, so that equal weights are told apart. The
+//! searches consider the weights largest first and prune
+//! branches that cannot reach the target, so they are
+//! practical well beyond the point where enumerating all
+//! `2^n` subsets is not.
+//!
+//! # Examples
+//!
+//! The 2015 day 17 example: store 25 liters of eggnog in
+//! containers.
+//!
+//! ```
+//! # use comb::*;
+//! let containers = [20, 15, 10, 5, 5];
+//! assert_eq!(subsets_with_sum(&containers, 25).count(), 4);
+//! let fewest = min_cardinality_subsets_with_sum(&containers, 25);
+//! assert_eq!(fewest, vec![vec![0, 3], vec![0, 4], vec![1, 2]]);
+//! ```
+
+use crate::partitions::subsets_of_size_with_sum;
+
+/// Iterator over subsets with a given sum. See
+/// `subsets_with_sum()`.
+#[derive(Debug, Clone)]
+pub struct SubsetsWithSum {
+    /// Original index of each weight, largest weight first.
+    order: Vec<usize>,
+    /// Weights, largest first.
+    sorted: Vec<u64>,
+    /// `suffix_sums[i]` is the sum of `sorted[i..]`.
+    suffix_sums: Vec<u64>,
+    /// Sum still needed.
+    rest: u64,
+    /// Positions in `sorted` of the current partial subset.
+    chosen: Vec<usize>,
+    /// Position of the next weight to try adding.
+    next_posn: usize,
+    /// True if the current partial subset has not yet been
+    /// checked for a hit.
+    fresh: bool,
+}
+
+impl Iterator for SubsetsWithSum {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        loop {
+            if self.fresh {
+                self.fresh = false;
+                if self.rest == 0 {
+                    let mut hit: Vec<usize> =
+                        self.chosen.iter().map(|&p| self.order[p]).collect();
+                    hit.sort_unstable();
+                    return Some(hit);
+                }
+            }
+            let rest = self.rest;
+            let extend = (self.next_posn..self.sorted.len())
+                .take_while(|&i| self.suffix_sums[i] >= rest)
+                .find(|&i| self.sorted[i] <= rest);
+            match extend {
+                Some(i) => {
+                    self.chosen.push(i);
+                    self.rest -= self.sorted[i];
+                    self.next_posn = i + 1;
+                    self.fresh = true;
+                }
+                None => {
+                    let p = self.chosen.pop()?;
+                    self.rest += self.sorted[p];
+                    self.next_posn = p + 1;
+                }
+            }
+        }
+    }
+}
+
+/// Iterate over the subsets of `weights` that sum to
+/// exactly `target`, as increasing index lists. The order
+/// of the subsets is unspecified.
+pub fn subsets_with_sum(weights: &[u64], target: u64) -> SubsetsWithSum {
+    let mut order: Vec<usize> = (0..weights.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(weights[i]));
+    let sorted: Vec<u64> = order.iter().map(|&i| weights[i]).collect();
+    let mut suffix_sums = vec![0; sorted.len() + 1];
+    for i in (0..sorted.len()).rev() {
+        suffix_sums[i] = suffix_sums[i + 1] + sorted[i];
+    }
+    SubsetsWithSum {
+        order,
+        sorted,
+        suffix_sums,
+        rest: target,
+        chosen: Vec::new(),
+        next_posn: 0,
+        fresh: true,
+    }
+}
+
+/// The subsets of `weights` with as few items as possible
+/// that sum to exactly `target`, as increasing index lists
+/// in lexicographic order. Sizes are tried smallest first,
+/// so larger subsets are never explored.
+pub fn min_cardinality_subsets_with_sum(
+    weights: &[u64],
+    target: u64,
+) -> Vec<Vec<usize>> {
+    let mut order: Vec<usize> = (0..weights.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(weights[i]));
+    let sorted: Vec<u64> = order.iter().map(|&i| weights[i]).collect();
+    for size in 0..=weights.len() {
+        let mut hits = Vec::new();
+        subsets_of_size_with_sum(&sorted, size, target, &mut |posns| {
+            let mut hit: Vec<usize> =
+                posns.iter().map(|&p| order[p]).collect();
+            hit.sort_unstable();
+            hits.push(hit);
+        });
+        if !hits.is_empty() {
+            hits.sort_unstable();
+            return hits;
+        }
+    }
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::subsets::subset_masks;
+    use crate::testutil::Rng;
+
+    fn brute_force(weights: &[u64], target: u64) -> Vec<Vec<usize>> {
+        let mut ways: Vec<Vec<usize>> = subset_masks(weights.len())
+            .map(|mask| {
+                (0..weights.len())
+                    .filter(|&i| mask & (1 << i) != 0)
+                    .collect::<Vec<usize>>()
+            })
+            .filter(|ix| ix.iter().map(|&i| weights[i]).sum::<u64>() == target)
+            .collect();
+        ways.sort();
+        ways
+    }
+
+    #[test]
+    fn eggnog() {
+        let containers = [20, 15, 10, 5, 5];
+        let mut ways: Vec<Vec<usize>> =
+            subsets_with_sum(&containers, 25).collect();
+        ways.sort();
+        let expected =
+            vec![vec![0, 3], vec![0, 4], vec![1, 2], vec![1, 3, 4]];
+        assert_eq!(ways, expected);
+        let fewest = min_cardinality_subsets_with_sum(&containers, 25);
+        assert_eq!(fewest.len(), 3);
+        assert!(min_cardinality_subsets_with_sum(&containers, 100).is_empty());
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..100 {
+            let n = rng.below(12);
+            let weights: Vec<u64> =
+                (0..n).map(|_| rng.below(8) as u64).collect();
+            let target = rng.below(20) as u64;
+            let mut ways: Vec<Vec<usize>> =
+                subsets_with_sum(&weights, target).collect();
+            ways.sort();
+            let expected = brute_force(&weights, target);
+            assert_eq!(ways, expected);
+            let min = expected.iter().map(|ix| ix.len()).min();
+            let fewest: Vec<Vec<usize>> = expected
+                .into_iter()
+                .filter(|ix| Some(ix.len()) == min)
+                .collect();
+            assert_eq!(
+                min_cardinality_subsets_with_sum(&weights, target),
+                fewest,
+            );
+        }
+    }
+
+    #[test]
+    fn many_items() {
+        // Powers of two have exactly one subset for each sum.
+        let weights: Vec<u64> = (0..40).map(|i| 1 << i).collect();
+        let target = 0b1011_0110_1100_0011_0101;
+        let ways: Vec<Vec<usize>> =
+            subsets_with_sum(&weights, target).collect();
+        assert_eq!(ways.len(), 1);
+        assert_eq!(
+            ways[0].iter().map(|&i| weights[i]).sum::<u64>(),
+            target,
+        );
+    }
+}
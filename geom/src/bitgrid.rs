@@ -0,0 +1,428 @@
+//! Dense grids of bits, for visited sets and boolean maps.
+//!
+//! A `BitGrid` takes one bit per cell, and whole rows can be
+//! combined or shifted a word at a time: handy for cellular
+//! automata.
+//!
+//! # Examples
+//!
+//! ```
+//! # use geom::*;
+//! let rows = ["#..#", ".##.", "...."];
+//! let cells = rows.iter().flat_map(|r| r.chars()).collect();
+//! let grid = Grid::new(3, 4, cells);
+//! let mut walls = BitGrid::from_grid(&grid, |&c| c == '#');
+//! assert_eq!(walls.count_ones(), 4);
+//! assert!(walls.get((1, 2)));
+//!
+//! // Open cells just south of a wall.
+//! let mut south = walls.clone();
+//! south.shift_rows(1);
+//! walls ^= &BitGrid::from_grid(&grid, |_| true);
+//! south &= &walls;
+//! let cells: Vec<(usize, usize)> = south.iter().collect();
+//! assert_eq!(cells, [(1, 0), (1, 3), (2, 1), (2, 2)]);
+//! ```
+
+use std::ops::{BitAndAssign, BitOrAssign, BitXorAssign};
+
+use crate::{Grid, GridBox};
+
+/// Bits per word of storage.
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A dense grid of bits, indexed by `(row, col)`. Each row
+/// is stored as words, with column `c` at bit `c % 64` of
+/// word `c / 64`. Bits past the last column are kept clear.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    rows: usize,
+    cols: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Make a grid with the given dimensions, all clear.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let words_per_row = cols.div_ceil(WORD_BITS);
+        BitGrid {
+            rows,
+            cols,
+            words_per_row,
+            words: vec![0; rows * words_per_row],
+        }
+    }
+
+    /// Make a grid the size of `grid`, with the bits set
+    /// where `pred` is true of the cell.
+    pub fn from_grid<T, F>(grid: &Grid<T>, pred: F) -> Self
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut bits = BitGrid::new(grid.rows(), grid.cols());
+        for r in 0..grid.rows() {
+            for c in 0..grid.cols() {
+                if pred(&grid[(r, c)]) {
+                    bits.set((r, c));
+                }
+            }
+        }
+        bits
+    }
+
+    /// Number of rows in the grid.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Number of columns in the grid.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Clip box matching the grid dimensions, for neighbor
+    /// calculations.
+    pub fn grid_box(&self) -> GridBox {
+        GridBox::new(self.rows as i64, self.cols as i64)
+    }
+
+    /// Index of the word holding a cell, and the cell's bit
+    /// in it.
+    fn locate(&self, (r, c): (usize, usize)) -> (usize, u64) {
+        assert!(r < self.rows && c < self.cols, "grid index out of range");
+        (r * self.words_per_row + c / WORD_BITS, 1 << (c % WORD_BITS))
+    }
+
+    /// The bit of a cell.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is outside the grid, as do the
+    /// other methods taking a cell.
+    pub fn get(&self, loc: (usize, usize)) -> bool {
+        let (i, bit) = self.locate(loc);
+        self.words[i] & bit != 0
+    }
+
+    /// Set the bit of a cell, returning true if it was
+    /// clear: like `HashSet::insert()`.
+    pub fn set(&mut self, loc: (usize, usize)) -> bool {
+        let (i, bit) = self.locate(loc);
+        let was_clear = self.words[i] & bit == 0;
+        self.words[i] |= bit;
+        was_clear
+    }
+
+    /// Clear the bit of a cell, returning true if it was set:
+    /// like `HashSet::remove()`.
+    pub fn clear(&mut self, loc: (usize, usize)) -> bool {
+        let (i, bit) = self.locate(loc);
+        let was_set = self.words[i] & bit != 0;
+        self.words[i] &= !bit;
+        was_set
+    }
+
+    /// Number of bits set.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Number of words storing each row.
+    pub fn words_per_row(&self) -> usize {
+        self.words_per_row
+    }
+
+    /// The words storing row `r`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row is outside the grid.
+    pub fn row_words(&self, r: usize) -> &[u64] {
+        assert!(r < self.rows, "grid row out of range");
+        let start = r * self.words_per_row;
+        &self.words[start..start + self.words_per_row]
+    }
+
+    /// Replace word `i` of row `r`. Bits past the last column
+    /// are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row or word is outside the grid.
+    pub fn set_row_word(&mut self, r: usize, i: usize, word: u64) {
+        assert!(
+            r < self.rows && i < self.words_per_row,
+            "grid word out of range",
+        );
+        let mask = if i + 1 == self.words_per_row {
+            self.last_word_mask()
+        } else {
+            !0
+        };
+        self.words[r * self.words_per_row + i] = word & mask;
+    }
+
+    /// Mask of the columns in use in the last word of a row.
+    fn last_word_mask(&self) -> u64 {
+        match self.cols % WORD_BITS {
+            0 => !0,
+            n => (1 << n) - 1,
+        }
+    }
+
+    /// Move every bit `n` rows down (toward higher rows), or
+    /// up if `n` is negative. Bits moved off the grid are
+    /// lost, and vacated rows are cleared.
+    pub fn shift_rows(&mut self, n: i64) {
+        let w = self.words_per_row;
+        let d = (n.unsigned_abs() as usize).min(self.rows);
+        let len = self.words.len();
+        if n >= 0 {
+            self.words.copy_within(..len - d * w, d * w);
+            self.words[..d * w].fill(0);
+        } else {
+            self.words.copy_within(d * w.., 0);
+            self.words[len - d * w..].fill(0);
+        }
+    }
+
+    /// Move every bit `n` columns right (toward higher
+    /// columns), or left if `n` is negative. Bits moved off
+    /// the grid are lost, and vacated columns are cleared.
+    pub fn shift_cols(&mut self, n: i64) {
+        let w = self.words_per_row;
+        if w == 0 {
+            return;
+        }
+        let d = (n.unsigned_abs() as usize).min(w * WORD_BITS);
+        let (dw, db) = (d / WORD_BITS, d % WORD_BITS);
+        let mask = self.last_word_mask();
+        for row in self.words.chunks_exact_mut(w) {
+            let old = row.to_vec();
+            let word = |i: Option<usize>| {
+                i.and_then(|i| old.get(i)).copied().unwrap_or(0)
+            };
+            for (i, new) in row.iter_mut().enumerate() {
+                *new = if n >= 0 {
+                    let hi = word(i.checked_sub(dw)) << db;
+                    let lo = match db {
+                        0 => 0,
+                        _ => word(i.checked_sub(dw + 1)) >> (64 - db),
+                    };
+                    hi | lo
+                } else {
+                    let lo = word(Some(i + dw)) >> db;
+                    let hi = match db {
+                        0 => 0,
+                        _ => word(Some(i + dw + 1)) << (64 - db),
+                    };
+                    hi | lo
+                };
+            }
+            row[w - 1] &= mask;
+        }
+    }
+
+    /// Iterator over the cells whose bits are set, in
+    /// row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let w = self.words_per_row.max(1);
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let (r, c0) = (i / w, i % w * WORD_BITS);
+            let mut rest = word;
+            std::iter::from_fn(move || {
+                if rest == 0 {
+                    return None;
+                }
+                let b = rest.trailing_zeros() as usize;
+                rest &= rest - 1;
+                Some((r, c0 + b))
+            })
+        })
+    }
+
+    /// Apply `op` to each word of this grid with the
+    /// matching word of `other`.
+    fn combine<F>(&mut self, other: &BitGrid, op: F)
+    where
+        F: Fn(&mut u64, u64),
+    {
+        assert!(
+            self.rows == other.rows && self.cols == other.cols,
+            "grid size mismatch",
+        );
+        for (w, &v) in self.words.iter_mut().zip(&other.words) {
+            op(w, v);
+        }
+    }
+}
+
+/// # Panics
+///
+/// Panics if the grids are not the same size, as for the
+/// other bitwise operations.
+impl BitOrAssign<&BitGrid> for BitGrid {
+    fn bitor_assign(&mut self, other: &BitGrid) {
+        self.combine(other, |w, v| *w |= v);
+    }
+}
+
+impl BitAndAssign<&BitGrid> for BitGrid {
+    fn bitand_assign(&mut self, other: &BitGrid) {
+        self.combine(other, |w, v| *w &= v);
+    }
+}
+
+impl BitXorAssign<&BitGrid> for BitGrid {
+    fn bitxor_assign(&mut self, other: &BitGrid) {
+        self.combine(other, |w, v| *w ^= v);
+    }
+}
+
+#[cfg(test)]
+fn bit_cells(bits: &BitGrid) -> Vec<(usize, usize)> {
+    let mut cells = Vec::new();
+    for r in 0..bits.rows() {
+        for c in 0..bits.cols() {
+            if bits.get((r, c)) {
+                cells.push((r, c));
+            }
+        }
+    }
+    cells
+}
+
+#[test]
+fn test_bitgrid_word_boundaries() {
+    for cols in [63, 64, 65, 129] {
+        let mut bits = BitGrid::new(3, cols);
+        let edges = [0, 62, 63, 64, 65, 127, 128];
+        let edges: Vec<usize> =
+            edges.into_iter().filter(|&c| c < cols).collect();
+        for &c in &edges {
+            assert!(bits.set((1, c)));
+            assert!(!bits.set((1, c)));
+        }
+        assert_eq!(bits.count_ones(), edges.len());
+        let expected: Vec<(usize, usize)> =
+            edges.iter().map(|&c| (1, c)).collect();
+        assert_eq!(bit_cells(&bits), expected);
+        assert_eq!(bits.iter().collect::<Vec<_>>(), expected);
+        assert!(!bits.get((0, cols - 1)) && !bits.get((2, 0)));
+
+        // Every shift distance matches moving the cells.
+        for n in [-130, -65, -64, -63, -1, 0, 1, 63, 64, 65, 130] {
+            let mut shifted = bits.clone();
+            shifted.shift_cols(n);
+            let moved: Vec<(usize, usize)> = expected
+                .iter()
+                .map(|&(r, c)| (r, c as i64 + n))
+                .filter(|&(_, c)| (0..cols as i64).contains(&c))
+                .map(|(r, c)| (r, c as usize))
+                .collect();
+            assert_eq!(shifted.iter().collect::<Vec<_>>(), moved, "{}", n);
+        }
+
+        assert!(bits.clear((1, cols - 1)) == edges.contains(&(cols - 1)));
+        assert!(!bits.get((1, cols - 1)));
+        bits.set_row_word(0, bits.words_per_row() - 1, !0);
+        let last = bits.row_words(0).last().unwrap();
+        assert_eq!(last.count_ones() as usize, (cols - 1) % 64 + 1);
+        assert!(bits.get((0, cols - 1)));
+    }
+}
+
+#[test]
+fn test_bitgrid_ops() {
+    let grid = Grid::new(4, 70, (0..280).collect());
+    let evens = BitGrid::from_grid(&grid, |&v| v % 2 == 0);
+    let threes = BitGrid::from_grid(&grid, |&v| v % 3 == 0);
+    let check = |bits: &BitGrid, f: &dyn Fn(i32) -> bool| {
+        for r in 0..4 {
+            for c in 0..70 {
+                assert_eq!(bits.get((r, c)), f(grid[(r, c)]), "{} {}", r, c);
+            }
+        }
+    };
+    let mut or = evens.clone();
+    or |= &threes;
+    check(&or, &|v| v % 2 == 0 || v % 3 == 0);
+    let mut and = evens.clone();
+    and &= &threes;
+    check(&and, &|v| v % 6 == 0);
+    let mut xor = evens.clone();
+    xor ^= &threes;
+    check(&xor, &|v| (v % 2 == 0) != (v % 3 == 0));
+
+    let mut down = evens.clone();
+    down.shift_rows(1);
+    check(&down, &|v| v >= 70 && v % 2 == 0);
+    down.shift_rows(-2);
+    check(&down, &|v| v < 140 && v % 2 == 0);
+    down.shift_rows(10);
+    assert_eq!(down.count_ones(), 0);
+    assert_eq!(evens.grid_box().clip((3, 69), (0, 1)), None::<(usize, usize)>);
+
+    // No columns to shift.
+    let mut empty = BitGrid::new(3, 0);
+    empty.shift_cols(5);
+    empty.shift_rows(1);
+    assert_eq!(empty.count_ones(), 0);
+}
+
+/// Breadth-first search of a maze, marking cells visited
+/// with `visit`, which returns true for a new cell. Returns
+/// the number of cells reached.
+#[cfg(test)]
+fn flood<F>(n: usize, mut visit: F) -> usize
+where
+    F: FnMut((usize, usize)) -> bool,
+{
+    use std::collections::VecDeque;
+    let wall = |(r, c): (usize, usize)| r % 4 == 2 && c % 64 != 7;
+    let clip = GridBox::new(n, n);
+    let mut queue = VecDeque::from([(0, 0)]);
+    visit((0, 0));
+    let mut reached = 0;
+    while let Some(loc) = queue.pop_front() {
+        reached += 1;
+        for next in clip.neighbors(loc, 1) {
+            if (next.0 == loc.0 || next.1 == loc.1)
+                && !wall(next)
+                && visit(next)
+            {
+                queue.push_back(next);
+            }
+        }
+    }
+    reached
+}
+
+#[test]
+#[ignore]
+fn test_bitgrid_beats_hashset() {
+    use std::collections::HashSet;
+    use std::time::Instant;
+
+    let n = 2000;
+    let start = Instant::now();
+    let mut bits = BitGrid::new(n, n);
+    let reached_bits = flood(n, |loc| bits.set(loc));
+    let bit_time = start.elapsed();
+
+    let start = Instant::now();
+    let mut set = HashSet::new();
+    let reached_set = flood(n, |loc| set.insert(loc));
+    let set_time = start.elapsed();
+
+    assert_eq!(reached_bits, reached_set);
+    assert_eq!(reached_bits, bits.count_ones());
+    assert!(reached_bits > n * n * 3 / 4);
+    assert!(
+        bit_time < set_time,
+        "BitGrid {:?}, HashSet {:?}",
+        bit_time,
+        set_time,
+    );
+}
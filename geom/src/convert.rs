@@ -1,5 +1,6 @@
+use std::any::type_name;
 use std::convert::TryInto;
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 
 pub trait ConvertInto<U> {
     fn convert_into(self) -> U;
@@ -7,10 +8,72 @@ pub trait ConvertInto<U> {
 
 impl<T, U> ConvertInto<U> for T
 where
-    T: TryInto<U>,
-    <T as TryInto<U>>::Error: Debug,
+    T: TryInto<U> + Copy + Debug,
 {
     fn convert_into(self) -> U {
-        self.try_into().unwrap()
+        match self.try_into() {
+            Ok(u) => u,
+            Err(_) => panic!("{}", ConvertError::new::<T, U>(self)),
+        }
     }
 }
+
+/// Error when a coordinate does not fit in the type asked
+/// for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConvertError {
+    /// The value, as shown by `Debug`.
+    pub value: String,
+    /// Name of the type it did not fit.
+    pub target: &'static str,
+}
+
+impl ConvertError {
+    fn new<T: Debug, U>(value: T) -> Self {
+        ConvertError {
+            value: format!("{:?}", value),
+            target: type_name::<U>(),
+        }
+    }
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "coordinate {} does not fit in {}", self.value, self.target)
+    }
+}
+
+impl std::error::Error for ConvertError {}
+
+/// Conversion of coordinates that reports values that do not
+/// fit rather than panicking.
+pub trait TryConvertInto<U> {
+    fn try_convert_into(self) -> Result<U, ConvertError>;
+}
+
+impl<T, U> TryConvertInto<U> for T
+where
+    T: TryInto<U> + Copy + Debug,
+{
+    fn try_convert_into(self) -> Result<U, ConvertError> {
+        self.try_into().map_err(|_| ConvertError::new::<T, U>(self))
+    }
+}
+
+#[test]
+fn test_try_convert() {
+    let big = 300i64;
+    let small: Result<u8, _> = big.try_convert_into();
+    let err = small.unwrap_err();
+    assert_eq!((err.value.as_str(), err.target), ("300", "u8"));
+    assert_eq!(err.to_string(), "coordinate 300 does not fit in u8");
+    assert_eq!(TryConvertInto::<u8>::try_convert_into(255i64), Ok(255));
+    let negative: Result<usize, _> = (-1i32).try_convert_into();
+    assert!(negative.is_err());
+}
+
+#[test]
+#[should_panic(expected = "coordinate -5 does not fit in u32")]
+fn test_convert_panic() {
+    let _: u32 = (-5i64).convert_into();
+}
@@ -6,6 +6,9 @@
 //! Direction and rotation management for Advent of Code
 //! solutions.
 
+use std::fmt;
+use std::str::FromStr;
+
 use crate::convert::ConvertInto;
 
 /// Symbolic direction constants. It is unfortunate that
@@ -64,6 +67,47 @@ impl Dirn {
         (r.convert_into(), c.convert_into())
     }
 
+    /// The opposite direction.
+    pub fn reverse(self) -> Dirn {
+        FACINGS[(self as usize + 2) % 4]
+    }
+
+    /// The direction whose displacement is `disp`, if any.
+    pub fn from_disp<T>(disp: (T, T)) -> Option<Dirn>
+    where
+        T: ConvertInto<i64>,
+    {
+        let disp = (disp.0.convert_into(), disp.1.convert_into());
+        DIRNS.iter().position(|&d| d == disp).map(|i| FACINGS[i])
+    }
+
+    /// The direction of `disp`, if it is a nonzero multiple
+    /// of the displacement of some direction: for instance,
+    /// `(0, -3)` gives `Left`.
+    pub fn from_disp_scaled<T>(disp: (T, T)) -> Option<Dirn>
+    where
+        T: ConvertInto<i64>,
+    {
+        let dr: i64 = disp.0.convert_into();
+        let dc: i64 = disp.1.convert_into();
+        Dirn::from_disp((dr.signum(), dc.signum()))
+    }
+
+    /// The direction as an arrow character: one of `^<v>`.
+    pub fn as_char(self) -> char {
+        ['^', '<', 'v', '>'][self as usize]
+    }
+
+    /// Parse a path of direction characters as accepted by
+    /// `Dirn::try_from()`, such as `"^^>>vv<"`. Whitespace
+    /// is skipped.
+    pub fn parse_path(path: &str) -> Result<Vec<Dirn>, DirnError> {
+        path.chars()
+            .filter(|ch| !ch.is_whitespace())
+            .map(Dirn::try_from)
+            .collect()
+    }
+
     /// Direction resulting from turning 90° in the given
     /// rotation direction the given number of times.
     pub fn turn<T>(self, rot: Rot, steps: T) -> Dirn
@@ -84,6 +128,102 @@ impl Dirn {
     }
 }
 
+/// Error when parsing an unknown direction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirnError(pub String);
+
+impl fmt::Display for DirnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown direction {:?}", self.0)
+    }
+}
+
+impl std::error::Error for DirnError {}
+
+impl TryFrom<char> for Dirn {
+    type Error = DirnError;
+
+    /// Direction from a character: `U`/`L`/`D`/`R`,
+    /// `N`/`W`/`S`/`E` (either case), or an arrow `^<v>`.
+    fn try_from(ch: char) -> Result<Self, Self::Error> {
+        use Dirn::*;
+        match ch.to_ascii_uppercase() {
+            'U' | 'N' | '^' => Ok(Up),
+            'L' | 'W' | '<' => Ok(Left),
+            'D' | 'S' | 'V' => Ok(Down),
+            'R' | 'E' | '>' => Ok(Right),
+            _ => Err(DirnError(ch.to_string())),
+        }
+    }
+}
+
+impl FromStr for Dirn {
+    type Err = DirnError;
+
+    /// Direction from a string of one character, as with
+    /// `Dirn::try_from()`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(ch), None) => {
+                Dirn::try_from(ch).map_err(|_| DirnError(s.to_string()))
+            }
+            _ => Err(DirnError(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Dirn {
+    /// Shows the direction as an arrow, as with
+    /// `Dirn::as_char()`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_char())
+    }
+}
+
+#[test]
+fn test_dirn_parse() {
+    for ch in ['<', 'L', 'W', 'l', 'w'] {
+        assert_eq!(Dirn::try_from(ch), Ok(Dirn::Left));
+    }
+    for vocab in ["^<v>", "ULDR", "NWSE", "uldr", "nwse"] {
+        assert_eq!(Dirn::parse_path(vocab).unwrap(), FACINGS, "{}", vocab);
+    }
+    for d in FACINGS {
+        assert_eq!(d.to_string().parse(), Ok(d));
+        assert_eq!(Dirn::try_from(d.as_char()), Ok(d));
+    }
+    let path = Dirn::parse_path("^^>\n>vv<").unwrap();
+    let shown: String = path.iter().map(|d| d.to_string()).collect();
+    assert_eq!(shown, "^^>>vv<");
+    assert_eq!(Dirn::try_from('x'), Err(DirnError("x".to_string())));
+    assert_eq!(Dirn::parse_path("^^x"), Err(DirnError("x".to_string())));
+    assert!("".parse::<Dirn>().is_err());
+    assert_eq!("up".parse::<Dirn>(), Err(DirnError("up".to_string())));
+}
+
+#[test]
+fn test_dirn_disp() {
+    use Dirn::*;
+    for d in FACINGS {
+        assert_eq!(Dirn::from_disp(d.disp::<i8>()), Some(d));
+        assert_eq!(d.reverse().reverse(), d);
+        assert_ne!(d.reverse(), d);
+        let (dr, dc) = d.disp::<i64>();
+        assert_eq!(d.reverse().disp(), (-dr, -dc));
+        assert_eq!(Dirn::from_disp_scaled((7 * dr, 7 * dc)), Some(d));
+    }
+    assert_eq!(Up.reverse(), Down);
+    assert_eq!(Left.reverse(), Right);
+    assert_eq!(Dirn::from_disp((0, -1)), Some(Left));
+    assert_eq!(Dirn::from_disp((0, -3)), None);
+    assert_eq!(Dirn::from_disp((1, 1)), None);
+    assert_eq!(Dirn::from_disp((0, 0)), None);
+    assert_eq!(Dirn::from_disp_scaled((0i32, -3)), Some(Left));
+    assert_eq!(Dirn::from_disp_scaled((2, 5)), None);
+    assert_eq!(Dirn::from_disp_scaled((0, 0)), None);
+}
+
 #[test]
 fn test_rot() {
     use Dirn::*;
@@ -97,3 +237,207 @@ fn test_rot() {
     assert_eq!(Up, Right.turn(CW, -9));
     assert_eq!(Down, Right.turn(CCW, -9));
 }
+
+/// Rotate the point `p` about the origin by 90° in the given
+/// rotation direction the given number of times. With the
+/// r-c coordinates of DIRNS this agrees with `Dirn::turn()`:
+/// rotating the displacement of a direction gives the
+/// displacement of the turned direction.
+pub fn rotate_point<T, U>(p: (T, T), rot: Rot, steps: U) -> (T, T)
+where
+    T: ConvertInto<i64>,
+    i64: ConvertInto<T>,
+    U: ConvertInto<i64>,
+{
+    let steps: i64 = steps.convert_into();
+    let steps = match rot {
+        Rot::CCW => steps,
+        Rot::CW => -steps,
+    };
+    let (mut r, mut c) = (p.0.convert_into(), p.1.convert_into());
+    for _ in 0..steps.rem_euclid(4) {
+        (r, c) = (-c, r);
+    }
+    (r.convert_into(), c.convert_into())
+}
+
+/// Rotate the point `p` about the point `center` as with
+/// `rotate_point()`.
+pub fn rotate_about<T, U>(
+    p: (T, T),
+    center: (T, T),
+    rot: Rot,
+    steps: U,
+) -> (T, T)
+where
+    T: ConvertInto<i64>,
+    i64: ConvertInto<T>,
+    U: ConvertInto<i64>,
+{
+    let cr: i64 = center.0.convert_into();
+    let cc: i64 = center.1.convert_into();
+    let r: i64 = p.0.convert_into();
+    let c: i64 = p.1.convert_into();
+    let (r, c) = rotate_point::<i64, U>((r - cr, c - cc), rot, steps);
+    ((r + cr).convert_into(), (c + cc).convert_into())
+}
+
+#[test]
+fn test_rotate_point() {
+    use Rot::*;
+    for d in FACINGS {
+        for steps in -9..=9 {
+            for rot in [CW, CCW] {
+                let turned = d.turn(rot, steps).disp::<i64>();
+                assert_eq!(rotate_point(d.disp::<i64>(), rot, steps), turned);
+            }
+        }
+    }
+    assert_eq!(rotate_point(DIRNS[0], CCW, 1), DIRNS[1]);
+    // 2020 day 12 waypoint: 4 north and 10 east, turned R90.
+    assert_eq!(rotate_point((-4, 10), CW, 1), (10, 4));
+    assert_eq!(rotate_point((-4, 10), CCW, 3), (10, 4));
+    assert_eq!(rotate_point((-4i32, 10), CW, 2), (4, -10));
+    assert_eq!(rotate_point((3, 7), CW, 0), (3, 7));
+
+    let center = (2u8, 3);
+    assert_eq!(rotate_about((2, 5), center, CW, 1), (4, 3));
+    assert_eq!(rotate_about((2, 5), center, CCW, 1), (0, 3));
+    assert_eq!(rotate_about((4, 4), center, CW, 2), (0, 2));
+    assert_eq!(rotate_about(center, center, CCW, 3), center);
+    for steps in 0..4 {
+        let p = rotate_about((1, 1), center, CCW, steps);
+        assert_eq!(rotate_about(p, center, CW, steps), (1, 1));
+    }
+}
+
+/// Symbolic constants for the eight compass directions,
+/// clockwise from north. These need to be matched to
+/// DIRNS8 and FACINGS8 below.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dirn8 {
+    N = 0,
+    NE = 1,
+    E = 2,
+    SE = 3,
+    S = 4,
+    SW = 5,
+    W = 6,
+    NW = 7,
+}
+
+/// Displacements induced by the compass directions, in the
+/// same r-c coordinate system as DIRNS.
+pub const DIRNS8: [(i64, i64); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+/// The possible compass facings.
+pub const FACINGS8: [Dirn8; 8] = {
+    use Dirn8::*;
+    [N, NE, E, SE, S, SW, W, NW]
+};
+
+impl Dirn8 {
+    /// Displacement resulting from a step in the given
+    /// direction.
+    pub fn disp<T>(self) -> (T, T)
+    where
+        i64: ConvertInto<T>,
+    {
+        let (r, c) = DIRNS8[self as usize];
+        (r.convert_into(), c.convert_into())
+    }
+
+    /// Apply the appropriate displacement for the given
+    /// distance in this direction to the given point.
+    pub fn displace<T, U>(self, point: (T, T), dist: U) -> (T, T)
+    where
+        T: ConvertInto<i64>,
+        i64: ConvertInto<T>,
+        U: ConvertInto<i64>,
+    {
+        let (dr, dc) = self.disp::<i64>();
+        let mut r = point.0.convert_into();
+        let mut c = point.1.convert_into();
+        let dist = dist.convert_into();
+        r += dist * dr;
+        c += dist * dc;
+        (r.convert_into(), c.convert_into())
+    }
+
+    /// The opposite direction.
+    pub fn reverse(self) -> Dirn8 {
+        FACINGS8[(self as usize + 4) % 8]
+    }
+
+    /// Direction resulting from turning 45° in the given
+    /// rotation direction the given number of times.
+    pub fn turn<T>(self, rot: Rot, steps: T) -> Dirn8
+    where
+        T: ConvertInto<i64>,
+    {
+        let steps: i64 = steps.convert_into();
+        let steps = match rot {
+            Rot::CW => steps,
+            Rot::CCW => -steps,
+        };
+        FACINGS8[(self as i64 + steps).rem_euclid(8) as usize]
+    }
+
+    /// The equivalent `Dirn`, if this is a cardinal
+    /// direction.
+    pub fn cardinal(self) -> Option<Dirn> {
+        Dirn::from_disp(self.disp::<i64>())
+    }
+}
+
+impl From<Dirn> for Dirn8 {
+    fn from(dirn: Dirn) -> Self {
+        FACINGS8[2 * ((4 - dirn as usize) % 4)]
+    }
+}
+
+#[test]
+fn test_rot8() {
+    use Dirn8::*;
+    use Rot::*;
+    assert_eq!(NW, N.turn(CCW, 1));
+    assert_eq!(NE, N.turn(CW, 1));
+    assert_eq!(W, N.turn(CCW, 2));
+    assert_eq!(SE, E.turn(CW, 1));
+    assert_eq!(S, SW.turn(CCW, 1));
+    assert_eq!(NW, N.turn(CW, 7));
+    assert_eq!(E, E.turn(CW, -16));
+    assert_eq!(E, E.turn(CCW, -16));
+    assert_eq!(NE, E.turn(CW, -9));
+    assert_eq!(SE, E.turn(CCW, -9));
+    assert_eq!(SW, NE.turn(CW, -4));
+}
+
+#[test]
+fn test_dirn8() {
+    for d in FACINGS8 {
+        assert_eq!(d.reverse().reverse(), d);
+        let (dr, dc) = d.disp::<i64>();
+        assert_eq!(d.reverse().disp(), (-dr, -dc));
+        assert_eq!(d.turn(Rot::CW, 4), d.reverse());
+        let far: (i64, i64) = d.displace((5, 5), 2);
+        assert_eq!(far, (5 + 2 * dr, 5 + 2 * dc));
+    }
+    for d in FACINGS {
+        let d8 = Dirn8::from(d);
+        assert_eq!(d8.disp::<i64>(), d.disp::<i64>());
+        assert_eq!(d8.cardinal(), Some(d));
+        assert_eq!(Dirn8::from(d.turn(Rot::CW, 1)), d8.turn(Rot::CW, 2));
+    }
+    assert_eq!(Dirn8::SW.cardinal(), None);
+    assert_eq!(Dirn8::NW.displace((3i32, 3), 2), (1, 1));
+}
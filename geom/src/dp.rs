@@ -0,0 +1,363 @@
+//! Tables for dynamic programming over two indices.
+//!
+//! A `DpTable` is filled one cell at a time in a fixed sweep
+//! order, each cell computed from cells the sweep has
+//! already filled. Reading a cell the sweep has not reached
+//! yet is a bug in the recurrence: in debug builds it panics,
+//! naming the cell.
+//!
+//! # Examples
+//!
+//! Minimal path sum moving only right and down.
+//!
+//! ```
+//! # use geom::*;
+//! let costs = [
+//!     [131, 673, 234, 103, 18],
+//!     [201, 96, 342, 965, 150],
+//!     [630, 803, 746, 422, 111],
+//!     [537, 699, 497, 121, 956],
+//!     [805, 732, 524, 37, 331],
+//! ];
+//! let mut dp = DpTable::new(5, 5);
+//! dp.fill_forward(|(r, c), done| {
+//!     let best = match (r, c) {
+//!         (0, 0) => 0,
+//!         (0, _) => *done.get((0, c - 1)),
+//!         (_, 0) => *done.get((r - 1, 0)),
+//!         _ => (*done.get((r - 1, c))).min(*done.get((r, c - 1))),
+//!     };
+//!     best + costs[r][c]
+//! });
+//! assert_eq!(*dp.corner(), 2427);
+//! ```
+
+use std::ops::Index;
+
+use crate::Grid;
+
+/// Table location as `(row, col)`.
+type Loc = (usize, usize);
+
+/// Order in which a table is filled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Sweep {
+    /// Row-major from the top left.
+    Forward,
+    /// Reverse row-major from the bottom right.
+    Backward,
+}
+
+/// Read-only access to the cells of a `DpTable` computed so
+/// far, for the closure computing the next cell.
+#[derive(Debug)]
+pub struct DpView<'a, T> {
+    /// Cells of the rows kept, starting at row `first_row`,
+    /// in row-major order.
+    cells: &'a [T],
+    first_row: usize,
+    rows: usize,
+    cols: usize,
+    at: Loc,
+    sweep: Sweep,
+}
+
+impl<'a, T> DpView<'a, T> {
+    /// Number of rows in the table.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Number of columns in the table.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// The cell being computed.
+    pub fn at(&self) -> Loc {
+        self.at
+    }
+
+    /// The value of cell `(r, c)`, which must already have
+    /// been computed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is out of range or its row is no
+    /// longer kept. In debug builds, also panics if the cell
+    /// has not been computed yet.
+    pub fn get(&self, (r, c): Loc) -> &'a T {
+        assert!(
+            r < self.rows && c < self.cols,
+            "DP cell ({}, {}) out of range",
+            r,
+            c,
+        );
+        let done = match self.sweep {
+            Sweep::Forward => (r, c) < self.at,
+            Sweep::Backward => (r, c) > self.at,
+        };
+        debug_assert!(
+            done,
+            "DP cell ({}, {}) read before it was computed, at ({}, {})",
+            r,
+            c,
+            self.at.0,
+            self.at.1,
+        );
+        assert!(r >= self.first_row, "DP row {} no longer kept", r);
+        &self.cells[(r - self.first_row) * self.cols + c]
+    }
+}
+
+/// A two-dimensional dynamic programming table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DpTable<T> {
+    table: Grid<T>,
+}
+
+impl<T: Default> DpTable<T> {
+    /// Make a table of the given size, ready to be filled.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let cells = (0..rows * cols).map(|_| T::default()).collect();
+        DpTable {
+            table: Grid::new(rows, cols, cells),
+        }
+    }
+}
+
+impl<T> DpTable<T> {
+    /// Number of rows in the table.
+    pub fn rows(&self) -> usize {
+        self.table.rows()
+    }
+
+    /// Number of columns in the table.
+    pub fn cols(&self) -> usize {
+        self.table.cols()
+    }
+
+    /// Fill every cell in the given order.
+    fn fill<F>(&mut self, sweep: Sweep, mut f: F)
+    where
+        F: FnMut(Loc, &DpView<T>) -> T,
+    {
+        let (rows, cols) = (self.rows(), self.cols());
+        for i in 0..rows * cols {
+            let i = match sweep {
+                Sweep::Forward => i,
+                Sweep::Backward => rows * cols - 1 - i,
+            };
+            let at = (i / cols, i % cols);
+            let view = DpView {
+                cells: self.table.cells(),
+                first_row: 0,
+                rows,
+                cols,
+                at,
+                sweep,
+            };
+            let value = f(at, &view);
+            self.table[at] = value;
+        }
+    }
+
+    /// Fill the table in row-major order from the top left.
+    /// The closure is given the location of each cell and a
+    /// view of the cells before it, and returns the value of
+    /// the cell.
+    pub fn fill_forward<F>(&mut self, f: F)
+    where
+        F: FnMut(Loc, &DpView<T>) -> T,
+    {
+        self.fill(Sweep::Forward, f);
+    }
+
+    /// Fill the table in reverse row-major order from the
+    /// bottom right. The closure is given the location of
+    /// each cell and a view of the cells after it, and
+    /// returns the value of the cell.
+    pub fn fill_backward<F>(&mut self, f: F)
+    where
+        F: FnMut(Loc, &DpView<T>) -> T,
+    {
+        self.fill(Sweep::Backward, f);
+    }
+
+    /// Compute a table of the given size as with
+    /// `fill_forward()`, but keep only the row being filled
+    /// and the one before it: the closure may read only
+    /// those. Returns the last row, which is empty if there
+    /// are no rows.
+    pub fn fill_rows_rolling<F>(rows: usize, cols: usize, mut f: F) -> Vec<T>
+    where
+        F: FnMut(Loc, &DpView<T>) -> T,
+    {
+        let mut cells: Vec<T> = Vec::with_capacity(2 * cols);
+        for r in 0..rows {
+            if r >= 2 {
+                cells.drain(..cols);
+            }
+            for c in 0..cols {
+                let view = DpView {
+                    cells: &cells,
+                    first_row: r.saturating_sub(1),
+                    rows,
+                    cols,
+                    at: (r, c),
+                    sweep: Sweep::Forward,
+                };
+                let value = f((r, c), &view);
+                cells.push(value);
+            }
+        }
+        let last = cells.len().saturating_sub(cols);
+        cells.split_off(last)
+    }
+
+    /// The top-left cell: the last filled by
+    /// `fill_backward()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the table is empty.
+    pub fn origin(&self) -> &T {
+        &self.table[(0, 0)]
+    }
+
+    /// The bottom-right cell: the last filled by
+    /// `fill_forward()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the table is empty.
+    pub fn corner(&self) -> &T {
+        &self.table[(self.rows() - 1, self.cols() - 1)]
+    }
+
+    /// The first row of the table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the table has no rows.
+    pub fn first_row(&self) -> &[T] {
+        self.table.row(0)
+    }
+
+    /// The last row of the table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the table has no rows.
+    pub fn last_row(&self) -> &[T] {
+        self.table.row(self.rows() - 1)
+    }
+
+    /// The table as a grid.
+    pub fn grid(&self) -> &Grid<T> {
+        &self.table
+    }
+
+    /// Turn the table into a grid.
+    pub fn into_grid(self) -> Grid<T> {
+        self.table
+    }
+}
+
+impl<T> Index<Loc> for DpTable<T> {
+    type Output = T;
+
+    fn index(&self, loc: Loc) -> &T {
+        &self.table[loc]
+    }
+}
+
+/// Number of ways the springs of a row can be assigned to
+/// match the runs of damaged springs (2023 day 12), with
+/// cell `(i, j)` counting the ways to match `springs[i..]`
+/// with `runs[j..]`.
+#[cfg(test)]
+fn arrangements(springs: &str, runs: &[usize]) -> u64 {
+    let s = springs.as_bytes();
+    let (n, m) = (s.len(), runs.len());
+    let mut dp = DpTable::new(n + 1, m + 1);
+    dp.fill_backward(|(i, j), done| {
+        if i == n {
+            return (j == m) as u64;
+        }
+        let mut ways = 0;
+        if s[i] != b'#' {
+            ways += done.get((i + 1, j));
+        }
+        if s[i] != b'.' && j < m {
+            let end = i + runs[j];
+            if end <= n
+                && !s[i..end].contains(&b'.')
+                && s.get(end) != Some(&b'#')
+            {
+                ways += done.get(((end + 1).min(n), j + 1));
+            }
+        }
+        ways
+    });
+    *dp.origin()
+}
+
+#[test]
+fn test_dp_runs() {
+    let rows: [(&str, &[usize], u64); 6] = [
+        ("???.###", &[1, 1, 3], 1),
+        (".??..??...?##.", &[1, 1, 3], 4),
+        ("?#?#?#?#?#?#?#?", &[1, 3, 1, 6], 1),
+        ("????.#...#...", &[4, 1, 1], 1),
+        ("????.######..#####.", &[1, 6, 5], 4),
+        ("?###????????", &[3, 2, 1], 10),
+    ];
+    for (springs, runs, ways) in rows {
+        assert_eq!(arrangements(springs, runs), ways, "{}", springs);
+    }
+    let unfolded = ["?###????????"; 5].join("?");
+    assert_eq!(arrangements(&unfolded, &[3, 2, 1].repeat(5)), 506250);
+}
+
+#[test]
+fn test_dp_rolling() {
+    // Number of monotone lattice paths, keeping the full
+    // table and just two rows.
+    let paths = |(r, c): Loc, done: &DpView<u64>| match (r, c) {
+        (0, _) | (_, 0) => 1,
+        _ => done.get((r - 1, c)) + done.get((r, c - 1)),
+    };
+    let mut dp = DpTable::new(17, 9);
+    dp.fill_forward(paths);
+    let last = DpTable::fill_rows_rolling(17, 9, paths);
+    assert_eq!(last, dp.last_row());
+    assert_eq!(*dp.corner(), 735471);
+    assert_eq!(dp.first_row(), [1; 9]);
+    assert_eq!(dp[(2, 2)], 6);
+    assert!(DpTable::fill_rows_rolling(0, 3, paths).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "no longer kept")]
+fn test_dp_rolling_old_row() {
+    DpTable::fill_rows_rolling(3, 2, |(r, c), done: &DpView<u8>| {
+        if r == 2 {
+            done.get((0, c));
+        }
+        0
+    });
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "DP cell (1, 1) read before it was computed")]
+fn test_dp_early_read() {
+    let mut dp = DpTable::new(2, 2);
+    dp.fill_forward(|(r, c), done: &DpView<u8>| {
+        if (r, c) == (1, 0) {
+            done.get((1, 1));
+        }
+        0
+    });
+}
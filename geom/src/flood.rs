@@ -0,0 +1,216 @@
+//! Flood fill and connected regions on a `GridBox`.
+//!
+//! # Examples
+//!
+//! ```
+//! # use geom::*;
+//! let map = ["..#..", "..#..", "##..."];
+//! let open = |(r, c): (usize, usize)| map[r].as_bytes()[c] == b'.';
+//! let clip_box = GridBox::new(3, 5);
+//! assert_eq!(clip_box.flood_fill((0, 0), open).len(), 4);
+//! assert_eq!(clip_box.flood_fill((0, 3), open).len(), 7);
+//! let sizes: Vec<usize> = clip_box
+//!     .connected_components(open)
+//!     .iter()
+//!     .map(|region| region.len())
+//!     .collect();
+//! assert_eq!(sizes, vec![4, 7]);
+//! ```
+
+use std::collections::{HashSet, VecDeque};
+
+use crate::convert::ConvertInto;
+use crate::dirns::{DIRNS, DIRNS8};
+use crate::GridBox;
+
+/// Location as `(row, col)`.
+type Loc = (i64, i64);
+
+impl GridBox {
+    /// Breadth-first search from `start` through passable
+    /// locations not yet seen, stepping by `steps`. Returns
+    /// the locations reached, in order.
+    fn flood<T, F>(
+        &self,
+        start: Loc,
+        steps: &[Loc],
+        passable: &mut F,
+        seen: &mut HashSet<Loc>,
+    ) -> Vec<Loc>
+    where
+        i64: ConvertInto<T>,
+        F: FnMut((T, T)) -> bool,
+    {
+        let mut passable =
+            |(r, c): Loc| passable((r.convert_into(), c.convert_into()));
+        if seen.contains(&start) || !passable(start) {
+            return Vec::new();
+        }
+        seen.insert(start);
+        let mut region = Vec::new();
+        let mut queue = VecDeque::from([start]);
+        while let Some(loc) = queue.pop_front() {
+            region.push(loc);
+            for &off in steps {
+                let Some(next) = self.clip::<i64, i64>(loc, off) else {
+                    continue;
+                };
+                if !seen.contains(&next) && passable(next) {
+                    seen.insert(next);
+                    queue.push_back(next);
+                }
+            }
+        }
+        region
+    }
+
+    /// Locations reachable from `start` in steps to any of
+    /// `steps`, in breadth-first order.
+    fn flood_from<T, F>(
+        &self,
+        start: (T, T),
+        steps: &[Loc],
+        mut passable: F,
+    ) -> Vec<(T, T)>
+    where
+        T: ConvertInto<i64>,
+        i64: ConvertInto<T>,
+        F: FnMut((T, T)) -> bool,
+    {
+        let start = (start.0.convert_into(), start.1.convert_into());
+        if !self.contains(start) {
+            return Vec::new();
+        }
+        let mut seen = HashSet::new();
+        self.flood(start, steps, &mut passable, &mut seen)
+            .into_iter()
+            .map(|(r, c)| (r.convert_into(), c.convert_into()))
+            .collect()
+    }
+
+    /// Return the locations reachable from `start` by steps
+    /// in the four cardinal directions through locations
+    /// for which `passable` is true, in breadth-first order
+    /// starting with `start`. Steps are clipped to the box,
+    /// so `passable` is only asked about locations inside
+    /// it. If `start` is outside the box or not passable,
+    /// nothing is reachable.
+    pub fn flood_fill<T, F>(&self, start: (T, T), passable: F) -> Vec<(T, T)>
+    where
+        T: ConvertInto<i64>,
+        i64: ConvertInto<T>,
+        F: FnMut((T, T)) -> bool,
+    {
+        self.flood_from(start, &DIRNS, passable)
+    }
+
+    /// As with `flood_fill()`, but stepping to any of the
+    /// eight surrounding locations.
+    pub fn flood_fill8<T, F>(&self, start: (T, T), passable: F) -> Vec<(T, T)>
+    where
+        T: ConvertInto<i64>,
+        i64: ConvertInto<T>,
+        F: FnMut((T, T)) -> bool,
+    {
+        self.flood_from(start, &DIRNS8, passable)
+    }
+
+    /// Partition the passable locations of the box into
+    /// regions connected by steps to any of `steps`.
+    fn components<T, F>(
+        &self,
+        steps: &[Loc],
+        mut passable: F,
+    ) -> Vec<Vec<(T, T)>>
+    where
+        i64: ConvertInto<T>,
+        F: FnMut((T, T)) -> bool,
+    {
+        let mut seen = HashSet::new();
+        let mut regions = Vec::new();
+        for start in self.cells::<i64>() {
+            let region = self.flood(start, steps, &mut passable, &mut seen);
+            if !region.is_empty() {
+                let region = region
+                    .into_iter()
+                    .map(|(r, c)| (r.convert_into(), c.convert_into()));
+                regions.push(region.collect());
+            }
+        }
+        regions
+    }
+
+    /// Partition the locations of the box for which
+    /// `passable` is true into regions connected by steps in
+    /// the four cardinal directions. The regions are in
+    /// row-major order of their first locations, each as
+    /// from `flood_fill()` from there.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the box is unclipped or unbounded.
+    pub fn connected_components<T, F>(&self, passable: F) -> Vec<Vec<(T, T)>>
+    where
+        i64: ConvertInto<T>,
+        F: FnMut((T, T)) -> bool,
+    {
+        self.components(&DIRNS, passable)
+    }
+
+    /// As with `connected_components()`, but with regions
+    /// connected by steps to any of the eight surrounding
+    /// locations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the box is unclipped or unbounded.
+    pub fn connected_components8<T, F>(&self, passable: F) -> Vec<Vec<(T, T)>>
+    where
+        i64: ConvertInto<T>,
+        F: FnMut((T, T)) -> bool,
+    {
+        self.components(&DIRNS8, passable)
+    }
+}
+
+#[test]
+fn test_flood_fill() {
+    let map = [
+        "##...#",
+        "#..#.#",
+        "####.#",
+        ".#####",
+        "#.....",
+    ];
+    let open = |(r, c): (usize, usize)| map[r].as_bytes()[c] == b'.';
+    let clip_box = GridBox::new(5, 6);
+    let region = clip_box.flood_fill((0, 3), open);
+    assert_eq!(region[0], (0, 3));
+    let mut sorted = region.clone();
+    sorted.sort();
+    let desired = vec![(0, 2), (0, 3), (0, 4), (1, 1), (1, 2), (1, 4), (2, 4)];
+    assert_eq!(sorted, desired);
+    // The region along the bottom border.
+    assert_eq!(clip_box.flood_fill((4, 5), open).len(), 5);
+    assert!(clip_box.flood_fill((0, 0), open).is_empty());
+    assert!(clip_box.flood_fill((5, 0), open).is_empty());
+    assert!(clip_box.flood_fill8((0, 6), open).is_empty());
+
+    let regions = clip_box.connected_components(open);
+    let firsts: Vec<(usize, usize)> = regions.iter().map(|r| r[0]).collect();
+    assert_eq!(firsts, vec![(0, 2), (3, 0), (4, 1)]);
+    let sizes: Vec<usize> = regions.iter().map(Vec::len).collect();
+    assert_eq!(sizes, vec![7, 1, 5]);
+    // Diagonal steps join the lone cell to the bottom row.
+    let regions = clip_box.connected_components8(open);
+    let sizes: Vec<usize> = regions.iter().map(Vec::len).collect();
+    assert_eq!(sizes, vec![7, 6]);
+    assert_eq!(clip_box.flood_fill8((3, 0), open).len(), 6);
+
+    // Big enough to overflow the stack if it recursed.
+    let big = GridBox::new(400, 400);
+    assert_eq!(big.flood_fill((0u32, 0), |_| true).len(), 160_000);
+    let unbounded = GridBox::new_unbounded();
+    let disk = |(r, c): (i64, i64)| r * r + c * c <= 100;
+    assert_eq!(unbounded.flood_fill((0, 0), disk).len(), 317);
+}
@@ -0,0 +1,319 @@
+//! Dense two-dimensional grids stored in row-major order.
+//!
+//! # Examples
+//!
+//! ```
+//! # use geom::*;
+//! let heights = Grid::from_digits(["2199", "3987"]);
+//! let low: Vec<u8> = heights
+//!     .iter_cells()
+//!     .filter(|&(loc, &h)| {
+//!         let clip_box = heights.grid_box();
+//!         clip_box.neighbors4(loc).all(|n| heights[n] > h)
+//!     })
+//!     .map(|(_, &h)| h)
+//!     .collect();
+//! assert_eq!(low, vec![1, 7]);
+//! assert_eq!(heights.get((-1, 0)), None);
+//! ```
+
+use std::ops::{Index, IndexMut};
+
+use crate::{convert::ConvertInto, GridBox};
+
+/// A dense grid of cells, indexed by `(row, col)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Make a grid with the given dimensions from a
+    /// row-major vector of cells.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of cells does not match the
+    /// dimensions.
+    pub fn new(rows: usize, cols: usize, cells: Vec<T>) -> Self {
+        assert_eq!(rows * cols, cells.len(), "grid size mismatch");
+        Grid { rows, cols, cells }
+    }
+
+    /// Make a grid from lines of text, with `f` giving the
+    /// cell for each character.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lines are not all the same length.
+    pub fn from_lines<I, S, F>(lines: I, mut f: F) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        F: FnMut(char) -> T,
+    {
+        let mut rows = 0;
+        let mut cols = None;
+        let mut cells = Vec::new();
+        for line in lines {
+            let start = cells.len();
+            cells.extend(line.as_ref().chars().map(&mut f));
+            let width = cells.len() - start;
+            let cols = *cols.get_or_insert(width);
+            assert_eq!(
+                width, cols,
+                "grid line {} has length {}, not {}",
+                rows, width, cols,
+            );
+            rows += 1;
+        }
+        Grid::new(rows, cols.unwrap_or(0), cells)
+    }
+
+    /// Number of rows in the grid.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Number of columns in the grid.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Clip box matching the grid dimensions, for neighbor
+    /// calculations.
+    pub fn grid_box(&self) -> GridBox {
+        GridBox::new(self.rows as i64, self.cols as i64)
+    }
+
+    /// The cell at `loc`, or `None` if `loc` is outside the
+    /// grid.
+    pub fn get<U>(&self, loc: (U, U)) -> Option<&T>
+    where
+        U: ConvertInto<i64>,
+    {
+        let i = self.offset(loc)?;
+        Some(&self.cells[i])
+    }
+
+    /// Mutable reference to the cell at `loc`, or `None` if
+    /// `loc` is outside the grid.
+    pub fn get_mut<U>(&mut self, loc: (U, U)) -> Option<&mut T>
+    where
+        U: ConvertInto<i64>,
+    {
+        let i = self.offset(loc)?;
+        Some(&mut self.cells[i])
+    }
+
+    /// Index into `cells` of `loc`, if it is in the grid.
+    fn offset<U>(&self, (r, c): (U, U)) -> Option<usize>
+    where
+        U: ConvertInto<i64>,
+    {
+        let r = usize::try_from(r.convert_into()).ok()?;
+        let c = usize::try_from(c.convert_into()).ok()?;
+        (r < self.rows && c < self.cols).then(|| r * self.cols + c)
+    }
+
+    /// Iterator over the locations and cells of the grid, in
+    /// row-major order.
+    pub fn iter_cells(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let cols = self.cols;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i / cols, i % cols), cell))
+    }
+
+    /// All the cells, in row-major order.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// The cells of row `r`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `r` is out of range.
+    pub fn row(&self, r: usize) -> &[T] {
+        assert!(r < self.rows, "grid row out of range");
+        &self.cells[r * self.cols..(r + 1) * self.cols]
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Make a grid of the given dimensions with `f` giving
+    /// the cell at each location.
+    fn from_fn<F>(rows: usize, cols: usize, f: F) -> Self
+    where
+        F: Fn(usize, usize) -> T,
+    {
+        let cells = (0..rows * cols).map(|i| f(i / cols, i % cols));
+        Grid::new(rows, cols, cells.collect())
+    }
+
+    /// The grid rotated 90° clockwise. The rows of the
+    /// result are the columns of this grid.
+    pub fn rotate_cw(&self) -> Self {
+        let rows = self.rows;
+        Grid::from_fn(self.cols, rows, |r, c| self[(rows - 1 - c, r)].clone())
+    }
+
+    /// The grid rotated 90° counterclockwise. The rows of
+    /// the result are the columns of this grid.
+    pub fn rotate_ccw(&self) -> Self {
+        let cols = self.cols;
+        Grid::from_fn(cols, self.rows, |r, c| self[(c, cols - 1 - r)].clone())
+    }
+
+    /// The grid with the order of its rows reversed: flipped
+    /// top to bottom.
+    pub fn flip_rows(&self) -> Self {
+        let rows = self.rows;
+        Grid::from_fn(rows, self.cols, |r, c| self[(rows - 1 - r, c)].clone())
+    }
+
+    /// The grid with the order of its columns reversed:
+    /// flipped left to right.
+    pub fn flip_cols(&self) -> Self {
+        let cols = self.cols;
+        Grid::from_fn(self.rows, cols, |r, c| self[(r, cols - 1 - c)].clone())
+    }
+
+    /// The grid flipped about its main diagonal, so that
+    /// rows become columns.
+    pub fn transpose(&self) -> Self {
+        Grid::from_fn(self.cols, self.rows, |r, c| self[(c, r)].clone())
+    }
+
+    /// Iterator over the eight symmetries of the grid: its
+    /// four rotations clockwise starting with the grid
+    /// itself, then the four rotations of its left-right
+    /// flip. A grid with symmetries of its own will produce
+    /// some grids more than once.
+    pub fn orientations(&self) -> impl Iterator<Item = Self> {
+        let mut grid = self.clone();
+        (0..8).map(move |i| {
+            if i == 4 {
+                grid = grid.flip_cols();
+            }
+            let next = grid.rotate_cw();
+            std::mem::replace(&mut grid, next)
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (r, c): (usize, usize)) -> &T {
+        assert!(r < self.rows && c < self.cols, "grid index out of range");
+        &self.cells[r * self.cols + c]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut T {
+        assert!(r < self.rows && c < self.cols, "grid index out of range");
+        &mut self.cells[r * self.cols + c]
+    }
+}
+
+impl Grid<u8> {
+    /// Make a grid of the digits in lines of text.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lines are not all the same length, or
+    /// contain a character that is not a decimal digit.
+    pub fn from_digits<I, S>(lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Grid::from_lines(lines, |ch| match ch.to_digit(10) {
+            Some(d) => d as u8,
+            None => panic!("grid character {:?} is not a digit", ch),
+        })
+    }
+}
+
+#[test]
+fn test_grid_from_lines() {
+    let text = "#..\n.#.\n..#\n.##\n";
+    let grid = Grid::from_lines(text.lines(), |ch| ch == '#');
+    assert_eq!((grid.rows(), grid.cols()), (4, 3));
+    assert!(grid[(3, 2)] && !grid[(3, 0)]);
+    assert_eq!(grid.get((1u8, 1)), Some(&true));
+    assert_eq!(grid.get((4, 0)), None);
+    assert_eq!(grid.get((0i64, -1)), None);
+    let diagonal: Vec<(usize, usize)> = grid
+        .iter_cells()
+        .filter(|&(_, &cell)| cell)
+        .map(|(loc, _)| loc)
+        .take(3)
+        .collect();
+    assert_eq!(diagonal, vec![(0, 0), (1, 1), (2, 2)]);
+
+    let mut digits = Grid::from_digits(vec!["123".to_string(), "456".into()]);
+    assert_eq!(digits.row(1), [4, 5, 6]);
+    *digits.get_mut((0, 2)).unwrap() = 9;
+    assert_eq!(digits.cells(), [1, 2, 9, 4, 5, 6]);
+    assert!(digits.get_mut((-1, 2)).is_none());
+    let empty = Grid::from_digits(Vec::<&str>::new());
+    assert_eq!((empty.rows(), empty.cols()), (0, 0));
+    assert_eq!(empty.grid_box().cells::<u8>().count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "grid line 2 has length 2, not 3")]
+fn test_grid_ragged() {
+    Grid::from_lines(["abc", "def", "gh"], |ch| ch);
+}
+
+#[test]
+#[should_panic(expected = "not a digit")]
+fn test_grid_not_digits() {
+    Grid::from_digits(["12", "3x"]);
+}
+
+#[test]
+fn test_grid_transforms() {
+    let grid = Grid::from_lines(["abc", "def"], |ch| ch);
+    let text = |g: &Grid<char>| {
+        let rows = (0..g.rows()).map(|r| g.row(r).iter().collect::<String>());
+        rows.collect::<Vec<_>>().join("/")
+    };
+    assert_eq!(text(&grid.rotate_cw()), "da/eb/fc");
+    assert_eq!(text(&grid.rotate_ccw()), "cf/be/ad");
+    assert_eq!(text(&grid.flip_rows()), "def/abc");
+    assert_eq!(text(&grid.flip_cols()), "cba/fed");
+    assert_eq!(text(&grid.transpose()), "ad/be/cf");
+
+    let mut turned = grid.clone();
+    for _ in 0..4 {
+        turned = turned.rotate_cw();
+    }
+    assert_eq!(turned, grid);
+    assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    assert_eq!(grid.transpose().transpose(), grid);
+    assert_eq!(grid.flip_rows().flip_rows(), grid);
+    assert_eq!(grid.rotate_cw().flip_cols(), grid.transpose());
+    assert_eq!(grid.rotate_cw().rotate_cw(), grid.flip_rows().flip_cols());
+
+    let all: Vec<Grid<char>> = grid.orientations().collect();
+    assert_eq!(all.len(), 8);
+    assert_eq!((&all[0], &all[1]), (&grid, &grid.rotate_cw()));
+    assert!(all.contains(&grid.transpose()));
+    for (i, a) in all.iter().enumerate() {
+        assert!(all[i + 1..].iter().all(|b| a != b));
+    }
+    // A symmetric grid repeats itself.
+    let plus = Grid::from_lines([".#.", "###", ".#."], |ch| ch);
+    assert!(plus.orientations().all(|g| g == plus));
+    let empty = Grid::<u8>::new(0, 3, Vec::new());
+    assert_eq!(empty.rotate_cw().rows(), 3);
+}
@@ -0,0 +1,284 @@
+//! Hexagonal grids.
+//!
+//! A `Hex` is a cell of a hex grid in axial coordinates.
+//! Hex grids come in two orientations, with different names
+//! for the six directions: "flat-topped" hexes have
+//! neighbors `n`, `ne`, `se`, `s`, `sw` and `nw` (2017 day
+//! 11), while "pointy-topped" hexes have neighbors `e`,
+//! `se`, `sw`, `w`, `nw` and `ne` (2020 day 24). The
+//! coordinates are the same either way.
+//!
+//! # Examples
+//!
+//! ```
+//! # use geom::*;
+//! let path: Vec<FlatHexDirn> = "se,sw,se,sw,sw"
+//!     .split(',')
+//!     .map(|d| d.parse().unwrap())
+//!     .collect();
+//! let end = path.iter().fold(Hex::ORIGIN, |h, d| h + d.disp());
+//! assert_eq!(hex_distance(Hex::ORIGIN, end), 3);
+//!
+//! let path = PointyHexDirn::parse_run("nwwswee").unwrap();
+//! let end = path.iter().fold(Hex::ORIGIN, |h, d| h + d.disp());
+//! assert_eq!(end, Hex::ORIGIN);
+//! ```
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Neg, Sub};
+use std::str::FromStr;
+
+/// A cell of a hex grid, in axial coordinates `q` and `r`.
+/// The third cube coordinate is `s() == -q - r`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+/// Displacements to the six neighbors of a hex, clockwise
+/// from pointy `e` (flat `se`).
+const HEX_DISPS: [Hex; 6] = [
+    Hex { q: 1, r: 0 },
+    Hex { q: 0, r: 1 },
+    Hex { q: -1, r: 1 },
+    Hex { q: -1, r: 0 },
+    Hex { q: 0, r: -1 },
+    Hex { q: 1, r: -1 },
+];
+
+impl Hex {
+    /// The hex at the origin.
+    pub const ORIGIN: Hex = Hex { q: 0, r: 0 };
+
+    /// The hex with the given axial coordinates.
+    pub fn new(q: i64, r: i64) -> Self {
+        Hex { q, r }
+    }
+
+    /// The third cube coordinate.
+    pub fn s(self) -> i64 {
+        -self.q - self.r
+    }
+
+    /// Return an iterator over the six neighbors of this
+    /// hex, clockwise.
+    pub fn neighbors(self) -> impl Iterator<Item = Hex> {
+        HEX_DISPS.into_iter().map(move |d| self + d)
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, other: Hex) -> Hex {
+        Hex::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl AddAssign for Hex {
+    fn add_assign(&mut self, other: Hex) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, other: Hex) -> Hex {
+        Hex::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl Neg for Hex {
+    type Output = Hex;
+
+    fn neg(self) -> Hex {
+        Hex::new(-self.q, -self.r)
+    }
+}
+
+/// The number of steps between two hexes.
+pub fn hex_distance(a: Hex, b: Hex) -> u64 {
+    let d = a - b;
+    (d.q.unsigned_abs() + d.r.unsigned_abs() + d.s().unsigned_abs()) / 2
+}
+
+/// Error when parsing an unknown hex direction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexDirnError(pub String);
+
+impl fmt::Display for HexDirnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown hex direction {:?}", self.0)
+    }
+}
+
+impl std::error::Error for HexDirnError {}
+
+/// Directions on a grid of flat-topped hexes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlatHexDirn {
+    N = 0,
+    NE = 1,
+    SE = 2,
+    S = 3,
+    SW = 4,
+    NW = 5,
+}
+
+/// The flat hex directions, clockwise from north.
+pub const FLAT_HEX_DIRNS: [FlatHexDirn; 6] = {
+    use FlatHexDirn::*;
+    [N, NE, SE, S, SW, NW]
+};
+
+/// Names of the flat hex directions, as in the puzzles.
+const FLAT_HEX_NAMES: [&str; 6] = ["n", "ne", "se", "s", "sw", "nw"];
+
+impl FlatHexDirn {
+    /// Displacement resulting from a step in this direction.
+    pub fn disp(self) -> Hex {
+        HEX_DISPS[(self as usize + 4) % 6]
+    }
+}
+
+impl fmt::Display for FlatHexDirn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(FLAT_HEX_NAMES[*self as usize])
+    }
+}
+
+impl FromStr for FlatHexDirn {
+    type Err = HexDirnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let i = FLAT_HEX_NAMES.iter().position(|&n| n == s);
+        i.map(|i| FLAT_HEX_DIRNS[i])
+            .ok_or_else(|| HexDirnError(s.to_string()))
+    }
+}
+
+/// Directions on a grid of pointy-topped hexes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointyHexDirn {
+    E = 0,
+    SE = 1,
+    SW = 2,
+    W = 3,
+    NW = 4,
+    NE = 5,
+}
+
+/// The pointy hex directions, clockwise from east.
+pub const POINTY_HEX_DIRNS: [PointyHexDirn; 6] = {
+    use PointyHexDirn::*;
+    [E, SE, SW, W, NW, NE]
+};
+
+/// Names of the pointy hex directions, as in the puzzles.
+const POINTY_HEX_NAMES: [&str; 6] = ["e", "se", "sw", "w", "nw", "ne"];
+
+impl PointyHexDirn {
+    /// Displacement resulting from a step in this direction.
+    pub fn disp(self) -> Hex {
+        HEX_DISPS[self as usize]
+    }
+
+    /// Parse a run of directions with no separators, such
+    /// as `"esenee"`.
+    pub fn parse_run(s: &str) -> Result<Vec<Self>, HexDirnError> {
+        let mut dirns = Vec::new();
+        let mut rest = s;
+        while !rest.is_empty() {
+            // Directions starting with n or s take two
+            // letters.
+            let n = if rest.starts_with(['n', 's']) { 2 } else { 1 };
+            let n = n.min(rest.len());
+            let Some(name) = rest.get(..n) else {
+                // Not on a character boundary, so not a name.
+                let name = rest.chars().take(n).collect();
+                return Err(HexDirnError(name));
+            };
+            dirns.push(name.parse()?);
+            rest = &rest[n..];
+        }
+        Ok(dirns)
+    }
+}
+
+impl fmt::Display for PointyHexDirn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(POINTY_HEX_NAMES[*self as usize])
+    }
+}
+
+impl FromStr for PointyHexDirn {
+    type Err = HexDirnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let i = POINTY_HEX_NAMES.iter().position(|&n| n == s);
+        i.map(|i| POINTY_HEX_DIRNS[i])
+            .ok_or_else(|| HexDirnError(s.to_string()))
+    }
+}
+
+#[test]
+fn test_hex_flat() {
+    // 2017 day 11 examples: path and final distance.
+    let walks = [
+        ("ne,ne,ne", 3),
+        ("ne,ne,sw,sw", 0),
+        ("ne,ne,s,s", 2),
+        ("se,sw,se,sw,sw", 3),
+    ];
+    for (walk, dist) in walks {
+        let path: Vec<FlatHexDirn> =
+            walk.split(',').map(|d| d.parse().unwrap()).collect();
+        let end = path.iter().fold(Hex::ORIGIN, |h, d| h + d.disp());
+        assert_eq!(hex_distance(Hex::ORIGIN, end), dist, "{}", walk);
+        assert_eq!(hex_distance(end, Hex::ORIGIN), dist, "{}", walk);
+        let names: Vec<String> = path.iter().map(|d| d.to_string()).collect();
+        assert_eq!(names.join(","), walk);
+    }
+    assert_eq!(
+        "up".parse::<FlatHexDirn>(),
+        Err(HexDirnError("up".to_string())),
+    );
+}
+
+#[test]
+fn test_hex_pointy() {
+    // 2020 day 24 examples.
+    let path = PointyHexDirn::parse_run("esew").unwrap();
+    let end = path.iter().fold(Hex::ORIGIN, |h, d| h + d.disp());
+    assert_eq!(end, PointyHexDirn::SE.disp());
+    let run = "sesenwnenenewseeswwswswwnenewsewsw";
+    let path = PointyHexDirn::parse_run(run).unwrap();
+    let names: Vec<String> = path.iter().map(|d| d.to_string()).collect();
+    assert_eq!(names.concat(), run);
+    assert!(PointyHexDirn::parse_run("eex").is_err());
+    assert!(PointyHexDirn::parse_run("n").is_err());
+    let err = Err(HexDirnError("é".to_string()));
+    assert_eq!(PointyHexDirn::parse_run("é"), err);
+    let err = Err(HexDirnError("nö".to_string()));
+    assert_eq!(PointyHexDirn::parse_run("enö"), err);
+
+    // Every hex has six neighbors at distance 1, each one
+    // step away in some direction of either orientation.
+    let hex = Hex::new(3, -7);
+    let neighbors: Vec<Hex> = hex.neighbors().collect();
+    let pointy: Vec<Hex> =
+        POINTY_HEX_DIRNS.iter().map(|d| hex + d.disp()).collect();
+    assert_eq!(neighbors, pointy);
+    for &n in &neighbors {
+        assert_eq!(hex_distance(hex, n), 1);
+        assert!(FLAT_HEX_DIRNS.iter().any(|d| hex + d.disp() == n));
+    }
+    let (a, b) = (Hex::new(-4, 9), Hex::new(2, 1));
+    assert_eq!(hex_distance(a, b), hex_distance(b, a));
+    assert_eq!(hex_distance(a, b), 8);
+    assert_eq!(hex_distance(a, a), 0);
+    assert_eq!(a + b - b, a);
+    assert_eq!(-a + a, Hex::ORIGIN);
+}
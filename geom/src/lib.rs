@@ -1,7 +1,34 @@
 mod convert;
+pub use convert::{ConvertError, TryConvertInto};
+
+mod bitgrid;
+pub use bitgrid::*;
 
 mod dirns;
 pub use dirns::*;
 
+mod dp;
+pub use dp::*;
+
+mod flood;
+
+mod grid;
+pub use grid::*;
+
+mod hex;
+pub use hex::*;
+
+mod line;
+pub use line::*;
+
 mod neighbors;
 pub use neighbors::*;
+
+mod neighbors3;
+pub use neighbors3::*;
+
+mod polygon;
+pub use polygon::*;
+
+mod walker;
+pub use walker::*;
@@ -0,0 +1,66 @@
+//! Lattice points on line segments.
+//!
+//! # Examples
+//!
+//! Points covered by a vent line (2021 day 5), clipped to a
+//! grid.
+//!
+//! ```
+//! # use geom::*;
+//! let clip_box = GridBox::new(4, 4);
+//! let covered: Vec<(u8, u8)> = line_between((5, 0), (0, 5))
+//!     .filter_map(|loc| clip_box.clip(loc, (0, 0)))
+//!     .collect();
+//! assert_eq!(covered, vec![(3, 2), (2, 3)]);
+//! ```
+
+use numberfns::gcd;
+
+use crate::convert::ConvertInto;
+
+/// Return an iterator over every lattice point on the line
+/// segment from `a` to `b`, inclusive of both ends, in order
+/// from `a`.
+///
+/// Only points exactly on the segment are produced: this is
+/// not Bresenham's algorithm. Horizontal, vertical and
+/// diagonal segments produce every point along them; the
+/// segment from `(0, 0)` to `(6, 3)` produces `(0, 0)`,
+/// `(2, 1)`, `(4, 2)` and `(6, 3)`; the segment from
+/// `(0, 0)` to `(5, 3)` produces just its ends.
+pub fn line_between<T>(a: (T, T), b: (T, T)) -> impl Iterator<Item = (T, T)>
+where
+    T: ConvertInto<i64>,
+    i64: ConvertInto<T>,
+{
+    let (r0, c0) = (a.0.convert_into(), a.1.convert_into());
+    let (r1, c1) = (b.0.convert_into(), b.1.convert_into());
+    let (dr, dc) = (r1 - r0, c1 - c0);
+    let steps = match (dr.unsigned_abs(), dc.unsigned_abs()) {
+        (0, n) | (n, 0) => n,
+        (m, n) => gcd(m, n),
+    } as i64;
+    let step = if steps == 0 {
+        (0, 0)
+    } else {
+        (dr / steps, dc / steps)
+    };
+    (0..=steps).map(move |i| {
+        let (r, c) = (r0 + i * step.0, c0 + i * step.1);
+        (r.convert_into(), c.convert_into())
+    })
+}
+
+#[test]
+fn test_line_between() {
+    let line = |a, b| line_between::<i32>(a, b).collect::<Vec<_>>();
+    assert_eq!(line((1, 1), (1, 3)), vec![(1, 1), (1, 2), (1, 3)]);
+    assert_eq!(line((9, 7), (7, 7)), vec![(9, 7), (8, 7), (7, 7)]);
+    assert_eq!(line((3, -1), (1, 1)), vec![(3, -1), (2, 0), (1, 1)]);
+    assert_eq!(line((0, 0), (6, 3)), vec![(0, 0), (2, 1), (4, 2), (6, 3)]);
+    assert_eq!(line((6, 3), (0, 0))[1], (4, 2));
+    assert_eq!(line((0, 0), (5, 3)), vec![(0, 0), (5, 3)]);
+    assert_eq!(line((-4, 2), (-4, 2)), vec![(-4, 2)]);
+    let long: Vec<(u64, u64)> = line_between((0, 0), (1000, 1000)).collect();
+    assert_eq!((long.len(), long[500]), (1001, (500, 500)));
+}
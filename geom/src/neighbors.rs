@@ -4,7 +4,7 @@
 //! # Examples
 //!
 //! ```
-//! # use aoc_geom::*;
+//! # use geom::*;
 //! let clip_box = GridBox::new(4, 4);
 //! let mut neighbors = clip_box
 //!     .neighbors((2, 0), 1)
@@ -18,9 +18,11 @@
 //! assert_eq!(neighbors, desired);
 //! ```
 
+use std::iter::Take;
 use std::marker::PhantomData;
 
-use crate::{convert::ConvertInto, dirns};
+use crate::convert::{ConvertError, ConvertInto, TryConvertInto};
+use crate::dirns;
 
 /// Description of the grid, for possible clipping.
 #[derive(Copy, Clone)]
@@ -29,6 +31,15 @@ pub enum GridBox {
     ClipBox((i64, i64)),
     /// Grid is unclipped.
     Unclipped,
+    /// Grid is clipped to the rectangle between the given
+    /// top-left and bottom-right corners, inclusive.
+    ClipRect((i64, i64), (i64, i64)),
+    /// Grid is unbounded in every direction: nothing is
+    /// clipped.
+    Unbounded,
+    /// Grid wraps around at the edges, with the given
+    /// numbers of rows and columns.
+    Torus((i64, i64)),
 }
 
 use self::GridBox::*;
@@ -49,8 +60,132 @@ impl GridBox {
         Unclipped
     }
 
+    /// Create a clip box for neighbor calculations with the
+    /// given top-left and bottom-right corners, inclusive.
+    /// The corners may be negative.
+    pub fn new_bounds<T>(min: (T, T), max: (T, T)) -> GridBox
+    where
+        T: ConvertInto<i64>,
+    {
+        ClipRect(
+            (min.0.convert_into(), min.1.convert_into()),
+            (max.0.convert_into(), max.1.convert_into()),
+        )
+    }
+
+    /// Create a clip box for neighbor calculations that
+    /// clips nothing, not even negative locations.
+    pub fn new_unbounded() -> GridBox {
+        Unbounded
+    }
+
+    /// Create a box of the given size that wraps around at
+    /// the edges: locations off one edge are taken to be on
+    /// the opposite one rather than clipped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either size is not positive.
+    pub fn new_torus<T>(row_size: T, col_size: T) -> GridBox
+    where
+        T: ConvertInto<i64>,
+    {
+        let size = (row_size.convert_into(), col_size.convert_into());
+        assert!(size.0 > 0 && size.1 > 0, "empty torus");
+        Torus(size)
+    }
+
+    /// The locations in the box as a half-open window
+    /// `start..end` in each coordinate, with `i64::MIN` and
+    /// `i64::MAX` standing in for no bound.
+    fn window(&self) -> ((i64, i64), (i64, i64)) {
+        match *self {
+            ClipBox(size) | Torus(size) => ((0, 0), size),
+            Unclipped => ((0, 0), (i64::MAX, i64::MAX)),
+            ClipRect(min, (r, c)) => {
+                (min, (r.saturating_add(1), c.saturating_add(1)))
+            }
+            Unbounded => ((i64::MIN, i64::MIN), (i64::MAX, i64::MAX)),
+        }
+    }
+
+    /// The top-left and bottom-right corners of the box,
+    /// inclusive, or `None` if it is unbounded in some
+    /// direction or empty.
+    pub fn bounds(&self) -> Option<((i64, i64), (i64, i64))> {
+        let ((r0, c0), (r1, c1)) = match *self {
+            ClipBox(_) | ClipRect(..) | Torus(_) => self.window(),
+            Unclipped | Unbounded => return None,
+        };
+        (r0 < r1 && c0 < c1).then_some(((r0, c0), (r1 - 1, c1 - 1)))
+    }
+
+    /// Number of rows in the box, or `None` if it is
+    /// unclipped.
+    pub fn rows(&self) -> Option<i64> {
+        match *self {
+            ClipBox(_) | ClipRect(..) | Torus(_) => {
+                let ((r0, _), (r1, _)) = self.window();
+                Some((r1 - r0).max(0))
+            }
+            Unclipped | Unbounded => None,
+        }
+    }
+
+    /// Number of columns in the box, or `None` if it is
+    /// unclipped.
+    pub fn cols(&self) -> Option<i64> {
+        match *self {
+            ClipBox(_) | ClipRect(..) | Torus(_) => {
+                let ((_, c0), (_, c1)) = self.window();
+                Some((c1 - c0).max(0))
+            }
+            Unclipped | Unbounded => None,
+        }
+    }
+
+    /// True if the given location is inside the box. **Negative
+    /// locations are outside even an unclipped box**, but not
+    /// an unbounded one.
+    pub fn contains<T>(&self, location: (T, T)) -> bool
+    where
+        T: ConvertInto<i64>,
+    {
+        let r = location.0.convert_into();
+        let c = location.1.convert_into();
+        let ((r0, c0), (r1, c1)) = self.window();
+        (r0..r1).contains(&r) && (c0..c1).contains(&c)
+    }
+
+    /// Return an iterator over every location in the box, in
+    /// row-major order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the box is unclipped or unbounded.
+    pub fn cells<T>(&self) -> impl Iterator<Item = (T, T)>
+    where
+        i64: ConvertInto<T>,
+    {
+        let ((r0, c0), (r1, c1)) = match *self {
+            ClipBox(_) | ClipRect(..) | Torus(_) => self.window(),
+            Unclipped | Unbounded => {
+                panic!("cells of an unclipped grid box")
+            }
+        };
+        (r0..r1)
+            .flat_map(move |r| (c0..c1).map(move |c| (r, c)))
+            .map(|(r, c)| (r.convert_into(), c.convert_into()))
+    }
+
     /// Return an iterator that will produce the neighbors
-    /// of the given location, clipped as needed.
+    /// of the given location, clipped as needed. The
+    /// location itself need not be inside the box: if it is
+    /// outside, the neighbors that are inside are produced.
+    /// On a torus the neighbors wrap around instead; a torus
+    /// smaller than the neighborhood will produce some
+    /// locations more than once, possibly including the
+    /// given one.
     pub fn neighbors<T, U>(
         &self,
         location: (T, T),
@@ -63,16 +198,143 @@ impl GridBox {
     {
         let r = location.0.convert_into();
         let c = location.1.convert_into();
-        assert!(r >= 0 && c >= 0);
-        if let ClipBox((row_size, col_size)) = *self {
-            assert!(r < row_size && c < col_size);
-        };
         Neighbors::new(self, (r, c), dist.convert_into())
     }
 
+    /// As with `neighbors()`, but checking that the given
+    /// location is inside the box.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the location is outside the box.
+    pub fn neighbors_strict<T, U>(
+        &self,
+        location: (T, T),
+        dist: U,
+    ) -> Neighbors<T>
+    where
+        T: ConvertInto<i64>,
+        i64: ConvertInto<T>,
+        U: ConvertInto<i64>,
+    {
+        let r = location.0.convert_into();
+        let c = location.1.convert_into();
+        assert!(
+            self.contains((r, c)),
+            "neighbors of ({}, {}) outside grid box",
+            r,
+            c,
+        );
+        Neighbors::new(self, (r, c), dist.convert_into())
+    }
+
+    /// As with `neighbors()`, but producing an error for any
+    /// neighbor that does not fit in `T`, rather than
+    /// panicking.
+    pub fn try_neighbors<T, U>(
+        &self,
+        location: (T, T),
+        dist: U,
+    ) -> impl Iterator<Item = Result<(T, T), ConvertError>>
+    where
+        T: ConvertInto<i64>,
+        i64: TryConvertInto<T>,
+        U: ConvertInto<i64>,
+    {
+        let r = location.0.convert_into();
+        let c = location.1.convert_into();
+        self.neighbors::<i64, _>((r, c), dist).map(|(r, c)| {
+            Ok((r.try_convert_into()?, c.try_convert_into()?))
+        })
+    }
+
+    /// Return an iterator that will produce the eight
+    /// surrounding cells of the given location, clipped as
+    /// needed as with `neighbors()`.
+    pub fn neighbors8<T>(&self, location: (T, T)) -> Neighbors<T>
+    where
+        T: ConvertInto<i64>,
+        i64: ConvertInto<T>,
+    {
+        self.neighbors(location, 1i64)
+    }
+
+    /// Return an iterator that will produce the neighbors of
+    /// the given location in the four cardinal directions,
+    /// clipped as needed as with `neighbors()`, in the order
+    /// of `DIRNS`.
+    pub fn neighbors4<T>(
+        &self,
+        location: (T, T),
+    ) -> impl Iterator<Item = (T, T)>
+    where
+        T: ConvertInto<i64>,
+        i64: ConvertInto<T>,
+    {
+        let r = location.0.convert_into();
+        let c = location.1.convert_into();
+        let clip_box = *self;
+        dirns::DIRNS.iter().filter_map(move |&off| {
+            clip_box
+                .clip::<i64, i64>((r, c), off)
+                .map(|(r, c)| (r.convert_into(), c.convert_into()))
+        })
+    }
+
+    /// Return an iterator that will produce the locations at
+    /// exactly Chebyshev distance `dist` from the given
+    /// location, clipped as needed, clockwise from the top
+    /// left. Distance 0 produces just the location itself,
+    /// if it is inside the box.
+    pub fn ring<T, U>(
+        &self,
+        location: (T, T),
+        dist: U,
+    ) -> impl Iterator<Item = (T, T)>
+    where
+        T: ConvertInto<i64>,
+        i64: ConvertInto<T>,
+        U: ConvertInto<i64>,
+    {
+        let r = location.0.convert_into();
+        let c = location.1.convert_into();
+        let clip_box = *self;
+        ring::<_, i64>(dist).filter_map(move |off| {
+            clip_box
+                .clip::<i64, i64>((r, c), off)
+                .map(|(r, c)| (r.convert_into(), c.convert_into()))
+        })
+    }
+
+    /// Return an iterator that will produce the locations at
+    /// exactly Manhattan distance `dist` from the given
+    /// location, clipped as needed, clockwise from the top.
+    /// Distance 0 produces just the location itself, if it
+    /// is inside the box.
+    pub fn manhattan_ring<T, U>(
+        &self,
+        location: (T, T),
+        dist: U,
+    ) -> impl Iterator<Item = (T, T)>
+    where
+        T: ConvertInto<i64>,
+        i64: ConvertInto<T>,
+        U: ConvertInto<i64>,
+    {
+        let r = location.0.convert_into();
+        let c = location.1.convert_into();
+        let clip_box = *self;
+        manhattan_ring::<_, i64>(dist).filter_map(move |off| {
+            clip_box
+                .clip::<i64, i64>((r, c), off)
+                .map(|(r, c)| (r.convert_into(), c.convert_into()))
+        })
+    }
+
     /// Return an iterator that will a beam from the
     /// given location in the given direction, stopping
-    /// at a grid boundary.
+    /// at a grid boundary. **On a torus the beam wraps
+    /// around and never stops:** see `beam_n()`.
     pub fn beam<T, U>(
         &self,
         location: (T, T),
@@ -90,6 +352,62 @@ impl GridBox {
         Beam::new(self, (r, c), (dr, dc))
     }
 
+    /// As with `beam()`, but producing an error for any
+    /// location that does not fit in `T`, rather than
+    /// panicking.
+    pub fn try_beam<T, U>(
+        &self,
+        location: (T, T),
+        step: (U, U),
+    ) -> impl Iterator<Item = Result<(T, T), ConvertError>> + '_
+    where
+        T: ConvertInto<i64>,
+        i64: TryConvertInto<T>,
+        U: ConvertInto<i64>,
+    {
+        let r = location.0.convert_into();
+        let c = location.1.convert_into();
+        self.beam::<i64, U>((r, c), step).map(|(r, c)| {
+            Ok((r.try_convert_into()?, c.try_convert_into()?))
+        })
+    }
+
+    /// Return an iterator that will a beam from the given
+    /// location in the given direction as with `beam()`, but
+    /// starting with the given location itself if it is in
+    /// bounds.
+    pub fn beam_inclusive<T, U>(
+        &self,
+        location: (T, T),
+        step: (U, U),
+    ) -> Beam<'_, T>
+    where
+        T: ConvertInto<i64>,
+        i64: ConvertInto<T>,
+        U: ConvertInto<i64>,
+    {
+        let mut beam = self.beam(location, step);
+        beam.start = true;
+        beam
+    }
+
+    /// Return an iterator that will a beam from the given
+    /// location in the given direction as with `beam()`, but
+    /// for at most `n` steps.
+    pub fn beam_n<T, U>(
+        &self,
+        location: (T, T),
+        step: (U, U),
+        n: usize,
+    ) -> Take<Beam<'_, T>>
+    where
+        T: ConvertInto<i64>,
+        i64: ConvertInto<T>,
+        U: ConvertInto<i64>,
+    {
+        self.beam(location, step).take(n)
+    }
+
     /// Return the source location adjusted by the given offset
     /// iff the dest location is in-bounds. This is useful when
     /// "manual" clipping is needed.
@@ -103,22 +421,22 @@ impl GridBox {
         let c = loc.1.convert_into();
         let dr = off.0.convert_into();
         let dc = off.1.convert_into();
-        let nr = r + dr;
-        let nc = c + dc;
-        if nr < 0 || nc < 0 {
+        let nr = r.checked_add(dr)?;
+        let nc = c.checked_add(dc)?;
+        if let Torus((rows, cols)) = *self {
+            let (nr, nc) = (nr.rem_euclid(rows), nc.rem_euclid(cols));
+            return Some((nr.convert_into(), nc.convert_into()));
+        }
+        if !self.contains((nr, nc)) {
             return None;
         }
-        if let ClipBox((row_size, col_size)) = *self {
-            if nr >= row_size || nc >= col_size {
-                return None;
-            }
-        };
         Some((nr.convert_into(), nc.convert_into()))
     }
 }
 
 /// Iterator over the neighbors of a point in the four cardinal
 /// directions, clipped as appropriate.
+#[derive(Clone)]
 pub struct Neighbors<T> {
     // Origin.
     orig: (i64, i64),
@@ -128,6 +446,8 @@ pub struct Neighbors<T> {
     start: (i64, i64),
     // Lower-right corner.
     end: (i64, i64),
+    // Torus size to wrap locations to, if any.
+    wrap: Option<(i64, i64)>,
     // Phantom type for iterator.
     phantom: PhantomData<T>,
 }
@@ -142,28 +462,60 @@ impl<T> Neighbors<T> {
     ) -> Self {
         assert!(dist > 0);
         let (r, c) = orig;
-        let start = (0.max(r - dist), 0.max(c - dist));
-        let end = if let ClipBox((rows, cols)) = *bounds {
-            (rows.min(r + dist + 1), cols.min(c + dist + 1))
-        } else {
-            (r + dist + 1, c + dist + 1)
+        let (window, wrap) = match *bounds {
+            Torus(size) => (Unbounded.window(), Some(size)),
+            _ => (bounds.window(), None),
         };
+        let ((r0, c0), (r1, c1)) = window;
+        let near = |x: i64, lo: i64| lo.max(x.saturating_sub(dist));
+        let far = |x: i64, hi: i64| hi.min(x.saturating_add(dist + 1));
+        let start = (near(r, r0), near(c, c0));
+        let mut end = (far(r, r1), far(c, c1));
+        if start.0 >= end.0 || start.1 >= end.1 {
+            // No neighbors are inside the box.
+            end = start;
+        }
         Neighbors {
             orig,
             loc: start,
             start,
             end,
+            wrap,
             phantom: PhantomData,
         }
     }
 }
 
+impl<T> Neighbors<T> {
+    /// Number of neighbors not yet produced.
+    fn remaining(&self) -> usize {
+        let (r, c) = self.loc;
+        if r >= self.end.0 {
+            return 0;
+        }
+        let width = self.end.1 - self.start.1;
+        let cells = (self.end.1 - c).max(0) + (self.end.0 - r - 1) * width;
+        // The origin is skipped if it is in the window and
+        // not yet passed.
+        let (or, oc) = self.orig;
+        let in_window = (self.start.0..self.end.0).contains(&or)
+            && (self.start.1..self.end.1).contains(&oc);
+        let ahead = or > r || (or == r && oc >= c);
+        (cells - (in_window && ahead) as i64) as usize
+    }
+}
+
 impl<T> Iterator for Neighbors<T>
 where
     i64: ConvertInto<T>,
 {
     type Item = (T, T);
 
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.remaining();
+        (n, Some(n))
+    }
+
     /// Return the next neighbor of the source point.
     fn next(&mut self) -> Option<Self::Item> {
         if self.loc == self.orig {
@@ -177,13 +529,18 @@ where
             self.loc = (self.loc.0 + 1, self.start.1);
             return self.next();
         }
-        let result =
-            (self.loc.0.convert_into(), self.loc.1.convert_into());
+        let (mut r, mut c) = self.loc;
+        if let Some((rows, cols)) = self.wrap {
+            (r, c) = (r.rem_euclid(rows), c.rem_euclid(cols));
+        }
+        let result = (r.convert_into(), c.convert_into());
         self.loc.1 += 1;
         Some(result)
     }
 }
 
+impl<T> ExactSizeIterator for Neighbors<T> where i64: ConvertInto<T> {}
+
 
 // Low case is taken care of by doctest above.
 #[test]
@@ -200,6 +557,178 @@ fn test_neighbors_hi() {
     assert_eq!(neighbors, desired);
 }
 
+#[test]
+fn test_neighbors_clipped() {
+    let clip_box = GridBox::new(4, 4);
+    let mut corner: Vec<(u8, u8)> = clip_box.neighbors8((0, 0)).collect();
+    corner.sort();
+    assert_eq!(corner, vec![(0, 1), (1, 0), (1, 1)]);
+    let mut corner: Vec<(u8, u8)> = clip_box.neighbors8((3, 0)).collect();
+    corner.sort();
+    assert_eq!(corner, vec![(2, 0), (2, 1), (3, 1)]);
+    let mut edge: Vec<(u8, u8)> = clip_box.neighbors8((2, 3)).collect();
+    edge.sort();
+    let desired = vec![
+        (1, 2), (1, 3),
+        (2, 2),
+        (3, 2), (3, 3),
+    ];
+    assert_eq!(edge, desired);
+    assert_eq!(clip_box.neighbors8((1, 2)).count(), 8);
+
+    let corner: Vec<(u8, u8)> = clip_box.neighbors4((3, 3)).collect();
+    assert_eq!(corner, vec![(2, 3), (3, 2)]);
+    let edge: Vec<(u8, u8)> = clip_box.neighbors4((0, 1)).collect();
+    assert_eq!(edge, vec![(0, 0), (1, 1), (0, 2)]);
+    assert_eq!(clip_box.neighbors4((2, 2)).count(), 4);
+}
+
+#[test]
+fn test_neighbors_unclipped() {
+    let grid = GridBox::new_grid();
+    let corner: Vec<(u32, u32)> = grid.neighbors4((0, 0)).collect();
+    assert_eq!(corner, vec![(1, 0), (0, 1)]);
+    assert_eq!(grid.neighbors8((0, 0)).count(), 3);
+    assert_eq!(grid.neighbors8((0, 1_000_000)).count(), 5);
+    assert_eq!(grid.neighbors4((1_000_000, 1_000_000)).count(), 4);
+    assert_eq!(grid.neighbors8((1_000_000, 1_000_000)).count(), 8);
+}
+
+#[test]
+fn test_grid_box_cells() {
+    let clip_box = GridBox::new(2u8, 3);
+    assert_eq!((clip_box.rows(), clip_box.cols()), (Some(2), Some(3)));
+    let cells: Vec<(usize, usize)> = clip_box.cells().collect();
+    let desired = vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2)];
+    assert_eq!(cells, desired);
+    assert!(cells.iter().all(|&loc| clip_box.contains(loc)));
+    assert!(!clip_box.contains((2, 0)));
+    assert!(!clip_box.contains((0, 3)));
+    assert!(!clip_box.contains((-1i8, 0)));
+    assert_eq!(GridBox::new(0, 5).cells::<u8>().count(), 0);
+
+    let grid = GridBox::new_grid();
+    assert_eq!(grid.rows(), None);
+    assert!(grid.contains((1u64 << 40, 7)));
+    assert!(!grid.contains((3i64, -1)));
+}
+
+#[test]
+#[should_panic(expected = "unclipped")]
+fn test_grid_box_cells_unclipped() {
+    let _ = GridBox::new_grid().cells::<i64>();
+}
+
+#[test]
+fn test_grid_box_bounds() {
+    let clip_box = GridBox::new_bounds((-2, -3), (1, 0));
+    assert_eq!(clip_box.bounds(), Some(((-2, -3), (1, 0))));
+    assert_eq!((clip_box.rows(), clip_box.cols()), (Some(4), Some(4)));
+    assert_eq!(clip_box.cells::<i8>().next(), Some((-2, -3)));
+    assert_eq!(clip_box.cells::<i8>().count(), 16);
+    assert!(clip_box.contains((-2, 0)));
+    assert!(!clip_box.contains((-3, 0)));
+    assert!(!clip_box.contains((0, 1)));
+
+    let mut corner: Vec<(i32, i32)> = clip_box.neighbors8((-2, -3)).collect();
+    corner.sort();
+    assert_eq!(corner, vec![(-2, -2), (-1, -3), (-1, -2)]);
+    let corner: Vec<(i32, i32)> = clip_box.neighbors4((1, 0)).collect();
+    assert_eq!(corner, vec![(0, 0), (1, -1)]);
+    assert_eq!(clip_box.neighbors((-1, -2), 1).len(), 8);
+    assert_eq!(clip_box.clip((0, 0), (-2, -3)), Some((-2, -3)));
+    assert_eq!(clip_box.clip((0, 0), (-3, 0)), None);
+    let beam: Vec<(i32, i32)> = clip_box.beam((1, 0), (-1, -1)).collect();
+    assert_eq!(beam, vec![(0, -1), (-1, -2), (-2, -3)]);
+
+    assert_eq!(GridBox::new(3, 2).bounds(), Some(((0, 0), (2, 1))));
+    assert_eq!(GridBox::new(0, 2).bounds(), None);
+    assert_eq!(GridBox::new_grid().bounds(), None);
+}
+
+#[test]
+fn test_grid_box_unbounded() {
+    let grid = GridBox::new_unbounded();
+    assert_eq!((grid.bounds(), grid.rows()), (None, None));
+    assert!(grid.contains((-1_000_000i64, -3)));
+    assert_eq!(grid.neighbors8((0i64, 0)).count(), 8);
+    assert_eq!(grid.neighbors((-5i64, 0), 2).len(), 24);
+    let corner: Vec<(i8, i8)> = grid.neighbors4((0, 0)).collect();
+    assert_eq!(corner, vec![(-1, 0), (0, -1), (1, 0), (0, 1)]);
+    assert_eq!(grid.clip((0i8, 0), (-1, -1)), Some((-1, -1)));
+    let beam: Vec<(i64, i64)> = grid.beam((0, 0), (-2, 1)).take(3).collect();
+    assert_eq!(beam, vec![(-2, 1), (-4, 2), (-6, 3)]);
+}
+
+#[test]
+fn test_grid_box_torus() {
+    let torus = GridBox::new_torus(3, 4);
+    assert_eq!(torus.bounds(), Some(((0, 0), (2, 3))));
+    assert_eq!(torus.cells::<u8>().count(), 12);
+    assert!(torus.contains((2, 3)) && !torus.contains((3, 0)));
+    assert_eq!(torus.clip((0u8, 0), (-1i8, -1)), Some((2, 3)));
+    assert_eq!(torus.clip((2u8, 3), (1i8, 1)), Some((0, 0)));
+    assert_eq!(torus.clip((1u8, 1), (-7i8, 9)), Some((0, 2)));
+
+    let mut corner: Vec<(u8, u8)> = torus.neighbors8((0, 0)).collect();
+    corner.sort();
+    let desired = vec![
+        (0, 1), (0, 3),
+        (1, 0), (1, 1), (1, 3),
+        (2, 0), (2, 1), (2, 3),
+    ];
+    assert_eq!(corner, desired);
+    assert_eq!(torus.neighbors8((0u8, 0)).len(), 8);
+    let corner: Vec<(u8, u8)> = torus.neighbors4((0, 0)).collect();
+    assert_eq!(corner, vec![(2, 0), (0, 3), (1, 0), (0, 1)]);
+    // The neighborhood overlaps itself, reaching some cells
+    // from both sides.
+    let wide: Vec<(u8, u8)> = torus.neighbors((0, 0), 2).collect();
+    assert_eq!(wide.len(), 24);
+    let times = |loc| wide.iter().filter(|&&l| l == loc).count();
+    assert_eq!((times((0, 0)), times((1, 2)), times((1, 0))), (0, 4, 2));
+    let tiny = GridBox::new_torus(1, 1);
+    assert_eq!(tiny.neighbors4((0u8, 0)).collect::<Vec<_>>(), [(0, 0); 4]);
+
+    let beam: Vec<(u8, u8)> = torus.beam_n((1, 1), (-1i8, -2), 4).collect();
+    assert_eq!(beam, vec![(0, 3), (2, 1), (1, 3), (0, 1)]);
+    assert_eq!(torus.beam((1u8, 1), (0i8, 1)).take(100).count(), 100);
+    let clip_box = GridBox::new(3, 4);
+    assert_eq!(clip_box.beam_n((1u8, 1), (0i8, 1), 10).count(), 2);
+}
+
+#[test]
+fn test_neighbors_outside() {
+    let clip_box = GridBox::new(4, 4);
+    let mut near: Vec<(i8, i8)> = clip_box.neighbors8((-1, 0)).collect();
+    near.sort();
+    assert_eq!(near, vec![(0, 0), (0, 1)]);
+    let mut near: Vec<(i8, i8)> = clip_box.neighbors((4, 4), 2).collect();
+    near.sort();
+    assert_eq!(near, vec![(2, 2), (2, 3), (3, 2), (3, 3)]);
+    let near: Vec<(i8, i8)> = clip_box.neighbors4((2, 4)).collect();
+    assert_eq!(near, vec![(2, 3)]);
+    assert_eq!(clip_box.neighbors((4i8, 4), 2).len(), 4);
+    assert_eq!(clip_box.ring((-2i8, 1), 2).count(), 4);
+    assert_eq!(clip_box.manhattan_ring((-1i8, -1), 0).count(), 0);
+    for loc in [(-2i8, 0), (0, 5), (9, 9), (-9, -9), (6, -3)] {
+        let mut far = clip_box.neighbors(loc, 1);
+        assert_eq!(far.len(), 0);
+        assert_eq!(far.next(), None);
+        assert_eq!(clip_box.neighbors4(loc).count(), 0);
+    }
+    let unclipped = GridBox::new_grid();
+    assert_eq!(unclipped.neighbors8((-1i8, -1)).collect::<Vec<_>>(), [(0, 0)]);
+    let strict = clip_box.neighbors_strict((3u8, 0), 1);
+    assert_eq!(strict.len(), 3);
+}
+
+#[test]
+#[should_panic(expected = "neighbors of (-1, 0) outside grid box")]
+fn test_neighbors_strict() {
+    GridBox::new(4, 4).neighbors_strict((-1, 0), 1i8);
+}
+
 /// Beam iterator in a given direction until edge-of-grid is
 /// reached.
 pub struct Beam<'a, T> {
@@ -209,6 +738,8 @@ pub struct Beam<'a, T> {
     loc: (i64, i64),
     // Step direction.
     step: (i64, i64),
+    // Starting location is still to be produced.
+    start: bool,
     // Phantom type for iterator.
     phantom: PhantomData<T>,
 }
@@ -226,6 +757,7 @@ impl<'a, T> Beam<'a, T> {
             clip,
             loc,
             step,
+            start: false,
             phantom: PhantomData,
         }
     }
@@ -238,6 +770,13 @@ where
     type Item = (T, T);
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.start {
+            self.start = false;
+            if let Some(l) = self.clip.clip::<i64, i64>(self.loc, (0, 0)) {
+                self.loc = l;
+                return Some((l.0.convert_into(), l.1.convert_into()));
+            }
+        }
         self.clip
             .clip::<i64, i64>(self.loc, self.step)
             .map(|l| {
@@ -273,6 +812,63 @@ fn test_beam_finite() {
     assert_eq!(beam, expected);
 }
 
+#[test]
+fn test_beam_inclusive() {
+    let grid = GridBox::new(5, 5);
+    // Trees visible from (2, 1) looking right, up to the
+    // first one at least as tall (2022 day 8).
+    let trees = [
+        [3, 0, 3, 7, 3],
+        [2, 5, 5, 1, 2],
+        [6, 5, 3, 3, 2],
+        [3, 3, 5, 4, 9],
+        [3, 5, 3, 9, 0],
+    ];
+    let mut beam = grid.beam_inclusive((2usize, 1), (0, 1));
+    let (r, c) = beam.next().unwrap();
+    let height = trees[r][c];
+    let mut seen = 0;
+    for (r, c) in beam {
+        seen += 1;
+        if trees[r][c] >= height {
+            break;
+        }
+    }
+    assert_eq!((height, seen), (5, 3));
+
+    // Starting on the boundary, heading out.
+    let edge: Vec<(u8, u8)> = grid.beam_inclusive((4, 2), (1i8, 0)).collect();
+    assert_eq!(edge, vec![(4, 2)]);
+    assert_eq!(grid.beam((4u8, 2), (1i8, 0)).count(), 0);
+    assert_eq!(grid.beam_n((4u8, 2), (1i8, 0), 3).count(), 0);
+    // Hitting the boundary before the step limit.
+    let short: Vec<(u8, u8)> = grid.beam_n((1, 2), (1i8, 1), 10).collect();
+    assert_eq!(short, vec![(2, 3), (3, 4)]);
+    let unclipped = GridBox::new_grid();
+    let long: Vec<(u8, u8)> = unclipped.beam_n((1, 2), (1i8, 1), 3).collect();
+    assert_eq!(long, vec![(2, 3), (3, 4), (4, 5)]);
+    let outside = unclipped.beam_inclusive((-1i64, 0), (1i8, 0));
+    assert_eq!(outside.take(2).collect::<Vec<_>>(), [(0, 0), (1, 0)]);
+}
+
+#[test]
+fn test_try_conversions() {
+    let grid = GridBox::new_grid();
+    let beam: Vec<_> = grid.try_beam((253u8, 0), (1i8, 0)).take(4).collect();
+    assert_eq!(beam[..2], [Ok((254, 0)), Ok((255, 0))]);
+    let err = beam[2].clone().unwrap_err();
+    assert_eq!(err.to_string(), "coordinate 256 does not fit in u8");
+    assert!(beam[3].is_err());
+
+    let near: Vec<_> = grid.try_neighbors((255u8, 0), 1).collect();
+    assert_eq!(near.len(), 5);
+    assert_eq!(near.iter().filter(|n| n.is_err()).count(), 2);
+    let clip_box = GridBox::new(3, 3);
+    let near: Result<Vec<(u8, u8)>, _> =
+        clip_box.try_neighbors((0, 0), 1).collect();
+    assert_eq!(near, Ok(vec![(0, 1), (1, 0), (1, 1)]));
+}
+
 pub fn neighbors4<T>() -> impl Iterator<Item = (T, T)>
 where
     i64: ConvertInto<T>,
@@ -282,17 +878,22 @@ where
         .map(|&(r, c)| (r.convert_into(), c.convert_into()))
 }
 
-pub fn neighbors8<T, U>(dist: T) -> impl Iterator<Item = (U, U)>
+pub fn neighbors8<T, U>(dist: T) -> impl ExactSizeIterator<Item = (U, U)>
 where
     T: ConvertInto<i64>,
     i64: ConvertInto<U>,
 {
     let dist = dist.convert_into();
     assert!(dist > 0);
-    (-dist ..= dist)
-        .flat_map(move |r| (-dist ..= dist).map(move |c| (r, c)))
-        .filter(|&p| p != (0, 0))
-        .map(|(r, c)| (r.convert_into(), c.convert_into()))
+    let side = 2 * dist + 1;
+    let center = side * side / 2;
+    // Number the square row-major, skipping the center.
+    (0..(side * side - 1) as usize).map(move |i| {
+        let i = i as i64;
+        let i = if i < center { i } else { i + 1 };
+        let (r, c) = (i / side - dist, i % side - dist);
+        (r.convert_into(), c.convert_into())
+    })
 }
 
 #[test]
@@ -305,6 +906,143 @@ fn test_neighbors8() {
         ( 1, -1), ( 1,  0), ( 1,  1),
     ];
     assert_eq!(v, desired);
+    let v: Vec<(i64, i64)> = neighbors8(3).collect();
+    assert_eq!(v.len(), 48);
+    assert_eq!(neighbors8::<_, i64>(3).len(), 48);
+    assert!(!v.contains(&(0, 0)));
+    assert_eq!((v[0], v[23]), ((-3, -3), (0, -1)));
+    assert_eq!((v[24], v[47]), ((0, 1), (3, 3)));
+}
+
+#[test]
+fn test_neighbors_len() {
+    let clip_box = GridBox::new(7, 5);
+    let grid = GridBox::new_grid();
+    let locs = [(3, 2), (0, 2), (6, 4), (0, 0), (5, 1), (2, 4)];
+    for &loc in &locs {
+        for dist in 1..=4 {
+            for bounds in [clip_box, grid] {
+                let mut neighbors = bounds.neighbors::<u32, _>(loc, dist);
+                let mut count = neighbors.clone().count();
+                assert_eq!(neighbors.len(), count, "{:?} {}", loc, dist);
+                // Stays exact partway through.
+                while neighbors.next().is_some() {
+                    count -= 1;
+                    assert_eq!(neighbors.len(), count);
+                }
+            }
+        }
+    }
+    assert_eq!(clip_box.neighbors8((3u8, 2)).len(), 8);
+    assert_eq!(clip_box.neighbors8((6u8, 4)).len(), 3);
+    assert_eq!(clip_box.neighbors((3u8, 2), 2).len(), 24);
+    assert_eq!(grid.neighbors((0u8, 0), 2).len(), 8);
+}
+
+/// Displacements to the locations at exactly Chebyshev
+/// distance `dist`: the border of a square, clockwise from
+/// the top left. Distance 0 gives just `(0, 0)`.
+pub fn ring<T, U>(dist: T) -> impl ExactSizeIterator<Item = (U, U)>
+where
+    T: ConvertInto<i64>,
+    i64: ConvertInto<U>,
+{
+    let dist = dist.convert_into();
+    assert!(dist >= 0);
+    let side = 2 * dist;
+    (0..(4 * side).max(1) as usize).map(move |i| {
+        if dist == 0 {
+            return (0.convert_into(), 0.convert_into());
+        }
+        let (k, d) = (i as i64 % side, dist);
+        let (r, c) = match i as i64 / side {
+            0 => (-d, -d + k),
+            1 => (-d + k, d),
+            2 => (d, d - k),
+            _ => (d - k, -d),
+        };
+        (r.convert_into(), c.convert_into())
+    })
+}
+
+/// Displacements to the locations at exactly Manhattan
+/// distance `dist`: the border of a diamond, clockwise from
+/// the top. Distance 0 gives just `(0, 0)`.
+pub fn manhattan_ring<T, U>(
+    dist: T,
+) -> impl ExactSizeIterator<Item = (U, U)>
+where
+    T: ConvertInto<i64>,
+    i64: ConvertInto<U>,
+{
+    let dist = dist.convert_into();
+    assert!(dist >= 0);
+    (0..(4 * dist).max(1) as usize).map(move |i| {
+        if dist == 0 {
+            return (0.convert_into(), 0.convert_into());
+        }
+        let (k, d) = (i as i64 % dist, dist);
+        let (r, c) = match i as i64 / dist {
+            0 => (-d + k, k),
+            1 => (k, d - k),
+            2 => (d - k, -k),
+            _ => (-k, -d + k),
+        };
+        (r.convert_into(), c.convert_into())
+    })
+}
+
+#[test]
+fn test_rings() {
+    for d in 0..6i64 {
+        let square: Vec<(i64, i64)> = ring(d).collect();
+        let diamond: Vec<(i64, i64)> = manhattan_ring(d).collect();
+        assert_eq!(square.len(), ring::<_, i64>(d).len());
+        assert_eq!(diamond.len(), manhattan_ring::<_, i64>(d).len());
+        let mut expect_square = Vec::new();
+        let mut expect_diamond = Vec::new();
+        for r in -d..=d {
+            for c in -d..=d {
+                if r.abs().max(c.abs()) == d {
+                    expect_square.push((r, c));
+                }
+                if r.abs() + c.abs() == d {
+                    expect_diamond.push((r, c));
+                }
+            }
+        }
+        let sorted = |mut v: Vec<(i64, i64)>| {
+            v.sort();
+            v
+        };
+        assert_eq!(sorted(square), expect_square, "{}", d);
+        assert_eq!(sorted(diamond), expect_diamond, "{}", d);
+    }
+    let square: Vec<(i8, i8)> = ring(1).collect();
+    let desired = vec![
+        (-1, -1), (-1, 0), (-1, 1), (0, 1),
+        (1, 1), (1, 0), (1, -1), (0, -1),
+    ];
+    assert_eq!(square, desired);
+    let diamond: Vec<(i8, i8)> = manhattan_ring(1).collect();
+    assert_eq!(diamond, vec![(-1, 0), (0, 1), (1, 0), (0, -1)]);
+}
+
+#[test]
+fn test_rings_clipped() {
+    let clip_box = GridBox::new(5, 5);
+    assert_eq!(clip_box.ring((2u8, 2), 0).collect::<Vec<_>>(), [(2, 2)]);
+    assert_eq!(clip_box.ring((2u8, 2), 2).count(), 16);
+    assert_eq!(clip_box.ring((2u8, 2), 3).count(), 0);
+    // Only the bottom and right sides of the square are in
+    // the grid, less their far ends.
+    let corner: Vec<(u8, u8)> = clip_box.ring((0, 0), 2).collect();
+    assert_eq!(corner, vec![(0, 2), (1, 2), (2, 2), (2, 1), (2, 0)]);
+    let edge: Vec<(u8, u8)> = clip_box.manhattan_ring((0, 3), 2).collect();
+    assert_eq!(edge, vec![(1, 4), (2, 3), (1, 2), (0, 1)]);
+    assert_eq!(clip_box.manhattan_ring((4u8, 4), 8).count(), 1);
+    let torus = GridBox::new_torus(5, 5);
+    assert_eq!(torus.manhattan_ring((0u8, 0), 2).count(), 8);
 }
 
 /// The ["Manhattan Distance"][1] between two points.
@@ -323,3 +1061,126 @@ where
     let dc = (c1 - c2).abs();
     (dr + dc).convert_into()
 }
+
+/// The ["Chebyshev Distance"][1] between two points: the
+/// number of king moves from one to the other. The distance
+/// is exact for any `i64` points.
+///
+/// # Panics
+///
+/// Panics if the distance does not fit in `U`.
+///
+/// [1]: http://en.wikipedia.org/wiki/Chebyshev_distance
+pub fn chebyshev_distance<T, U>((r1, c1): (T, T), (r2, c2): (T, T)) -> U
+where
+    T: ConvertInto<i64>,
+    u64: ConvertInto<U>,
+{
+    let r1: i64 = r1.convert_into();
+    let c1: i64 = c1.convert_into();
+    let r2: i64 = r2.convert_into();
+    let c2: i64 = c2.convert_into();
+    let dr = r1.abs_diff(r2);
+    let dc = c1.abs_diff(c2);
+    dr.max(dc).convert_into()
+}
+
+/// The square of the [Euclidean distance][1] between two
+/// points, which is an integer and orders points the same
+/// way. Each squared difference is done in `u128`, which
+/// holds the square of any `i64` difference, but the sum of
+/// two can be too big for it.
+///
+/// # Panics
+///
+/// Panics if the result does not fit in `u128`, or in `U`.
+///
+/// [1]: http://en.wikipedia.org/wiki/Euclidean_distance
+pub fn euclidean_distance_squared<T, U>(
+    (r1, c1): (T, T),
+    (r2, c2): (T, T),
+) -> U
+where
+    T: ConvertInto<i64>,
+    u128: ConvertInto<U>,
+{
+    let r1: i64 = r1.convert_into();
+    let c1: i64 = c1.convert_into();
+    let r2: i64 = r2.convert_into();
+    let c2: i64 = c2.convert_into();
+    let dr = u128::from(r1.abs_diff(r2));
+    let dc = u128::from(c1.abs_diff(c2));
+    (dr * dr)
+        .checked_add(dc * dc)
+        .expect("squared distance overflow")
+        .convert_into()
+}
+
+#[test]
+fn test_distances() {
+    // Point pairs, with their Manhattan, Chebyshev and
+    // squared Euclidean distances.
+    type Point = (i64, i64);
+    let pairs: [(Point, Point, [i64; 3]); 5] = [
+        ((0, 0), (0, 0), [0, 0, 0]),
+        ((1, 2), (4, 6), [7, 4, 25]),
+        ((-3, 5), (2, -1), [11, 6, 61]),
+        ((7, 7), (7, -2), [9, 9, 81]),
+        ((0, 0), (-2, 2), [4, 2, 8]),
+    ];
+    for (a, b, [m, c, e]) in pairs {
+        for (a, b) in [(a, b), (b, a)] {
+            assert_eq!(manhattan_distance::<_, i64>(a, b), m);
+            assert_eq!(chebyshev_distance::<_, i64>(a, b), c);
+            assert_eq!(euclidean_distance_squared::<_, i64>(a, b), e);
+        }
+        // Chebyshev ≤ Euclidean ≤ Manhattan.
+        assert!(c * c <= e && e <= m * m);
+    }
+    let d: u8 = chebyshev_distance((3u32, 10), (5, 1));
+    assert_eq!(d, 9);
+    let far = (i64::MAX / 2, i64::MAX / 2);
+    let e: u128 = euclidean_distance_squared((0, 0), far);
+    assert_eq!(e, 2 * (i64::MAX as u128 / 2).pow(2));
+
+    // The widest differences.
+    let (lo, hi) = ((i64::MIN, 0), (i64::MAX, 0));
+    let c: u64 = chebyshev_distance(lo, hi);
+    assert_eq!(c, u64::MAX);
+    let e: u128 = euclidean_distance_squared(lo, hi);
+    assert_eq!(e, u128::from(u64::MAX).pow(2));
+}
+
+#[test]
+#[should_panic(expected = "overflow")]
+fn test_euclidean_distance_overflow() {
+    let corner = (i64::MIN, i64::MIN);
+    let _: u128 = euclidean_distance_squared(corner, (i64::MAX, i64::MAX));
+}
+
+/// The bounding box `(min, max)` of some points, with each
+/// coordinate of `min` the smallest of its coordinates and
+/// likewise for `max`. Returns `None` if there are no
+/// points.
+pub fn bounding_box<T, I>(points: I) -> Option<((T, T), (T, T))>
+where
+    T: Copy + Ord,
+    I: IntoIterator<Item = (T, T)>,
+{
+    points.into_iter().fold(None, |b, (r, c)| {
+        Some(match b {
+            None => ((r, c), (r, c)),
+            Some(((r0, c0), (r1, c1))) => {
+                ((r0.min(r), c0.min(c)), (r1.max(r), c1.max(c)))
+            }
+        })
+    })
+}
+
+#[test]
+fn test_bounding_box() {
+    let points = [(3i64, -1), (-2, 4), (0, 0)];
+    assert_eq!(bounding_box(points), Some(((-2, -1), (3, 4))));
+    assert_eq!(bounding_box([(7u8, 9)]), Some(((7, 9), (7, 9))));
+    assert_eq!(bounding_box(Vec::<(i32, i32)>::new()), None);
+}
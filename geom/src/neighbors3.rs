@@ -0,0 +1,212 @@
+//! Neighbor calculations on three-dimensional grids.
+//!
+//! This mirrors the two-dimensional `GridBox`, with
+//! locations as `(x, y, z)` triples.
+//!
+//! # Examples
+//!
+//! ```
+//! # use geom::*;
+//! let clip_box = GridBox3::new(3, 3, 3);
+//! assert_eq!(clip_box.neighbors26((1, 1, 1)).count(), 26);
+//! let mut faces: Vec<(u8, u8, u8)> =
+//!     clip_box.neighbors6((0, 2, 1)).collect();
+//! faces.sort();
+//! assert_eq!(faces, vec![(0, 1, 1), (0, 2, 0), (0, 2, 2), (1, 2, 1)]);
+//! ```
+
+use crate::convert::ConvertInto;
+
+/// Location as `(x, y, z)`.
+type Loc3 = (i64, i64, i64);
+
+/// Displacements to the six face neighbors of a cube.
+pub const DIRNS3: [Loc3; 6] = [
+    (-1, 0, 0),
+    (1, 0, 0),
+    (0, -1, 0),
+    (0, 1, 0),
+    (0, 0, -1),
+    (0, 0, 1),
+];
+
+/// Description of a three-dimensional grid, for clipping.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct GridBox3 {
+    /// Least location inside the box.
+    start: Loc3,
+    /// Greatest location inside the box, plus one in each
+    /// coordinate.
+    end: Loc3,
+}
+
+impl GridBox3 {
+    /// Create a clip box for neighbor calculations, with
+    /// the given sizes in each dimension.
+    pub fn new<T>(x_size: T, y_size: T, z_size: T) -> GridBox3
+    where
+        T: ConvertInto<i64>,
+    {
+        GridBox3 {
+            start: (0, 0, 0),
+            end: (
+                x_size.convert_into(),
+                y_size.convert_into(),
+                z_size.convert_into(),
+            ),
+        }
+    }
+
+    /// Create a clip box for neighbor calculations that
+    /// clips nothing, not even negative locations.
+    pub fn new_unbounded() -> GridBox3 {
+        GridBox3 {
+            start: (i64::MIN, i64::MIN, i64::MIN),
+            end: (i64::MAX, i64::MAX, i64::MAX),
+        }
+    }
+
+    /// True if the given location is inside the box.
+    pub fn contains<T>(&self, location: (T, T, T)) -> bool
+    where
+        T: ConvertInto<i64>,
+    {
+        let x = location.0.convert_into();
+        let y = location.1.convert_into();
+        let z = location.2.convert_into();
+        (self.start.0..self.end.0).contains(&x)
+            && (self.start.1..self.end.1).contains(&y)
+            && (self.start.2..self.end.2).contains(&z)
+    }
+
+    /// Return the source location adjusted by the given
+    /// offset iff the dest location is in-bounds.
+    pub fn clip<T, U>(
+        &self,
+        loc: (T, T, T),
+        off: (U, U, U),
+    ) -> Option<(T, T, T)>
+    where
+        T: ConvertInto<i64>,
+        i64: ConvertInto<T>,
+        U: ConvertInto<i64>,
+    {
+        let x = loc.0.convert_into().checked_add(off.0.convert_into())?;
+        let y = loc.1.convert_into().checked_add(off.1.convert_into())?;
+        let z = loc.2.convert_into().checked_add(off.2.convert_into())?;
+        if !self.contains((x, y, z)) {
+            return None;
+        }
+        Some((x.convert_into(), y.convert_into(), z.convert_into()))
+    }
+
+    /// Return an iterator over the neighbors of the given
+    /// location at the given offsets, clipped as needed. The
+    /// location itself need not be inside the box.
+    fn neighbors_at<T, I>(
+        &self,
+        location: (T, T, T),
+        offsets: I,
+    ) -> impl Iterator<Item = (T, T, T)>
+    where
+        T: ConvertInto<i64>,
+        i64: ConvertInto<T>,
+        I: Iterator<Item = Loc3>,
+    {
+        let loc = (
+            location.0.convert_into(),
+            location.1.convert_into(),
+            location.2.convert_into(),
+        );
+        let clip_box = *self;
+        offsets.filter_map(move |off| {
+            clip_box.clip::<i64, i64>(loc, off).map(|(x, y, z)| {
+                (x.convert_into(), y.convert_into(), z.convert_into())
+            })
+        })
+    }
+
+    /// Return an iterator that will produce the 26
+    /// surrounding cells of the given location, clipped as
+    /// needed.
+    pub fn neighbors26<T>(
+        &self,
+        location: (T, T, T),
+    ) -> impl Iterator<Item = (T, T, T)>
+    where
+        T: ConvertInto<i64>,
+        i64: ConvertInto<T>,
+    {
+        let offsets = (0..27)
+            .map(|i| (i / 9 - 1, i / 3 % 3 - 1, i % 3 - 1))
+            .filter(|&off| off != (0, 0, 0));
+        self.neighbors_at(location, offsets)
+    }
+
+    /// Return an iterator that will produce the neighbors of
+    /// the given location across the six faces of its cube,
+    /// clipped as needed, in the order of `DIRNS3`.
+    pub fn neighbors6<T>(
+        &self,
+        location: (T, T, T),
+    ) -> impl Iterator<Item = (T, T, T)>
+    where
+        T: ConvertInto<i64>,
+        i64: ConvertInto<T>,
+    {
+        self.neighbors_at(location, DIRNS3.into_iter())
+    }
+}
+
+#[test]
+fn test_neighbors3_clipped() {
+    let clip_box = GridBox3::new(4, 4, 4);
+    let mut corner: Vec<(u8, u8, u8)> =
+        clip_box.neighbors26((3, 0, 3)).collect();
+    corner.sort();
+    let desired = vec![
+        (2, 0, 2), (2, 0, 3), (2, 1, 2), (2, 1, 3),
+        (3, 0, 2), (3, 1, 2), (3, 1, 3),
+    ];
+    assert_eq!(corner, desired);
+    assert_eq!(clip_box.neighbors26((0u8, 1, 2)).count(), 17);
+    assert_eq!(clip_box.neighbors26((0u8, 0, 1)).count(), 11);
+    assert_eq!(clip_box.neighbors26((1u8, 2, 2)).count(), 26);
+    let faces: Vec<(u8, u8, u8)> = clip_box.neighbors6((0, 0, 0)).collect();
+    assert_eq!(faces, vec![(1, 0, 0), (0, 1, 0), (0, 0, 1)]);
+    assert_eq!(clip_box.neighbors6((2u8, 1, 3)).count(), 5);
+    assert_eq!(clip_box.clip((3u8, 3, 3), (0i8, 0, 1)), None);
+    assert_eq!(clip_box.clip((3u8, 3, 3), (-3i8, 0, -1)), Some((0, 3, 2)));
+    assert!(!clip_box.contains((0i8, -1, 0)));
+}
+
+#[test]
+fn test_conway_cubes() {
+    use std::collections::{HashMap, HashSet};
+
+    // 2020 day 17 part 1 example.
+    let start = [".#.", "..#", "###"];
+    let mut active: HashSet<Loc3> = HashSet::new();
+    for (y, row) in start.iter().enumerate() {
+        for (x, ch) in row.chars().enumerate() {
+            if ch == '#' {
+                active.insert((x as i64, y as i64, 0));
+            }
+        }
+    }
+    let space = GridBox3::new_unbounded();
+    for _ in 0..6 {
+        let mut counts: HashMap<Loc3, usize> = HashMap::new();
+        for &cube in &active {
+            for n in space.neighbors26(cube) {
+                *counts.entry(n).or_default() += 1;
+            }
+        }
+        active = counts
+            .into_iter()
+            .filter(|&(cube, n)| n == 3 || (n == 2 && active.contains(&cube)))
+            .map(|(cube, _)| cube)
+            .collect();
+    }
+    assert_eq!(active.len(), 112);
+}
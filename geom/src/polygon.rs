@@ -0,0 +1,153 @@
+//! Areas and lattice point counts of lattice polygons.
+//!
+//! A polygon is given as its list of vertices in order
+//! around it. The list may or may not repeat the first
+//! vertex at the end.
+//!
+//! # Examples
+//!
+//! The lagoon dug from the 2023 day 18 example plan holds
+//! the trench itself plus everything inside it.
+//!
+//! ```
+//! # use geom::*;
+//! let plan = [
+//!     ('R', 6), ('D', 5), ('L', 2), ('D', 2), ('R', 2),
+//!     ('D', 2), ('L', 5), ('U', 2), ('L', 1), ('U', 2),
+//!     ('R', 2), ('U', 3), ('L', 2), ('U', 2),
+//! ];
+//! let mut pos = (0i64, 0i64);
+//! let mut vertices = vec![pos];
+//! for (d, n) in plan {
+//!     let (dr, dc) = match d {
+//!         'U' => (-1, 0),
+//!         'D' => (1, 0),
+//!         'L' => (0, -1),
+//!         'R' => (0, 1),
+//!         _ => unreachable!(),
+//!     };
+//!     pos = (pos.0 + dr * n, pos.1 + dc * n);
+//!     vertices.push(pos);
+//! }
+//! let lagoon = interior_points(&vertices) + boundary_points(&vertices);
+//! assert_eq!(lagoon, 62);
+//! ```
+
+use numberfns::gcd;
+
+/// The vertices of a polygon with any repeat of the first
+/// vertex at the end removed.
+fn open_vertices(vertices: &[(i64, i64)]) -> &[(i64, i64)] {
+    match vertices {
+        [first, .., last] if first == last => &vertices[..vertices.len() - 1],
+        _ => vertices,
+    }
+}
+
+/// The edges of a polygon, each from a vertex to the next
+/// and finally from the last vertex back to the first.
+fn edges(
+    vertices: &[(i64, i64)],
+) -> impl Iterator<Item = ((i64, i64), (i64, i64))> + '_ {
+    let vertices = open_vertices(vertices);
+    let next = vertices.iter().cycle().skip(1);
+    vertices.iter().copied().zip(next.copied())
+}
+
+/// Twice the signed area of the polygon with the given
+/// `vertices`, by the [shoelace
+/// formula](https://en.wikipedia.org/wiki/Shoelace_formula).
+/// Twice the area of a lattice polygon is always an
+/// integer. Taking the coordinates as *(x, y)*, the area is
+/// positive when the vertices run counterclockwise.
+///
+/// # Panics
+///
+/// Panics if the result does not fit in `i64`.
+pub fn polygon_area2(vertices: &[(i64, i64)]) -> i64 {
+    let area2: i128 = edges(vertices)
+        .map(|((x0, y0), (x1, y1))| {
+            i128::from(x0) * i128::from(y1) - i128::from(x1) * i128::from(y0)
+        })
+        .sum();
+    i64::try_from(area2).expect("polygon area overflow")
+}
+
+/// The area of the polygon with the given `vertices`,
+/// whichever way around they run.
+pub fn polygon_area(vertices: &[(i64, i64)]) -> f64 {
+    polygon_area2(vertices).unsigned_abs() as f64 / 2.0
+}
+
+/// The number of lattice points on the boundary of the
+/// polygon with the given `vertices`, counting each vertex
+/// once.
+pub fn boundary_points(vertices: &[(i64, i64)]) -> u64 {
+    edges(vertices)
+        .map(|((x0, y0), (x1, y1))| {
+            match ((x1 - x0).unsigned_abs(), (y1 - y0).unsigned_abs()) {
+                (0, n) | (n, 0) => n,
+                (m, n) => gcd(m, n),
+            }
+        })
+        .sum()
+}
+
+/// The number of lattice points strictly inside the
+/// polygon with the given `vertices`, by [Pick's
+/// theorem](https://en.wikipedia.org/wiki/Pick%27s_theorem).
+/// The polygon must not cross itself. A degenerate polygon
+/// with no area has no interior points.
+pub fn interior_points(vertices: &[(i64, i64)]) -> u64 {
+    // Pick: A = I + B/2 - 1, so I = (2A - B + 2) / 2.
+    let area2 = polygon_area2(vertices).unsigned_abs();
+    let boundary = boundary_points(vertices);
+    (area2 + 2).saturating_sub(boundary) / 2
+}
+
+#[test]
+fn test_polygon_area() {
+    let square = [(0, 0), (1, 0), (1, 1), (0, 1)];
+    assert_eq!(polygon_area2(&square), 2);
+    assert_eq!(polygon_area(&square), 1.0);
+    let mut closed = square.to_vec();
+    closed.push((0, 0));
+    assert_eq!(polygon_area2(&closed), 2);
+    let mut clockwise = square;
+    clockwise.reverse();
+    assert_eq!(polygon_area2(&clockwise), -2);
+    assert_eq!(polygon_area(&clockwise), 1.0);
+
+    let triangle = [(0, 0), (3, 0), (0, 1)];
+    assert_eq!(polygon_area(&triangle), 1.5);
+
+    let big = 1_000_000_000;
+    let rect = [(-big, -big), (big, -big), (big, big), (-big, big)];
+    assert_eq!(polygon_area2(&rect), 8 * big * big);
+
+    assert_eq!(polygon_area2(&[]), 0);
+    assert_eq!(polygon_area2(&[(5, 5)]), 0);
+}
+
+#[test]
+fn test_lattice_points() {
+    let square = [(0, 0), (1, 0), (1, 1), (0, 1)];
+    assert_eq!(boundary_points(&square), 4);
+    assert_eq!(interior_points(&square), 0);
+
+    let rect = [(0, 0), (0, 1000), (2000, 1000), (2000, 0), (0, 0)];
+    assert_eq!(boundary_points(&rect), 6000);
+    assert_eq!(interior_points(&rect), 999 * 1999);
+
+    // The diagonal edge has no lattice points between its
+    // ends; the others have 2 and 1.
+    let triangle = [(0, 0), (3, 0), (0, 2)];
+    assert_eq!(boundary_points(&triangle), 6);
+    assert_eq!(interior_points(&triangle), 1);
+
+    // Collinear vertices: out to (4, 4) and back.
+    let flat = [(0, 0), (2, 2), (4, 4)];
+    assert_eq!(polygon_area2(&flat), 0);
+    assert_eq!(boundary_points(&flat), 8);
+    assert_eq!(interior_points(&flat), 0);
+}
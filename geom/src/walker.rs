@@ -0,0 +1,198 @@
+//! An agent with a position and a facing, walking a grid.
+//!
+//! # Examples
+//!
+//! ```
+//! # use geom::*;
+//! let mut walker = Walker::new((0, 0), Dirn::Up);
+//! walker.turn(Rot::CW, 1);
+//! walker.step(3);
+//! assert_eq!((walker.pos(), walker.facing()), ((0, 3), Dirn::Right));
+//! let clip_box = GridBox::new(4, 4);
+//! assert_eq!(walker.step_clipped(&clip_box, 1), None);
+//! walker.turn(Rot::CW, 1);
+//! assert_eq!(walker.step_clipped(&clip_box, 3), Some((3, 3)));
+//! ```
+
+use crate::{convert::ConvertInto, Dirn, GridBox, Rot};
+
+/// Location as `(row, col)`.
+type Loc = (i64, i64);
+
+/// A position and a facing, which can turn and step
+/// forward. A walker can also record its trail: every
+/// location it has been at, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Walker {
+    pos: Loc,
+    facing: Dirn,
+    trail: Option<Vec<Loc>>,
+}
+
+impl Walker {
+    /// Make a walker at the given position and facing, not
+    /// recording its trail.
+    pub fn new<T>(pos: (T, T), facing: Dirn) -> Self
+    where
+        T: ConvertInto<i64>,
+    {
+        Walker {
+            pos: (pos.0.convert_into(), pos.1.convert_into()),
+            facing,
+            trail: None,
+        }
+    }
+
+    /// Start recording the trail, from the current position.
+    pub fn with_trail(mut self) -> Self {
+        self.trail = Some(vec![self.pos]);
+        self
+    }
+
+    /// The current position.
+    pub fn pos(&self) -> Loc {
+        self.pos
+    }
+
+    /// The current facing.
+    pub fn facing(&self) -> Dirn {
+        self.facing
+    }
+
+    /// Move to the given position without walking there.
+    /// It starts a new leg of the trail, if recorded.
+    pub fn set_pos<T>(&mut self, pos: (T, T))
+    where
+        T: ConvertInto<i64>,
+    {
+        self.pos = (pos.0.convert_into(), pos.1.convert_into());
+        if let Some(trail) = &mut self.trail {
+            trail.push(self.pos);
+        }
+    }
+
+    /// Face the given direction.
+    pub fn set_facing(&mut self, facing: Dirn) {
+        self.facing = facing;
+    }
+
+    /// Iterator over the recorded trail, starting where
+    /// recording started. Empty if the trail is not being
+    /// recorded.
+    pub fn trail(&self) -> impl Iterator<Item = Loc> + '_ {
+        self.trail.iter().flatten().copied()
+    }
+
+    /// Turn 90° in the given rotation direction the given
+    /// number of times, as with `Dirn::turn()`.
+    pub fn turn<T>(&mut self, rot: Rot, steps: T)
+    where
+        T: ConvertInto<i64>,
+    {
+        self.facing = self.facing.turn(rot, steps);
+    }
+
+    /// Walk `dist` steps forward, or backward if `dist` is
+    /// negative. Returns the new position.
+    pub fn step<T>(&mut self, dist: T) -> Loc
+    where
+        T: ConvertInto<i64>,
+    {
+        let dist: i64 = dist.convert_into();
+        if let Some(trail) = &mut self.trail {
+            let unit = dist.signum();
+            for i in 1..=dist.abs() {
+                trail.push(self.facing.displace(self.pos, unit * i));
+            }
+        }
+        self.pos = self.facing.displace(self.pos, dist);
+        self.pos
+    }
+
+    /// Walk `dist` steps forward as with `step()`, but only
+    /// if every step stays inside `clip_box`; a torus wraps
+    /// the walker around. Returns the new position, or
+    /// `None` if the walker could not move.
+    pub fn step_clipped<T>(
+        &mut self,
+        clip_box: &GridBox,
+        dist: T,
+    ) -> Option<Loc>
+    where
+        T: ConvertInto<i64>,
+    {
+        let dist: i64 = dist.convert_into();
+        let (dr, dc) = self.facing.disp::<i64>();
+        let unit = (dr * dist.signum(), dc * dist.signum());
+        let mut path = Vec::new();
+        let mut pos = self.pos;
+        for _ in 0..dist.abs() {
+            pos = clip_box.clip(pos, unit)?;
+            path.push(pos);
+        }
+        if let Some(trail) = &mut self.trail {
+            trail.extend(path);
+        }
+        self.pos = pos;
+        Some(pos)
+    }
+}
+
+#[test]
+fn test_walker_taxicab() {
+    use crate::manhattan_distance;
+    use std::collections::HashSet;
+
+    // 2016 day 1.
+    let walk = |instructions: &str| {
+        let mut walker = Walker::new((0, 0), Dirn::Up).with_trail();
+        for step in instructions.split(", ") {
+            let (turn, dist) = step.split_at(1);
+            let rot = if turn == "R" { Rot::CW } else { Rot::CCW };
+            walker.turn(rot, 1);
+            walker.step(dist.parse::<i64>().unwrap());
+        }
+        walker
+    };
+    let examples = [("R2, L3", 5), ("R2, R2, R2", 2), ("R5, L5, R5, R3", 12)];
+    for (instructions, dist) in examples {
+        let walker = walk(instructions);
+        let d: i64 = manhattan_distance((0, 0), walker.pos());
+        assert_eq!(d, dist, "{}", instructions);
+    }
+
+    let walker = walk("R8, R4, R4, R8");
+    assert_eq!(walker.trail().count(), 25);
+    let mut seen = HashSet::new();
+    let twice = walker.trail().find(|&loc| !seen.insert(loc)).unwrap();
+    assert_eq!(twice, (0, 4));
+    assert_eq!(manhattan_distance::<_, i64>((0, 0), twice), 4);
+}
+
+#[test]
+fn test_walker_clipped() {
+    let clip_box = GridBox::new(3, 3);
+    let mut walker = Walker::new((1, 1), Dirn::Left).with_trail();
+    assert_eq!(walker.step_clipped(&clip_box, 2), None);
+    assert_eq!(walker.pos(), (1, 1));
+    assert_eq!(walker.step_clipped(&clip_box, -1), Some((1, 2)));
+    assert_eq!(walker.step_clipped(&clip_box, 2), Some((1, 0)));
+    walker.turn(Rot::CCW, 1);
+    assert_eq!(walker.facing(), Dirn::Down);
+    let torus = GridBox::new_torus(3, 3);
+    assert_eq!(walker.step_clipped(&torus, 4), Some((2, 0)));
+    let trail: Vec<Loc> = walker.trail().collect();
+    let desired = vec![
+        (1, 1), (1, 2), (1, 1), (1, 0),
+        (2, 0), (0, 0), (1, 0), (2, 0),
+    ];
+    assert_eq!(trail, desired);
+
+    let mut walker = Walker::new((5u8, 5), Dirn::Right);
+    walker.step(-7);
+    assert_eq!(walker.pos(), (5, -2));
+    assert_eq!(walker.trail().count(), 0);
+    walker.set_facing(Dirn::Up);
+    walker.set_pos((0, 0));
+    assert_eq!(walker.step(2), (-2, 0));
+}
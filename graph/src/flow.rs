@@ -0,0 +1,245 @@
+//! Maximum flows and minimum cuts of undirected graphs in
+//! which every edge has capacity 1.
+//!
+//! By the max-flow min-cut theorem, the maximum flow from
+//! one node to another is the least number of edges whose
+//! removal separates them.
+//!
+//! # Examples
+//!
+//! Two triangles joined by one edge.
+//!
+//! ```
+//! # use graph::*;
+//! let edges = [(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 6), (6, 4)];
+//! let graph: UGraph<u32> = edges.into_iter().collect();
+//! let (flow, cut) = max_flow(&graph, &1, &6);
+//! assert_eq!(flow, 1);
+//! assert_eq!(cut, [(&3, &4)]);
+//! assert_eq!(global_min_cut_partition(&graph), Some((1, [3, 3])));
+//! ```
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+use crate::UGraph;
+
+/// Maximum flow from `s` to `t` by the Edmonds–Karp
+/// algorithm, with the nodes on the source side of a
+/// minimum cut.
+fn unit_flow(adj: &[HashSet<usize>], s: usize, t: usize) -> (usize, Vec<bool>) {
+    // Flow from `u` to `v` along the edge between them,
+    // which is minus the flow from `v` to `u`. An edge can
+    // carry one unit either way, so there is room from `u`
+    // to `v` whenever this is less than 1.
+    let mut flow: HashMap<(usize, usize), i32> = HashMap::new();
+    let mut total = 0;
+    loop {
+        let mut prev = vec![None; adj.len()];
+        let mut seen = vec![false; adj.len()];
+        seen[s] = true;
+        let mut queue = VecDeque::from([s]);
+        while let Some(u) = queue.pop_front() {
+            if u == t {
+                break;
+            }
+            for &v in &adj[u] {
+                let f = flow.get(&(u, v)).copied().unwrap_or(0);
+                if !seen[v] && f < 1 {
+                    seen[v] = true;
+                    prev[v] = Some(u);
+                    queue.push_back(v);
+                }
+            }
+        }
+        if !seen[t] {
+            return (total, seen);
+        }
+        let mut v = t;
+        while let Some(u) = prev[v] {
+            *flow.entry((u, v)).or_insert(0) += 1;
+            *flow.entry((v, u)).or_insert(0) -= 1;
+            v = u;
+        }
+        total += 1;
+    }
+}
+
+/// The maximum flow from `source` to `sink` in `graph`,
+/// each edge carrying at most one unit in either direction,
+/// along with the edges of a minimum cut. Each cut edge is
+/// given as its source-side node then its sink-side node.
+///
+/// This is the [Edmonds–Karp Algorithm][1], which is quick
+/// when the flow is small.
+///
+/// # Panics
+///
+/// Panics if `source` or `sink` is not in the graph, or
+/// they are the same.
+///
+/// [1]: https://en.wikipedia.org/wiki/Edmonds%E2%80%93Karp_algorithm
+pub fn max_flow<'a, N>(
+    graph: &'a UGraph<N>,
+    source: &N,
+    sink: &N,
+) -> (usize, Vec<(&'a N, &'a N)>)
+where
+    N: Clone + Eq + Hash,
+{
+    let s = graph.index[source];
+    let t = graph.index[sink];
+    assert!(s != t, "flow from a node to itself");
+    let (flow, source_side) = unit_flow(&graph.adj, s, t);
+    let mut cut = Vec::new();
+    for (u, nbrs) in graph.adj.iter().enumerate() {
+        for &v in nbrs {
+            if source_side[u] && !source_side[v] {
+                cut.push((&graph.labels[u], &graph.labels[v]));
+            }
+        }
+    }
+    (flow, cut)
+}
+
+/// The size of a minimum cut of `graph`: the least number
+/// of edges whose removal splits it in two. Returns the
+/// size of the cut and the numbers of nodes on either side,
+/// or `None` if the graph has fewer than two nodes.
+///
+/// Some node must be on the other side of the cut from the
+/// first node, so this tries a maximum flow from the first
+/// node to every other. That is fine for the small cuts of
+/// the puzzles, but slow for large ones.
+pub fn global_min_cut_partition<N>(
+    graph: &UGraph<N>,
+) -> Option<(usize, [usize; 2])>
+where
+    N: Clone + Eq + Hash,
+{
+    let n = graph.node_count();
+    let mut best: Option<(usize, [usize; 2])> = None;
+    for t in 1..n {
+        if let Some((cut, _)) = best {
+            if cut == 0 {
+                break;
+            }
+        }
+        let (flow, source_side) = unit_flow(&graph.adj, 0, t);
+        if best.map(|(cut, _)| flow < cut).unwrap_or(true) {
+            let size = source_side.iter().filter(|&&s| s).count();
+            best = Some((flow, [size, n - size]));
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::testutil::Rng;
+
+    #[test]
+    fn snowverload() {
+        // 2023 day 25 example.
+        let wiring = [
+            "jqt: rhn xhk nvd",
+            "rsh: frs pzl lsr",
+            "xhk: hfx",
+            "cmg: qnr nvd lhk bvb",
+            "rhn: xhk bvb hfx",
+            "bvb: xhk hfx",
+            "pzl: lsr hfx nvd",
+            "qnr: nvd",
+            "ntq: jqt hfx bvb xhk",
+            "nvd: lhk",
+            "lsr: lhk",
+            "rzs: qnr cmg lsr rsh",
+            "frs: qnr lhk lsr",
+        ];
+        let mut graph = UGraph::new();
+        for line in wiring {
+            let (a, bs) = line.split_once(": ").unwrap();
+            for b in bs.split(' ') {
+                graph.add_edge(a, b);
+            }
+        }
+        let (cut, [a, b]) = global_min_cut_partition(&graph).unwrap();
+        assert_eq!(cut, 3);
+        assert_eq!(a * b, 54);
+        assert_eq!(a + b, 15);
+
+        let (flow, cut) = max_flow(&graph, &"jqt", &"cmg");
+        assert_eq!(flow, 3);
+        let mut cut: Vec<[&str; 2]> = cut
+            .into_iter()
+            .map(|(&u, &v)| {
+                let mut edge = [u, v];
+                edge.sort();
+                edge
+            })
+            .collect();
+        cut.sort();
+        assert_eq!(cut, [["bvb", "cmg"], ["hfx", "pzl"], ["jqt", "nvd"]]);
+    }
+
+    #[test]
+    fn against_brute_force() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..200 {
+            let n = rng.below(8) + 2;
+            let mut graph = UGraph::new();
+            let mut edges = Vec::new();
+            for i in 0..n {
+                graph.add_node(i);
+            }
+            for _ in 0..rng.below(3 * n) {
+                let (a, b) = (rng.below(n), rng.below(n));
+                if a != b && graph.add_edge(a, b) {
+                    edges.push((a, b));
+                }
+            }
+            // Number of edges crossing the cut given by a set
+            // of nodes as bits.
+            let crossing = |side: usize| {
+                edges
+                    .iter()
+                    .filter(|&&(a, b)| (side >> a & 1) != (side >> b & 1))
+                    .count()
+            };
+
+            let (s, t) = (rng.below(n), rng.below(n));
+            if s != t {
+                let least = (0..1usize << n)
+                    .filter(|&side| side >> s & 1 == 1 && side >> t & 1 == 0)
+                    .map(crossing)
+                    .min()
+                    .unwrap();
+                let (flow, cut) = max_flow(&graph, &s, &t);
+                assert_eq!(flow, least);
+                assert_eq!(cut.len(), least);
+                // Removing the cut separates `s` from `t`.
+                let mut rest = UGraph::new();
+                for i in 0..n {
+                    rest.add_node(i);
+                }
+                for &(a, b) in &edges {
+                    if !cut.contains(&(&a, &b)) && !cut.contains(&(&b, &a)) {
+                        rest.add_edge(a, b);
+                    }
+                }
+                assert_eq!(max_flow(&rest, &s, &t).0, 0);
+            }
+
+            let least = (1..(1usize << n) - 1).map(crossing).min().unwrap();
+            let (cut, [a, b]) = global_min_cut_partition(&graph).unwrap();
+            assert_eq!(cut, least);
+            assert_eq!(a + b, n);
+            assert!(a > 0 && b > 0);
+        }
+        let mut single = UGraph::new();
+        single.add_node('a');
+        assert_eq!(global_min_cut_partition(&single), None);
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Graph algorithms for Advent of Code solutions.
+//!
+//! Graphs are mostly given implicitly, by a list of nodes
+//! and a neighbor function returning `(cost, node)` pairs in
+//! the style of `astar::SearchState::neighbors()`.
+
+mod flow;
+pub use flow::*;
+
+mod paths;
+pub use paths::*;
+
+mod scc;
+pub use scc::*;
+
+mod topo;
+pub use topo::*;
+
+mod ugraph;
+pub use ugraph::*;
+
+#[cfg(test)]
+mod testutil;
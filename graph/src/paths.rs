@@ -0,0 +1,287 @@
+//! Shortest-path distances between many pairs of nodes.
+//!
+//! Valve-style puzzles care only about travel times between
+//! a handful of interesting nodes in a larger graph. Use
+//! `condense()` to get the reduced weighted graph over just
+//! those nodes, or `all_pairs_shortest_paths()` for the full
+//! distance table of a small graph.
+//!
+//! # Examples
+//!
+//! ```
+//! # use graph::*;
+//! // A path a - b - c - d with unit costs.
+//! let edges = [("a", "b"), ("b", "c"), ("c", "d")];
+//! let neighbors = |n: &&str| {
+//!     edges
+//!         .iter()
+//!         .filter_map(|&(x, y)| match () {
+//!             _ if x == *n => Some((1, y)),
+//!             _ if y == *n => Some((1, x)),
+//!             _ => None,
+//!         })
+//!         .collect::<Vec<_>>()
+//! };
+//! let dists = all_pairs_shortest_paths(&["a", "b", "c", "d"], neighbors);
+//! assert_eq!(dists[&("a", "d")], 3);
+//! let reduced = condense(&["a", "d"], neighbors);
+//! assert_eq!(reduced[&"a"], vec![(3, "d")]);
+//! ```
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+/// All-pairs shortest path costs between the given nodes
+/// via the [Floyd–Warshall Algorithm][1]. The neighbor
+/// function gives the `(cost, node)` pairs of the outgoing
+/// edges of a node; edges to nodes not in `nodes` are
+/// ignored. The result contains an entry for each ordered
+/// pair `(from, to)` such that `to` is reachable from
+/// `from`, including `(n, n)` with cost 0.
+///
+/// This takes time cubic in the number of nodes: for a
+/// large graph with few nodes of interest, `condense()` is
+/// the better choice.
+///
+/// [1]: https://en.wikipedia.org/wiki/Floyd%E2%80%93Warshall_algorithm
+pub fn all_pairs_shortest_paths<N, F, I>(
+    nodes: &[N],
+    mut neighbors: F,
+) -> HashMap<(N, N), usize>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (usize, N)>,
+{
+    let index: HashMap<&N, usize> =
+        nodes.iter().enumerate().map(|(i, n)| (n, i)).collect();
+    let nnodes = nodes.len();
+    let mut dist = vec![vec![None; nnodes]; nnodes];
+    for (i, n) in nodes.iter().enumerate() {
+        dist[i][i] = Some(0);
+        for (cost, m) in neighbors(n) {
+            if let Some(&j) = index.get(&m) {
+                dist[i][j] = Some(match dist[i][j] {
+                    Some(d) if d <= cost => d,
+                    _ => cost,
+                });
+            }
+        }
+    }
+    for k in 0..nnodes {
+        let row_k = dist[k].clone();
+        for row in dist.iter_mut() {
+            let dik = match row[k] {
+                Some(d) => d,
+                None => continue,
+            };
+            for (dij, dkj) in row.iter_mut().zip(&row_k) {
+                if let Some(dkj) = dkj {
+                    let d = dik + dkj;
+                    if dij.map(|dij| d < dij).unwrap_or(true) {
+                        *dij = Some(d);
+                    }
+                }
+            }
+        }
+    }
+    let mut result = HashMap::new();
+    for (i, row) in dist.into_iter().enumerate() {
+        for (j, d) in row.into_iter().enumerate() {
+            if let Some(d) = d {
+                result.insert((nodes[i].clone(), nodes[j].clone()), d);
+            }
+        }
+    }
+    result
+}
+
+/// Frontier node for Dijkstra's Algorithm.
+struct Frontier<N> {
+    cost: Reverse<usize>,
+    node: N,
+}
+
+impl<N> PartialEq for Frontier<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+
+impl<N> Eq for Frontier<N> {}
+
+impl<N> PartialOrd for Frontier<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> Ord for Frontier<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cost.cmp(&other.cost)
+    }
+}
+
+/// Least cost from `start` to every node reachable from it,
+/// via [Dijkstra's Algorithm][1]. The neighbor function is
+/// as for `all_pairs_shortest_paths()`, but the graph need
+/// not be given explicitly.
+///
+/// [1]: https://en.wikipedia.org/wiki/Dijkstra's_algorithm
+pub fn shortest_paths_from<N, F, I>(
+    start: &N,
+    mut neighbors: F,
+) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (usize, N)>,
+{
+    let mut settled = HashMap::new();
+    let mut pq = BinaryHeap::new();
+    pq.push(Frontier {
+        cost: Reverse(0),
+        node: start.clone(),
+    });
+    while let Some(Frontier {
+        cost: Reverse(cost),
+        node,
+    }) = pq.pop()
+    {
+        if settled.contains_key(&node) {
+            continue;
+        }
+        for (step, next) in neighbors(&node) {
+            if !settled.contains_key(&next) {
+                pq.push(Frontier {
+                    cost: Reverse(cost + step),
+                    node: next,
+                });
+            }
+        }
+        settled.insert(node, cost);
+    }
+    settled
+}
+
+/// Reduce a graph to just the given interesting nodes. The
+/// result maps each interesting node to the `(cost, node)`
+/// pairs of the other interesting nodes reachable from it,
+/// in the order they appear in `interesting`; the cost is
+/// that of the shortest path through the full graph.
+///
+/// This runs `shortest_paths_from()` from each interesting
+/// node, so the full graph need not be listed.
+pub fn condense<N, F, I>(
+    interesting: &[N],
+    mut neighbors: F,
+) -> HashMap<N, Vec<(usize, N)>>
+where
+    N: Clone + Eq + Hash,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (usize, N)>,
+{
+    let mut result = HashMap::new();
+    for n in interesting {
+        let dists = shortest_paths_from(n, &mut neighbors);
+        let edges = interesting
+            .iter()
+            .filter(|&m| m != n)
+            .filter_map(|m| dists.get(m).map(|&d| (d, m.clone())))
+            .collect();
+        result.insert(n.clone(), edges);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// Tunnels from the 2022 day 16 example.
+    const VALVES: &[(&str, u64, &[&str])] = &[
+        ("AA", 0, &["DD", "II", "BB"]),
+        ("BB", 13, &["CC", "AA"]),
+        ("CC", 2, &["DD", "BB"]),
+        ("DD", 20, &["CC", "AA", "EE"]),
+        ("EE", 3, &["FF", "DD"]),
+        ("FF", 0, &["EE", "GG"]),
+        ("GG", 0, &["FF", "HH"]),
+        ("HH", 22, &["GG"]),
+        ("II", 0, &["AA", "JJ"]),
+        ("JJ", 21, &["II"]),
+    ];
+
+    fn tunnels(valve: &&'static str) -> Vec<(usize, &'static str)> {
+        let (_, _, exits) =
+            VALVES.iter().find(|(v, _, _)| v == valve).unwrap();
+        exits.iter().map(|&e| (1, e)).collect()
+    }
+
+    const EXPECTED: &[(&str, &str, usize)] = &[
+        ("AA", "BB", 1),
+        ("AA", "CC", 2),
+        ("AA", "DD", 1),
+        ("AA", "EE", 2),
+        ("AA", "HH", 5),
+        ("AA", "JJ", 2),
+        ("BB", "JJ", 3),
+        ("DD", "HH", 4),
+        ("HH", "JJ", 7),
+        ("CC", "EE", 2),
+    ];
+
+    #[test]
+    fn valve_distances() {
+        let all: Vec<&str> = VALVES.iter().map(|v| v.0).collect();
+        let dists = all_pairs_shortest_paths(&all, tunnels);
+        assert_eq!(dists.len(), all.len() * all.len());
+        for &(a, b, d) in EXPECTED {
+            assert_eq!(dists[&(a, b)], d);
+            assert_eq!(dists[&(b, a)], d);
+        }
+        for &a in &all {
+            assert_eq!(dists[&(a, a)], 0);
+        }
+    }
+
+    #[test]
+    fn valve_condense() {
+        let interesting: Vec<&str> = VALVES
+            .iter()
+            .filter(|&&(v, rate, _)| v == "AA" || rate > 0)
+            .map(|v| v.0)
+            .collect();
+        let reduced = condense(&interesting, tunnels);
+        assert_eq!(reduced.len(), 7);
+        for edges in reduced.values() {
+            assert_eq!(edges.len(), 6);
+        }
+        let all: Vec<&str> = VALVES.iter().map(|v| v.0).collect();
+        let dists = all_pairs_shortest_paths(&all, tunnels);
+        for (a, edges) in reduced {
+            for (d, b) in edges {
+                assert_eq!(dists[&(a, b)], d);
+            }
+        }
+    }
+
+    #[test]
+    fn unreachable_omitted() {
+        let nodes = [0u8, 1, 2];
+        let dists = all_pairs_shortest_paths(&nodes, |&n: &u8| {
+            if n == 0 {
+                vec![(5, 1), (2, 2)]
+            } else if n == 2 {
+                vec![(1, 1)]
+            } else {
+                vec![]
+            }
+        });
+        assert_eq!(dists[&(0, 1)], 3);
+        assert!(!dists.contains_key(&(1, 0)));
+        assert_eq!(dists.len(), 6);
+    }
+}
@@ -0,0 +1,352 @@
+//! Strongly-connected components, and 2-SAT by way of them.
+//!
+//! Nodes here are numbered `0..n` and edges are `(from, to)`
+//! pairs of node numbers.
+//!
+//! # Examples
+//!
+//! ```
+//! # use graph::*;
+//! let edges = [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3)];
+//! let sccs = strongly_connected_components(5, &edges);
+//! assert_eq!(sccs, vec![vec![3, 4], vec![0, 1, 2]]);
+//!
+//! // Pick at least one of each pair, but not both 0 and 1.
+//! let mut sat = TwoSat::new(3);
+//! sat.add_clause(Lit::pos(0), Lit::pos(1));
+//! sat.add_clause(Lit::pos(1), Lit::pos(2));
+//! sat.add_clause(!Lit::pos(0), !Lit::pos(1));
+//! sat.add_clause(Lit::pos(0), Lit::neg(2));
+//! assert_eq!(sat.solve(), Some(vec![true, false, true]));
+//! ```
+
+use std::ops::Not;
+
+/// The strongly-connected components of the graph with
+/// nodes `0..n` and the given edges, by [Tarjan's
+/// Algorithm][1]. The nodes of each component are in
+/// increasing order. The components are in reverse
+/// topological order: no edge leads from a component to an
+/// earlier one.
+///
+/// The depth-first search keeps its own stack, so long
+/// paths will not overflow the call stack.
+///
+/// # Panics
+///
+/// Panics if an edge has an endpoint not less than `n`.
+///
+/// [1]: https://en.wikipedia.org/wiki/Tarjan%27s_strongly_connected_components_algorithm
+pub fn strongly_connected_components(
+    n: usize,
+    edges: &[(usize, usize)],
+) -> Vec<Vec<usize>> {
+    const UNSEEN: usize = usize::MAX;
+
+    let mut succs = vec![Vec::new(); n];
+    for &(a, b) in edges {
+        assert!(b < n, "edge to node {} of {}", b, n);
+        succs[a].push(b);
+    }
+
+    let mut index = vec![UNSEEN; n];
+    let mut low = vec![UNSEEN; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut next_index = 0;
+    let mut components = Vec::new();
+    for root in 0..n {
+        if index[root] != UNSEEN {
+            continue;
+        }
+        // Each call is a node and how many of its successors
+        // have been looked at.
+        let mut calls = vec![(root, 0)];
+        index[root] = next_index;
+        low[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        while let Some((v, i)) = calls.last_mut() {
+            let v = *v;
+            if let Some(&w) = succs[v].get(*i) {
+                *i += 1;
+                if index[w] == UNSEEN {
+                    index[w] = next_index;
+                    low[w] = next_index;
+                    next_index += 1;
+                    stack.push(w);
+                    on_stack[w] = true;
+                    calls.push((w, 0));
+                } else if on_stack[w] {
+                    low[v] = low[v].min(index[w]);
+                }
+                continue;
+            }
+            calls.pop();
+            if let Some(&(u, _)) = calls.last() {
+                low[u] = low[u].min(low[v]);
+            }
+            if low[v] == index[v] {
+                let mut component = Vec::new();
+                loop {
+                    let w = stack.pop().unwrap();
+                    on_stack[w] = false;
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                component.sort_unstable();
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+/// A literal of a `TwoSat` instance: a variable or its
+/// negation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lit {
+    /// The variable.
+    pub var: usize,
+    /// True if this is the negation of the variable.
+    pub negated: bool,
+}
+
+impl Lit {
+    /// The literal that is true when `var` is.
+    pub fn pos(var: usize) -> Self {
+        Lit {
+            var,
+            negated: false,
+        }
+    }
+
+    /// The literal that is true when `var` is false.
+    pub fn neg(var: usize) -> Self {
+        Lit { var, negated: true }
+    }
+
+    /// Node of this literal in the implication graph.
+    fn node(self) -> usize {
+        2 * self.var + self.negated as usize
+    }
+}
+
+impl Not for Lit {
+    type Output = Lit;
+
+    fn not(self) -> Lit {
+        Lit {
+            var: self.var,
+            negated: !self.negated,
+        }
+    }
+}
+
+/// A [2-SAT][1] instance: a conjunction of clauses, each the
+/// disjunction of two literals. Constraints like "not both
+/// `a` and `b`" are `add_clause(!a, !b)`, and "if `a` then
+/// `b`" is `add_clause(!a, b)`.
+///
+/// [1]: https://en.wikipedia.org/wiki/2-satisfiability
+#[derive(Debug, Clone)]
+pub struct TwoSat {
+    nvars: usize,
+    /// Edges of the implication graph.
+    implications: Vec<(usize, usize)>,
+}
+
+impl TwoSat {
+    /// Make an instance with variables `0..nvars` and no
+    /// clauses.
+    pub fn new(nvars: usize) -> Self {
+        TwoSat {
+            nvars,
+            implications: Vec::new(),
+        }
+    }
+
+    /// Require that `a` or `b` (or both) be true.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a literal's variable is out of range.
+    pub fn add_clause(&mut self, a: Lit, b: Lit) {
+        for lit in [a, b] {
+            assert!(lit.var < self.nvars, "no variable {}", lit.var);
+        }
+        self.implications.push(((!a).node(), b.node()));
+        self.implications.push(((!b).node(), a.node()));
+    }
+
+    /// A value for each variable satisfying every clause,
+    /// or `None` if there is none. This takes time linear in
+    /// the size of the instance.
+    pub fn solve(&self) -> Option<Vec<bool>> {
+        let n = 2 * self.nvars;
+        let sccs = strongly_connected_components(n, &self.implications);
+        let mut component = vec![0; n];
+        for (c, nodes) in sccs.iter().enumerate() {
+            for &node in nodes {
+                component[node] = c;
+            }
+        }
+        // A variable is true when its positive literal
+        // comes later in topological order, which is earlier
+        // in the component list.
+        (0..self.nvars)
+            .map(|var| {
+                let pos = component[Lit::pos(var).node()];
+                let neg = component[Lit::neg(var).node()];
+                (pos != neg).then_some(pos < neg)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::testutil::Rng;
+
+    /// A random literal on one of `nvars` variables.
+    fn random_lit(rng: &mut Rng, nvars: usize) -> Lit {
+        Lit {
+            var: rng.below(nvars),
+            negated: rng.below(2) == 1,
+        }
+    }
+
+    fn satisfies(clauses: &[(Lit, Lit)], values: &[bool]) -> bool {
+        let holds = |lit: Lit| values[lit.var] != lit.negated;
+        clauses.iter().all(|&(a, b)| holds(a) || holds(b))
+    }
+
+    #[test]
+    fn components() {
+        let edges = [
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (3, 1),
+            (3, 2),
+            (3, 4),
+            (4, 3),
+            (4, 5),
+            (5, 2),
+            (5, 6),
+            (6, 5),
+            (7, 4),
+            (7, 6),
+            (7, 7),
+        ];
+        let sccs = strongly_connected_components(9, &edges);
+        let expected =
+            [vec![0, 1, 2], vec![5, 6], vec![3, 4], vec![7], vec![8]];
+        assert_eq!(sccs, expected);
+        assert!(strongly_connected_components(0, &[]).is_empty());
+
+        // Reverse topological order, for a random graph.
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        let n = 200;
+        let edges: Vec<(usize, usize)> = (0..400)
+            .map(|_| (rng.below(n), rng.below(n)))
+            .collect();
+        let sccs = strongly_connected_components(n, &edges);
+        let mut component = vec![0; n];
+        for (c, nodes) in sccs.iter().enumerate() {
+            for &node in nodes {
+                component[node] = c;
+            }
+        }
+        assert_eq!(sccs.iter().map(Vec::len).sum::<usize>(), n);
+        for &(a, b) in &edges {
+            assert!(component[a] >= component[b]);
+        }
+    }
+
+    #[test]
+    fn two_sat() {
+        let clauses = [
+            (Lit::pos(0), Lit::neg(1)),
+            (Lit::neg(0), Lit::neg(1)),
+            (Lit::pos(1), Lit::pos(2)),
+            (Lit::neg(2), Lit::pos(3)),
+        ];
+        let mut sat = TwoSat::new(4);
+        for &(a, b) in &clauses {
+            sat.add_clause(a, b);
+        }
+        let values = sat.solve().unwrap();
+        assert!(satisfies(&clauses, &values));
+        assert!(!values[1] && values[2] && values[3]);
+
+        // x0 and x1 must differ, as must x1 and x2, and x0
+        // and x2: impossible.
+        let mut sat = TwoSat::new(3);
+        for (a, b) in [(0, 1), (1, 2), (0, 2)] {
+            sat.add_clause(Lit::pos(a), Lit::pos(b));
+            sat.add_clause(Lit::neg(a), Lit::neg(b));
+        }
+        assert_eq!(sat.solve(), None);
+    }
+
+    #[test]
+    fn against_brute_force() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..300 {
+            let nvars = rng.below(6) + 1;
+            let clauses: Vec<(Lit, Lit)> = (0..rng.below(12))
+                .map(|_| {
+                    (random_lit(&mut rng, nvars), random_lit(&mut rng, nvars))
+                })
+                .collect();
+            let mut sat = TwoSat::new(nvars);
+            for &(a, b) in &clauses {
+                sat.add_clause(a, b);
+            }
+            let brute = (0..1 << nvars).any(|bits: usize| {
+                let values: Vec<bool> =
+                    (0..nvars).map(|v| bits >> v & 1 == 1).collect();
+                satisfies(&clauses, &values)
+            });
+            match sat.solve() {
+                Some(values) => assert!(satisfies(&clauses, &values)),
+                None => assert!(!brute, "{:?}", clauses),
+            }
+        }
+    }
+
+    #[test]
+    fn deep_graph() {
+        // One cycle through a million nodes, which would
+        // overflow a recursive search.
+        let n = 1_000_000;
+        let edges: Vec<(usize, usize)> =
+            (0..n).map(|i| (i, (i + 1) % n)).collect();
+        let sccs = strongly_connected_components(n, &edges);
+        assert_eq!(sccs.len(), 1);
+        assert_eq!(sccs[0].len(), n);
+
+        // A long chain of implications with a planted
+        // solution.
+        let mut rng = Rng(0x1234_5678_9abc_def1);
+        let nvars = 500_000;
+        let planted: Vec<bool> =
+            (0..nvars).map(|_| rng.below(2) == 1).collect();
+        let lit = |var: usize| Lit {
+            var,
+            negated: !planted[var],
+        };
+        let mut sat = TwoSat::new(nvars);
+        for var in 1..nvars {
+            sat.add_clause(!lit(var - 1), lit(var));
+        }
+        sat.add_clause(lit(0), lit(0));
+        assert_eq!(sat.solve(), Some(planted));
+    }
+}
@@ -0,0 +1,182 @@
+//! Topological ordering of dependency graphs.
+//!
+//! Dependencies are given as `(before, after)` edge pairs.
+//!
+//! # Examples
+//!
+//! ```
+//! # use graph::*;
+//! let edges = [('c', 'a'), ('a', 'b'), ('c', 'b')];
+//! let order = topo_sort(&['a', 'b', 'c'], &edges).unwrap();
+//! assert_eq!(order, vec!['c', 'a', 'b']);
+//! assert!(is_topo_ordered(&order, &edges));
+//!
+//! let cycle = topo_sort(&['a', 'b'], &[('a', 'b'), ('b', 'a')]);
+//! assert_eq!(cycle, Err(Cycle(vec!['a', 'b'])));
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+
+/// Error returned when a graph has no topological order.
+/// The nodes of some cycle are listed in edge order: each
+/// node must come before the next, and the last before the
+/// first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N>(pub Vec<N>);
+
+impl<N: Debug> Display for Cycle<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "dependency cycle: {:?}", self.0)
+    }
+}
+
+impl<N: Debug> std::error::Error for Cycle<N> {}
+
+/// Topologically sort the given nodes, such that for each
+/// edge `(a, b)` node `a` comes before node `b`. Edges with
+/// an endpoint not in `nodes` are ignored. When more than
+/// one node is ready, the least by `Ord` goes first, so the
+/// order is deterministic.
+///
+/// On failure, returns the nodes of some cycle.
+pub fn topo_sort<N>(nodes: &[N], edges: &[(N, N)]) -> Result<Vec<N>, Cycle<N>>
+where
+    N: Clone + Ord,
+{
+    let mut succs: BTreeMap<&N, Vec<&N>> =
+        nodes.iter().map(|n| (n, Vec::new())).collect();
+    let mut npreds: BTreeMap<&N, usize> =
+        nodes.iter().map(|n| (n, 0)).collect();
+    for (a, b) in edges {
+        if succs.contains_key(a) && succs.contains_key(b) {
+            succs.get_mut(a).unwrap().push(b);
+            *npreds.get_mut(b).unwrap() += 1;
+        }
+    }
+
+    let mut ready: BTreeSet<&N> = npreds
+        .iter()
+        .filter(|&(_, &np)| np == 0)
+        .map(|(&n, _)| n)
+        .collect();
+    let mut order = Vec::with_capacity(npreds.len());
+    while let Some(n) = ready.pop_first() {
+        order.push(n.clone());
+        for &s in &succs[n] {
+            let np = npreds.get_mut(s).unwrap();
+            *np -= 1;
+            if *np == 0 {
+                ready.insert(s);
+            }
+        }
+    }
+    if order.len() == npreds.len() {
+        return Ok(order);
+    }
+
+    // Every remaining node has a remaining predecessor, so
+    // walking predecessors from any of them must revisit a
+    // node: the walk from there on is a cycle.
+    let mut preds: BTreeMap<&N, &N> = BTreeMap::new();
+    for (a, b) in edges {
+        if npreds.get(a).map(|&np| np > 0).unwrap_or(false)
+            && npreds.get(b).map(|&np| np > 0).unwrap_or(false)
+        {
+            preds.entry(b).or_insert(a);
+        }
+    }
+    let mut walk = Vec::new();
+    let mut cur = *npreds.iter().find(|&(_, &np)| np > 0).unwrap().0;
+    while !walk.contains(&cur) {
+        walk.push(cur);
+        cur = preds[cur];
+    }
+    let start = walk.iter().position(|&n| n == cur).unwrap();
+    let mut cycle: Vec<N> = walk[start..].iter().map(|&n| n.clone()).collect();
+    cycle.reverse();
+    let least = (0..cycle.len()).min_by_key(|&i| &cycle[i]).unwrap();
+    cycle.rotate_left(least);
+    Err(Cycle(cycle))
+}
+
+/// Return true if for every edge `(a, b)` with both
+/// endpoints in `seq`, `a` appears before `b` in `seq`.
+pub fn is_topo_ordered<N>(seq: &[N], edges: &[(N, N)]) -> bool
+where
+    N: Eq + Hash,
+{
+    let posn: HashMap<&N, usize> =
+        seq.iter().enumerate().map(|(i, n)| (n, i)).collect();
+    edges.iter().all(|(a, b)| match (posn.get(a), posn.get(b)) {
+        (Some(pa), Some(pb)) => pa < pb,
+        _ => true,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn step_order() {
+        // 2018 day 7 example.
+        let edges = [
+            ('C', 'A'),
+            ('C', 'F'),
+            ('A', 'B'),
+            ('A', 'D'),
+            ('B', 'E'),
+            ('D', 'E'),
+            ('F', 'E'),
+        ];
+        let nodes: Vec<char> = "ABCDEF".chars().collect();
+        let order = topo_sort(&nodes, &edges).unwrap();
+        assert_eq!(order.into_iter().collect::<String>(), "CABDFE");
+    }
+
+    #[test]
+    fn page_ordering() {
+        // 2024 day 5 example.
+        let rules: Vec<(u32, u32)> = [
+            (47, 53), (97, 13), (97, 61), (97, 47), (75, 29), (61, 13),
+            (75, 53), (29, 13), (97, 29), (53, 29), (61, 53), (97, 53),
+            (61, 29), (47, 13), (75, 47), (97, 75), (47, 61), (75, 61),
+            (47, 29), (75, 13), (53, 13),
+        ]
+        .to_vec();
+        let updates: &[&[u32]] = &[
+            &[75, 47, 61, 53, 29],
+            &[97, 61, 53, 29, 13],
+            &[75, 29, 13],
+            &[75, 97, 47, 61, 53],
+            &[61, 13, 29],
+            &[97, 13, 75, 29, 47],
+        ];
+        let mut ordered = 0;
+        let mut repaired = 0;
+        for &update in updates {
+            let middle = update.len() / 2;
+            if is_topo_ordered(update, &rules) {
+                ordered += update[middle];
+            } else {
+                let fixed = topo_sort(update, &rules).unwrap();
+                assert!(is_topo_ordered(&fixed, &rules));
+                repaired += fixed[middle];
+            }
+        }
+        assert_eq!(ordered, 143);
+        assert_eq!(repaired, 123);
+    }
+
+    #[test]
+    fn cycle_reported() {
+        let edges = [(1, 2), (2, 3), (3, 4), (4, 2), (0, 1), (4, 5)];
+        let result = topo_sort(&[0, 1, 2, 3, 4, 5], &edges);
+        assert_eq!(result, Err(Cycle(vec![2, 3, 4])));
+        let edges = [(1, 1)];
+        assert_eq!(topo_sort(&[1], &edges), Err(Cycle(vec![1])));
+    }
+}
@@ -0,0 +1,297 @@
+//! Undirected graphs with arbitrary node labels.
+//!
+//! Node labels are interned: each is given an index when
+//! first seen, and the graph works on indices internally.
+//!
+//! # Examples
+//!
+//! ```
+//! # use graph::*;
+//! let graph: UGraph<&str> =
+//!     [("a", "b"), ("b", "c"), ("c", "a"), ("c", "d")].into_iter().collect();
+//! assert_eq!(graph.triangles().len(), 1);
+//! let mut clique = graph.max_clique();
+//! clique.sort();
+//! assert_eq!(clique, [&"a", &"b", &"c"]);
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// An undirected graph without self-loops or multiple
+/// edges, stored as a set of neighbors for each node.
+#[derive(Debug, Clone)]
+pub struct UGraph<N> {
+    /// Label of each node, by index.
+    pub(crate) labels: Vec<N>,
+    /// Index of each node, by label.
+    pub(crate) index: HashMap<N, usize>,
+    /// Neighbors of each node, by index.
+    pub(crate) adj: Vec<HashSet<usize>>,
+}
+
+impl<N> Default for UGraph<N> {
+    fn default() -> Self {
+        UGraph {
+            labels: Vec::new(),
+            index: HashMap::new(),
+            adj: Vec::new(),
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> UGraph<N> {
+    /// Make an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index of node `n`, adding it if needed.
+    fn intern(&mut self, n: N) -> usize {
+        if let Some(&i) = self.index.get(&n) {
+            return i;
+        }
+        let i = self.labels.len();
+        self.labels.push(n.clone());
+        self.index.insert(n, i);
+        self.adj.push(HashSet::new());
+        i
+    }
+
+    /// Add node `n` with no edges, if it is not already in
+    /// the graph.
+    pub fn add_node(&mut self, n: N) {
+        self.intern(n);
+    }
+
+    /// Add an edge between `a` and `b`, adding the nodes as
+    /// needed. Returns false if the edge was already there.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` and `b` are the same node.
+    pub fn add_edge(&mut self, a: N, b: N) -> bool {
+        let (i, j) = (self.intern(a), self.intern(b));
+        assert!(i != j, "self-loop in undirected graph");
+        self.adj[j].insert(i);
+        self.adj[i].insert(j)
+    }
+
+    /// Number of nodes in the graph.
+    pub fn node_count(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Number of edges in the graph.
+    pub fn edge_count(&self) -> usize {
+        self.adj.iter().map(HashSet::len).sum::<usize>() / 2
+    }
+
+    /// True if `n` is a node of the graph.
+    pub fn contains_node(&self, n: &N) -> bool {
+        self.index.contains_key(n)
+    }
+
+    /// True if there is an edge between `a` and `b`.
+    pub fn has_edge(&self, a: &N, b: &N) -> bool {
+        match (self.index.get(a), self.index.get(b)) {
+            (Some(i), Some(j)) => self.adj[*i].contains(j),
+            _ => false,
+        }
+    }
+
+    /// Iterator over the nodes of the graph, in the order
+    /// they were added.
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.labels.iter()
+    }
+
+    /// Iterator over the neighbors of `n`, in no particular
+    /// order. A node not in the graph has no neighbors.
+    pub fn neighbors(&self, n: &N) -> impl Iterator<Item = &N> {
+        self.index
+            .get(n)
+            .into_iter()
+            .flat_map(|&i| self.adj[i].iter().map(|&j| &self.labels[j]))
+    }
+
+    /// Every triangle of the graph: each set of three nodes
+    /// that are all neighbors, once.
+    pub fn triangles(&self) -> Vec<[&N; 3]> {
+        let mut triangles = Vec::new();
+        for (i, ni) in self.adj.iter().enumerate() {
+            for &j in ni.iter().filter(|&&j| j > i) {
+                for &k in ni.iter().filter(|&&k| k > j) {
+                    if self.adj[j].contains(&k) {
+                        let l = &self.labels;
+                        triangles.push([&l[i], &l[j], &l[k]]);
+                    }
+                }
+            }
+        }
+        triangles
+    }
+
+    /// The nodes of a largest clique of the graph: a largest
+    /// set of nodes that are all neighbors. If there are
+    /// several, which one is unspecified. The nodes are in
+    /// no particular order.
+    ///
+    /// This is the [Bron–Kerbosch Algorithm][1] with
+    /// pivoting, which takes exponential time in the worst
+    /// case but is fast on the sparse graphs of the
+    /// puzzles.
+    ///
+    /// [1]: https://en.wikipedia.org/wiki/Bron%E2%80%93Kerbosch_algorithm
+    pub fn max_clique(&self) -> Vec<&N> {
+        let mut best = Vec::new();
+        let candidates = (0..self.labels.len()).collect();
+        let excluded = HashSet::new();
+        self.bron_kerbosch(&mut Vec::new(), candidates, excluded, &mut best);
+        best.into_iter().map(|i| &self.labels[i]).collect()
+    }
+
+    /// Extend `clique` by nodes of `candidates` in every
+    /// maximal way that adds no node of `excluded`, keeping
+    /// the largest clique found in `best`.
+    fn bron_kerbosch(
+        &self,
+        clique: &mut Vec<usize>,
+        mut candidates: HashSet<usize>,
+        mut excluded: HashSet<usize>,
+        best: &mut Vec<usize>,
+    ) {
+        if candidates.is_empty() {
+            if excluded.is_empty() && clique.len() > best.len() {
+                best.clone_from(clique);
+            }
+            return;
+        }
+        if clique.len() + candidates.len() <= best.len() {
+            return;
+        }
+        // Any maximal clique contains the pivot or a node
+        // that is not its neighbor, so only those need be
+        // tried.
+        let pivot = candidates
+            .iter()
+            .chain(&excluded)
+            .copied()
+            .max_by_key(|&u| self.adj[u].intersection(&candidates).count())
+            .unwrap();
+        let tries: Vec<usize> = candidates
+            .difference(&self.adj[pivot])
+            .copied()
+            .collect();
+        for v in tries {
+            let adj = &self.adj[v];
+            clique.push(v);
+            self.bron_kerbosch(
+                clique,
+                candidates.intersection(adj).copied().collect(),
+                excluded.intersection(adj).copied().collect(),
+                best,
+            );
+            clique.pop();
+            candidates.remove(&v);
+            excluded.insert(v);
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> FromIterator<(N, N)> for UGraph<N> {
+    fn from_iter<I: IntoIterator<Item = (N, N)>>(edges: I) -> Self {
+        let mut graph = UGraph::new();
+        for (a, b) in edges {
+            graph.add_edge(a, b);
+        }
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::testutil::Rng;
+
+    #[test]
+    fn lan_party() {
+        // 2024 day 23 example.
+        let connections = "kh-tc qp-kh de-cg ka-co yn-aq qp-ub cg-tb vc-aq \
+                           tb-ka wh-tc yn-cg kh-ub ta-co de-co tc-td tb-wq \
+                           wh-td ta-ka td-qp aq-cg wq-ub ub-vc de-ta wq-aq \
+                           wq-vc wh-yn ka-de kh-ta co-tc wh-qp tb-vc td-yn";
+        let graph: UGraph<&str> = connections
+            .split_whitespace()
+            .map(|c| c.split_once('-').unwrap())
+            .collect();
+        assert_eq!(graph.node_count(), 16);
+        assert_eq!(graph.edge_count(), 32);
+        assert!(graph.has_edge(&"tc", &"kh"));
+        assert!(!graph.has_edge(&"tc", &"ka"));
+        let mut neighbors: Vec<&str> =
+            graph.neighbors(&"kh").copied().collect();
+        neighbors.sort();
+        assert_eq!(neighbors, ["qp", "ta", "tc", "ub"]);
+
+        let triangles = graph.triangles();
+        assert_eq!(triangles.len(), 12);
+        let with_t = triangles
+            .iter()
+            .filter(|t| t.iter().any(|n| n.starts_with('t')))
+            .count();
+        assert_eq!(with_t, 7);
+
+        let mut clique = graph.max_clique();
+        clique.sort();
+        let password: Vec<&str> = clique.into_iter().copied().collect();
+        assert_eq!(password.join(","), "co,de,ka,ta");
+    }
+
+    #[test]
+    fn against_brute_force() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..100 {
+            let n = rng.below(10) + 1;
+            let mut graph = UGraph::new();
+            for i in 0..n {
+                graph.add_node(i);
+            }
+            for _ in 0..rng.below(3 * n) {
+                let (a, b) = (rng.below(n), rng.below(n));
+                if a != b {
+                    graph.add_edge(a, b);
+                }
+            }
+            let is_clique = |nodes: &[usize]| {
+                nodes.iter().enumerate().all(|(k, a)| {
+                    nodes[k + 1..].iter().all(|b| graph.has_edge(a, b))
+                })
+            };
+            let largest = (0..1usize << n)
+                .filter(|&bits| {
+                    let nodes: Vec<usize> =
+                        (0..n).filter(|&i| bits >> i & 1 == 1).collect();
+                    is_clique(&nodes)
+                })
+                .map(|bits| bits.count_ones() as usize)
+                .max()
+                .unwrap();
+            let clique: Vec<usize> =
+                graph.max_clique().into_iter().copied().collect();
+            assert_eq!(clique.len(), largest);
+            assert!(is_clique(&clique));
+
+            let mut count = 0;
+            for a in 0..n {
+                for b in a + 1..n {
+                    for c in b + 1..n {
+                        count += is_clique(&[a, b, c]) as usize;
+                    }
+                }
+            }
+            assert_eq!(graph.triangles().len(), count);
+        }
+    }
+}
@@ -0,0 +1,272 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! [Base64][1] encoding, and encoding with any alphabet
+//! whose size is a power of two.
+//!
+//! With an alphabet of `2^k` symbols, the input is taken as
+//! a bit string, most significant bit of each byte first,
+//! and cut into `k`-bit symbols; the last symbol is filled
+//! out with zero bits. Base64 is this with a 64-symbol
+//! alphabet, padded with `=` to a multiple of four symbols.
+//!
+//! [1]: https://www.rfc-editor.org/rfc/rfc4648
+//!
+//! # Examples
+//!
+//! ```
+//! # use hexstring::*;
+//! assert_eq!(encode_base64(b"foob"), "Zm9vYg==");
+//! assert_eq!(decode_base64("Zm9vYg==").unwrap(), b"foob");
+//! assert_eq!(decode_base64("Zm9vYg").unwrap(), b"foob");
+//! assert_eq!(encode_base64_url(&[0xfb, 0xff]), "-_8=");
+//! assert_eq!(encode_with_alphabet(b"A", b"01"), "01000001");
+//! ```
+
+use std::fmt;
+
+/// The standard base64 alphabet.
+pub const BASE64_STANDARD: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The URL- and filename-safe base64 alphabet.
+pub const BASE64_URL_SAFE: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Error in decoding with an alphabet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The character at the given byte offset was not in
+    /// the alphabet.
+    BadChar(usize, char),
+    /// The input had a number of symbols, given here, that
+    /// no input encodes to.
+    BadLength(usize),
+    /// The padding was wrong, or the final symbol had
+    /// nonzero fill bits, at the given byte offset.
+    BadPadding(usize),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::BadChar(posn, ch) => {
+                write!(f, "bad character {:?} at offset {}", ch, posn)
+            }
+            DecodeError::BadLength(n) => write!(f, "bad length {}", n),
+            DecodeError::BadPadding(posn) => {
+                write!(f, "bad padding at offset {}", posn)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Number of bits per symbol of an alphabet.
+///
+/// # Panics
+///
+/// Panics if the alphabet size is not a power of two
+/// between 2 and 256.
+fn symbol_bits(nsymbols: usize) -> u32 {
+    assert!(
+        nsymbols.is_power_of_two() && (2..=256).contains(&nsymbols),
+        "alphabet size {} is not a power of two from 2 to 256",
+        nsymbols,
+    );
+    nsymbols.trailing_zeros()
+}
+
+/// Encode bytes with the given alphabet, which must have a
+/// power-of-two size from 2 to 256. No padding is added.
+///
+/// # Panics
+///
+/// Panics if the alphabet size is unsuitable.
+pub fn encode_with_alphabet<const N: usize>(
+    bytes: &[u8],
+    alphabet: &[u8; N],
+) -> String {
+    let k = symbol_bits(N);
+    let mask = (1u32 << k) - 1;
+    let len = (8 * bytes.len()).div_ceil(k as usize);
+    let mut result = String::with_capacity(len);
+    let mut acc = 0u32;
+    let mut nbits = 0;
+    for &b in bytes {
+        acc = (acc << 8) | b as u32;
+        nbits += 8;
+        while nbits >= k {
+            nbits -= k;
+            result.push(alphabet[((acc >> nbits) & mask) as usize] as char);
+        }
+        acc &= (1 << nbits) - 1;
+    }
+    if nbits > 0 {
+        result.push(alphabet[((acc << (k - nbits)) & mask) as usize] as char);
+    }
+    result
+}
+
+/// Decode a string encoded with `encode_with_alphabet()`
+/// using the same alphabet.
+///
+/// # Panics
+///
+/// Panics if the alphabet size is unsuitable.
+pub fn decode_with_alphabet<const N: usize>(
+    s: &str,
+    alphabet: &[u8; N],
+) -> Result<Vec<u8>, DecodeError> {
+    let k = symbol_bits(N);
+    let mut table = [None; 256];
+    for (i, &c) in alphabet.iter().enumerate() {
+        table[c as usize] = Some(i as u32);
+    }
+    let mut bytes = Vec::with_capacity(k as usize * s.len() / 8);
+    let mut acc = 0u32;
+    let mut nbits = 0;
+    let mut nsymbols = 0;
+    let mut last = 0;
+    for (posn, ch) in s.char_indices() {
+        let v = u8::try_from(ch)
+            .ok()
+            .and_then(|c| table[c as usize])
+            .ok_or(DecodeError::BadChar(posn, ch))?;
+        acc = (acc << k) | v;
+        nbits += k;
+        nsymbols += 1;
+        last = posn;
+        if nbits >= 8 {
+            nbits -= 8;
+            bytes.push((acc >> nbits) as u8);
+            acc &= (1 << nbits) - 1;
+        }
+    }
+    // Leftover bits must be fill for the last symbol.
+    if nbits >= k {
+        return Err(DecodeError::BadLength(nsymbols));
+    }
+    if acc != 0 {
+        return Err(DecodeError::BadPadding(last));
+    }
+    Ok(bytes)
+}
+
+/// Encode bytes as base64 with the given alphabet, padded.
+fn encode_padded(bytes: &[u8], alphabet: &[u8; 64]) -> String {
+    let mut result = encode_with_alphabet(bytes, alphabet);
+    while !result.len().is_multiple_of(4) {
+        result.push('=');
+    }
+    result
+}
+
+/// Decode base64 with the given alphabet, with or without
+/// padding.
+fn decode_padded(
+    s: &str,
+    alphabet: &[u8; 64],
+) -> Result<Vec<u8>, DecodeError> {
+    let body = s.trim_end_matches('=');
+    let npad = s.len() - body.len();
+    if npad > 0 && (npad > 2 || !s.len().is_multiple_of(4)) {
+        return Err(DecodeError::BadPadding(body.len()));
+    }
+    decode_with_alphabet(body, alphabet)
+}
+
+/// Encode bytes as standard base64, with padding.
+pub fn encode_base64(bytes: &[u8]) -> String {
+    encode_padded(bytes, BASE64_STANDARD)
+}
+
+/// Decode standard base64. Padding is optional, but must be
+/// correct if present.
+pub fn decode_base64(s: &str) -> Result<Vec<u8>, DecodeError> {
+    decode_padded(s, BASE64_STANDARD)
+}
+
+/// Encode bytes as URL-safe base64, with padding.
+pub fn encode_base64_url(bytes: &[u8]) -> String {
+    encode_padded(bytes, BASE64_URL_SAFE)
+}
+
+/// Decode URL-safe base64. Padding is optional, but must be
+/// correct if present.
+pub fn decode_base64_url(s: &str) -> Result<Vec<u8>, DecodeError> {
+    decode_padded(s, BASE64_URL_SAFE)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::testutil::Rng;
+
+    const BASE32: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+    #[test]
+    fn rfc_vectors() {
+        let vectors = [
+            ("", "", ""),
+            ("f", "Zg==", "MY"),
+            ("fo", "Zm8=", "MZXQ"),
+            ("foo", "Zm9v", "MZXW6"),
+            ("foob", "Zm9vYg==", "MZXW6YQ"),
+            ("fooba", "Zm9vYmE=", "MZXW6YTB"),
+            ("foobar", "Zm9vYmFy", "MZXW6YTBOI"),
+        ];
+        for (plain, b64, b32) in vectors {
+            let bytes = plain.as_bytes();
+            assert_eq!(encode_base64(bytes), b64);
+            assert_eq!(decode_base64(b64).unwrap(), bytes);
+            assert_eq!(encode_with_alphabet(bytes, BASE32), b32);
+            assert_eq!(decode_with_alphabet(b32, BASE32).unwrap(), bytes);
+            let b16 = encode_with_alphabet(bytes, b"0123456789ABCDEF");
+            assert_eq!(b16, crate::encode_hex_upper(bytes));
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut rng = Rng(0x1234_5678_9abc_def1);
+        for _ in 0..100 {
+            let bytes: Vec<u8> =
+                (0..rng.below(50)).map(|_| rng.below(256) as u8).collect();
+            let b64 = encode_base64(&bytes);
+            assert_eq!(b64.len() % 4, 0);
+            assert_eq!(decode_base64(&b64).unwrap(), bytes);
+            let url = encode_base64_url(&bytes);
+            assert!(!url.contains(['+', '/']));
+            assert_eq!(decode_base64_url(&url).unwrap(), bytes);
+            let unpadded = url.trim_end_matches('=');
+            assert_eq!(decode_base64_url(unpadded).unwrap(), bytes);
+            let bin = encode_with_alphabet(&bytes, b"01");
+            assert_eq!(decode_with_alphabet(&bin, b"01").unwrap(), bytes);
+            let oct = encode_with_alphabet(&bytes, b"abcdefgh");
+            let decoded = decode_with_alphabet(&oct, b"abcdefgh").unwrap();
+            assert_eq!(decoded, bytes);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(decode_base64("Zm9v!"), Err(DecodeError::BadChar(4, '!')));
+        assert_eq!(decode_base64("Zm9vY"), Err(DecodeError::BadLength(5)));
+        assert_eq!(decode_base64("Zh=="), Err(DecodeError::BadPadding(1)));
+        assert_eq!(decode_base64("Zg="), Err(DecodeError::BadPadding(2)));
+        assert_eq!(decode_base64("Z==="), Err(DecodeError::BadPadding(1)));
+        let e = decode_base64_url("+w==");
+        assert_eq!(e, Err(DecodeError::BadChar(0, '+')));
+    }
+
+    #[test]
+    #[should_panic(expected = "not a power of two")]
+    fn bad_alphabet() {
+        encode_with_alphabet(b"x", b"abc");
+    }
+}
@@ -0,0 +1,258 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Fixed-width binary strings and bit vectors.
+//!
+//! Binary strings are written most significant bit first,
+//! with no prefix, and may be up to 64 bits wide.
+//!
+//! # Examples
+//!
+//! ```
+//! # use hexstring::*;
+//! assert_eq!(from_binary_str("00101"), Ok(5));
+//! assert_eq!(to_binary_string(5, 5), "00101");
+//! assert_eq!(u64_to_bits(5, 3), vec![true, false, true]);
+//! assert_eq!(bits_to_u64(&[true, false, true]), 5);
+//! assert_eq!(from_binary_str("0120"), Err(BinaryError::BadDigit(2, '2')));
+//! ```
+
+use std::fmt;
+
+/// Maximum width of a binary string.
+pub const MAX_BINARY_WIDTH: usize = 64;
+
+/// Error in parsing binary strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BinaryError {
+    /// The string was empty.
+    Empty,
+    /// The string was wider than `MAX_BINARY_WIDTH`; the
+    /// width is given.
+    TooWide(usize),
+    /// The character at the given byte offset was not a
+    /// binary digit.
+    BadDigit(usize, char),
+    /// The row with the given index had the given width
+    /// rather than the expected width of the first row.
+    Ragged {
+        row: usize,
+        width: usize,
+        expected: usize,
+    },
+    /// The row with the given index failed to parse.
+    Row(usize, Box<BinaryError>),
+}
+
+impl fmt::Display for BinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BinaryError::Empty => write!(f, "empty binary string"),
+            BinaryError::TooWide(w) => {
+                write!(f, "binary string of width {} is too wide", w)
+            }
+            BinaryError::BadDigit(posn, ch) => {
+                write!(f, "bad binary digit {:?} at offset {}", ch, posn)
+            }
+            BinaryError::Ragged {
+                row,
+                width,
+                expected,
+            } => write!(
+                f,
+                "row {} has width {}, expected {}",
+                row, width, expected,
+            ),
+            BinaryError::Row(row, e) => write!(f, "row {}: {}", row, e),
+        }
+    }
+}
+
+impl std::error::Error for BinaryError {}
+
+/// Parse a string of binary digits.
+pub fn from_binary_str(s: &str) -> Result<u64, BinaryError> {
+    if s.is_empty() {
+        return Err(BinaryError::Empty);
+    }
+    let mut value = 0;
+    let mut width = 0;
+    for (posn, ch) in s.char_indices() {
+        let bit = match ch {
+            '0' => 0,
+            '1' => 1,
+            _ => return Err(BinaryError::BadDigit(posn, ch)),
+        };
+        width += 1;
+        value = (value << 1) | bit;
+    }
+    if width > MAX_BINARY_WIDTH {
+        return Err(BinaryError::TooWide(width));
+    }
+    Ok(value)
+}
+
+/// Format the low `width` bits of `v` as a zero-padded
+/// binary string.
+///
+/// # Panics
+///
+/// Panics if `width` is greater than `MAX_BINARY_WIDTH`.
+pub fn to_binary_string(v: u64, width: usize) -> String {
+    u64_to_bits(v, width)
+        .into_iter()
+        .map(|b| if b { '1' } else { '0' })
+        .collect()
+}
+
+/// The number whose binary digits, most significant first,
+/// are the given bits.
+///
+/// # Panics
+///
+/// Panics if there are more than `MAX_BINARY_WIDTH` bits.
+pub fn bits_to_u64(bits: &[bool]) -> u64 {
+    assert!(
+        bits.len() <= MAX_BINARY_WIDTH,
+        "bits_to_u64: {} bits is too many",
+        bits.len(),
+    );
+    bits.iter().fold(0, |acc, &b| (acc << 1) | b as u64)
+}
+
+/// The low `width` bits of `v`, most significant first.
+///
+/// # Panics
+///
+/// Panics if `width` is greater than `MAX_BINARY_WIDTH`.
+pub fn u64_to_bits(v: u64, width: usize) -> Vec<bool> {
+    assert!(
+        width <= MAX_BINARY_WIDTH,
+        "u64_to_bits: width {} is too wide",
+        width,
+    );
+    (0..width).rev().map(|i| v & (1 << i) != 0).collect()
+}
+
+/// Parse rows of binary digits, all of the same width,
+/// returning their values and the common width. No rows
+/// gives width 0.
+///
+/// # Examples
+///
+/// The 2021 day 3 power consumption example.
+///
+/// ```
+/// # use hexstring::*;
+/// let report = "00100\n11110\n10110\n10111\n10101\n01111\n\
+///               00111\n11100\n10000\n11001\n00010\n01010";
+/// let (values, width) = parse_bit_rows(report.lines()).unwrap();
+/// let mut gamma = vec![false; width];
+/// for (i, g) in gamma.iter_mut().enumerate() {
+///     let ones = values
+///         .iter()
+///         .filter(|&&v| u64_to_bits(v, width)[i])
+///         .count();
+///     *g = 2 * ones > values.len();
+/// }
+/// let gamma = bits_to_u64(&gamma);
+/// let epsilon = !gamma & ((1 << width) - 1);
+/// assert_eq!(to_binary_string(gamma, width), "10110");
+/// assert_eq!(gamma * epsilon, 198);
+/// ```
+pub fn parse_bit_rows<I>(lines: I) -> Result<(Vec<u64>, usize), BinaryError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut values = Vec::new();
+    let mut width = None;
+    for (row, line) in lines.into_iter().enumerate() {
+        let line = line.as_ref();
+        let v = from_binary_str(line)
+            .map_err(|e| BinaryError::Row(row, Box::new(e)))?;
+        let w = line.len();
+        match width {
+            None => width = Some(w),
+            Some(expected) if expected != w => {
+                return Err(BinaryError::Ragged {
+                    row,
+                    width: w,
+                    expected,
+                });
+            }
+            Some(_) => (),
+        }
+        values.push(v);
+    }
+    Ok((values, width.unwrap_or(0)))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn diagnostic_report() {
+        // 2021 day 3 example: life support rating.
+        let report = [
+            "00100", "11110", "10110", "10111", "10101", "01111", "00111",
+            "11100", "10000", "11001", "00010", "01010",
+        ];
+        let (values, width) = parse_bit_rows(report).unwrap();
+        assert_eq!(width, 5);
+        let rating = |most: bool| {
+            let mut keep = values.clone();
+            for i in (0..width).rev() {
+                if keep.len() == 1 {
+                    break;
+                }
+                let ones = keep.iter().filter(|&&v| v & (1 << i) != 0).count();
+                let want = (2 * ones >= keep.len()) == most;
+                keep.retain(|&v| (v & (1 << i) != 0) == want);
+            }
+            keep[0]
+        };
+        assert_eq!(to_binary_string(rating(true), width), "10111");
+        assert_eq!(to_binary_string(rating(false), width), "01010");
+        assert_eq!(rating(true) * rating(false), 230);
+    }
+
+    #[test]
+    fn round_trips() {
+        for v in [0, 1, 5, 0xdead_beef, u64::MAX] {
+            let s = to_binary_string(v, 64);
+            assert_eq!(from_binary_str(&s), Ok(v));
+            assert_eq!(bits_to_u64(&u64_to_bits(v, 64)), v);
+        }
+        assert_eq!(to_binary_string(0b1101, 2), "01");
+        assert_eq!(to_binary_string(7, 0), "");
+        assert_eq!(bits_to_u64(&[]), 0);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(from_binary_str(""), Err(BinaryError::Empty));
+        assert_eq!(
+            from_binary_str(&"1".repeat(65)),
+            Err(BinaryError::TooWide(65)),
+        );
+        assert_eq!(
+            parse_bit_rows(["101", "11", "111"]),
+            Err(BinaryError::Ragged {
+                row: 1,
+                width: 2,
+                expected: 3,
+            }),
+        );
+        let e = parse_bit_rows(["101", "1x1"]).unwrap_err();
+        let bad = BinaryError::BadDigit(1, 'x');
+        assert_eq!(e, BinaryError::Row(1, Box::new(bad)));
+        assert_eq!(e.to_string(), "row 1: bad binary digit 'x' at offset 1");
+        let none: [&str; 0] = [];
+        assert_eq!(parse_bit_rows(none), Ok((vec![], 0)));
+    }
+}
@@ -0,0 +1,208 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Reading hex strings as bit streams.
+//!
+//! Each hex digit is exactly four bits, most significant
+//! first, so leading zeros are preserved.
+//!
+//! # Examples
+//!
+//! ```
+//! # use hexstring::*;
+//! let bits: String = hex_bits("0a")
+//!     .map(|b| if b { '1' } else { '0' })
+//!     .collect();
+//! assert_eq!(bits, "00001010");
+//!
+//! // A 2021 day 16 literal value packet.
+//! let mut reader = BitReader::from_hex("D2FE28");
+//! assert_eq!(reader.read_bits(3), 6); // version
+//! assert_eq!(reader.read_bits(3), 4); // type ID
+//! let mut value = 0;
+//! loop {
+//!     let more = reader.read_bit();
+//!     value = (value << 4) | reader.read_bits(4);
+//!     if !more {
+//!         break;
+//!     }
+//! }
+//! assert_eq!(value, 2021);
+//! assert_eq!(reader.remaining(), 3);
+//! ```
+
+/// Iterate over the bits of a string of hex digits, four per
+/// digit, most significant bit first.
+///
+/// # Panics
+///
+/// Panics on reaching a character that is not a hex digit.
+pub fn hex_bits(s: &str) -> impl Iterator<Item = bool> + '_ {
+    s.chars().flat_map(|ch| {
+        let d = ch
+            .to_digit(16)
+            .unwrap_or_else(|| panic!("hex_bits: bad hex digit {:?}", ch));
+        (0..4).rev().map(move |i| d & (1 << i) != 0)
+    })
+}
+
+/// Reader for consuming a bit stream a field at a time.
+#[derive(Debug, Clone)]
+pub struct BitReader {
+    /// The bits of the stream.
+    bits: Vec<bool>,
+    /// Position of the next bit to read.
+    posn: usize,
+}
+
+impl BitReader {
+    /// Make a reader over the given bits.
+    pub fn new<I: IntoIterator<Item = bool>>(bits: I) -> Self {
+        BitReader {
+            bits: bits.into_iter().collect(),
+            posn: 0,
+        }
+    }
+
+    /// Make a reader over the bits of a string of hex
+    /// digits, as given by `hex_bits()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string contains a non-hex character.
+    pub fn from_hex(s: &str) -> Self {
+        BitReader::new(hex_bits(s))
+    }
+
+    /// Read the next bit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no bits remaining.
+    pub fn read_bit(&mut self) -> bool {
+        assert!(self.posn < self.bits.len(), "read_bit: out of bits");
+        self.posn += 1;
+        self.bits[self.posn - 1]
+    }
+
+    /// Read the next `n` bits as an unsigned number, most
+    /// significant bit first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is more than 64 or if there are fewer
+    /// than `n` bits remaining.
+    pub fn read_bits(&mut self, n: usize) -> u64 {
+        assert!(n <= 64, "read_bits: {} bits is too many", n);
+        assert!(n <= self.remaining(), "read_bits: out of bits");
+        let field = &self.bits[self.posn..self.posn + n];
+        self.posn += n;
+        field.iter().fold(0, |acc, &b| (acc << 1) | b as u64)
+    }
+
+    /// Number of bits read so far.
+    pub fn position(&self) -> usize {
+        self.posn
+    }
+
+    /// Number of bits remaining to be read.
+    pub fn remaining(&self) -> usize {
+        self.bits.len() - self.posn
+    }
+
+    /// Skip to the next position that is a multiple of `n`
+    /// bits from the start of the stream (or to the end of
+    /// the stream, if that is sooner). Returns the number
+    /// of bits skipped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn align(&mut self, n: usize) -> usize {
+        assert!(n > 0, "align: alignment must be positive");
+        let target = self.posn.next_multiple_of(n).min(self.bits.len());
+        let skipped = target - self.posn;
+        self.posn = target;
+        skipped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// Parse a 2021 day 16 packet, returning its version
+    /// sum.
+    fn version_sum(reader: &mut BitReader) -> u64 {
+        let mut sum = reader.read_bits(3);
+        let type_id = reader.read_bits(3);
+        if type_id == 4 {
+            while reader.read_bit() {
+                reader.read_bits(4);
+            }
+            reader.read_bits(4);
+        } else if reader.read_bit() {
+            let count = reader.read_bits(11);
+            for _ in 0..count {
+                sum += version_sum(reader);
+            }
+        } else {
+            let len = reader.read_bits(15) as usize;
+            let end = reader.position() + len;
+            while reader.position() < end {
+                sum += version_sum(reader);
+            }
+        }
+        sum
+    }
+
+    #[test]
+    fn bits_packets() {
+        let examples = [
+            ("8A004A801A8002F478", 16),
+            ("620080001611562C8802118E34", 12),
+            ("C0015000016115A2E0802F182340", 23),
+            ("A0016C880162017C3686B18A3D4780", 31),
+        ];
+        for (hex, expected) in examples {
+            let mut reader = BitReader::from_hex(hex);
+            assert_eq!(reader.remaining(), 4 * hex.len());
+            assert_eq!(version_sum(&mut reader), expected);
+            reader.align(4);
+            assert_eq!(reader.position() % 4, 0);
+        }
+    }
+
+    #[test]
+    fn alignment() {
+        let mut reader = BitReader::from_hex("f0f");
+        assert_eq!(reader.read_bits(0), 0);
+        assert_eq!(reader.read_bits(3), 0b111);
+        assert_eq!(reader.align(4), 1);
+        assert_eq!(reader.align(4), 0);
+        assert_eq!(reader.read_bits(5), 0b00001);
+        assert_eq!(reader.align(8), 3);
+        assert_eq!(reader.remaining(), 0);
+        let mut reader = BitReader::new([true, false, true]);
+        assert_eq!(reader.align(8), 0);
+        assert!(reader.read_bit());
+        assert_eq!(reader.align(8), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bits")]
+    fn overrun() {
+        let mut reader = BitReader::from_hex("7");
+        reader.read_bits(5);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be positive")]
+    fn align_zero() {
+        let mut reader = BitReader::from_hex("7");
+        reader.align(0);
+    }
+}
@@ -0,0 +1,142 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Conversion between byte strings and hex strings.
+//!
+//! # Examples
+//!
+//! ```
+//! # use hexstring::*;
+//! let bytes = decode_hex("00fF7a").unwrap();
+//! assert_eq!(bytes, vec![0x00, 0xff, 0x7a]);
+//! assert_eq!(encode_hex(&bytes), "00ff7a");
+//! assert_eq!(encode_hex_upper(&bytes), "00FF7A");
+//! assert_eq!(decode_hex("0g"), Err(HexError::BadDigit(1, 'g')));
+//! assert_eq!(decode_hex_relaxed("00 ff\n7a").unwrap(), bytes);
+//! ```
+
+use std::fmt;
+
+use crate::hex_string;
+
+/// Error in decoding a hex string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HexError {
+    /// The string had an odd number of hex digits, given
+    /// here.
+    OddLength(usize),
+    /// The character at the given byte offset in the string
+    /// was not a hex digit.
+    BadDigit(usize, char),
+}
+
+impl fmt::Display for HexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HexError::OddLength(n) => {
+                write!(f, "odd number of hex digits: {}", n)
+            }
+            HexError::BadDigit(posn, ch) => {
+                write!(f, "bad hex digit {:?} at offset {}", ch, posn)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HexError {}
+
+/// Decode hex digits given as `(offset, char)` pairs.
+fn decode_digits<I>(digits: I) -> Result<Vec<u8>, HexError>
+where
+    I: Iterator<Item = (usize, char)>,
+{
+    let mut bytes = Vec::with_capacity(digits.size_hint().0 / 2);
+    let mut high = None;
+    let mut ndigits = 0;
+    for (posn, ch) in digits {
+        let d = ch.to_digit(16).ok_or(HexError::BadDigit(posn, ch))? as u8;
+        ndigits += 1;
+        match high.take() {
+            None => high = Some(d),
+            Some(h) => bytes.push((h << 4) | d),
+        }
+    }
+    if high.is_some() {
+        return Err(HexError::OddLength(ndigits));
+    }
+    Ok(bytes)
+}
+
+/// Decode a string of hex digits, in either case, to bytes.
+pub fn decode_hex(s: &str) -> Result<Vec<u8>, HexError> {
+    decode_digits(s.char_indices())
+}
+
+/// Decode a string of hex digits as with `decode_hex()`,
+/// ignoring any whitespace.
+pub fn decode_hex_relaxed(s: &str) -> Result<Vec<u8>, HexError> {
+    decode_digits(s.char_indices().filter(|(_, ch)| !ch.is_whitespace()))
+}
+
+/// Encode bytes as a string of lowercase hex digits. This
+/// is the same as `hex_string()`.
+pub fn encode_hex(bytes: &[u8]) -> String {
+    hex_string(bytes)
+}
+
+/// Encode bytes as a string of uppercase hex digits.
+pub fn encode_hex_upper(bytes: &[u8]) -> String {
+    hex_string(bytes).to_ascii_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::testutil::Rng;
+
+    #[test]
+    fn round_trip() {
+        let mut rng = Rng(0x0123_4567_89ab_cdef);
+        for _ in 0..200 {
+            let len = rng.below(40);
+            let bytes: Vec<u8> =
+                (0..len).map(|_| rng.below(256) as u8).collect();
+            let lower = encode_hex(&bytes);
+            let upper = encode_hex_upper(&bytes);
+            assert_eq!(lower.len(), 2 * len);
+            assert_eq!(decode_hex(&lower).unwrap(), bytes);
+            assert_eq!(decode_hex(&upper).unwrap(), bytes);
+            let spaced: String = lower
+                .chars()
+                .enumerate()
+                .flat_map(|(i, c)| {
+                    let sep = if i % 7 == 6 { Some('\n') } else { None };
+                    std::iter::once(c).chain(sep)
+                })
+                .collect();
+            assert_eq!(decode_hex_relaxed(&spaced).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(decode_hex("abc"), Err(HexError::OddLength(3)));
+        assert_eq!(decode_hex("12g4"), Err(HexError::BadDigit(2, 'g')));
+        assert_eq!(decode_hex("12 4"), Err(HexError::BadDigit(2, ' ')));
+        assert_eq!(decode_hex_relaxed(" 1 2 3"), Err(HexError::OddLength(3)));
+        assert_eq!(
+            decode_hex_relaxed("é 0g"),
+            Err(HexError::BadDigit(0, 'é')),
+        );
+        assert_eq!(
+            decode_hex_relaxed("00 0g"),
+            Err(HexError::BadDigit(4, 'g')),
+        );
+        let msg = HexError::BadDigit(4, 'g').to_string();
+        assert_eq!(msg, "bad hex digit 'g' at offset 4");
+        assert_eq!(decode_hex(""), Ok(vec![]));
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Grouped hex dumps for debugging, and parsing them back.
+//!
+//! # Examples
+//!
+//! ```
+//! # use hexstring::*;
+//! let bytes: Vec<u8> = (0..10).collect();
+//! let dump = format_hex_grouped(&bytes, 2, 8, true);
+//! assert_eq!(dump, "00000000: 0001 0203 0405 0607\n00000008: 0809\n");
+//! assert_eq!(parse_hex_dump(&dump).unwrap(), bytes);
+//! ```
+
+use crate::{decode_hex_relaxed, hex_string, HexError};
+
+/// Format bytes as a hex dump with `per_line` bytes per
+/// line, in groups of `group` bytes separated by spaces.
+/// Groups start afresh on each line. If `offsets` is true,
+/// each line starts with the offset of its first byte, in
+/// hex, followed by a colon. Every line, including the
+/// last, ends with a newline.
+///
+/// # Panics
+///
+/// Panics if `group` or `per_line` is 0.
+pub fn format_hex_grouped(
+    bytes: &[u8],
+    group: usize,
+    per_line: usize,
+    offsets: bool,
+) -> String {
+    assert!(group > 0 && per_line > 0, "format_hex_grouped: zero width");
+    let mut result = String::new();
+    for (i, line) in bytes.chunks(per_line).enumerate() {
+        if offsets {
+            result.push_str(&format!("{:08x}: ", i * per_line));
+        }
+        let groups: Vec<String> = line.chunks(group).map(hex_string).collect();
+        result.push_str(&groups.join(" "));
+        result.push('\n');
+    }
+    result
+}
+
+/// Parse a hex dump as produced by `format_hex_grouped()`.
+/// On each line, anything up to and including a colon is
+/// taken to be an offset and ignored, as is whitespace; the
+/// rest must be hex digits. The position in a `BadDigit`
+/// error is the byte offset in the whole dump.
+pub fn parse_hex_dump(s: &str) -> Result<Vec<u8>, HexError> {
+    let mut bytes = Vec::new();
+    let mut start = 0;
+    for line in s.split_inclusive('\n') {
+        let skip = line.find(':').map(|i| i + 1).unwrap_or(0);
+        let decoded =
+            decode_hex_relaxed(&line[skip..]).map_err(|e| match e {
+                HexError::BadDigit(posn, ch) => {
+                    HexError::BadDigit(start + skip + posn, ch)
+                }
+                e => e,
+            })?;
+        bytes.extend(decoded);
+        start += line.len();
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::testutil::Rng;
+
+    #[test]
+    fn round_trip() {
+        let mut rng = Rng(0x0bad_cafe_dead_f00d);
+        let configs = [(1, 1), (1, 16), (2, 16), (4, 16), (4, 10), (8, 32)];
+        for _ in 0..30 {
+            let bytes: Vec<u8> =
+                (0..rng.below(100)).map(|_| rng.below(256) as u8).collect();
+            for (group, per_line) in configs {
+                for offsets in [false, true] {
+                    let dump =
+                        format_hex_grouped(&bytes, group, per_line, offsets);
+                    let nlines = bytes.len().div_ceil(per_line);
+                    assert_eq!(dump.lines().count(), nlines);
+                    assert_eq!(parse_hex_dump(&dump).unwrap(), bytes);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn layout() {
+        let bytes: Vec<u8> = (0xf0..=0xff).chain(0..3).collect();
+        let dump = format_hex_grouped(&bytes, 4, 16, true);
+        let expected = "\
+            00000000: f0f1f2f3 f4f5f6f7 f8f9fafb fcfdfeff\n\
+            00000010: 000102\n";
+        assert_eq!(dump, expected);
+        assert_eq!(format_hex_grouped(&bytes[..3], 2, 4, false), "f0f1 f2\n");
+        assert_eq!(format_hex_grouped(&[], 2, 4, true), "");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let dump = "00000000: 0001 0203\n00000004: 04x5\n";
+        assert_eq!(parse_hex_dump(dump), Err(HexError::BadDigit(32, 'x')));
+        assert_eq!(&dump[32..33], "x");
+        let dump = "0001 020\n";
+        assert_eq!(parse_hex_dump(dump), Err(HexError::OddLength(7)));
+        assert_eq!(parse_hex_dump("  00 01\n\n 0a"), Ok(vec![0, 1, 10]));
+    }
+}
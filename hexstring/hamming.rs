@@ -0,0 +1,115 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Bit counts and Hamming distances over hex strings, byte
+//! strings and character strings.
+//!
+//! # Examples
+//!
+//! ```
+//! # use hexstring::*;
+//! assert_eq!(count_ones_hex("a0c1"), 5);
+//! assert_eq!(count_ones_bytes(&[0xa0, 0xc1]), 5);
+//! assert_eq!(hamming_bytes(&[0xff], &[0x0f]), Some(4));
+//! assert_eq!(hamming_str("fghij", "fguij"), Some(1));
+//! assert_eq!(common_chars("fghij", "fguij"), "fgij");
+//! ```
+
+/// Number of one bits in a string of hex digits.
+///
+/// # Panics
+///
+/// Panics if the string contains a non-hex character.
+pub fn count_ones_hex(s: &str) -> u32 {
+    s.chars()
+        .map(|ch| {
+            ch.to_digit(16)
+                .unwrap_or_else(|| panic!("count_ones_hex: bad digit {:?}", ch))
+                .count_ones()
+        })
+        .sum()
+}
+
+/// Number of one bits in a byte string.
+pub fn count_ones_bytes(bytes: &[u8]) -> u32 {
+    bytes.iter().map(|b| b.count_ones()).sum()
+}
+
+/// Number of bits that differ between two byte strings, or
+/// `None` if their lengths differ.
+pub fn hamming_bytes(a: &[u8], b: &[u8]) -> Option<u32> {
+    if a.len() != b.len() {
+        return None;
+    }
+    Some(a.iter().zip(b).map(|(x, y)| (x ^ y).count_ones()).sum())
+}
+
+/// Number of positions at which two strings have different
+/// characters, or `None` if they have different numbers of
+/// characters.
+pub fn hamming_str(a: &str, b: &str) -> Option<u32> {
+    let mut diffs = 0;
+    let mut bs = b.chars();
+    for ca in a.chars() {
+        if bs.next()? != ca {
+            diffs += 1;
+        }
+    }
+    if bs.next().is_some() {
+        return None;
+    }
+    Some(diffs)
+}
+
+/// The characters at which two strings agree position by
+/// position, in order. Characters past the end of the
+/// shorter string are dropped.
+pub fn common_chars(a: &str, b: &str) -> String {
+    a.chars()
+        .zip(b.chars())
+        .filter(|(ca, cb)| ca == cb)
+        .map(|(ca, _)| ca)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn box_ids() {
+        // 2018 day 2 part 2 example.
+        let ids = [
+            "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
+        ];
+        let mut found = Vec::new();
+        for (i, a) in ids.iter().enumerate() {
+            for b in &ids[i + 1..] {
+                if hamming_str(a, b) == Some(1) {
+                    found.push(common_chars(a, b));
+                }
+            }
+        }
+        assert_eq!(found, vec!["fgij"]);
+        assert_eq!(hamming_str("abcde", "axcye"), Some(2));
+        assert_eq!(hamming_str("abc", "abcd"), None);
+        assert_eq!(hamming_str("abcd", "abc"), None);
+        assert_eq!(hamming_str("", ""), Some(0));
+    }
+
+    #[test]
+    fn bit_counts() {
+        assert_eq!(count_ones_hex(""), 0);
+        assert_eq!(count_ones_hex("ffFF"), 16);
+        let hex = "a0c20170";
+        let bytes = crate::decode_hex(hex).unwrap();
+        assert_eq!(count_ones_hex(hex), count_ones_bytes(&bytes));
+        assert_eq!(hamming_bytes(&bytes, &bytes), Some(0));
+        let ones = count_ones_bytes(&bytes);
+        assert_eq!(hamming_bytes(&bytes, &[0; 4]), Some(ones));
+        assert_eq!(hamming_bytes(&bytes, &[0; 3]), None);
+    }
+}
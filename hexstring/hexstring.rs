@@ -8,15 +8,45 @@
 //! These are not strictly necessary, but provide some
 //! convenience.
 
+mod base64;
+pub use base64::*;
+
+mod binary;
+pub use binary::*;
+
+mod bits;
+pub use bits::*;
+
+mod codec;
+pub use codec::*;
+
+mod dump;
+pub use dump::*;
+
+mod hamming;
+pub use hamming::*;
+
+mod md5;
+pub use md5::*;
+
+mod nibbles;
+pub use nibbles::*;
+
+mod stream;
+pub use stream::*;
+
+#[cfg(test)]
+mod testutil;
+
 /// Given an input between 0 and 15, return the
 /// corresponding hex digit. This may be faster than
 /// `std::char::from_digit()`.
 #[inline]
 pub fn hex_digit(n: u8) -> char {
     let d = if n <= 9 {
-        n as u8 + b'0'
+        n + b'0'
     } else {
-        n as u8 - 10 + b'a'
+        n - 10 + b'a'
     };
     d as char
 }
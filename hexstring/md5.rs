@@ -0,0 +1,217 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! [MD5][1] hashing for hash-mining puzzles.
+//!
+//! MD5 is implemented here directly from RFC 1321 to avoid a
+//! dependency. It is not suitable for anything
+//! security-related, which is fine for Advent of Code.
+//!
+//! [1]: https://www.rfc-editor.org/rfc/rfc1321
+//!
+//! # Examples
+//!
+//! ```
+//! # use hexstring::*;
+//! assert_eq!(md5_hex(b""), "d41d8cd98f00b204e9800998ecf8427e");
+//! let digest = md5_digest(b"abcdef609043");
+//! assert_eq!(leading_zero_nibbles(&digest), 5);
+//! ```
+
+/// Per-round shift amounts.
+const SHIFTS: [[u32; 4]; 4] = [
+    [7, 12, 17, 22],
+    [5, 9, 14, 20],
+    [4, 11, 16, 23],
+    [6, 10, 15, 21],
+];
+
+/// Per-step additive constants: the integer part of
+/// `2^32 * abs(sin(i + 1))`.
+const K: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a,
+    0xa8304613, 0xfd469501, 0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be,
+    0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821, 0xf61e2562, 0xc040b340,
+    0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8,
+    0x676f02d9, 0x8d2a4c8a, 0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c,
+    0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70, 0x289b7ec6, 0xeaa127fa,
+    0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92,
+    0xffeff47d, 0x85845dd1, 0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1,
+    0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+/// Mix one 64-byte block into the hash state.
+fn md5_block(state: &mut [u32; 4], block: &[u8]) {
+    let mut m = [0u32; 16];
+    for (w, chunk) in m.iter_mut().zip(block.chunks_exact(4)) {
+        *w = u32::from_le_bytes(chunk.try_into().unwrap());
+    }
+    let [mut a, mut b, mut c, mut d] = *state;
+    for i in 0..64 {
+        let (f, g) = match i / 16 {
+            0 => ((b & c) | (!b & d), i),
+            1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+            2 => (b ^ c ^ d, (3 * i + 5) % 16),
+            _ => (c ^ (b | !d), (7 * i) % 16),
+        };
+        let f = f
+            .wrapping_add(a)
+            .wrapping_add(K[i])
+            .wrapping_add(m[g]);
+        a = d;
+        d = c;
+        c = b;
+        b = b.wrapping_add(f.rotate_left(SHIFTS[i / 16][i % 4]));
+    }
+    for (s, x) in state.iter_mut().zip([a, b, c, d]) {
+        *s = s.wrapping_add(x);
+    }
+}
+
+/// The MD5 digest of the given bytes.
+pub fn md5_digest(input: &[u8]) -> [u8; 16] {
+    let mut state: [u32; 4] =
+        [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+    let mut blocks = input.chunks_exact(64);
+    for block in blocks.by_ref() {
+        md5_block(&mut state, block);
+    }
+    // Pad with a 1 bit, zeros, and the bit length, filling
+    // one or two final blocks.
+    let rest = blocks.remainder();
+    let mut tail = [0u8; 128];
+    tail[..rest.len()].copy_from_slice(rest);
+    tail[rest.len()] = 0x80;
+    let ntail = if rest.len() < 56 { 64 } else { 128 };
+    let nbits = (input.len() as u64).wrapping_mul(8);
+    tail[ntail - 8..ntail].copy_from_slice(&nbits.to_le_bytes());
+    for block in tail[..ntail].chunks_exact(64) {
+        md5_block(&mut state, block);
+    }
+    let mut digest = [0u8; 16];
+    for (d, s) in digest.chunks_exact_mut(4).zip(state) {
+        d.copy_from_slice(&s.to_le_bytes());
+    }
+    digest
+}
+
+/// The MD5 digest of the given bytes, as a lowercase hex
+/// string.
+pub fn md5_hex(input: &[u8]) -> String {
+    crate::hex_string(&md5_digest(input))
+}
+
+/// Number of leading zero hex digits in a digest.
+pub fn leading_zero_nibbles(digest: &[u8]) -> u32 {
+    let mut count = 0;
+    for &b in digest {
+        if b == 0 {
+            count += 2;
+            continue;
+        }
+        if b >> 4 == 0 {
+            count += 1;
+        }
+        break;
+    }
+    count
+}
+
+/// Find the least number `n >= start` such that the MD5
+/// digest of `key` followed by the decimal digits of `n`
+/// starts with at least `zero_nibbles` zero hex digits, as
+/// in 2015 day 4. The input is built in a reused buffer and
+/// no strings are formatted.
+///
+/// # Examples
+///
+/// ```
+/// # use hexstring::*;
+/// assert_eq!(find_suffix_with_prefix("abcdef", 5, 0), 609043);
+/// ```
+pub fn find_suffix_with_prefix(
+    key: &str,
+    zero_nibbles: u32,
+    start: u64,
+) -> u64 {
+    let mut buf: Vec<u8> = key.as_bytes().to_vec();
+    let mut digits = [0u8; 20];
+    for n in start.. {
+        buf.truncate(key.len());
+        let mut i = digits.len();
+        let mut m = n;
+        loop {
+            i -= 1;
+            digits[i] = b'0' + (m % 10) as u8;
+            m /= 10;
+            if m == 0 {
+                break;
+            }
+        }
+        buf.extend_from_slice(&digits[i..]);
+        if leading_zero_nibbles(&md5_digest(&buf)) >= zero_nibbles {
+            return n;
+        }
+    }
+    unreachable!("find_suffix_with_prefix: counter overflow")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn rfc_test_suite() {
+        let cases: [(&str, &str); 7] = [
+            ("", "d41d8cd98f00b204e9800998ecf8427e"),
+            ("a", "0cc175b9c0f1b6a831c399e269772661"),
+            ("abc", "900150983cd24fb0d6963f7d28e17f72"),
+            ("message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+            (
+                "abcdefghijklmnopqrstuvwxyz",
+                "c3fcd3d76192e4007dfb496cca67e13b",
+            ),
+            (
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZ\
+                 abcdefghijklmnopqrstuvwxyz0123456789",
+                "d174ab98d277d9f5a5611c2c9f419d9f",
+            ),
+            (
+                "1234567890123456789012345678901234567890\
+                 1234567890123456789012345678901234567890",
+                "57edf4a22be3c955ac49da2e2107b67a",
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(md5_hex(input.as_bytes()), expected);
+        }
+    }
+
+    #[test]
+    fn padding_boundaries() {
+        // Lengths around the one- and two-block padding cases.
+        let expected = [
+            (55, "ef1772b6dff9a122358552954ad0df65"),
+            (56, "3b0c8ac703f828b04c6c197006d17218"),
+            (63, "b06521f39153d618550606be297466d5"),
+            (64, "014842d480b571495a4a0363793f7367"),
+        ];
+        for (len, hex) in expected {
+            assert_eq!(md5_hex(&vec![b'a'; len]), hex);
+        }
+    }
+
+    #[test]
+    fn mining() {
+        assert_eq!(find_suffix_with_prefix("abcdef", 5, 0), 609043);
+        assert_eq!(find_suffix_with_prefix("pqrstuv", 5, 1048000), 1048970);
+        assert_eq!(leading_zero_nibbles(&[0, 0, 0x0f, 0xff]), 5);
+        assert_eq!(leading_zero_nibbles(&[0x10]), 0);
+        assert_eq!(leading_zero_nibbles(&[0; 16]), 32);
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Four-bit values from hex strings and raw digests.
+//!
+//! Nibbles are taken big-endian within each byte: the high
+//! nibble comes first, matching the hex string form.
+//!
+//! # Examples
+//!
+//! ```
+//! # use hexstring::*;
+//! let digest = md5_digest(b"hijkl");
+//! let hex = md5_hex(b"hijkl");
+//! assert_eq!(&hex[..4], "ced9");
+//! assert_eq!(first_n_nibbles(&digest, 4), 0xced9);
+//! assert_eq!(nibble_at(&digest, 1), 0xe);
+//! assert!(nibbles(&hex).take(4).eq([0xc, 0xe, 0xd, 0x9]));
+//! ```
+
+/// Iterate over the values of the digits of a hex string.
+///
+/// # Panics
+///
+/// Panics on reaching a character that is not a hex digit.
+pub fn nibbles(s: &str) -> impl Iterator<Item = u8> + '_ {
+    s.chars().map(|ch| {
+        ch.to_digit(16)
+            .unwrap_or_else(|| panic!("nibbles: bad hex digit {:?}", ch))
+            as u8
+    })
+}
+
+/// The `i`-th nibble of a byte string.
+///
+/// # Panics
+///
+/// Panics if `i` is not less than twice the length of the
+/// byte string.
+pub fn nibble_at(digest: &[u8], i: usize) -> u8 {
+    let b = digest[i / 2];
+    if i.is_multiple_of(2) {
+        b >> 4
+    } else {
+        b & 0xf
+    }
+}
+
+/// The first `n` nibbles of a byte string as a number, with
+/// the first nibble most significant.
+///
+/// # Panics
+///
+/// Panics if `n` is greater than 16 or than twice the
+/// length of the byte string.
+pub fn first_n_nibbles(digest: &[u8], n: usize) -> u64 {
+    assert!(n <= 16, "first_n_nibbles: {} nibbles is too many", n);
+    (0..n).fold(0, |acc, i| (acc << 4) | nibble_at(digest, i) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::*;
+
+    use std::collections::VecDeque;
+
+    #[test]
+    fn nibble_order() {
+        let bytes = [0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0x5a];
+        let hex = encode_hex(&bytes);
+        let from_hex: Vec<u8> = nibbles(&hex).collect();
+        let from_bytes: Vec<u8> =
+            (0..2 * bytes.len()).map(|i| nibble_at(&bytes, i)).collect();
+        assert_eq!(from_hex, from_bytes);
+        assert_eq!(from_hex[..4], [0, 1, 2, 3]);
+        assert_eq!(first_n_nibbles(&bytes, 16), 0x0123_4567_89ab_cdef);
+        assert_eq!(first_n_nibbles(&bytes, 3), 0x012);
+        assert_eq!(first_n_nibbles(&bytes, 0), 0);
+    }
+
+    /// Shortest path through the 2016 day 17 vault.
+    fn vault_path(passcode: &str) -> Option<String> {
+        let moves = [('U', 0, -1), ('D', 0, 1), ('L', -1, 0), ('R', 1, 0)];
+        let mut queue = VecDeque::from([((0i32, 0i32), String::new())]);
+        let mut buf = Vec::new();
+        while let Some(((x, y), path)) = queue.pop_front() {
+            if (x, y) == (3, 3) {
+                return Some(path);
+            }
+            buf.clear();
+            buf.extend_from_slice(passcode.as_bytes());
+            buf.extend_from_slice(path.as_bytes());
+            let digest = md5_digest(&buf);
+            for (i, &(dirn, dx, dy)) in moves.iter().enumerate() {
+                let (nx, ny) = (x + dx, y + dy);
+                let open = nibble_at(&digest, i) > 0xa;
+                if open && (0..4).contains(&nx) && (0..4).contains(&ny) {
+                    let mut next = path.clone();
+                    next.push(dirn);
+                    queue.push_back(((nx, ny), next));
+                }
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn vault_doors() {
+        assert_eq!(vault_path("hijkl"), None);
+        assert_eq!(vault_path("ihgpwlah").unwrap(), "DDRRRD");
+        assert_eq!(vault_path("kglvqrro").unwrap(), "DDUDRLRRUDRD");
+    }
+}
@@ -0,0 +1,211 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Streaming hex encoding and decoding over `std::io`.
+//!
+//! # Examples
+//!
+//! ```
+//! # use hexstring::*;
+//! use std::io::{Read, Write};
+//!
+//! let mut writer = HexWriter::new(Vec::new());
+//! writer.write_all(b"hi!").unwrap();
+//! let hex = writer.into_inner();
+//! assert_eq!(hex, b"686921");
+//!
+//! let mut reader = HexReader::new(&hex[..]);
+//! let mut bytes = Vec::new();
+//! reader.read_to_end(&mut bytes).unwrap();
+//! assert_eq!(bytes, b"hi!");
+//! ```
+
+use std::io::{self, Read, Write};
+
+use crate::{hex_digit, HexError};
+
+/// Writer that hex-encodes the bytes written through it,
+/// in lowercase, and passes the hex digits on to an inner
+/// writer.
+#[derive(Debug)]
+pub struct HexWriter<W: Write> {
+    inner: W,
+}
+
+impl<W: Write> HexWriter<W> {
+    /// Make a hex writer over the given writer.
+    pub fn new(inner: W) -> Self {
+        HexWriter { inner }
+    }
+
+    /// Return the inner writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for HexWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut hex = Vec::with_capacity(2 * buf.len());
+        for b in buf {
+            hex.push(hex_digit(b >> 4) as u8);
+            hex.push(hex_digit(b & 0xf) as u8);
+        }
+        self.inner.write_all(&hex)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Reader that decodes hex digits, in either case, read
+/// from an inner reader. Digit pairs may be split across
+/// reads of the inner reader.
+///
+/// A bad digit, or an odd number of digits at end of input,
+/// gives an `io::Error` of kind `InvalidData` wrapping a
+/// `HexError`, with the byte offset of a bad digit counted
+/// from the start of the stream.
+#[derive(Debug)]
+pub struct HexReader<R: Read> {
+    inner: R,
+    /// Offset in the stream of the next digit.
+    posn: usize,
+    /// High nibble of a byte whose low nibble is not yet
+    /// read.
+    high: Option<u8>,
+    /// Buffer for hex digits read.
+    buf: Vec<u8>,
+}
+
+impl<R: Read> HexReader<R> {
+    /// Make a hex reader over the given reader.
+    pub fn new(inner: R) -> Self {
+        HexReader {
+            inner,
+            posn: 0,
+            high: None,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Return the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for HexReader<R> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        if out.is_empty() {
+            return Ok(0);
+        }
+        let invalid =
+            |e: HexError| io::Error::new(io::ErrorKind::InvalidData, e);
+        let mut nout = 0;
+        // Keep reading until at least one byte is decoded,
+        // since returning 0 would signal end of input.
+        while nout == 0 {
+            let want = 2 * out.len() - self.high.is_some() as usize;
+            self.buf.resize(want, 0);
+            let nread = self.inner.read(&mut self.buf)?;
+            if nread == 0 {
+                if self.high.is_some() {
+                    return Err(invalid(HexError::OddLength(self.posn)));
+                }
+                return Ok(0);
+            }
+            for &c in &self.buf[..nread] {
+                let ch = c as char;
+                let d = ch
+                    .to_digit(16)
+                    .ok_or_else(|| invalid(HexError::BadDigit(self.posn, ch)))?;
+                self.posn += 1;
+                match self.high.take() {
+                    None => self.high = Some(d as u8),
+                    Some(h) => {
+                        out[nout] = (h << 4) | d as u8;
+                        nout += 1;
+                    }
+                }
+            }
+        }
+        Ok(nout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::testutil::Rng;
+
+    /// Reader handing out at most one byte per read.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() || out.is_empty() {
+                return Ok(0);
+            }
+            out[0] = self.0[0];
+            self.0 = &self.0[1..];
+            Ok(1)
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut rng = Rng(0xfeed_face_cafe_beef);
+        for _ in 0..50 {
+            let bytes: Vec<u8> =
+                (0..rng.below(100)).map(|_| rng.below(256) as u8).collect();
+            let mut writer = HexWriter::new(Vec::new());
+            for b in &bytes {
+                writer.write_all(&[*b]).unwrap();
+            }
+            writer.flush().unwrap();
+            let hex = writer.into_inner();
+            assert_eq!(hex, crate::encode_hex(&bytes).into_bytes());
+
+            let mut reader = HexReader::new(Trickle(&hex));
+            let mut decoded = Vec::new();
+            loop {
+                let mut chunk = vec![0; 1 + rng.below(3)];
+                let n = reader.read(&mut chunk).unwrap();
+                if n == 0 {
+                    break;
+                }
+                decoded.extend_from_slice(&chunk[..n]);
+            }
+            assert_eq!(decoded, bytes);
+
+            let upper = crate::encode_hex_upper(&bytes);
+            let mut decoded = Vec::new();
+            HexReader::new(upper.as_bytes())
+                .read_to_end(&mut decoded)
+                .unwrap();
+            assert_eq!(decoded, bytes);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let decode = |hex: &[u8]| {
+            let mut bytes = Vec::new();
+            let mut reader = HexReader::new(Trickle(hex));
+            reader.read_to_end(&mut bytes).map(|_| bytes)
+        };
+        let e = decode(b"0011z2").unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        let inner = e.into_inner().unwrap().downcast::<HexError>().unwrap();
+        assert_eq!(*inner, HexError::BadDigit(4, 'z'));
+        let e = decode(b"00112").unwrap_err();
+        assert_eq!(e.to_string(), "odd number of hex digits: 5");
+        assert_eq!(decode(b"").unwrap(), vec![]);
+    }
+}
@@ -0,0 +1,20 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Helpers shared by the tests in this crate.
+
+/// Small deterministic pseudo-random number generator
+/// (xorshift), to avoid a dependency.
+pub(crate) struct Rng(pub(crate) u64);
+
+impl Rng {
+    /// A pseudo-random number less than `n`.
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+}
@@ -0,0 +1,402 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Rendering maps to the terminal with ANSI colors.
+//!
+//! # Examples
+//!
+//! ```
+//! # use maprender::*;
+//! # use std::collections::HashSet;
+//! let rocks: HashSet<(i64, i64)> = [(0, 0), (2, 0)].into_iter().collect();
+//! let plain = render_ansi(&rocks, AnsiStyle::new('#'));
+//! assert_eq!(plain, "# #\n");
+//! let red = render_ansi(&rocks, AnsiStyle::new('#').fg(Rgb::RED));
+//! assert_eq!(red, "\x1b[38;2;255;0;0m#\x1b[0m \x1b[38;2;255;0;0m#\x1b[0m\n");
+//! ```
+
+use std::io::{self, IsTerminal, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::{Paint, RenderSource, Rgb};
+
+/// How a cell is drawn in the terminal: a glyph with
+/// optional colors and weight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AnsiStyle {
+    /// Character drawn for the cell.
+    pub glyph: char,
+    /// Foreground color, if not the terminal default.
+    pub fg: Option<Rgb>,
+    /// Background color, if not the terminal default.
+    pub bg: Option<Rgb>,
+    /// Draw the glyph in bold.
+    pub bold: bool,
+}
+
+impl AnsiStyle {
+    /// Unstyled cell showing `glyph`.
+    pub fn new(glyph: char) -> Self {
+        AnsiStyle {
+            glyph,
+            fg: None,
+            bg: None,
+            bold: false,
+        }
+    }
+
+    /// This style with the given foreground color.
+    pub fn fg(self, color: Rgb) -> Self {
+        AnsiStyle {
+            fg: Some(color),
+            ..self
+        }
+    }
+
+    /// This style with the given background color.
+    pub fn bg(self, color: Rgb) -> Self {
+        AnsiStyle {
+            bg: Some(color),
+            ..self
+        }
+    }
+
+    /// This style in bold.
+    pub fn bold(self) -> Self {
+        AnsiStyle { bold: true, ..self }
+    }
+
+    /// True if the style needs no escape sequence.
+    fn is_plain(&self) -> bool {
+        self.fg.is_none() && self.bg.is_none() && !self.bold
+    }
+
+    /// True if the two styles have the same escape
+    /// sequence, regardless of glyph.
+    fn same_sgr(&self, other: &AnsiStyle) -> bool {
+        (self.fg, self.bg, self.bold) == (other.fg, other.bg, other.bold)
+    }
+
+    /// Append the SGR escape sequence selecting this style.
+    fn push_sgr(&self, out: &mut String) {
+        let mut params = Vec::new();
+        if self.bold {
+            params.push("1".to_string());
+        }
+        if let Some(Rgb(r, g, b)) = self.fg {
+            params.push(format!("38;2;{};{};{}", r, g, b));
+        }
+        if let Some(Rgb(r, g, b)) = self.bg {
+            params.push(format!("48;2;{};{};{}", r, g, b));
+        }
+        out.push_str(&format!("\x1b[{}m", params.join(";")));
+    }
+}
+
+/// Escape sequence resetting all styles.
+const RESET: &str = "\x1b[0m";
+
+/// Glyph marking a row clipped at the right edge.
+pub const CLIP_MARK: char = '>';
+
+/// Render a map as lines of text, with cells drawn as given
+/// by `palette` and empty cells as spaces. Escape sequences
+/// are emitted only where the style changes, and styles are
+/// reset at the end of each line. Each line including the
+/// last is terminated by a newline; an empty map is the
+/// empty string.
+pub fn render_ansi<S, P>(view: &S, palette: P) -> String
+where
+    S: RenderSource + ?Sized,
+    P: Paint<S::Cell, AnsiStyle>,
+{
+    render_text(view, palette, None, true)
+}
+
+/// Render a map as for `render_ansi()`, but with lines
+/// clipped to at most `width` columns. A clipped line ends
+/// with `CLIP_MARK` in place of its last visible cell.
+pub fn render_ansi_clipped<S, P>(view: &S, palette: P, width: usize) -> String
+where
+    S: RenderSource + ?Sized,
+    P: Paint<S::Cell, AnsiStyle>,
+{
+    render_text(view, palette, Some(width), true)
+}
+
+/// Render a map as text, clipped to `width` columns if
+/// given, with escape sequences only if `color` is true.
+fn render_text<S, P>(
+    view: &S,
+    palette: P,
+    width: Option<usize>,
+    color: bool,
+) -> String
+where
+    S: RenderSource + ?Sized,
+    P: Paint<S::Cell, AnsiStyle>,
+{
+    let ((r0, c0), (r1, c1)) = match view.bounds() {
+        Some(bounds) => bounds,
+        None => return String::new(),
+    };
+    let ncols = (c1 - c0 + 1) as usize;
+    let (shown, clipped) = match width {
+        Some(w) if w < ncols => (w.saturating_sub(1), w > 0),
+        _ => (ncols, false),
+    };
+    let plain = AnsiStyle::new(' ');
+    let mut out = String::new();
+    for row in r0..=r1 {
+        let mut current = plain;
+        for col in c0..c0 + shown as i64 {
+            let style = match view.cell(row, col) {
+                Some(v) => palette.paint(&v),
+                None => plain,
+            };
+            if color && !style.same_sgr(&current) {
+                if !current.is_plain() {
+                    out.push_str(RESET);
+                }
+                if !style.is_plain() {
+                    style.push_sgr(&mut out);
+                }
+                current = style;
+            }
+            out.push(style.glyph);
+        }
+        if color && !current.is_plain() {
+            out.push_str(RESET);
+        }
+        if clipped {
+            out.push(CLIP_MARK);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Environment variable giving the terminal width, as set by
+/// most shells.
+pub const COLUMNS_VAR: &str = "COLUMNS";
+
+/// Terminal width assumed if `COLUMNS` is unset or bad.
+pub const DEFAULT_COLUMNS: usize = 80;
+
+/// Animator that redraws a map in place in the terminal
+/// each step.
+///
+/// When the output is a terminal, each frame is drawn from
+/// the top left of the screen over the last, clipped to the
+/// terminal width, and frames are throttled to the maximum
+/// frame rate if one is set. Otherwise frames are written
+/// one after another as plain text separated by blank
+/// lines, with no escape sequences and no throttling.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use maprender::*;
+/// # use std::collections::HashSet;
+/// let mut animator = TerminalAnimator::new().max_fps(10);
+/// let mut cart: HashSet<(i64, i64)> = HashSet::new();
+/// for x in 0..20 {
+///     cart.clear();
+///     cart.insert((x, 0));
+///     animator.show(&cart, AnsiStyle::new('>').bold())?;
+/// }
+/// animator.finish()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct TerminalAnimator<W: Write> {
+    out: W,
+    tty: bool,
+    width: usize,
+    min_interval: Option<Duration>,
+    last: Option<Instant>,
+    nframes: usize,
+}
+
+impl TerminalAnimator<io::Stdout> {
+    /// Animator writing to standard output. The width is
+    /// taken from the `COLUMNS` environment variable if set.
+    pub fn new() -> Self {
+        let out = io::stdout();
+        let tty = out.is_terminal();
+        let width = std::env::var(COLUMNS_VAR)
+            .ok()
+            .and_then(|c| c.parse().ok())
+            .filter(|&c| c > 0)
+            .unwrap_or(DEFAULT_COLUMNS);
+        TerminalAnimator::with_writer(out, tty, width)
+    }
+}
+
+impl Default for TerminalAnimator<io::Stdout> {
+    fn default() -> Self {
+        TerminalAnimator::new()
+    }
+}
+
+impl<W: Write> TerminalAnimator<W> {
+    /// Animator writing to `out`, which is treated as a
+    /// terminal `width` columns wide if `tty` is true.
+    pub fn with_writer(out: W, tty: bool, width: usize) -> Self {
+        TerminalAnimator {
+            out,
+            tty,
+            width,
+            min_interval: None,
+            last: None,
+            nframes: 0,
+        }
+    }
+
+    /// Limit terminal redraws to at most `fps` frames per
+    /// second. A rate of 0 means no limit.
+    pub fn max_fps(self, fps: u32) -> Self {
+        let min_interval =
+            (fps > 0).then(|| Duration::from_secs(1) / fps);
+        TerminalAnimator {
+            min_interval,
+            ..self
+        }
+    }
+
+    /// Number of frames shown so far.
+    pub fn frames(&self) -> usize {
+        self.nframes
+    }
+
+    /// Show a frame of the given map, with cells drawn as
+    /// given by `palette`.
+    pub fn show<S, P>(&mut self, view: &S, palette: P) -> io::Result<()>
+    where
+        S: RenderSource + ?Sized,
+        P: Paint<S::Cell, AnsiStyle>,
+    {
+        if !self.tty {
+            if self.nframes > 0 {
+                writeln!(self.out)?;
+            }
+            let text = render_text(view, palette, None, false);
+            self.out.write_all(text.as_bytes())?;
+            self.nframes += 1;
+            return self.out.flush();
+        }
+
+        if let (Some(interval), Some(last)) = (self.min_interval, self.last) {
+            let elapsed = last.elapsed();
+            if elapsed < interval {
+                thread::sleep(interval - elapsed);
+            }
+        }
+        let text = render_text(view, palette, Some(self.width), true);
+        let mut screen = String::new();
+        if self.nframes == 0 {
+            // Clear the screen and hide the cursor.
+            screen.push_str("\x1b[2J\x1b[?25l");
+        }
+        screen.push_str("\x1b[H");
+        for line in text.lines() {
+            screen.push_str(line);
+            screen.push_str("\x1b[K\n");
+        }
+        screen.push_str("\x1b[J");
+        self.out.write_all(screen.as_bytes())?;
+        self.out.flush()?;
+        self.last = Some(Instant::now());
+        self.nframes += 1;
+        Ok(())
+    }
+
+    /// Finish the animation, restoring the cursor, and
+    /// return the writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.tty && self.nframes > 0 {
+            self.out.write_all(b"\x1b[?25h")?;
+            self.out.flush()?;
+        }
+        Ok(self.out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use std::collections::HashMap;
+
+    fn tiny() -> HashMap<(i64, i64), char> {
+        [((0, 0), '#'), ((1, 0), '#'), ((2, 0), 'o'), ((1, 1), '.')]
+            .into_iter()
+            .collect()
+    }
+
+    fn style(c: &char) -> AnsiStyle {
+        match c {
+            '#' => AnsiStyle::new('#').fg(Rgb::GRAY),
+            'o' => AnsiStyle::new('o').fg(Rgb::YELLOW).bold(),
+            _ => AnsiStyle::new(*c),
+        }
+    }
+
+    #[test]
+    fn escapes() {
+        let text = render_ansi(&tiny(), style);
+        let expected = concat!(
+            "\x1b[38;2;128;128;128m##\x1b[0m",
+            "\x1b[1;38;2;255;255;0mo\x1b[0m\n",
+            " . \n",
+        );
+        assert_eq!(text, expected);
+
+        let bg = render_ansi(&tiny(), AnsiStyle::new('x').bg(Rgb::BLUE));
+        let expected = concat!(
+            "\x1b[48;2;0;0;255mxxx\x1b[0m\n",
+            " \x1b[48;2;0;0;255mx\x1b[0m \n",
+        );
+        assert_eq!(bg, expected);
+    }
+
+    #[test]
+    fn clipping() {
+        let plain = |c: &char| AnsiStyle::new(*c);
+        assert_eq!(render_ansi_clipped(&tiny(), plain, 3), "##o\n . \n");
+        assert_eq!(render_ansi_clipped(&tiny(), plain, 2), "#>\n >\n");
+        let clipped = render_ansi_clipped(&tiny(), style, 2);
+        let first = "\x1b[38;2;128;128;128m#\x1b[0m>";
+        assert_eq!(clipped.lines().next().unwrap(), first);
+        assert_eq!(render_ansi(&HashMap::<_, char>::new(), plain), "");
+    }
+
+    #[test]
+    fn animator_fallback() {
+        let mut animator = TerminalAnimator::with_writer(Vec::new(), false, 2);
+        let mut map = tiny();
+        animator.show(&map, style).unwrap();
+        map.insert((3, 1), 'o');
+        animator.show(&map, style).unwrap();
+        assert_eq!(animator.frames(), 2);
+        let out = String::from_utf8(animator.finish().unwrap()).unwrap();
+        assert_eq!(out, "##o\n . \n\n##o \n . o\n");
+    }
+
+    #[test]
+    fn animator_tty() {
+        let mut animator = TerminalAnimator::with_writer(Vec::new(), true, 2);
+        let plain = |c: &char| AnsiStyle::new(*c);
+        animator.show(&tiny(), plain).unwrap();
+        animator.show(&tiny(), plain).unwrap();
+        let out = String::from_utf8(animator.finish().unwrap()).unwrap();
+        let frame = "\x1b[H#>\x1b[K\n >\x1b[K\n\x1b[J";
+        let expected =
+            format!("\x1b[2J\x1b[?25l{}{}\x1b[?25h", frame, frame);
+        assert_eq!(out, expected);
+    }
+}
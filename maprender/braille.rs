@@ -0,0 +1,187 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Rendering large maps compactly with Unicode braille.
+//!
+//! Each braille character shows a block of 2 columns by 4
+//! rows of cells as dots, so a map a terminal could never
+//! hold at one character per cell shrinks eightfold. Cells
+//! are either occupied (a dot) or not, as decided by a
+//! `Paint` giving `bool`.
+//!
+//! # Examples
+//!
+//! ```
+//! # use maprender::*;
+//! # use std::collections::HashSet;
+//! let line: HashSet<(i64, i64)> = (0..4).map(|i| (i, i)).collect();
+//! assert_eq!(render_braille(&line, true), "⠑⢄\n");
+//!
+//! let depths = geom::Grid::new(1, 2, vec![3, 7]);
+//! assert_eq!(render_braille(&depths, at_least(5)), "⠈\n");
+//! assert_eq!(braille_dots('⠈'), Some([[false, true], [false, false],
+//!                                     [false, false], [false, false]]));
+//! ```
+
+use crate::{Paint, RenderSource};
+
+/// Columns of cells in a braille character.
+pub const BRAILLE_WIDTH: usize = 2;
+
+/// Rows of cells in a braille character.
+pub const BRAILLE_HEIGHT: usize = 4;
+
+/// The empty braille character, with no dots.
+const BLANK: u32 = 0x2800;
+
+/// Bit of each dot in a braille character, by row and
+/// column. The first three rows were the original six-dot
+/// braille; the bottom row was added later, hence the jump.
+const DOT_BITS: [[u8; BRAILLE_WIDTH]; BRAILLE_HEIGHT] =
+    [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Dots of a braille character, by row and column.
+pub type BrailleDots = [[bool; BRAILLE_WIDTH]; BRAILLE_HEIGHT];
+
+/// Every cell is occupied or not, as given.
+impl<T: ?Sized> Paint<T, bool> for bool {
+    fn paint(&self, _: &T) -> bool {
+        *self
+    }
+}
+
+/// A `Paint` for `render_braille()` counting cells as
+/// occupied if they are at least `threshold`.
+pub fn at_least<T: PartialOrd>(threshold: T) -> impl Fn(&T) -> bool {
+    move |v| *v >= threshold
+}
+
+/// The braille character showing the given dots.
+pub fn braille_char(dots: BrailleDots) -> char {
+    let mut bits = 0;
+    for (row, dot_row) in dots.iter().enumerate() {
+        for (col, &dot) in dot_row.iter().enumerate() {
+            if dot {
+                bits |= DOT_BITS[row][col];
+            }
+        }
+    }
+    char::from_u32(BLANK + bits as u32).unwrap()
+}
+
+/// The dots of the given braille character, or `None` if it
+/// is not one. The inverse of `braille_char()`.
+pub fn braille_dots(c: char) -> Option<BrailleDots> {
+    let bits = (c as u32).checked_sub(BLANK).filter(|&b| b <= 0xff)?;
+    let mut dots = [[false; BRAILLE_WIDTH]; BRAILLE_HEIGHT];
+    for (row, dot_row) in dots.iter_mut().enumerate() {
+        for (col, dot) in dot_row.iter_mut().enumerate() {
+            *dot = bits & DOT_BITS[row][col] as u32 != 0;
+        }
+    }
+    Some(dots)
+}
+
+/// Render a map as lines of braille characters, each
+/// showing 2×4 cells with a dot for each cell that
+/// `occupied` says is. Empty cells have no dot. Blocks at
+/// the right and bottom edges are padded with empty cells.
+pub fn render_braille<S, P>(view: &S, occupied: P) -> String
+where
+    S: RenderSource + ?Sized,
+    P: Paint<S::Cell, bool>,
+{
+    let ((r0, c0), (r1, c1)) = match view.bounds() {
+        Some(bounds) => bounds,
+        None => return String::new(),
+    };
+    let (bh, bw) = (BRAILLE_HEIGHT as i64, BRAILLE_WIDTH as i64);
+    let mut text = String::new();
+    for row in (r0..=r1).step_by(BRAILLE_HEIGHT) {
+        for col in (c0..=c1).step_by(BRAILLE_WIDTH) {
+            let mut dots = [[false; BRAILLE_WIDTH]; BRAILLE_HEIGHT];
+            for (dr, dot_row) in (0..bh).zip(dots.iter_mut()) {
+                for (dc, dot) in (0..bw).zip(dot_row.iter_mut()) {
+                    *dot = view
+                        .cell(row + dr, col + dc)
+                        .is_some_and(|v| occupied.paint(&v));
+                }
+            }
+            text.push(braille_char(dots));
+        }
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use std::collections::HashSet;
+
+    use geom::Grid;
+
+    /// Dots from rows of `#` and `.`.
+    fn dots(rows: [&str; 4]) -> BrailleDots {
+        let mut dots = [[false; 2]; 4];
+        for (r, row) in rows.iter().enumerate() {
+            for (c, ch) in row.chars().enumerate() {
+                dots[r][c] = ch == '#';
+            }
+        }
+        dots
+    }
+
+    #[test]
+    fn known_glyphs() {
+        let cases = [
+            (["..", "..", "..", ".."], '⠀'),
+            (["#.", "..", "..", ".."], '⠁'),
+            (["#.", "#.", "#.", ".."], '⠇'),
+            ([".#", "..", "..", ".."], '⠈'),
+            ([".#", ".#", ".#", ".."], '⠸'),
+            (["..", "..", "..", "#."], '⡀'),
+            (["..", "..", "..", ".#"], '⢀'),
+            (["#.", ".#", "#.", ".#"], '⢕'),
+            (["##", "##", "##", "##"], '⣿'),
+        ];
+        for (rows, glyph) in cases {
+            assert_eq!(braille_char(dots(rows)), glyph, "{:?}", rows);
+            assert_eq!(braille_dots(glyph), Some(dots(rows)));
+        }
+        assert_eq!(braille_dots('#'), None);
+        assert_eq!(braille_dots('\u{2900}'), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        for bits in 0..=0xff {
+            let c = char::from_u32(BLANK + bits).unwrap();
+            assert_eq!(braille_char(braille_dots(c).unwrap()), c);
+        }
+    }
+
+    #[test]
+    fn map_blocks() {
+        // A 5×3 map needs 2×2 characters, padded at the
+        // bottom and right.
+        let rows = ["#.#", ".#.", "#.#", ".#.", "###"];
+        let cells = rows.iter().flat_map(|r| r.chars()).collect();
+        let grid = Grid::new(5, 3, cells);
+        let text = render_braille(&grid, |&c: &char| c == '#');
+        assert_eq!(text, "⢕⠅\n⠉⠁\n");
+
+        let big: HashSet<(i64, i64)> =
+            [(0, 0), (299, 199)].into_iter().collect();
+        let text = render_braille(&big, true);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 50);
+        assert!(lines.iter().all(|l| l.chars().count() == 150));
+        assert!(lines[0].starts_with('⠁'));
+        assert!(lines[49].ends_with('⢀'));
+    }
+}
@@ -0,0 +1,371 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Minimal zlib compression and checksums for image
+//! encoding, to avoid a dependency.
+//!
+//! Compression is greedy LZ77 with hash chains, emitted as
+//! a single [deflate][1] block with the fixed Huffman code.
+//! This is far from optimal, but rendered maps are mostly
+//! long runs and repeated rows, which it handles well.
+//!
+//! [1]: https://www.rfc-editor.org/rfc/rfc1951
+
+/// Deflate window size.
+const WINDOW: usize = 1 << 15;
+/// Number of hash chain heads.
+const HASH_SIZE: usize = 1 << 15;
+/// Longest hash chain walked per position.
+const MAX_CHAIN: usize = 32;
+/// Shortest and longest deflate matches.
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+
+/// Base match lengths for length symbols 257 on.
+const LENGTH_BASE: [usize; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59,
+    67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+/// Extra bits for length symbols 257 on.
+const LENGTH_EXTRA: [u32; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4,
+    5, 5, 5, 5, 0,
+];
+/// Base distances for distance symbols.
+const DIST_BASE: [usize; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513,
+    769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+/// Extra bits for distance symbols.
+const DIST_EXTRA: [u32; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10,
+    11, 11, 12, 12, 13, 13,
+];
+
+/// Writer of a little-endian bit stream.
+struct BitWriter {
+    bytes: Vec<u8>,
+    acc: u64,
+    nbits: u32,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        BitWriter {
+            bytes: Vec::new(),
+            acc: 0,
+            nbits: 0,
+        }
+    }
+
+    /// Write the low `n` bits of `value`, least significant
+    /// first.
+    fn bits(&mut self, value: u32, n: u32) {
+        self.acc |= (value as u64) << self.nbits;
+        self.nbits += n;
+        while self.nbits >= 8 {
+            self.bytes.push(self.acc as u8);
+            self.acc >>= 8;
+            self.nbits -= 8;
+        }
+    }
+
+    /// Write an `n`-bit Huffman code, most significant bit
+    /// first.
+    fn code(&mut self, code: u32, n: u32) {
+        self.bits(code.reverse_bits() >> (32 - n), n);
+    }
+
+    /// Pad to a byte boundary and return the bytes.
+    fn finish(mut self) -> Vec<u8> {
+        if self.nbits > 0 {
+            self.bytes.push(self.acc as u8);
+        }
+        self.bytes
+    }
+
+    /// Write a literal/length symbol in the fixed code.
+    fn symbol(&mut self, s: u32) {
+        match s {
+            0..=143 => self.code(0x30 + s, 8),
+            144..=255 => self.code(0x190 + s - 144, 9),
+            256..=279 => self.code(s - 256, 7),
+            _ => self.code(0xc0 + s - 280, 8),
+        }
+    }
+
+    /// Write a match of the given length and distance.
+    fn copy(&mut self, len: usize, dist: usize) {
+        let l = LENGTH_BASE.partition_point(|&b| b <= len) - 1;
+        self.symbol(257 + l as u32);
+        self.bits((len - LENGTH_BASE[l]) as u32, LENGTH_EXTRA[l]);
+        let d = DIST_BASE.partition_point(|&b| b <= dist) - 1;
+        self.code(d as u32, 5);
+        self.bits((dist - DIST_BASE[d]) as u32, DIST_EXTRA[d]);
+    }
+}
+
+/// Hash of the three bytes at the start of `s`.
+fn hash3(s: &[u8]) -> usize {
+    let v = (s[0] as usize) << 16 | (s[1] as usize) << 8 | s[2] as usize;
+    (v.wrapping_mul(0x9e37_79b1) >> 8) % HASH_SIZE
+}
+
+/// Hash chains over the positions of three-byte strings
+/// seen so far.
+struct Chains {
+    /// Most recent position with each hash.
+    head: Vec<usize>,
+    /// Previous position with the same hash as each
+    /// position, indexed modulo the window size.
+    prev: Vec<usize>,
+}
+
+impl Chains {
+    fn new() -> Self {
+        Chains {
+            head: vec![usize::MAX; HASH_SIZE],
+            prev: vec![usize::MAX; WINDOW],
+        }
+    }
+
+    /// Record the string at position `i`.
+    fn insert(&mut self, data: &[u8], i: usize) {
+        if i + MIN_MATCH <= data.len() {
+            let h = hash3(&data[i..]);
+            self.prev[i % WINDOW] = self.head[h];
+            self.head[h] = i;
+        }
+    }
+
+    /// Longest match for the string at position `i` as
+    /// `(length, distance)`, or length 0 if none.
+    fn longest_match(&self, data: &[u8], i: usize) -> (usize, usize) {
+        let mut best = (0, 0);
+        if i + MIN_MATCH > data.len() {
+            return best;
+        }
+        let limit = (data.len() - i).min(MAX_MATCH);
+        let mut cand = self.head[hash3(&data[i..])];
+        for _ in 0..MAX_CHAIN {
+            if cand == usize::MAX || i - cand > WINDOW {
+                break;
+            }
+            let len = data[cand..]
+                .iter()
+                .zip(&data[i..i + limit])
+                .take_while(|(a, b)| a == b)
+                .count();
+            if len > best.0 {
+                best = (len, i - cand);
+                if len == limit {
+                    break;
+                }
+            }
+            // A slot reused by a later position ends the chain.
+            let next = self.prev[cand % WINDOW];
+            if next >= cand {
+                break;
+            }
+            cand = next;
+        }
+        best
+    }
+}
+
+/// Compress bytes as a zlib stream.
+pub(crate) fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    let mut out = BitWriter::new();
+    // Final block, fixed Huffman code.
+    out.bits(1, 1);
+    out.bits(1, 2);
+    let mut chains = Chains::new();
+    let mut i = 0;
+    while i < data.len() {
+        let (len, dist) = chains.longest_match(data, i);
+        if len >= MIN_MATCH {
+            out.copy(len, dist);
+            for j in i..i + len {
+                chains.insert(data, j);
+            }
+            i += len;
+        } else {
+            out.symbol(data[i] as u32);
+            chains.insert(data, i);
+            i += 1;
+        }
+    }
+    out.symbol(256);
+
+    let mut result = vec![0x78, 0x01];
+    result.extend(out.finish());
+    result.extend(adler32(data).to_be_bytes());
+    result
+}
+
+/// Adler-32 checksum, as used by zlib.
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552) {
+        for &x in chunk {
+            a += x as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
+
+/// Table for byte-at-a-time CRC-32.
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 { 0xedb8_8320 ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+};
+
+/// CRC-32 checksum, as used by PNG and zlib.
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |c, &b| {
+        CRC_TABLE[((c ^ b as u32) & 0xff) as usize] ^ (c >> 8)
+    })
+}
+
+/// Decompress a zlib stream made by `zlib_compress()`: only
+/// stored and fixed-code blocks are supported.
+#[cfg(test)]
+pub(crate) fn zlib_decompress(z: &[u8]) -> Vec<u8> {
+    struct BitReader<'a> {
+        bytes: &'a [u8],
+        posn: usize,
+    }
+
+    impl BitReader<'_> {
+        fn bits(&mut self, n: u32) -> usize {
+            let mut v = 0;
+            for i in 0..n {
+                let bit = (self.bytes[self.posn / 8] >> (self.posn % 8)) & 1;
+                v |= (bit as usize) << i;
+                self.posn += 1;
+            }
+            v
+        }
+
+        /// Read a fixed Huffman code of `n` bits, most
+        /// significant first, continuing from `code`.
+        fn more(&mut self, code: usize, n: u32) -> usize {
+            (0..n).fold(code, |c, _| (c << 1) | self.bits(1))
+        }
+
+        fn symbol(&mut self) -> usize {
+            let c = self.more(0, 7);
+            if c < 0x18 {
+                return c + 256;
+            }
+            let c = self.more(c, 1);
+            match c {
+                0x30..=0xbf => c - 0x30,
+                0xc0..=0xc7 => c - 0xc0 + 280,
+                _ => self.more(c, 1) - 0x190 + 144,
+            }
+        }
+    }
+
+    assert_eq!((z[0] as u32 * 256 + z[1] as u32) % 31, 0);
+    let mut r = BitReader {
+        bytes: &z[2..z.len() - 4],
+        posn: 0,
+    };
+    let mut out: Vec<u8> = Vec::new();
+    loop {
+        let last = r.bits(1);
+        match r.bits(2) {
+            0 => {
+                r.posn = r.posn.next_multiple_of(8);
+                let len = r.bits(16);
+                r.bits(16);
+                let start = r.posn / 8;
+                out.extend_from_slice(&r.bytes[start..start + len]);
+                r.posn += 8 * len;
+            }
+            1 => loop {
+                let s = r.symbol();
+                if s < 256 {
+                    out.push(s as u8);
+                    continue;
+                }
+                if s == 256 {
+                    break;
+                }
+                let l = s - 257;
+                let len = LENGTH_BASE[l] + r.bits(LENGTH_EXTRA[l]);
+                let d = r.more(0, 5);
+                let dist = DIST_BASE[d] + r.bits(DIST_EXTRA[d]);
+                for _ in 0..len {
+                    out.push(out[out.len() - dist]);
+                }
+            },
+            t => panic!("unsupported deflate block type {}", t),
+        }
+        if last == 1 {
+            break;
+        }
+    }
+    let check = u32::from_be_bytes(z[z.len() - 4..].try_into().unwrap());
+    assert_eq!(check, adler32(&out));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut inputs: Vec<Vec<u8>> = vec![
+            vec![],
+            b"a".to_vec(),
+            b"abcabcabcabcabcabcabcabcabc".to_vec(),
+            vec![7; 100_000],
+        ];
+        let mut x = 0x1234_5678u32;
+        let noise: Vec<u8> = (0..50_000)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 17;
+                x ^= x << 5;
+                (x % 4) as u8
+            })
+            .collect();
+        inputs.push(noise);
+        let rows: Vec<u8> = (0..300)
+            .flat_map(|r| (0..400).map(move |c| ((r / 10 + c / 7) % 3) as u8))
+            .collect();
+        inputs.push(rows);
+        for data in inputs {
+            let z = zlib_compress(&data);
+            assert_eq!(zlib_decompress(&z), data);
+        }
+        assert!(zlib_compress(&[7; 100_000]).len() < 1000);
+    }
+}
@@ -0,0 +1,323 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Highlighting what changed between steps of a
+//! simulation.
+//!
+//! A cell has changed if its value differs between the two
+//! maps, including if it is present in only one of them.
+//!
+//! # Examples
+//!
+//! ```
+//! # use maprender::*;
+//! # use std::collections::HashSet;
+//! let before: HashSet<(i64, i64)> = [(0, 0), (1, 0)].into_iter().collect();
+//! let after: HashSet<(i64, i64)> = [(1, 0), (2, 0)].into_iter().collect();
+//! let options = RenderOptions::default();
+//! let highlight = Highlight::Color(Rgb::RED);
+//! let frame = render_diff(&before, &after, Rgb::WHITE, highlight, &options);
+//! assert_eq!(frame.pixels(), &[Rgb::RED, Rgb::WHITE, Rgb::RED]);
+//! ```
+
+use std::io;
+use std::path::Path;
+
+use crate::{Bounds, Frame, Paint, Recorder, RecorderOptions};
+use crate::{RenderOptions, RenderSource, Rgb};
+
+/// How changed cells are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    /// Changed cells are drawn in this color.
+    Color(Rgb),
+    /// Changed cells are drawn half in their own color and
+    /// half in this one. Cells removed in the new map
+    /// blend with the background.
+    Blend(Rgb),
+}
+
+impl Highlight {
+    /// Color of a changed cell whose own color is `color`.
+    fn apply(&self, color: Rgb) -> Rgb {
+        match *self {
+            Highlight::Color(h) => h,
+            Highlight::Blend(Rgb(r, g, b)) => {
+                let mix = |x: u8, y: u8| ((x as u16 + y as u16) / 2) as u8;
+                Rgb(mix(color.0, r), mix(color.1, g), mix(color.2, b))
+            }
+        }
+    }
+}
+
+/// Smallest bounds covering both given bounds.
+fn union(a: Option<Bounds>, b: Option<Bounds>) -> Option<Bounds> {
+    match (a, b) {
+        (Some(((r0, c0), (r1, c1))), Some(((s0, d0), (s1, d1)))) => {
+            Some(((r0.min(s0), c0.min(d0)), (r1.max(s1), c1.max(d1))))
+        }
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+/// Render the part of `next` within `window`, highlighting
+/// cells that differ from `prev`.
+fn diff_frame<A, B, P>(
+    prev: &A,
+    next: &B,
+    window: Bounds,
+    options: &RenderOptions,
+    palette: P,
+    highlight: Highlight,
+) -> Frame
+where
+    A: RenderSource + ?Sized,
+    B: RenderSource<Cell = A::Cell> + ?Sized,
+    A::Cell: PartialEq,
+    P: Paint<A::Cell>,
+{
+    // Color each cell of the new map, highlighted if
+    // changed, then draw the colors as a map of their own.
+    let ((r0, c0), (r1, c1)) = window;
+    let width = (c1 - c0 + 1).max(0) as usize;
+    let mut colors = Vec::new();
+    for row in r0..=r1 {
+        for col in c0..=c1 {
+            let cell = next.cell(row, col);
+            let changed = prev.cell(row, col) != cell;
+            let color = cell.map(|v| palette.paint(&v));
+            colors.push(match (changed, color) {
+                (false, color) => color,
+                (true, color) => {
+                    Some(highlight.apply(color.unwrap_or(options.background)))
+                }
+            });
+        }
+    }
+    let colored = Snapshot {
+        bounds: Some(window),
+        width,
+        cells: colors,
+    };
+    crate::render_frame(&colored, window, options, |&c: &Rgb| c)
+}
+
+/// Render `next` as with `render_frame()` over the bounds of
+/// both maps, with cells that differ from `prev` drawn as
+/// given by `highlight`.
+pub fn render_diff<A, B, P>(
+    prev: &A,
+    next: &B,
+    palette: P,
+    highlight: Highlight,
+    options: &RenderOptions,
+) -> Frame
+where
+    A: RenderSource + ?Sized,
+    B: RenderSource<Cell = A::Cell> + ?Sized,
+    A::Cell: PartialEq,
+    P: Paint<A::Cell>,
+{
+    match union(prev.bounds(), next.bounds()) {
+        Some(window) => {
+            diff_frame(prev, next, window, options, palette, highlight)
+        }
+        None => Frame::new(0, 0, options.background),
+    }
+}
+
+/// Copy of the cells of a map within its bounds.
+#[derive(Debug, Clone)]
+struct Snapshot<T> {
+    bounds: Option<Bounds>,
+    width: usize,
+    /// Cells in row-major order.
+    cells: Vec<Option<T>>,
+}
+
+impl<T> Snapshot<T> {
+    /// Copy the cells of the given map.
+    fn of<S>(view: &S) -> Self
+    where
+        S: RenderSource<Cell = T> + ?Sized,
+    {
+        let bounds = view.bounds();
+        let ((r0, c0), (r1, c1)) = bounds.unwrap_or(((0, 0), (-1, -1)));
+        let cells = (r0..=r1)
+            .flat_map(|r| (c0..=c1).map(move |c| (r, c)))
+            .map(|(r, c)| view.cell(r, c))
+            .collect();
+        let width = (c1 - c0 + 1) as usize;
+        Snapshot {
+            bounds,
+            width,
+            cells,
+        }
+    }
+}
+
+impl<T: Clone> RenderSource for Snapshot<T> {
+    type Cell = T;
+
+    fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    fn cell(&self, row: i64, col: i64) -> Option<T> {
+        let ((r0, c0), (r1, c1)) = self.bounds?;
+        if row < r0 || row > r1 || col < c0 || col > c1 {
+            return None;
+        }
+        let i = (row - r0) as usize * self.width + (col - c0) as usize;
+        self.cells[i].clone()
+    }
+}
+
+/// A `Recorder` that highlights the cells changed since the
+/// previous frame, as with `render_diff()`. The first frame
+/// has no highlights.
+#[derive(Debug)]
+pub struct DiffRecorder<T> {
+    recorder: Recorder,
+    highlight: Highlight,
+    prev: Option<Snapshot<T>>,
+}
+
+impl<T: Clone + PartialEq> DiffRecorder<T> {
+    /// Start recording an animation to be written to the
+    /// given path, with changes drawn as given by
+    /// `highlight`.
+    pub fn new<P: AsRef<Path>>(
+        path: P,
+        options: RecorderOptions,
+        highlight: Highlight,
+    ) -> Self {
+        DiffRecorder {
+            recorder: Recorder::new(path, options),
+            highlight,
+            prev: None,
+        }
+    }
+
+    /// The underlying recorder, for example to set its
+    /// viewport.
+    pub fn recorder(&mut self) -> &mut Recorder {
+        &mut self.recorder
+    }
+
+    /// Add a frame showing the given map, with cells colored
+    /// by `palette` and changed cells highlighted. Returns
+    /// false if the frame was skipped as identical to the
+    /// last one.
+    pub fn add_frame<S, P>(&mut self, view: &S, palette: P) -> bool
+    where
+        S: RenderSource<Cell = T> + ?Sized,
+        P: Paint<T>,
+    {
+        let next = Snapshot::of(view);
+        let prev = self.prev.take().unwrap_or_else(|| next.clone());
+        let content = union(prev.bounds, next.bounds);
+        let highlight = self.highlight;
+        let added = self.recorder.add_rendered(content, |window, options| {
+            diff_frame(&prev, &next, window, options, palette, highlight)
+        });
+        self.prev = Some(next);
+        added
+    }
+
+    /// Write the animation, returning the number of frames
+    /// written.
+    pub fn finish(self) -> io::Result<usize> {
+        self.recorder.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::gif::tests::decode_gif;
+    use crate::png::tests::temp_path;
+
+    use std::collections::{HashMap, HashSet};
+
+    use geom::Grid;
+
+    fn grid(rows: [&str; 4]) -> Grid<char> {
+        Grid::new(4, 4, rows.iter().flat_map(|r| r.chars()).collect())
+    }
+
+    fn palette(c: &char) -> Rgb {
+        match c {
+            '#' => Rgb::WHITE,
+            _ => Rgb::BLACK,
+        }
+    }
+
+    #[test]
+    fn two_changes() {
+        let before = grid(["#...", ".#..", "..#.", "...#"]);
+        let after = grid(["#...", ".#.#", "....", "...#"]);
+        let options = RenderOptions::scaled(3);
+        let highlight = Highlight::Color(Rgb::RED);
+        let frame = render_diff(&before, &after, palette, highlight, &options);
+        assert_eq!((frame.width(), frame.height()), (12, 12));
+        let red = frame.pixels().iter().filter(|&&p| p == Rgb::RED).count();
+        assert_eq!(red, 2 * 3 * 3);
+        assert_eq!(frame.pixel(10, 4), Rgb::RED);
+        assert_eq!(frame.pixel(7, 7), Rgb::RED);
+        assert_eq!(frame.pixel(0, 0), Rgb::WHITE);
+
+        let blend = Highlight::Blend(Rgb::RED);
+        let frame = render_diff(&before, &after, palette, blend, &options);
+        assert_eq!(frame.pixel(10, 4), Rgb(255, 127, 127));
+        assert_eq!(frame.pixel(7, 7), Rgb(127, 0, 0));
+    }
+
+    #[test]
+    fn sparse_presence() {
+        let before: HashMap<(i64, i64), u8> =
+            [((0, 0), 1), ((5, 0), 2)].into_iter().collect();
+        let after: HashMap<(i64, i64), u8> =
+            [((0, 0), 1), ((5, 0), 3), ((0, 2), 1)].into_iter().collect();
+        let options = RenderOptions::default();
+        let highlight = Highlight::Color(Rgb::RED);
+        let frame =
+            render_diff(&before, &after, Rgb::WHITE, highlight, &options);
+        assert_eq!((frame.width(), frame.height()), (6, 3));
+        let red: Vec<(usize, usize)> = (0..3)
+            .flat_map(|y| (0..6).map(move |x| (x, y)))
+            .filter(|&(x, y)| frame.pixel(x, y) == Rgb::RED)
+            .collect();
+        assert_eq!(red, [(5, 0), (0, 2)]);
+    }
+
+    #[test]
+    fn diff_recorder() {
+        let path = temp_path("diff.gif");
+        let options = RecorderOptions {
+            render: RenderOptions::scaled(1),
+            ..RecorderOptions::default()
+        };
+        let highlight = Highlight::Color(Rgb::RED);
+        let mut recorder = DiffRecorder::new(&path, options, highlight);
+        let mut cells: HashSet<(i64, i64)> = HashSet::new();
+        for x in 0..4 {
+            cells.insert((x, 0));
+            recorder.add_frame(&cells, Rgb::WHITE);
+        }
+        assert_eq!(recorder.finish().unwrap(), 4);
+        let (frames, _) = decode_gif(&std::fs::read(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+        let reds: Vec<usize> = frames
+            .iter()
+            .map(|f| f.pixels().iter().filter(|&&p| p == Rgb::RED).count())
+            .collect();
+        assert_eq!(reds, [0, 1, 1, 1]);
+        assert_eq!(frames[3].pixel(3, 0), Rgb::RED);
+        assert_eq!(frames[3].pixel(2, 0), Rgb::WHITE);
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! In-memory RGB images.
+
+use crate::deflate::crc32;
+use crate::Rgb;
+
+/// A rectangular RGB image, stored row-major. Pixels are
+/// addressed by `(x, y)` with the origin at top left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Frame {
+    /// Make a frame of the given size filled with the given
+    /// color.
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Frame {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// Make a frame showing a `width` by `height` array of
+    /// cells, each drawn as a `scale` by `scale` square of
+    /// the color given by `cell(row, col)`.
+    pub fn from_cells<F>(
+        width: usize,
+        height: usize,
+        scale: usize,
+        mut cell: F,
+    ) -> Self
+    where
+        F: FnMut(usize, usize) -> Rgb,
+    {
+        let mut frame = Frame::new(width * scale, height * scale, Rgb::BLACK);
+        for row in 0..height {
+            for col in 0..width {
+                let color = cell(row, col);
+                frame.fill_rect(col * scale, row * scale, scale, scale, color);
+            }
+        }
+        frame
+    }
+
+    /// Width in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The pixels, row-major.
+    pub fn pixels(&self) -> &[Rgb] {
+        &self.pixels
+    }
+
+    /// Make a frame from a buffer of row-major RGBA pixels,
+    /// as made by `to_rgba()`. Alpha is ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer size does not match the
+    /// dimensions.
+    pub fn from_rgba(width: usize, height: usize, rgba: &[u8]) -> Self {
+        assert_eq!(rgba.len(), 4 * width * height, "rgba size mismatch");
+        let pixels = rgba.chunks(4).map(|p| Rgb(p[0], p[1], p[2])).collect();
+        Frame {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// The pixels as a buffer of row-major RGBA bytes, fully
+    /// opaque.
+    pub fn to_rgba(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|&Rgb(r, g, b)| [r, g, b, 255])
+            .collect()
+    }
+
+    /// CRC-32 of the dimensions and pixels, for cheaply
+    /// pinning what a render produces.
+    pub fn checksum(&self) -> u32 {
+        let mut data = Vec::with_capacity(8 + 3 * self.pixels.len());
+        data.extend((self.width as u32).to_be_bytes());
+        data.extend((self.height as u32).to_be_bytes());
+        for &Rgb(r, g, b) in &self.pixels {
+            data.extend([r, g, b]);
+        }
+        crc32(&data)
+    }
+
+    /// Color of the pixel at `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pixel is outside the frame.
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        assert!(x < self.width && y < self.height, "pixel out of frame");
+        self.pixels[y * self.width + x]
+    }
+
+    /// Set the color of the pixel at `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pixel is outside the frame.
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: Rgb) {
+        assert!(x < self.width && y < self.height, "pixel out of frame");
+        self.pixels[y * self.width + x] = color;
+    }
+
+    /// Fill a `w` by `h` rectangle with top left corner at
+    /// `(x, y)`. The rectangle is clipped to the frame.
+    pub fn fill_rect(
+        &mut self,
+        x: usize,
+        y: usize,
+        w: usize,
+        h: usize,
+        color: Rgb,
+    ) {
+        let x1 = (x + w).min(self.width);
+        let y1 = (y + h).min(self.height);
+        for row in y.min(y1)..y1 {
+            let start = row * self.width;
+            self.pixels[start + x.min(x1)..start + x1].fill(color);
+        }
+    }
+}
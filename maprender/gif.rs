@@ -0,0 +1,994 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Recording animated GIFs of a simulation.
+//!
+//! Frames are buffered in memory as they are added and the
+//! file is written by `Recorder::finish()`, since the image
+//! size is not known until every frame has been seen. For
+//! long simulations, the recorder options can keep just
+//! some of the frames offered.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # use maprender::*;
+//! # use std::collections::HashSet;
+//! let mut recorder = Recorder::new("walk.gif", RecorderOptions::default());
+//! let mut walker: HashSet<(i64, i64)> = HashSet::new();
+//! for x in 0..10 {
+//!     walker.insert((x, x / 2));
+//!     recorder.add_frame(&walker, Rgb::WHITE);
+//! }
+//! recorder.finish()?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::collections::hash_map::{Entry, HashMap};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::{render_frame, Bounds, Frame, Paint, RenderOptions, RenderSource};
+use crate::{Rgb, Viewport};
+
+/// How frames with differing bounds are fitted to the
+/// animation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Canvas {
+    /// Grow the canvas to cover the bounds of every frame.
+    Grow,
+    /// Crop every frame to the bounds of the first.
+    Crop,
+}
+
+/// Options for a `Recorder`.
+#[derive(Debug, Clone)]
+pub struct RecorderOptions {
+    /// Delay between frames in milliseconds, unless changed
+    /// by `Recorder::set_last_delay()`. GIF delays are in
+    /// hundredths of a second, so this is rounded down to a
+    /// multiple of 10.
+    pub delay_ms: u32,
+    /// Number of times to repeat the animation after the
+    /// first showing, with 0 meaning forever, or `None` to
+    /// play once.
+    pub loop_count: Option<u16>,
+    /// If true, a global color table is made from the
+    /// colors of the first frame; later frames only get
+    /// their own color table if they use other colors.
+    /// Otherwise every frame has its own color table.
+    pub global_palette: bool,
+    /// How frames with differing bounds are handled.
+    pub canvas: Canvas,
+    /// Window of each map to draw. Frames drawn through a
+    /// `Viewport::Follow` window are placed on the canvas
+    /// relative to the window rather than the map, so the
+    /// animation stays the size of the window as it moves.
+    /// Such frames are never cropped.
+    pub viewport: Viewport,
+    /// If true, a frame identical to the one before it is
+    /// dropped.
+    pub skip_identical: bool,
+    /// Keep only every `every_nth` frame offered, starting
+    /// with the first. Frames not kept are never rendered.
+    pub every_nth: usize,
+    /// Keep at most this many frames, spread evenly over the
+    /// run. This is done in one pass without knowing the
+    /// length of the run: frames are kept at some stride,
+    /// starting at 1, and when there are too many, every
+    /// other one is dropped and the stride doubled. So
+    /// between half this many and this many frames are
+    /// kept, not counting those skipped for other reasons.
+    pub max_frames: Option<usize>,
+    /// Drop a frame unless at least this fraction of its
+    /// cells differ from the last frame kept. A frame with
+    /// different bounds is always kept.
+    pub min_change: f64,
+    /// How cells are drawn.
+    pub render: RenderOptions,
+}
+
+impl Default for RecorderOptions {
+    fn default() -> Self {
+        RecorderOptions {
+            delay_ms: 100,
+            loop_count: Some(0),
+            global_palette: true,
+            canvas: Canvas::Grow,
+            viewport: Viewport::Full,
+            skip_identical: true,
+            every_nth: 1,
+            max_frames: None,
+            min_change: 0.0,
+            render: RenderOptions::scaled(4),
+        }
+    }
+}
+
+/// A frame kept by a `Recorder`.
+#[derive(Debug)]
+struct Kept {
+    /// Canvas bounds, if not empty.
+    bounds: Option<Bounds>,
+    frame: Frame,
+    /// Position among the frames offered after `every_nth`.
+    step: usize,
+    /// Delay after this frame, if not the default.
+    delay_ms: Option<u32>,
+}
+
+/// Recorder of an animated GIF, a frame at a time.
+#[derive(Debug)]
+pub struct Recorder {
+    path: PathBuf,
+    options: RecorderOptions,
+    frames: Vec<Kept>,
+    /// Number of frames offered so far.
+    offered: usize,
+    /// Stride of frames kept for `max_frames`.
+    stride: usize,
+}
+
+impl Recorder {
+    /// Start recording an animation to be written to the
+    /// given path.
+    pub fn new<P: AsRef<Path>>(path: P, options: RecorderOptions) -> Self {
+        Recorder {
+            path: path.as_ref().to_path_buf(),
+            options,
+            frames: Vec::new(),
+            offered: 0,
+            stride: 1,
+        }
+    }
+
+    /// Number of frames kept so far.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// True if no frames have been kept.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Change the viewport for frames added from now on:
+    /// for example, to move a `Viewport::Follow` window.
+    pub fn set_viewport(&mut self, viewport: Viewport) {
+        self.options.viewport = viewport;
+    }
+
+    /// Set the delay after the last frame kept, in place of
+    /// `RecorderOptions::delay_ms`: for example, to linger
+    /// on the final state. Returns false if there are no
+    /// frames.
+    pub fn set_last_delay(&mut self, delay_ms: u32) -> bool {
+        match self.frames.last_mut() {
+            Some(kept) => {
+                kept.delay_ms = Some(delay_ms);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Add a frame showing the given map, with cells colored
+    /// by `palette`. Returns false if the frame was not kept,
+    /// as identical to the last one or by the options for
+    /// keeping frames.
+    pub fn add_frame<S, P>(&mut self, view: &S, palette: P) -> bool
+    where
+        S: RenderSource + ?Sized,
+        P: Paint<S::Cell>,
+    {
+        self.add_frame_with(view, palette, |_| ())
+    }
+
+    /// Add a frame as with `add_frame()`, letting `decorate`
+    /// draw on the rendered frame first: for example, a
+    /// caption with `draw_text()`.
+    pub fn add_frame_with<S, P, F>(
+        &mut self,
+        view: &S,
+        palette: P,
+        decorate: F,
+    ) -> bool
+    where
+        S: RenderSource + ?Sized,
+        P: Paint<S::Cell>,
+        F: FnOnce(&mut Frame),
+    {
+        self.add_rendered(view.bounds(), |window, options| {
+            let mut frame = render_frame(view, window, options, palette);
+            decorate(&mut frame);
+            frame
+        })
+    }
+
+    /// Add a frame of a map with the given bounds, drawn by
+    /// `render` given the window to draw and the render
+    /// options. Returns false if the frame was not kept.
+    pub(crate) fn add_rendered<F>(
+        &mut self,
+        content: Option<Bounds>,
+        render: F,
+    ) -> bool
+    where
+        F: FnOnce(Bounds, &RenderOptions) -> Frame,
+    {
+        let offered = self.offered;
+        self.offered += 1;
+        let every_nth = self.options.every_nth.max(1);
+        if !offered.is_multiple_of(every_nth) {
+            return false;
+        }
+        let step = offered / every_nth;
+        if !step.is_multiple_of(self.stride) {
+            return false;
+        }
+
+        let opts = &self.options;
+        let follow = matches!(opts.viewport, Viewport::Follow { .. });
+        let window = match (opts.canvas, self.frames.first()) {
+            (Canvas::Crop, Some(first)) if !follow => first.bounds,
+            _ => opts.viewport.window(content),
+        };
+        let frame = match window {
+            Some(w) => render(w, &opts.render),
+            None => Frame::new(0, 0, opts.render.background),
+        };
+        let bounds = match window {
+            Some(((r0, c0), (r1, c1))) if follow => {
+                Some(((0, 0), (r1 - r0, c1 - c0)))
+            }
+            _ => window,
+        };
+        if let Some(last) = self.frames.last() {
+            if last.bounds == bounds {
+                let same = last.frame == frame;
+                if same && opts.skip_identical {
+                    return false;
+                }
+                let change = match bounds {
+                    Some(b) if !same && opts.min_change > 0.0 => {
+                        changed_cells(b, &opts.render, &last.frame, &frame)
+                    }
+                    _ => 0.0,
+                };
+                if change < opts.min_change {
+                    return false;
+                }
+            }
+        }
+        self.frames.push(Kept {
+            bounds,
+            frame,
+            step,
+            delay_ms: None,
+        });
+
+        if let Some(max) = self.options.max_frames {
+            if self.frames.len() > max.max(1) {
+                let stride = 2 * self.stride;
+                self.frames.retain(|k| k.step.is_multiple_of(stride));
+                self.stride = stride;
+            }
+        }
+        true
+    }
+
+    /// Write the animation, returning the number of frames
+    /// written. An animation with no frames, or only empty
+    /// ones, is a single background pixel.
+    pub fn finish(self) -> io::Result<usize> {
+        let gif = self.encode()?;
+        std::fs::write(&self.path, gif)?;
+        Ok(self.frames.len().max(1))
+    }
+
+    /// Compose the frames onto the canvas and encode them.
+    fn encode(&self) -> io::Result<Vec<u8>> {
+        let opts = &self.options;
+        let render = &opts.render;
+        let canvas = self
+            .frames
+            .iter()
+            .filter_map(|k| k.bounds)
+            .reduce(|((r0, c0), (r1, c1)), ((s0, d0), (s1, d1))| {
+                ((r0.min(s0), c0.min(d0)), (r1.max(s1), c1.max(d1)))
+            });
+        let (origin, width, height) = match canvas {
+            Some(((r0, c0), (r1, c1))) => {
+                let w = render.span((c1 - c0 + 1) as usize);
+                let h = render.span((r1 - r0 + 1) as usize);
+                ((r0, c0), w, h)
+            }
+            None => ((0, 0), 1, 1),
+        };
+        if width > u16::MAX as usize || height > u16::MAX as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "GIF canvas too large",
+            ));
+        }
+        let blank = Frame::new(width, height, render.background);
+        let mut composed = Vec::with_capacity(self.frames.len());
+        let mut delays = Vec::with_capacity(self.frames.len());
+        for Kept {
+            bounds,
+            frame,
+            delay_ms,
+            ..
+        } in &self.frames
+        {
+            let mut canvas = blank.clone();
+            if let Some(((r0, c0), _)) = bounds {
+                // Frames start with their grid lines, if any.
+                let line = render.offset(0);
+                let x0 = render.offset((c0 - origin.1) as usize) - line;
+                let y0 = render.offset((r0 - origin.0) as usize) - line;
+                for y in 0..frame.height() {
+                    for x in 0..frame.width() {
+                        canvas.set_pixel(x0 + x, y0 + y, frame.pixel(x, y));
+                    }
+                }
+            }
+            composed.push(canvas);
+            delays.push(delay_ms.unwrap_or(opts.delay_ms));
+        }
+        if composed.is_empty() {
+            composed.push(blank);
+            delays.push(opts.delay_ms);
+        }
+        encode_gif(&composed, &delays, opts)
+    }
+}
+
+/// A GIF color table, padded to a power-of-two size.
+struct ColorTable {
+    colors: Vec<Rgb>,
+    index: HashMap<Rgb, u8>,
+}
+
+impl ColorTable {
+    /// Make a color table holding the colors of the given
+    /// frame, or `None` if there are too many.
+    fn new(frame: &Frame) -> Option<ColorTable> {
+        let mut colors = Vec::new();
+        let mut index = HashMap::new();
+        for &p in frame.pixels() {
+            if let Entry::Vacant(e) = index.entry(p) {
+                if colors.len() == 256 {
+                    return None;
+                }
+                e.insert(colors.len() as u8);
+                colors.push(p);
+            }
+        }
+        Some(ColorTable { colors, index })
+    }
+
+    /// Number of index bits: the table has `2^bits` entries.
+    fn bits(&self) -> u32 {
+        self.colors.len().max(2).next_power_of_two().trailing_zeros()
+    }
+
+    /// Color indices of the frame's pixels, if all are in
+    /// the table.
+    fn indices(&self, frame: &Frame) -> Option<Vec<u8>> {
+        frame.pixels().iter().map(|p| self.index.get(p).copied()).collect()
+    }
+
+    /// Append the padded table.
+    fn write(&self, gif: &mut Vec<u8>) {
+        for i in 0..1 << self.bits() {
+            let color = self.colors.get(i).copied();
+            let Rgb(r, g, b) = color.unwrap_or(Rgb::BLACK);
+            gif.extend([r, g, b]);
+        }
+    }
+}
+
+/// Fraction of the cells of a map with the given bounds that
+/// differ between two frames of it drawn with the given
+/// options, judged by the top left pixel of each cell.
+fn changed_cells(
+    ((r0, c0), (r1, c1)): Bounds,
+    render: &RenderOptions,
+    a: &Frame,
+    b: &Frame,
+) -> f64 {
+    let (rows, cols) = ((r1 - r0 + 1) as usize, (c1 - c0 + 1) as usize);
+    let mut changed = 0;
+    for row in 0..rows {
+        for col in 0..cols {
+            let (x, y) = (render.offset(col), render.offset(row));
+            if a.pixel(x, y) != b.pixel(x, y) {
+                changed += 1;
+            }
+        }
+    }
+    changed as f64 / (rows * cols) as f64
+}
+
+/// Encode the given same-sized frames as an animated GIF,
+/// with the given delay in milliseconds after each.
+fn encode_gif(
+    frames: &[Frame],
+    delays: &[u32],
+    opts: &RecorderOptions,
+) -> io::Result<Vec<u8>> {
+    let too_many = || {
+        io::Error::new(io::ErrorKind::InvalidData, "frame has over 256 colors")
+    };
+    let (width, height) = (frames[0].width(), frames[0].height());
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend((width as u16).to_le_bytes());
+    gif.extend((height as u16).to_le_bytes());
+    let global = if opts.global_palette {
+        Some(ColorTable::new(&frames[0]).ok_or_else(too_many)?)
+    } else {
+        None
+    };
+    match &global {
+        Some(table) => {
+            gif.extend([0xf0 | (table.bits() - 1) as u8, 0, 0]);
+            table.write(&mut gif);
+        }
+        None => gif.extend([0x70, 0, 0]),
+    }
+    if let Some(n) = opts.loop_count {
+        gif.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01");
+        gif.extend(n.to_le_bytes());
+        gif.push(0);
+    }
+    for (frame, &delay_ms) in frames.iter().zip(delays) {
+        let delay = (delay_ms / 10).min(u16::MAX as u32) as u16;
+        // Graphic control: no disposal, no transparency.
+        gif.extend([0x21, 0xf9, 0x04, 0x04]);
+        gif.extend(delay.to_le_bytes());
+        gif.extend([0, 0]);
+
+        gif.push(0x2c);
+        gif.extend([0, 0, 0, 0]);
+        gif.extend((width as u16).to_le_bytes());
+        gif.extend((height as u16).to_le_bytes());
+        let global_indices = global.as_ref().and_then(|t| t.indices(frame));
+        let (indices, bits) = match global_indices {
+            Some(indices) => {
+                gif.push(0);
+                (indices, global.as_ref().unwrap().bits())
+            }
+            None => {
+                let local = ColorTable::new(frame).ok_or_else(too_many)?;
+                gif.push(0x80 | (local.bits() - 1) as u8);
+                local.write(&mut gif);
+                (local.indices(frame).unwrap(), local.bits())
+            }
+        };
+        let min_code_size = bits.max(2);
+        gif.push(min_code_size as u8);
+        for block in lzw_encode(&indices, min_code_size).chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend(block);
+        }
+        gif.push(0);
+    }
+    gif.push(0x3b);
+    Ok(gif)
+}
+
+/// Writer of a little-endian stream of variable-size
+/// codes.
+struct CodeWriter {
+    out: Vec<u8>,
+    acc: u32,
+    nbits: u32,
+}
+
+impl CodeWriter {
+    /// Write a code of `size` bits.
+    fn emit(&mut self, code: u16, size: u32) {
+        self.acc |= (code as u32) << self.nbits;
+        self.nbits += size;
+        while self.nbits >= 8 {
+            self.out.push(self.acc as u8);
+            self.acc >>= 8;
+            self.nbits -= 8;
+        }
+    }
+
+    /// Flush any partial byte and return the bytes.
+    fn finish(mut self) -> Vec<u8> {
+        if self.nbits > 0 {
+            self.out.push(self.acc as u8);
+        }
+        self.out
+    }
+}
+
+/// LZW-compress color indices as GIF image data, with the
+/// given minimum code size.
+fn lzw_encode(indices: &[u8], min_code_size: u32) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut w = CodeWriter {
+        out: Vec::new(),
+        acc: 0,
+        nbits: 0,
+    };
+    let mut codes: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut size = min_code_size + 1;
+    w.emit(clear, size);
+    let (&first, rest) = match indices.split_first() {
+        Some(split) => split,
+        None => {
+            w.emit(end, size);
+            return w.finish();
+        }
+    };
+    let mut prefix = first as u16;
+    for &k in rest {
+        if let Some(&code) = codes.get(&(prefix, k)) {
+            prefix = code;
+            continue;
+        }
+        w.emit(prefix, size);
+        codes.insert((prefix, k), next);
+        next += 1;
+        if next > 1 << size && size < 12 {
+            size += 1;
+        }
+        // Start over when the code table is full.
+        if next == 4096 {
+            w.emit(clear, size);
+            codes.clear();
+            next = end + 1;
+            size = min_code_size + 1;
+        }
+        prefix = k as u16;
+    }
+    w.emit(prefix, size);
+    // The decoder adds a table entry on reading the last
+    // code, so it may widen the end code though no entry
+    // was added here.
+    if next == 1 << size && size < 12 {
+        size += 1;
+    }
+    w.emit(end, size);
+    w.finish()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+
+    use super::*;
+    use crate::draw_text;
+    use crate::png::tests::temp_path;
+
+    use std::collections::HashSet;
+
+    /// Decode GIF image data.
+    fn lzw_decode(data: &[u8], min_code_size: u32) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let reset = |table: &mut Vec<Vec<u8>>| {
+            table.clear();
+            table.extend((0..clear).map(|i| vec![i as u8]));
+            table.push(vec![]);
+            table.push(vec![]);
+        };
+        reset(&mut table);
+        let mut size = min_code_size + 1;
+        let mut posn = 0;
+        let mut out = Vec::new();
+        let mut prev: Option<usize> = None;
+        loop {
+            let mut code = 0;
+            for i in 0..size as usize {
+                let bit = (data[(posn + i) / 8] >> ((posn + i) % 8)) & 1;
+                code |= (bit as usize) << i;
+            }
+            posn += size as usize;
+            if code == clear {
+                reset(&mut table);
+                size = min_code_size + 1;
+                prev = None;
+                continue;
+            }
+            if code == end {
+                return out;
+            }
+            let entry = match prev {
+                None => table[code].clone(),
+                Some(p) => {
+                    let entry = if code < table.len() {
+                        table[code].clone()
+                    } else {
+                        assert_eq!(code, table.len());
+                        let mut e = table[p].clone();
+                        e.push(table[p][0]);
+                        e
+                    };
+                    let mut added = table[p].clone();
+                    added.push(entry[0]);
+                    if table.len() < 4096 {
+                        table.push(added);
+                    }
+                    if table.len() == 1 << size && size < 12 {
+                        size += 1;
+                    }
+                    entry
+                }
+            };
+            out.extend_from_slice(&entry);
+            prev = Some(code);
+        }
+    }
+
+    /// Read a color table of `2^bits` entries.
+    fn read_table(gif: &[u8], posn: &mut usize, bits: u32) -> Vec<Rgb> {
+        let n = 1 << bits;
+        let table = gif[*posn..*posn + 3 * n]
+            .chunks(3)
+            .map(|c| Rgb(c[0], c[1], c[2]))
+            .collect();
+        *posn += 3 * n;
+        table
+    }
+
+    /// Decode a GIF made by `encode_gif()` into its frames
+    /// and loop count.
+    pub(crate) fn decode_gif(gif: &[u8]) -> (Vec<Frame>, Option<u16>) {
+        let (frames, loops) = decode_gif_timed(gif);
+        (frames.into_iter().map(|(f, _)| f).collect(), loops)
+    }
+
+    /// Decode a GIF as for `decode_gif()`, with the delay
+    /// after each frame in hundredths of a second.
+    fn decode_gif_timed(gif: &[u8]) -> (Vec<(Frame, u16)>, Option<u16>) {
+        assert_eq!(&gif[..6], b"GIF89a");
+        let u16_at = |i: usize| u16::from_le_bytes([gif[i], gif[i + 1]]);
+        let (width, height) = (u16_at(6) as usize, u16_at(8) as usize);
+        let mut posn = 13;
+        let global = if gif[10] & 0x80 != 0 {
+            Some(read_table(gif, &mut posn, (gif[10] & 7) as u32 + 1))
+        } else {
+            None
+        };
+        let mut frames = Vec::new();
+        let mut loops = None;
+        let mut delay = 0;
+        loop {
+            match gif[posn] {
+                0x21 => {
+                    if gif[posn + 1] == 0xff {
+                        assert_eq!(&gif[posn + 3..posn + 14], b"NETSCAPE2.0");
+                        loops = Some(u16_at(posn + 16));
+                    }
+                    if gif[posn + 1] == 0xf9 {
+                        delay = u16_at(posn + 4);
+                    }
+                    posn += 2;
+                    while gif[posn] != 0 {
+                        posn += gif[posn] as usize + 1;
+                    }
+                    posn += 1;
+                }
+                0x2c => {
+                    assert_eq!(u16_at(posn + 5) as usize, width);
+                    assert_eq!(u16_at(posn + 7) as usize, height);
+                    let flags = gif[posn + 9];
+                    posn += 10;
+                    let table = if flags & 0x80 != 0 {
+                        read_table(gif, &mut posn, (flags & 7) as u32 + 1)
+                    } else {
+                        global.clone().unwrap()
+                    };
+                    let min_code_size = gif[posn] as u32;
+                    posn += 1;
+                    let mut data = Vec::new();
+                    while gif[posn] != 0 {
+                        let n = gif[posn] as usize;
+                        data.extend_from_slice(&gif[posn + 1..posn + 1 + n]);
+                        posn += n + 1;
+                    }
+                    posn += 1;
+                    let indices = lzw_decode(&data, min_code_size);
+                    assert_eq!(indices.len(), width * height);
+                    let mut frame = Frame::new(width, height, Rgb::BLACK);
+                    for (i, &ix) in indices.iter().enumerate() {
+                        let color = table[ix as usize];
+                        frame.set_pixel(i % width, i / width, color);
+                    }
+                    frames.push((frame, delay));
+                }
+                0x3b => return (frames, loops),
+                b => panic!("unexpected GIF block {:#x}", b),
+            }
+        }
+    }
+
+    /// One step of Conway's Life.
+    fn life(cells: &HashSet<(i64, i64)>) -> HashSet<(i64, i64)> {
+        let mut counts: HashMap<(i64, i64), usize> = HashMap::new();
+        for &(x, y) in cells {
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    if (dx, dy) != (0, 0) {
+                        *counts.entry((x + dx, y + dy)).or_default() += 1;
+                    }
+                }
+            }
+        }
+        counts
+            .into_iter()
+            .filter(|&(p, n)| n == 3 || (n == 2 && cells.contains(&p)))
+            .map(|(p, _)| p)
+            .collect()
+    }
+
+    #[test]
+    fn glider() {
+        let mut cells: HashSet<(i64, i64)> =
+            [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)].into_iter().collect();
+        let path = temp_path("glider.gif");
+        let options = RecorderOptions {
+            render: RenderOptions::scaled(2),
+            loop_count: Some(3),
+            ..RecorderOptions::default()
+        };
+        let mut recorder = Recorder::new(&path, options);
+        for _ in 0..20 {
+            assert!(recorder.add_frame(&cells, Rgb::WHITE));
+            cells = life(&cells);
+        }
+        assert_eq!(recorder.finish().unwrap(), 20);
+        let (frames, loops) = decode_gif(&std::fs::read(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loops, Some(3));
+        assert_eq!(frames.len(), 20);
+        // Over 20 steps the glider moves 5 cells diagonally.
+        assert_eq!((frames[0].width(), frames[0].height()), (16, 16));
+        assert_eq!(frames[0].pixel(2, 0), Rgb::WHITE);
+        assert_eq!(frames[0].pixel(0, 0), Rgb::BLACK);
+        for frame in &frames {
+            let live = frame.pixels().iter().filter(|&&p| p == Rgb::WHITE);
+            assert_eq!(live.count(), 5 * 4);
+        }
+    }
+
+    #[test]
+    fn skip_and_crop() {
+        let path = temp_path("blinker.gif");
+        let options = RecorderOptions {
+            canvas: Canvas::Crop,
+            global_palette: false,
+            render: RenderOptions::scaled(1),
+            ..RecorderOptions::default()
+        };
+        let mut recorder = Recorder::new(&path, options);
+        let block: HashSet<(i64, i64)> =
+            [(0, 0), (1, 0), (0, 1), (1, 1)].into_iter().collect();
+        assert!(recorder.add_frame(&block, Rgb::RED));
+        assert!(!recorder.add_frame(&block, Rgb::RED));
+        // Cropped to the first frame, this is no change.
+        let mut grown = block.clone();
+        grown.insert((5, 5));
+        assert!(!recorder.add_frame(&grown, Rgb::RED));
+        let mut shrunk = block.clone();
+        shrunk.remove(&(0, 0));
+        assert!(recorder.add_frame(&shrunk, Rgb::RED));
+        assert!(recorder.add_frame(&block, Rgb::BLUE));
+        assert_eq!(recorder.len(), 3);
+        recorder.finish().unwrap();
+        let (frames, _) = decode_gif(&std::fs::read(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(frames.len(), 3);
+        assert!(frames.iter().all(|f| (f.width(), f.height()) == (2, 2)));
+        assert_eq!(frames[1].pixel(0, 0), Rgb::BLACK);
+        assert_eq!(frames[1].pixel(1, 1), Rgb::RED);
+        assert_eq!(frames[2].pixel(1, 1), Rgb::BLUE);
+    }
+
+    #[test]
+    fn follow_walker() {
+        // Walls every 100 cells across a big sparse map.
+        let mut map: HashMap<(i64, i64), char> = HashMap::new();
+        for i in (0..1000).step_by(100) {
+            for j in 0..1000 {
+                map.insert((i, j), '#');
+                map.insert((j, i), '#');
+            }
+        }
+        let palette = |&c: &char| match c {
+            '@' => Rgb::RED,
+            _ => Rgb::GRAY,
+        };
+        let path = temp_path("walker.gif");
+        let options = RecorderOptions {
+            render: RenderOptions::scaled(1),
+            skip_identical: false,
+            ..RecorderOptions::default()
+        };
+        let mut recorder = Recorder::new(&path, options);
+        for step in 0..300 {
+            let walker = (3 * step + 7, 3 * step + 1);
+            let under = map.insert(walker, '@');
+            recorder.set_viewport(Viewport::Follow {
+                center: (walker.1, walker.0),
+                half_extent: (5, 5),
+            });
+            recorder.add_frame(&map, palette);
+            match under {
+                Some(c) => map.insert(walker, c),
+                None => map.remove(&walker),
+            };
+        }
+        recorder.finish().unwrap();
+        let (frames, _) = decode_gif(&std::fs::read(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(frames.len(), 300);
+        for frame in &frames {
+            assert_eq!((frame.width(), frame.height()), (10, 10));
+            assert_eq!(frame.pixel(5, 5), Rgb::RED);
+        }
+        let walls = frames.iter().filter(|f| f.pixels().contains(&Rgb::GRAY));
+        assert!(walls.count() > 0);
+    }
+
+    #[test]
+    fn captioned_grid() {
+        let path = temp_path("captioned.gif");
+        let options = RecorderOptions {
+            render: RenderOptions {
+                grid_lines: Some(Rgb::GRAY),
+                ..RenderOptions::scaled(8)
+            },
+            ..RecorderOptions::default()
+        };
+        let mut recorder = Recorder::new(&path, options);
+        let mut cells: HashSet<(i64, i64)> = HashSet::new();
+        for step in 0..3 {
+            cells.insert((step, step));
+            let caption = format!("{}", step);
+            recorder.add_frame_with(&cells, Rgb::WHITE, |frame| {
+                draw_text(frame, (1, 1), &caption, Rgb::RED);
+            });
+        }
+        recorder.finish().unwrap();
+        let (frames, _) = decode_gif(&std::fs::read(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(frames.len(), 3);
+        for frame in &frames {
+            assert_eq!((frame.width(), frame.height()), (28, 28));
+        }
+        // The first frame covers only the top left cell.
+        assert_eq!(frames[0].pixel(0, 20), Rgb::BLACK);
+        assert_eq!(frames[2].pixel(0, 20), Rgb::GRAY);
+        assert_eq!(frames[2].pixel(22, 22), Rgb::WHITE);
+        // Top of the "1" and "2" glyphs.
+        assert_eq!(frames[1].pixel(3, 1), Rgb::RED);
+        assert_eq!(frames[2].pixel(1, 1), Rgb::WHITE);
+        assert_eq!(frames[2].pixel(2, 1), Rgb::RED);
+    }
+
+    #[test]
+    fn lzw_round_trip() {
+        let mut x = 0x2468_ace1u32;
+        let cases = [(0, 2), (1, 2), (10_000, 2), (50_000, 256), (30_000, 16)];
+        for (len, ncolors) in cases {
+            let indices: Vec<u8> = (0..len)
+                .map(|i| {
+                    x ^= x << 13;
+                    x ^= x >> 17;
+                    x ^= x << 5;
+                    if i % 3 == 0 { (x % ncolors) as u8 } else { 0 }
+                })
+                .collect();
+            let bits = (ncolors as usize).next_power_of_two().trailing_zeros();
+            let min_code_size = bits.max(2);
+            let data = lzw_encode(&indices, min_code_size);
+            assert_eq!(lzw_decode(&data, min_code_size), indices);
+        }
+
+        // Short inputs, which can end just as the code size
+        // grows.
+        for len in 0..200 {
+            let indices: Vec<u8> = (0..len)
+                .map(|_| {
+                    x ^= x << 13;
+                    x ^= x >> 17;
+                    x ^= x << 5;
+                    if x.is_multiple_of(3) { (x >> 8) as u8 % 4 } else { 0 }
+                })
+                .collect();
+            let data = lzw_encode(&indices, 2);
+            assert_eq!(lzw_decode(&data, 2), indices, "length {}", len);
+        }
+    }
+
+    /// Offer 1000 frames of a 100-cell line filling up from
+    /// the left, starting over when full, returning the
+    /// recorder.
+    fn fill_line(options: RecorderOptions) -> Recorder {
+        let options = RecorderOptions {
+            render: RenderOptions::scaled(1),
+            ..options
+        };
+        let mut recorder = Recorder::new(temp_path("unused.gif"), options);
+        for step in 0..1000 {
+            let line: HashMap<(i64, i64), bool> =
+                (0..100).map(|x| ((x, 0), x <= step % 100)).collect();
+            recorder.add_frame(&line, |&on: &bool| {
+                if on { Rgb::WHITE } else { Rgb::BLACK }
+            });
+        }
+        recorder
+    }
+
+    #[test]
+    fn frame_policies() {
+        let recorder = fill_line(RecorderOptions::default());
+        assert_eq!(recorder.len(), 1000);
+
+        let recorder = fill_line(RecorderOptions {
+            every_nth: 7,
+            ..RecorderOptions::default()
+        });
+        assert_eq!(recorder.len(), 143);
+
+        // Kept at stride 16 once over 800 frames, from 0 to
+        // 992.
+        let recorder = fill_line(RecorderOptions {
+            max_frames: Some(100),
+            ..RecorderOptions::default()
+        });
+        assert_eq!(recorder.len(), 63);
+        assert!(recorder.frames.iter().all(|k| k.step % 16 == 0));
+        assert_eq!(recorder.frames.last().unwrap().step, 992);
+
+        let recorder = fill_line(RecorderOptions {
+            every_nth: 2,
+            max_frames: Some(100),
+            ..RecorderOptions::default()
+        });
+        assert_eq!(recorder.len(), 63);
+        assert_eq!(recorder.frames.last().unwrap().step, 496);
+
+        // Each step fills 1 cell of 100, so a 5% change takes
+        // 5 steps.
+        let recorder = fill_line(RecorderOptions {
+            min_change: 0.05,
+            ..RecorderOptions::default()
+        });
+        assert_eq!(recorder.len(), 200);
+    }
+
+    #[test]
+    fn lingering_last_frame() {
+        let path = temp_path("linger.gif");
+        let options = RecorderOptions {
+            delay_ms: 50,
+            render: RenderOptions::scaled(1),
+            ..RecorderOptions::default()
+        };
+        let mut recorder = Recorder::new(&path, options);
+        assert!(!recorder.set_last_delay(1000));
+        let mut cells: HashSet<(i64, i64)> = HashSet::new();
+        for x in 0..3 {
+            cells.insert((x, 0));
+            recorder.add_frame(&cells, Rgb::WHITE);
+        }
+        assert!(recorder.set_last_delay(2000));
+        recorder.finish().unwrap();
+        let (frames, _) = decode_gif_timed(&std::fs::read(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+        let delays: Vec<u16> = frames.iter().map(|&(_, d)| d).collect();
+        assert_eq!(delays, [5, 5, 200]);
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Checking renders against known-good images.
+//!
+//! A golden image is a buffer of RGBA bytes, as made by
+//! `Frame::to_rgba()`, usually embedded in a test with
+//! `include_bytes!()`. When a frame does not match, both
+//! images are written as PNG files to a temporary directory
+//! to look at, along with the frame as RGBA bytes to
+//! replace the golden image if the change was intended.
+//! For cheaper pinning, compare `Frame::checksum()` with a
+//! known value instead.
+//!
+//! # Examples
+//!
+//! ```
+//! # use maprender::*;
+//! let mut frame = Frame::new(2, 1, Rgb::BLACK);
+//! frame.set_pixel(1, 0, Rgb(250, 0, 0));
+//! let golden = [0, 0, 0, 255, 255, 0, 0, 255];
+//! assert_frame_matches_within(&frame, &golden, 5);
+//! ```
+
+use std::fs;
+use std::path::PathBuf;
+
+use crate::{encode_png, Frame, Rgb};
+
+/// Check that `frame` matches the `golden` RGBA image
+/// exactly. See `assert_frame_matches_within()`.
+///
+/// # Panics
+///
+/// Panics if the images do not match.
+#[track_caller]
+pub fn assert_frame_matches(frame: &Frame, golden: &[u8]) {
+    assert_frame_matches_within(frame, golden, 0);
+}
+
+/// Check that `frame` matches the `golden` RGBA image, which
+/// must be the same size, with each color channel within
+/// `tolerance` of the golden one. Alpha is ignored.
+///
+/// # Panics
+///
+/// Panics if the images do not match, after writing
+/// `actual.png`, `golden.png` and `actual.rgba` to a
+/// temporary directory and printing where.
+#[track_caller]
+pub fn assert_frame_matches_within(
+    frame: &Frame,
+    golden: &[u8],
+    tolerance: u8,
+) {
+    let (width, height) = (frame.width(), frame.height());
+    if golden.len() != 4 * width * height {
+        let dir = write_mismatch(frame, None);
+        panic!(
+            "golden image is {} bytes, but {}×{} frame needs {}; \
+             frame written to {}",
+            golden.len(),
+            width,
+            height,
+            4 * width * height,
+            dir.display(),
+        );
+    }
+    let expected = Frame::from_rgba(width, height, golden);
+    let close = |Rgb(r0, g0, b0): Rgb, Rgb(r1, g1, b1): Rgb| {
+        r0.abs_diff(r1) <= tolerance
+            && g0.abs_diff(g1) <= tolerance
+            && b0.abs_diff(b1) <= tolerance
+    };
+    let bad: Vec<usize> = frame
+        .pixels()
+        .iter()
+        .zip(expected.pixels())
+        .enumerate()
+        .filter(|&(_, (&p, &q))| !close(p, q))
+        .map(|(i, _)| i)
+        .collect();
+    if let Some(&first) = bad.first() {
+        let dir = write_mismatch(frame, Some(&expected));
+        let (x, y) = (first % width, first / width);
+        panic!(
+            "{} pixels differ from golden image, first at ({}, {}): \
+             {:?} vs {:?}; images written to {}",
+            bad.len(),
+            x,
+            y,
+            frame.pixel(x, y),
+            expected.pixel(x, y),
+            dir.display(),
+        );
+    }
+}
+
+/// Write the frame, and the golden image if any, to a
+/// temporary directory named for the frame, returning the
+/// directory.
+fn write_mismatch(frame: &Frame, golden: Option<&Frame>) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("maprender-golden-{:08x}", frame.checksum()));
+    let write = |name: &str, data: &[u8]| {
+        let path = dir.join(name);
+        match fs::write(&path, data) {
+            Ok(()) => eprintln!("wrote {}", path.display()),
+            Err(e) => eprintln!("could not write {}: {}", path.display(), e),
+        }
+    };
+    if let Err(e) = fs::create_dir_all(&dir) {
+        eprintln!("could not create {}: {}", dir.display(), e);
+    }
+    write("actual.rgba", &frame.to_rgba());
+    if frame.width() > 0 && frame.height() > 0 {
+        write("actual.png", &encode_png(frame));
+        if let Some(golden) = golden {
+            write("golden.png", &encode_png(golden));
+        }
+    }
+    dir
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::gif::tests::decode_gif;
+    use crate::png::tests::{decode_png, temp_path};
+    use crate::*;
+
+    use std::collections::HashSet;
+
+    use geom::Grid;
+
+    fn maze() -> Grid<char> {
+        let rows = ["#####", "#..##", "##..#", "#####"];
+        Grid::new(4, 5, rows.iter().flat_map(|r| r.chars()).collect())
+    }
+
+    fn walls() -> Palette<char> {
+        Palette::from_map([('#', Rgb::GRAY), ('.', Rgb::BLACK)])
+    }
+
+    #[test]
+    fn png_golden() {
+        let path = temp_path("golden.png");
+        let options = RenderOptions {
+            grid_lines: Some(Rgb::BLUE),
+            ..RenderOptions::scaled(2)
+        };
+        render_png_view(&path, &maze(), &options, walls()).unwrap();
+        let frame = decode_png(&fs::read(&path).unwrap());
+        fs::remove_file(&path).unwrap();
+        assert_frame_matches(&frame, include_bytes!("golden/maze.rgba"));
+    }
+
+    #[test]
+    fn gif_golden() {
+        let path = temp_path("golden.gif");
+        let options = RecorderOptions {
+            render: RenderOptions::scaled(3),
+            ..RecorderOptions::default()
+        };
+        let mut recorder = Recorder::new(&path, options);
+        let mut sand: HashSet<(i64, i64)> = HashSet::new();
+        for x in 0..3 {
+            sand.insert((x, x % 2));
+            recorder.add_frame_with(&sand, Rgb::YELLOW, |frame| {
+                draw_text(frame, (1, 0), "S", Rgb::RED);
+            });
+        }
+        recorder.finish().unwrap();
+        let (frames, _) = decode_gif(&fs::read(&path).unwrap());
+        fs::remove_file(&path).unwrap();
+        assert_frame_matches(&frames[2], include_bytes!("golden/sand.rgba"));
+    }
+
+    #[test]
+    fn diff_golden() {
+        let before = maze();
+        let mut after = maze();
+        after[(1, 3)] = '.';
+        after[(2, 1)] = '.';
+        let frame = render_diff(
+            &before,
+            &after,
+            walls(),
+            Highlight::Blend(Rgb::RED),
+            &RenderOptions::scaled(2),
+        );
+        assert_frame_matches(&frame, include_bytes!("golden/diff.rgba"));
+    }
+
+    #[test]
+    fn text_golden() {
+        let mut frame = Frame::new(text_width("AoC 42") + 2, 9, Rgb::BLUE);
+        draw_text(&mut frame, (1, 1), "AoC 42", Rgb::WHITE);
+        assert_frame_matches(&frame, include_bytes!("golden/text.rgba"));
+    }
+
+    #[test]
+    fn checksums() {
+        let stops = [(0.0, Rgb::BLUE), (0.5, Rgb::GREEN), (1.0, Rgb::RED)];
+        let heights = Grid::new(2, 5, (0..10).collect());
+        let heat = Palette::gradient(&stops).auto(&heights);
+        let bounds = heights.bounds().unwrap();
+        let options = RenderOptions::default();
+        let frame = render_frame(&heights, bounds, &options, &heat);
+        assert_eq!(frame.checksum(), 0xe1e2_69bf);
+
+        let empty = Frame::new(0, 0, Rgb::BLACK);
+        let thin = Frame::new(0, 1, Rgb::BLACK);
+        assert_ne!(empty.checksum(), thin.checksum());
+    }
+
+    #[test]
+    fn tolerance() {
+        let frame = render_frame(
+            &maze(),
+            ((0, 0), (3, 4)),
+            &RenderOptions::default(),
+            walls(),
+        );
+        let mut golden = frame.to_rgba();
+        assert_eq!(Frame::from_rgba(5, 4, &golden), frame);
+        golden[4] += 3;
+        golden[7] = 0;
+        assert_frame_matches_within(&frame, &golden, 3);
+        let strict = std::panic::catch_unwind(|| {
+            assert_frame_matches(&frame, &golden);
+        });
+        assert!(strict.is_err());
+    }
+}
@@ -5,6 +5,53 @@
 
 //! Map rendering for Advent of Code solutions.
 
+mod ansi;
+pub use ansi::*;
+
+mod braille;
+pub use braille::*;
+
+mod color;
+pub use color::*;
+
+mod deflate;
+
+mod diff;
+pub use diff::*;
+
+mod frame;
+pub use frame::*;
+
+mod gif;
+pub use gif::*;
+
+mod golden;
+pub use golden::*;
+
+mod ocr;
+pub use ocr::*;
+
+mod palette;
+pub use palette::*;
+
+mod points;
+pub use points::*;
+
+mod png;
+pub use png::*;
+
+mod source;
+pub use source::*;
+
+mod svg;
+pub use svg::*;
+
+mod text;
+pub use text::*;
+
+mod viewport;
+pub use viewport::*;
+
 use std::collections::{HashMap, HashSet};
 
 /// Compute the bounding box of a set of coordinates. The
@@ -0,0 +1,289 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Reading the block letters some puzzles draw as their
+//! answer.
+//!
+//! Both the usual letters 6 cells high and the rarer ones
+//! 10 cells high are known. Letters are found by splitting
+//! the map at blank columns.
+//!
+//! # Examples
+//!
+//! ```
+//! # use maprender::*;
+//! # use std::collections::HashSet;
+//! let screen = [
+//!     "#..#.###..",
+//!     "#..#..#...",
+//!     "####..#...",
+//!     "#..#..#...",
+//!     "#..#..#...",
+//!     "#..#.###..",
+//! ];
+//! let lit: HashSet<(i64, i64)> = screen
+//!     .iter()
+//!     .enumerate()
+//!     .flat_map(|(y, row)| {
+//!         row.match_indices('#').map(move |(x, _)| (x as i64, y as i64))
+//!     })
+//!     .collect();
+//! assert_eq!(ocr_letters(&lit), Some("HI".to_string()));
+//! ```
+
+use crate::{Paint, RenderSource};
+
+/// Character given for a letter that is not recognized.
+pub const UNKNOWN_LETTER: char = '?';
+
+/// A set of letters, each drawn as rows of `#` and `.`.
+type Letters = &'static [(char, &'static [&'static str])];
+
+/// Letters 6 cells high, mostly 4 wide.
+const SMALL_LETTERS: Letters = &[
+    ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', &["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Letters 10 cells high and 6 wide.
+const LARGE_LETTERS: Letters = &[
+    ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#",
+            "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', &["#####.", "#....#", "#....#", "#....#", "#####.",
+            "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', &[".####.", "#....#", "#.....", "#.....", "#.....",
+            "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', &["######", "#.....", "#.....", "#.....", "#####.",
+            "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', &["######", "#.....", "#.....", "#.....", "#####.",
+            "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', &[".####.", "#....#", "#.....", "#.....", "#.....",
+            "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', &["#....#", "#....#", "#....#", "#....#", "######",
+            "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', &["...###", "....#.", "....#.", "....#.", "....#.",
+            "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....",
+            "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', &["#.....", "#.....", "#.....", "#.....", "#.....",
+            "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#",
+            "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', &["#####.", "#....#", "#....#", "#....#", "#####.",
+            "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', &["#####.", "#....#", "#....#", "#....#", "#####.",
+            "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..",
+            "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', &["######", ".....#", ".....#", "....#.", "...#..",
+            "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+/// Known letter sets, by height.
+const FONTS: &[(usize, Letters)] = &[(6, SMALL_LETTERS), (10, LARGE_LETTERS)];
+
+/// Columns of a letter drawn as rows of `#` and `.`, each
+/// as a bitmask with bit `i` set if row `i` is lit.
+fn letter_columns(rows: &[&str]) -> Vec<u16> {
+    let mut columns = vec![0; rows[0].len()];
+    for (i, row) in rows.iter().enumerate() {
+        for (col, c) in columns.iter_mut().zip(row.bytes()) {
+            if c == b'#' {
+                *col |= 1 << i;
+            }
+        }
+    }
+    columns
+}
+
+/// Read the letters of a run of non-blank columns onto the
+/// end of `text`. Letters may touch, so the longest letter
+/// matching the start of the run is taken each time.
+fn read_run(letters: Letters, run: &[u16], text: &mut String) {
+    let letters: Vec<(char, Vec<u16>)> = letters
+        .iter()
+        .map(|&(c, rows)| (c, letter_columns(rows)))
+        .collect();
+    let mut rest = run;
+    while !rest.is_empty() {
+        let best = letters
+            .iter()
+            .filter(|(_, columns)| rest.starts_with(columns))
+            .max_by_key(|(_, columns)| columns.len());
+        match best {
+            Some((c, columns)) => {
+                text.push(*c);
+                rest = &rest[columns.len()..];
+            }
+            None => {
+                text.push(UNKNOWN_LETTER);
+                return;
+            }
+        }
+    }
+}
+
+/// Read the letters drawn by the cells of a map, counting
+/// every cell present as lit. Returns `None` if nothing is
+/// lit or the lit rows are not the height of any known
+/// letters. Letters not recognized are read as
+/// `UNKNOWN_LETTER`.
+pub fn ocr_letters<S>(view: &S) -> Option<String>
+where
+    S: RenderSource + ?Sized,
+{
+    ocr_letters_with(view, true)
+}
+
+/// Read the letters drawn by the cells of a map, as for
+/// `ocr_letters()`, but with a cell lit only if `lit` says
+/// so: say `|&c: &char| c == '#'` for a grid of `#` and
+/// `.`.
+pub fn ocr_letters_with<S, P>(view: &S, lit: P) -> Option<String>
+where
+    S: RenderSource + ?Sized,
+    P: Paint<S::Cell, bool>,
+{
+    let ((r0, c0), (r1, c1)) = view.bounds()?;
+    let is_lit = |r, c| view.cell(r, c).is_some_and(|v| lit.paint(&v));
+    let lit_rows: Vec<i64> = (r0..=r1)
+        .filter(|&r| (c0..=c1).any(|c| is_lit(r, c)))
+        .collect();
+    let (top, bottom) = (*lit_rows.first()?, *lit_rows.last()?);
+    let height = (bottom - top + 1) as usize;
+    let &(_, letters) = FONTS.iter().find(|&&(h, _)| h == height)?;
+
+    let columns: Vec<u16> = (c0..=c1)
+        .map(|c| {
+            (top..=bottom)
+                .enumerate()
+                .filter(|&(_, r)| is_lit(r, c))
+                .fold(0, |mask, (i, _)| mask | 1 << i)
+        })
+        .collect();
+    let mut text = String::new();
+    for run in columns.split(|&mask| mask == 0) {
+        read_run(letters, run, &mut text);
+    }
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::render_points_ascii;
+
+    use std::collections::HashSet;
+
+    use geom::Grid;
+
+    /// Grid of the characters of the given lines.
+    fn screen(lines: &[&str]) -> Grid<char> {
+        let cells = lines.iter().flat_map(|l| l.chars()).collect();
+        Grid::new(lines.len(), lines[0].len(), cells)
+    }
+
+    /// Letters drawn in a row from `font`, `gap` columns
+    /// apart.
+    fn draw(font: Letters, word: &str, gap: usize) -> Vec<String> {
+        let height = font[0].1.len();
+        let mut lines = vec![String::new(); height];
+        for c in word.chars() {
+            let (_, rows) = font.iter().find(|&&(l, _)| l == c).unwrap();
+            for (line, row) in lines.iter_mut().zip(rows.iter()) {
+                line.push_str(row);
+                line.push_str(&".".repeat(gap));
+            }
+        }
+        lines
+    }
+
+    #[test]
+    fn tables() {
+        for &(height, letters) in FONTS {
+            for (i, &(c, rows)) in letters.iter().enumerate() {
+                assert_eq!(rows.len(), height, "{}", c);
+                assert!(rows.iter().all(|r| r.len() == rows[0].len()));
+                let columns = letter_columns(rows);
+                assert!(columns.iter().all(|&m| m != 0), "{}", c);
+                for &(d, other) in &letters[..i] {
+                    assert_ne!(columns, letter_columns(other), "{} {}", c, d);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn small_letters() {
+        let word = "HKUJGAJZ";
+        let lines = draw(SMALL_LETTERS, word, 1);
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let lit = |&c: &char| c == '#';
+        assert_eq!(ocr_letters_with(&screen(&lines), lit).unwrap(), word);
+
+        // Every letter, with Y touching its neighbor.
+        let all = "ABCEFGHIJKLOPRSUYZ";
+        let spaced = draw(SMALL_LETTERS, &all[..16], 1);
+        let touching = draw(SMALL_LETTERS, &all[16..], 0);
+        let lines: Vec<String> = spaced
+            .iter()
+            .zip(&touching)
+            .map(|(a, b)| format!("{}{}", a, b))
+            .collect();
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        assert_eq!(ocr_letters_with(&screen(&lines), lit).unwrap(), all);
+    }
+
+    #[test]
+    fn unknown_and_unreadable() {
+        let lines = [
+            "#..#.#...#.####",
+            "#..#.##.##.#...",
+            "####.#.#.#.###.",
+            "#..#.#...#.#...",
+            "#..#.#...#.#...",
+            "#..#.#...#.####",
+        ];
+        let lit = |&c: &char| c == '#';
+        let text = ocr_letters_with(&screen(&lines), lit).unwrap();
+        assert_eq!(text, "H?E");
+        assert_eq!(ocr_letters_with(&screen(&lines[..5]), lit), None);
+        assert_eq!(ocr_letters(&HashSet::new()), None);
+    }
+
+    #[test]
+    fn large_letters() {
+        let lines = draw(LARGE_LETTERS, "NZXR", 2);
+        let points: HashSet<(i64, i64)> = lines
+            .iter()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.match_indices('#')
+                    .map(move |(x, _)| (x as i64 + 100, y as i64 - 50))
+            })
+            .collect();
+        assert_eq!(ocr_letters(&points).unwrap(), "NZXR");
+        let text = render_points_ascii(points.iter().copied());
+        let top = &lines[0][..lines[0].len() - 2];
+        assert_eq!(text.lines().next().unwrap(), top);
+    }
+}
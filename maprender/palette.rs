@@ -0,0 +1,506 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Mapping cell values to colors.
+//!
+//! Every renderer takes a `Paint` saying how to color a
+//! cell. A `Palette`, a closure over a reference to the
+//! cell, or a single color for every cell will do.
+//! Closures need their parameter type written out, since
+//! they are not passed where an `Fn` is expected.
+//!
+//! # Examples
+//!
+//! ```
+//! # use maprender::*;
+//! let walls = Palette::from_map([('#', Rgb::GRAY), ('.', Rgb::BLACK)]);
+//! assert_eq!(walls.color(&'#'), Rgb::GRAY);
+//! assert_eq!(walls.color(&'?'), Rgb::MAGENTA);
+//!
+//! let heights = Palette::grayscale(0u8, 9);
+//! assert_eq!(heights.color(&9), Rgb::WHITE);
+//!
+//! let stops = [(0.0, Rgb::BLUE), (1.0, Rgb::RED)];
+//! let heat = Palette::gradient(&stops).fixed(0, 100);
+//! assert_eq!(heat.color(&50), Rgb(128, 0, 128));
+//!
+//! let by_hand = |&c: &char| if c == '#' { Rgb::GRAY } else { Rgb::BLACK };
+//! assert_eq!(by_hand.paint(&'#'), walls.color(&'#'));
+//! assert_eq!(Rgb::RED.paint(&'#'), Rgb::RED);
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use crate::{AnsiStyle, RenderSource, Rgb};
+
+/// A way of coloring cells of type `T` with colors of type
+/// `C`: either `Rgb` for images or `AnsiStyle` for the
+/// terminal.
+pub trait Paint<T: ?Sized, C = Rgb> {
+    /// Color of the given cell.
+    fn paint(&self, cell: &T) -> C;
+}
+
+impl<T: ?Sized, C, F> Paint<T, C> for F
+where
+    F: Fn(&T) -> C,
+{
+    fn paint(&self, cell: &T) -> C {
+        self(cell)
+    }
+}
+
+/// Every cell is this color.
+impl<T: ?Sized> Paint<T> for Rgb {
+    fn paint(&self, _: &T) -> Rgb {
+        *self
+    }
+}
+
+/// Every cell is drawn this way.
+impl<T: ?Sized> Paint<T, AnsiStyle> for AnsiStyle {
+    fn paint(&self, _: &T) -> AnsiStyle {
+        *self
+    }
+}
+
+/// Numbers that can be shaded by `Palette::grayscale()`.
+pub trait Scalar: Copy {
+    /// The number as a float.
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! scalar {
+    ($($t:ty),*) => {
+        $(impl Scalar for $t {
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+scalar!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+/// Colors handed out in order by `Palette::categorical()`,
+/// chosen to be easy to tell apart.
+pub const CATEGORICAL_COLORS: [Rgb; 10] = [
+    Rgb(31, 119, 180),
+    Rgb(255, 127, 14),
+    Rgb(44, 160, 44),
+    Rgb(214, 39, 40),
+    Rgb(148, 103, 189),
+    Rgb(140, 86, 75),
+    Rgb(227, 119, 194),
+    Rgb(127, 127, 127),
+    Rgb(188, 189, 34),
+    Rgb(23, 190, 207),
+];
+
+/// The `n`-th categorical color. Past the end of
+/// `CATEGORICAL_COLORS`, hues are spread around the color
+/// wheel by the golden ratio.
+fn categorical_color(n: usize) -> Rgb {
+    if let Some(&color) = CATEGORICAL_COLORS.get(n) {
+        return color;
+    }
+    let hue = (n as f64 * 0.618_033_988_75).fract() * 6.0;
+    let (v, s) = (0.9, 0.7);
+    let f = hue.fract();
+    let p = v * (1.0 - s);
+    let q = v * (1.0 - s * f);
+    let t = v * (1.0 - s * (1.0 - f));
+    let (r, g, b) = match hue as u32 {
+        0 => (v, t, p),
+        1 => (q, v, p),
+        2 => (p, v, t),
+        3 => (p, q, v),
+        4 => (t, p, v),
+        _ => (v, p, q),
+    };
+    let channel = |x: f64| (x * 255.0).round() as u8;
+    Rgb(channel(r), channel(g), channel(b))
+}
+
+/// Color of a value, if the palette covers it.
+type Rule<T> = Box<dyn Fn(&T) -> Option<Rgb>>;
+
+/// A mapping from cell values to colors, with a fallback
+/// color for values it does not cover.
+///
+/// A categorical palette assigns colors as values are first
+/// seen, so the same palette should be used for every frame
+/// of an animation to keep the colors stable.
+pub struct Palette<T> {
+    rule: Rule<T>,
+    fallback: Rgb,
+}
+
+impl<T> fmt::Debug for Palette<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Palette")
+            .field("fallback", &self.fallback)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Eq + Hash + 'static> Palette<T> {
+    /// Palette giving the listed colors to the listed
+    /// values.
+    pub fn from_map<I>(colors: I) -> Self
+    where
+        I: IntoIterator<Item = (T, Rgb)>,
+    {
+        let colors: HashMap<T, Rgb> = colors.into_iter().collect();
+        Palette::new(move |v| colors.get(v).copied())
+    }
+}
+
+impl<T: Eq + Hash + Clone + 'static> Palette<T> {
+    /// Palette giving each new value the next of the
+    /// `CATEGORICAL_COLORS` (and then more) as it is first
+    /// seen. A value keeps its color for the life of the
+    /// palette.
+    pub fn categorical() -> Self {
+        let seen: RefCell<HashMap<T, Rgb>> = RefCell::new(HashMap::new());
+        Palette::new(move |v: &T| {
+            let mut seen = seen.borrow_mut();
+            let n = seen.len();
+            let color = seen.entry(v.clone()).or_insert_with(|| {
+                categorical_color(n)
+            });
+            Some(*color)
+        })
+    }
+}
+
+impl<T: Scalar + 'static> Palette<T> {
+    /// Palette shading values from black at `min` to white
+    /// at `max`, clamped outside that range. If `min` and
+    /// `max` are equal, everything is white.
+    pub fn grayscale(min: T, max: T) -> Self {
+        let (min, max) = (min.to_f64(), max.to_f64());
+        Palette::new(move |&v: &T| {
+            let v = v.to_f64();
+            let frac = if max > min {
+                ((v - min) / (max - min)).clamp(0.0, 1.0)
+            } else {
+                1.0
+            };
+            let level = (frac * 255.0).round() as u8;
+            Some(Rgb(level, level, level))
+        })
+    }
+}
+
+/// A palette in the making that shades numbers along a
+/// color gradient; see `Palette::gradient()`. It becomes a
+/// palette once the range of values to shade is known.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient<T> {
+    /// Positions and colors, sorted by position.
+    stops: Vec<(f64, Rgb)>,
+    log: bool,
+    values: PhantomData<fn(&T)>,
+}
+
+impl<T: Scalar + 'static> Gradient<T> {
+    /// This gradient on a log scale, for heavy-tailed values
+    /// such as visit counts. Values are offset so that the
+    /// minimum is 0, then shaded by the log of one more than
+    /// that.
+    pub fn log_scale(self) -> Self {
+        Gradient { log: true, ..self }
+    }
+
+    /// Color at position `t` along the gradient, linearly
+    /// interpolated between the stops on either side. The
+    /// color is that of the end stop past either end.
+    pub fn color_at(&self, t: f64) -> Rgb {
+        let stops = &self.stops;
+        let i = stops.partition_point(|&(p, _)| p <= t);
+        if i == 0 {
+            return stops[0].1;
+        }
+        if i == stops.len() {
+            return stops[i - 1].1;
+        }
+        let ((p0, Rgb(r0, g0, b0)), (p1, Rgb(r1, g1, b1))) =
+            (stops[i - 1], stops[i]);
+        let frac = (t - p0) / (p1 - p0);
+        let mix = |x: u8, y: u8| {
+            (x as f64 + (y as f64 - x as f64) * frac).round() as u8
+        };
+        Rgb(mix(r0, r1), mix(g0, g1), mix(b0, b1))
+    }
+
+    /// Palette shading values from `min` at position 0 along
+    /// the gradient to `max` at position 1. Give the same
+    /// range for every frame of an animation so that colors
+    /// do not shift. If `min` and `max` are equal,
+    /// everything is at position 1.
+    pub fn fixed(self, min: T, max: T) -> Palette<T> {
+        let (min, max) = (min.to_f64(), max.to_f64());
+        let scale = move |v: f64| {
+            let v = (v - min).max(0.0);
+            if self.log {
+                v.ln_1p()
+            } else {
+                v
+            }
+        };
+        let top = scale(max);
+        Palette::new(move |&v: &T| {
+            let t = if top > 0.0 { scale(v.to_f64()) / top } else { 1.0 };
+            Some(self.color_at(t))
+        })
+    }
+
+    /// Palette shading the values of the given map from its
+    /// smallest at position 0 along the gradient to its
+    /// largest at position 1, as for `fixed()`.
+    pub fn auto<S>(self, view: &S) -> Palette<T>
+    where
+        S: RenderSource<Cell = T> + ?Sized,
+    {
+        let ((r0, c0), (r1, c1)) =
+            view.bounds().unwrap_or(((0, 0), (-1, -1)));
+        let mut range: Option<(T, T)> = None;
+        for row in r0..=r1 {
+            for col in c0..=c1 {
+                let Some(v) = view.cell(row, col) else {
+                    continue;
+                };
+                let x = v.to_f64();
+                range = Some(match range {
+                    None => (v, v),
+                    Some((lo, hi)) if x < lo.to_f64() => (v, hi),
+                    Some((lo, hi)) if x > hi.to_f64() => (lo, v),
+                    Some(r) => r,
+                });
+            }
+        }
+        match range {
+            Some((min, max)) => self.fixed(min, max),
+            None => Palette::new(|_| None),
+        }
+    }
+}
+
+impl<T: Scalar + 'static> Palette<T> {
+    /// Start a palette shading numbers along a gradient
+    /// through the given colors at the given positions, which
+    /// normally run from 0 to 1. Finish it with
+    /// `Gradient::fixed()` or `Gradient::auto()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no stops.
+    pub fn gradient(stops: &[(f64, Rgb)]) -> Gradient<T> {
+        assert!(!stops.is_empty(), "gradient without stops");
+        let mut stops = stops.to_vec();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Gradient {
+            stops,
+            log: false,
+            values: PhantomData,
+        }
+    }
+}
+
+impl<T> Palette<T> {
+    /// Palette coloring by `rule`, where a rule result of
+    /// `None` gives the fallback color.
+    pub fn new<F>(rule: F) -> Self
+    where
+        F: Fn(&T) -> Option<Rgb> + 'static,
+    {
+        Palette {
+            rule: Box::new(rule),
+            fallback: Rgb::MAGENTA,
+        }
+    }
+
+    /// This palette with the given fallback color, in place
+    /// of the default `Rgb::MAGENTA`.
+    pub fn with_fallback(self, fallback: Rgb) -> Self {
+        Palette { fallback, ..self }
+    }
+
+    /// The fallback color.
+    pub fn fallback(&self) -> Rgb {
+        self.fallback
+    }
+
+    /// Color of the given value.
+    pub fn color(&self, v: &T) -> Rgb {
+        (self.rule)(v).unwrap_or(self.fallback)
+    }
+}
+
+impl<T> Paint<T> for Palette<T> {
+    fn paint(&self, cell: &T) -> Rgb {
+        self.color(cell)
+    }
+}
+
+/// Cells are drawn as solid blocks in the palette color.
+impl<T> Paint<T, AnsiStyle> for Palette<T> {
+    fn paint(&self, cell: &T) -> AnsiStyle {
+        AnsiStyle::new('█').fg(self.color(cell))
+    }
+}
+
+impl<T, C> Paint<T, C> for &Palette<T>
+where
+    Palette<T>: Paint<T, C>,
+{
+    fn paint(&self, cell: &T) -> C {
+        (*self).paint(cell)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{render_ansi, render_frame, RenderOptions};
+
+    use geom::Grid;
+
+    use std::collections::HashMap;
+
+    #[test]
+    fn categorical_stable() {
+        let palette = Palette::categorical();
+        let first: HashMap<(i64, i64), char> =
+            [((0, 0), 'a'), ((1, 0), 'b'), ((2, 0), 'c')]
+                .into_iter()
+                .collect();
+        let second: HashMap<(i64, i64), char> =
+            [((0, 0), 'c'), ((1, 0), 'd'), ((2, 0), 'a')]
+                .into_iter()
+                .collect();
+        let bounds = ((0, 0), (0, 2));
+        let options = RenderOptions::default();
+        let f1 = render_frame(&first, bounds, &options, &palette);
+        let f2 = render_frame(&second, bounds, &options, &palette);
+        assert_eq!(f1.pixel(0, 0), f2.pixel(2, 0));
+        assert_eq!(f1.pixel(2, 0), f2.pixel(0, 0));
+        let colors = [f1.pixel(0, 0), f1.pixel(1, 0), f1.pixel(2, 0)];
+        assert_eq!(colors, CATEGORICAL_COLORS[..3]);
+        assert_eq!(f2.pixel(1, 0), CATEGORICAL_COLORS[3]);
+        assert_eq!(palette.color(&'b'), CATEGORICAL_COLORS[1]);
+    }
+
+    #[test]
+    fn many_categories() {
+        let palette = Palette::categorical();
+        let colors: Vec<Rgb> = (0..100).map(|i| palette.color(&i)).collect();
+        for (i, c) in colors.iter().enumerate() {
+            assert_eq!(palette.color(&i), *c);
+            assert!(!colors[..i].contains(c), "color {} repeated", i);
+        }
+    }
+
+    #[test]
+    fn grayscale_and_fallback() {
+        let heights = Palette::grayscale(-10i64, 10);
+        assert_eq!(heights.color(&-20), Rgb::BLACK);
+        assert_eq!(heights.color(&0), Rgb(128, 128, 128));
+        assert_eq!(heights.color(&10), Rgb::WHITE);
+        let flat = Palette::grayscale(1.5, 1.5);
+        assert_eq!(flat.color(&0.0), Rgb::WHITE);
+
+        let walls = Palette::from_map([('#', Rgb::WHITE)])
+            .with_fallback(Rgb::BLUE);
+        assert_eq!(walls.fallback(), Rgb::BLUE);
+        assert_eq!(walls.color(&'.'), Rgb::BLUE);
+        let map: HashMap<(i64, i64), char> =
+            [((0, 0), '#'), ((1, 0), '.')].into_iter().collect();
+        let text = render_ansi(&map, &walls);
+        let white = "\x1b[38;2;255;255;255m█";
+        let blue = "\x1b[38;2;0;0;255m█";
+        assert_eq!(text, format!("{}\x1b[0m{}\x1b[0m\n", white, blue));
+    }
+
+    #[test]
+    fn gradient_stops() {
+        let stops = [(1.0, Rgb::WHITE), (0.0, Rgb::BLACK), (0.5, Rgb::RED)];
+        let gradient = Palette::<f64>::gradient(&stops);
+        assert_eq!(gradient.color_at(-1.0), Rgb::BLACK);
+        assert_eq!(gradient.color_at(0.0), Rgb::BLACK);
+        assert_eq!(gradient.color_at(0.25), Rgb(128, 0, 0));
+        assert_eq!(gradient.color_at(0.5), Rgb::RED);
+        assert_eq!(gradient.color_at(0.75), Rgb(255, 128, 128));
+        assert_eq!(gradient.color_at(1.0), Rgb::WHITE);
+        assert_eq!(gradient.color_at(2.0), Rgb::WHITE);
+        let one = Palette::<f64>::gradient(&[(0.3, Rgb::BLUE)]);
+        assert_eq!(one.color_at(0.0), Rgb::BLUE);
+        assert_eq!(one.color_at(1.0), Rgb::BLUE);
+    }
+
+    #[test]
+    fn gradient_normalization() {
+        let stops = [(0.0, Rgb::BLACK), (1.0, Rgb::WHITE)];
+        let first = Grid::new(1, 3, vec![0u32, 5, 10]);
+        let second = Grid::new(1, 3, vec![0u32, 10, 20]);
+        let options = RenderOptions::default();
+        let bounds = ((0, 0), (0, 2));
+
+        // Auto normalization stretches each frame to the full
+        // gradient, so the same value changes color.
+        let auto1 = Palette::gradient(&stops).auto(&first);
+        let auto2 = Palette::gradient(&stops).auto(&second);
+        let f1 = render_frame(&first, bounds, &options, &auto1);
+        let f2 = render_frame(&second, bounds, &options, &auto2);
+        assert_eq!(f1.pixel(2, 0), Rgb::WHITE);
+        assert_eq!(f2.pixel(2, 0), Rgb::WHITE);
+        assert_eq!(f2.pixel(1, 0), Rgb(128, 128, 128));
+
+        // A fixed range keeps colors stable.
+        let fixed = Palette::gradient(&stops).fixed(0, 20);
+        let f1 = render_frame(&first, bounds, &options, &fixed);
+        let f2 = render_frame(&second, bounds, &options, &fixed);
+        assert_eq!(f1.pixel(2, 0), f2.pixel(1, 0));
+        assert_eq!(f1.pixel(2, 0), Rgb(128, 128, 128));
+        assert_eq!(f2.pixel(2, 0), Rgb::WHITE);
+
+        let log = Palette::gradient(&stops).log_scale().fixed(0u64, 9999);
+        assert_eq!(log.color(&0), Rgb::BLACK);
+        assert_eq!(log.color(&99), Rgb(128, 128, 128));
+        assert_eq!(log.color(&9999), Rgb::WHITE);
+    }
+
+    #[test]
+    fn distance_map() {
+        // Distances from a search are sparse: unreached cells
+        // are missing, and stay the background color.
+        let dist: HashMap<(i64, i64), usize> = (0..5)
+            .filter(|&x| x != 3)
+            .map(|x| ((x, 0), x as usize * 2))
+            .collect();
+        let heat = [(0.0, Rgb::BLUE), (1.0, Rgb::RED)];
+        let options = RenderOptions::default();
+        let frame = render_frame(
+            &dist,
+            ((0, 0), (0, 4)),
+            &options,
+            Palette::gradient(&heat).auto(&dist),
+        );
+        let colors = [
+            Rgb::BLUE,
+            Rgb(64, 0, 191),
+            Rgb(128, 0, 128),
+            options.background,
+            Rgb::RED,
+        ];
+        assert_eq!(frame.pixels(), colors);
+    }
+}
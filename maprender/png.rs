@@ -0,0 +1,225 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Rendering maps to PNG image files.
+//!
+//! # Examples
+//!
+//! Render the dots of the 2021 day 13 example after
+//! folding, at 10 pixels per dot.
+//!
+//! ```no_run
+//! # use maprender::*;
+//! # use std::collections::HashMap;
+//! # let dots: Vec<(i64, i64)> = vec![];
+//! let paper: HashMap<_, _> = dots.iter().map(|&d| (d, ())).collect();
+//! let options = RenderOptions::scaled(10);
+//! render_png_view("code.png", &paper, &options, Rgb::WHITE)?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::io;
+use std::path::Path;
+
+use crate::deflate::{crc32, zlib_compress};
+use crate::{render_frame, Frame, Paint, RenderOptions, RenderSource, Rgb};
+
+/// Append a PNG chunk.
+fn push_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// Encode a frame as an 8-bit RGB PNG image.
+///
+/// # Panics
+///
+/// Panics if the frame has no pixels, which PNG does not
+/// allow.
+pub fn encode_png(frame: &Frame) -> Vec<u8> {
+    let (width, height) = (frame.width(), frame.height());
+    assert!(width > 0 && height > 0, "encode_png: empty frame");
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+
+    let mut header = Vec::with_capacity(13);
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    // 8-bit RGB, standard compression and filtering, no
+    // interlace.
+    header.extend([8, 2, 0, 0, 0]);
+    push_chunk(&mut png, b"IHDR", &header);
+
+    let mut raw = Vec::with_capacity((3 * width + 1) * height);
+    for row in frame.pixels().chunks(width) {
+        // No filtering.
+        raw.push(0);
+        for &Rgb(r, g, b) in row {
+            raw.extend([r, g, b]);
+        }
+    }
+    push_chunk(&mut png, b"IDAT", &zlib_compress(&raw));
+    push_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+/// Write a frame to the given path as a PNG image.
+pub fn write_png<P: AsRef<Path>>(path: P, frame: &Frame) -> io::Result<()> {
+    if frame.width() == 0 || frame.height() == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "empty PNG image",
+        ));
+    }
+    std::fs::write(path, encode_png(frame))
+}
+
+/// Write a PNG image of a `width` by `height` array of
+/// cells, with `cell(row, col)` giving the color of each
+/// and each cell drawn `scale` pixels square.
+pub fn render_png<P, F>(
+    path: P,
+    width: usize,
+    height: usize,
+    scale: usize,
+    cell: F,
+) -> io::Result<()>
+where
+    P: AsRef<Path>,
+    F: Fn(usize, usize) -> Rgb,
+{
+    write_png(path, &Frame::from_cells(width, height, scale, cell))
+}
+
+/// Write a PNG image of a map, just covering its bounds,
+/// drawn as given by `options`. Cells with a value are
+/// colored by `palette`. An empty map is an error, since
+/// PNG does not allow empty images.
+pub fn render_png_view<P, S, Q>(
+    path: P,
+    view: &S,
+    options: &RenderOptions,
+    palette: Q,
+) -> io::Result<()>
+where
+    P: AsRef<Path>,
+    S: RenderSource + ?Sized,
+    Q: Paint<S::Cell>,
+{
+    let frame = match view.bounds() {
+        Some(bounds) => render_frame(view, bounds, options, palette),
+        None => Frame::new(0, 0, options.background),
+    };
+    write_png(path, &frame)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+
+    use super::*;
+    use crate::deflate::zlib_decompress;
+
+    use std::collections::HashMap;
+
+    use geom::Grid;
+
+    /// Decode a PNG made by `encode_png()`.
+    pub(crate) fn decode_png(png: &[u8]) -> Frame {
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        let mut posn = 8;
+        let (mut width, mut height) = (0, 0);
+        let mut idat = Vec::new();
+        loop {
+            let len =
+                u32::from_be_bytes(png[posn..posn + 4].try_into().unwrap());
+            let len = len as usize;
+            let kind = &png[posn + 4..posn + 8];
+            let data = &png[posn + 8..posn + 8 + len];
+            let crc = &png[posn + 8 + len..posn + 12 + len];
+            let check = crc32(&png[posn + 4..posn + 8 + len]);
+            assert_eq!(crc, check.to_be_bytes());
+            match kind {
+                b"IHDR" => {
+                    width = u32::from_be_bytes(data[..4].try_into().unwrap());
+                    height = u32::from_be_bytes(data[4..8].try_into().unwrap());
+                    assert_eq!(&data[8..], [8, 2, 0, 0, 0]);
+                }
+                b"IDAT" => idat.extend_from_slice(data),
+                b"IEND" => break,
+                _ => panic!("unexpected chunk"),
+            }
+            posn += 12 + len;
+        }
+        let (width, height) = (width as usize, height as usize);
+        let raw = zlib_decompress(&idat);
+        assert_eq!(raw.len(), (3 * width + 1) * height);
+        let mut frame = Frame::new(width, height, Rgb::BLACK);
+        for (y, row) in raw.chunks(3 * width + 1).enumerate() {
+            assert_eq!(row[0], 0);
+            for (x, p) in row[1..].chunks(3).enumerate() {
+                frame.set_pixel(x, y, Rgb(p[0], p[1], p[2]));
+            }
+        }
+        frame
+    }
+
+    /// Path for a scratch file in the temp directory.
+    pub(crate) fn temp_path(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir()
+            .join(format!("maprender-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join(name)
+    }
+
+    #[test]
+    fn folded_paper() {
+        // 2021 day 13 example, after both folds.
+        let dots = [
+            (6, 10), (0, 14), (9, 10), (0, 3), (10, 4), (4, 11), (6, 0),
+            (6, 12), (4, 1), (0, 13), (10, 12), (3, 4), (3, 0), (8, 4),
+            (1, 10), (2, 14), (8, 10), (9, 0),
+        ];
+        let fold = |v: i64, at: i64| if v > at { 2 * at - v } else { v };
+        let paper: HashMap<(i64, i64), ()> = dots
+            .iter()
+            .map(|&(x, y)| ((fold(x, 5), fold(y, 7)), ()))
+            .collect();
+        assert_eq!(paper.len(), 16);
+
+        let path = temp_path("paper.png");
+        let options = RenderOptions::scaled(3);
+        render_png_view(&path, &paper, &options, Rgb::WHITE).unwrap();
+        let frame = decode_png(&std::fs::read(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+        // The folded paper shows a 5×5 square outline.
+        assert_eq!((frame.width(), frame.height()), (15, 15));
+        assert_eq!(frame.pixel(0, 0), Rgb::WHITE);
+        assert_eq!(frame.pixel(14, 14), Rgb::WHITE);
+        assert_eq!(frame.pixel(7, 7), Rgb::BLACK);
+        assert_eq!(frame.pixel(7, 2), Rgb::WHITE);
+    }
+
+    #[test]
+    fn grid_image() {
+        let grid = Grid::new(2, 3, vec![0u8, 1, 2, 3, 4, 5]);
+        let path = temp_path("grid.png");
+        let palette = |&v: &u8| Rgb(v, 10 * v, 0);
+        let options = RenderOptions::scaled(4);
+        render_png_view(&path, &grid, &options, palette).unwrap();
+        let frame = decode_png(&std::fs::read(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!((frame.width(), frame.height()), (12, 8));
+        assert_eq!(frame.pixel(11, 0), Rgb(2, 20, 0));
+        assert_eq!(frame.pixel(4, 7), Rgb(4, 40, 0));
+
+        let empty: HashMap<(i64, i64), ()> = HashMap::new();
+        let options = RenderOptions::default();
+        let result = render_png_view(&path, &empty, &options, Rgb::WHITE);
+        assert!(result.is_err());
+    }
+}
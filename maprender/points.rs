@@ -0,0 +1,189 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Printing clouds of `(x, y)` points as plain text.
+//!
+//! Points are drawn as `#` on a background of `.` over
+//! their bounding box. If the box is too big to print, say
+//! because a simulation has not converged yet, it is drawn
+//! shrunk instead, so that printing every step is cheap.
+//!
+//! # Examples
+//!
+//! ```
+//! # use maprender::*;
+//! let points = [(10, 20), (11, 21), (12, 20)];
+//! assert_eq!(render_points_ascii(points), "#.#\n.#.\n");
+//!
+//! let options = PointsOptions {
+//!     y_axis: YAxis::Up,
+//!     ..PointsOptions::default()
+//! };
+//! assert_eq!(render_points_ascii_with(points, &options), ".#.\n#.#\n");
+//! ```
+
+use geom::bounding_box;
+
+/// Which way the y axis points when printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum YAxis {
+    /// Larger y is lower, as for screen coordinates.
+    #[default]
+    Down,
+    /// Larger y is higher, as for math coordinates.
+    Up,
+}
+
+/// Options for `render_points_ascii_with()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PointsOptions {
+    /// Which way the y axis points.
+    pub y_axis: YAxis,
+    /// Character for a point.
+    pub point: char,
+    /// Character for empty space.
+    pub empty: char,
+    /// Largest width and height to print, in characters.
+    /// Bigger clouds are shrunk by a whole factor to fit,
+    /// with a point shown for each block of space holding
+    /// any points.
+    pub max_size: (usize, usize),
+}
+
+impl Default for PointsOptions {
+    fn default() -> Self {
+        PointsOptions {
+            y_axis: YAxis::Down,
+            point: '#',
+            empty: '.',
+            max_size: (200, 100),
+        }
+    }
+}
+
+/// Print the given `(x, y)` points as `#` on `.` over their
+/// bounding box, with y increasing downward. See
+/// `render_points_ascii_with()` for details.
+pub fn render_points_ascii<I>(points: I) -> String
+where
+    I: IntoIterator<Item = (i64, i64)>,
+{
+    render_points_ascii_with(points, &PointsOptions::default())
+}
+
+/// Print the given `(x, y)` points over their bounding box
+/// as given by `options`. Each line ends with a newline. No
+/// points give the empty string.
+///
+/// # Panics
+///
+/// Panics if either dimension of `options.max_size` is 0.
+pub fn render_points_ascii_with<I>(
+    points: I,
+    options: &PointsOptions,
+) -> String
+where
+    I: IntoIterator<Item = (i64, i64)>,
+{
+    let (max_width, max_height) = options.max_size;
+    assert!(max_width > 0 && max_height > 0, "empty maximum size");
+    let points: Vec<(i64, i64)> = points.into_iter().collect();
+    let ((x0, y0), (x1, y1)) = match bounding_box(points.iter().copied()) {
+        Some(bounds) => bounds,
+        None => return String::new(),
+    };
+
+    // Sizes are computed in u128 so that far-flung points
+    // cannot overflow.
+    let width = x1.abs_diff(x0) as u128 + 1;
+    let height = y1.abs_diff(y0) as u128 + 1;
+    let shrink = width
+        .div_ceil(max_width as u128)
+        .max(height.div_ceil(max_height as u128));
+    let cols = width.div_ceil(shrink) as usize;
+    let rows = height.div_ceil(shrink) as usize;
+
+    let mut lit = vec![false; rows * cols];
+    for &(x, y) in &points {
+        let dy = match options.y_axis {
+            YAxis::Down => y.abs_diff(y0),
+            YAxis::Up => y1.abs_diff(y),
+        };
+        let c = (x.abs_diff(x0) as u128 / shrink) as usize;
+        let r = (dy as u128 / shrink) as usize;
+        lit[r * cols + c] = true;
+    }
+
+    let mut text = String::with_capacity(rows * (cols + 1));
+    for row in lit.chunks(cols) {
+        for &on in row {
+            text.push(if on { options.point } else { options.empty });
+        }
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// Points of the `#`s in the given lines, with the top
+    /// left at `origin`.
+    fn points(origin: (i64, i64), lines: &[&str]) -> Vec<(i64, i64)> {
+        let (x0, y0) = origin;
+        let mut points = Vec::new();
+        for (y, line) in lines.iter().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if c == '#' {
+                    points.push((x0 + x as i64, y0 + y as i64));
+                }
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn letters() {
+        let hi = [
+            "#...#..###",
+            "#...#...#.",
+            "#####...#.",
+            "#...#...#.",
+            "#...#..###",
+        ];
+        let text = render_points_ascii(points((-40, 7), &hi));
+        assert_eq!(text, hi.map(|l| format!("{}\n", l)).concat());
+
+        let options = PointsOptions {
+            y_axis: YAxis::Up,
+            point: '█',
+            empty: ' ',
+            ..PointsOptions::default()
+        };
+        let l = ["#..", "#..", "###"];
+        let text = render_points_ascii_with(points((0, 0), &l), &options);
+        assert_eq!(text, "███\n█  \n█  \n");
+        assert_eq!(render_points_ascii([]), "");
+    }
+
+    #[test]
+    fn shrunk() {
+        let options = PointsOptions {
+            max_size: (4, 4),
+            ..PointsOptions::default()
+        };
+        let far = [(i64::MIN, i64::MIN), (i64::MAX, i64::MAX), (0, -1)];
+        let text = render_points_ascii_with(far, &options);
+        assert_eq!(text, "#...\n..#.\n....\n...#\n");
+
+        // A 10×3 cloud shrinks by 3 to 4×1.
+        let rows = ["#.........", "..........", "......#..#"];
+        let line = points((0, 0), &rows);
+        let text = render_points_ascii_with(line, &options);
+        assert_eq!(text, "#.##\n");
+    }
+}
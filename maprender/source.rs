@@ -0,0 +1,280 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Things that can be rendered as maps.
+//!
+//! A `RenderSource` is a map of cells addressed by
+//! `(row, col)`, with bounds. Every renderer takes one.
+//! Sources are provided for
+//!
+//! * a `geom::Grid`;
+//! * a sparse `HashMap` of cells or `HashSet` of foreground
+//!   points, keyed by `(x, y)` as elsewhere in this crate,
+//!   with `x` as the column and `y` as the row;
+//! * a `geom::GridBox` paired with a function from
+//!   `(row, col)` to a cell, if any.
+//!
+//! # Examples
+//!
+//! ```
+//! # use maprender::*;
+//! # use geom::{Grid, GridBox};
+//! let grid = Grid::from_lines(["#..", ".#.", "..#"], |c| c);
+//! let palette = Palette::from_map([('#', Rgb::WHITE), ('.', Rgb::BLACK)]);
+//! print!("{}", render_ansi(&grid, &palette));
+//! let options = RenderOptions::scaled(8);
+//! let dir = std::env::temp_dir();
+//! render_png_view(dir.join("diagonal.png"), &grid, &options, &palette)?;
+//!
+//! let circle = |r: i64, c: i64| (r * r + c * c < 64).then_some(());
+//! let quadrant: (GridBox, &dyn Fn(i64, i64) -> Option<()>) =
+//!     (GridBox::new(8, 8), &circle);
+//! render_png_view(dir.join("quadrant.png"), &quadrant, &options, Rgb::RED)?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasher;
+
+use geom::{Grid, GridBox};
+
+use crate::{Frame, Paint, Rgb};
+
+/// Inclusive bounds `((min_row, min_col), (max_row,
+/// max_col))` of a map.
+pub type Bounds = ((i64, i64), (i64, i64));
+
+/// A map that can be rendered.
+pub trait RenderSource {
+    /// Type of the map cells.
+    type Cell;
+
+    /// Bounds of the map, or `None` if the map is empty.
+    fn bounds(&self) -> Option<Bounds>;
+
+    /// Value of the cell at `(row, col)`, or `None` if there
+    /// is nothing there.
+    fn cell(&self, row: i64, col: i64) -> Option<Self::Cell>;
+}
+
+impl<H: BuildHasher> RenderSource for HashSet<(i64, i64), H> {
+    type Cell = ();
+
+    fn bounds(&self) -> Option<Bounds> {
+        xy_bounds(self.iter())
+    }
+
+    fn cell(&self, row: i64, col: i64) -> Option<()> {
+        self.contains(&(col, row)).then_some(())
+    }
+}
+
+impl<T: Clone, H: BuildHasher> RenderSource for HashMap<(i64, i64), T, H> {
+    type Cell = T;
+
+    fn bounds(&self) -> Option<Bounds> {
+        xy_bounds(self.keys())
+    }
+
+    fn cell(&self, row: i64, col: i64) -> Option<T> {
+        self.get(&(col, row)).cloned()
+    }
+}
+
+impl<T: Clone> RenderSource for Grid<T> {
+    type Cell = T;
+
+    fn bounds(&self) -> Option<Bounds> {
+        let (rows, cols) = (self.rows() as i64, self.cols() as i64);
+        (rows > 0 && cols > 0).then_some(((0, 0), (rows - 1, cols - 1)))
+    }
+
+    fn cell(&self, row: i64, col: i64) -> Option<T> {
+        let (rows, cols) = (self.rows() as i64, self.cols() as i64);
+        if row < 0 || col < 0 || row >= rows || col >= cols {
+            return None;
+        }
+        Some(self[(row as usize, col as usize)].clone())
+    }
+}
+
+/// A function giving the cell at `(row, col)`, over the
+/// cells of a grid box. An unclipped or unbounded grid box
+/// has no bounds, so cannot be rendered.
+impl<T> RenderSource for (GridBox, &dyn Fn(i64, i64) -> Option<T>) {
+    type Cell = T;
+
+    fn bounds(&self) -> Option<Bounds> {
+        self.0.bounds()
+    }
+
+    fn cell(&self, row: i64, col: i64) -> Option<T> {
+        self.0.clip((row, col), (0, 0))?;
+        (self.1)(row, col)
+    }
+}
+
+/// Bounds of a set of `(x, y)` points.
+fn xy_bounds<'a, I>(points: I) -> Option<Bounds>
+where
+    I: Iterator<Item = &'a (i64, i64)>,
+{
+    geom::bounding_box(points.map(|&(x, y)| (y, x)))
+}
+
+/// How map cells are drawn as pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Width and height of each cell in pixels.
+    pub scale: usize,
+    /// Color of empty cells.
+    pub background: Rgb,
+    /// Color of 1-pixel lines drawn between and around the
+    /// cells, if any.
+    pub grid_lines: Option<Rgb>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            scale: 1,
+            background: Rgb::BLACK,
+            grid_lines: None,
+        }
+    }
+}
+
+impl RenderOptions {
+    /// Default options, with cells `scale` pixels square.
+    pub fn scaled(scale: usize) -> Self {
+        RenderOptions {
+            scale,
+            ..RenderOptions::default()
+        }
+    }
+
+    /// Width of the grid lines in pixels.
+    fn line_width(&self) -> usize {
+        self.grid_lines.is_some() as usize
+    }
+
+    /// Pixel offset of the cell `n` cells from the edge.
+    pub fn offset(&self, n: usize) -> usize {
+        n * (self.scale + self.line_width()) + self.line_width()
+    }
+
+    /// Pixels spanned by `ncells` cells side by side,
+    /// including grid lines. No cells span no pixels.
+    pub fn span(&self, ncells: usize) -> usize {
+        if ncells == 0 {
+            return 0;
+        }
+        self.offset(ncells)
+    }
+}
+
+/// Render the part of a map within `bounds` to a frame,
+/// drawn as given by `options`. Cells with a value are
+/// colored by `palette`.
+pub fn render_frame<S, P>(
+    view: &S,
+    bounds: Bounds,
+    options: &RenderOptions,
+    palette: P,
+) -> Frame
+where
+    S: RenderSource + ?Sized,
+    P: Paint<S::Cell>,
+{
+    let ((r0, c0), (r1, c1)) = bounds;
+    let height = (r1 - r0 + 1).max(0) as usize;
+    let width = (c1 - c0 + 1).max(0) as usize;
+    let lines = options.grid_lines.unwrap_or(options.background);
+    let mut frame =
+        Frame::new(options.span(width), options.span(height), lines);
+    let scale = options.scale;
+    for r in 0..height {
+        for c in 0..width {
+            let color = view
+                .cell(r0 + r as i64, c0 + c as i64)
+                .map(|v| palette.paint(&v))
+                .unwrap_or(options.background);
+            let (x, y) = (options.offset(c), options.offset(r));
+            frame.fill_rect(x, y, scale, scale, color);
+        }
+    }
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn sources_agree() {
+        let grid = Grid::new(2, 3, "#.#...".chars().collect());
+        let sparse: HashMap<(i64, i64), char> = [(0, 0), (2, 0)]
+            .into_iter()
+            .map(|xy| (xy, '#'))
+            .collect();
+        let hash = |r: i64, c: i64| (r == 0 && c != 1).then_some('#');
+        let fun: (GridBox, &dyn Fn(i64, i64) -> Option<char>) =
+            (GridBox::new(2, 3), &hash);
+        assert_eq!(grid.bounds(), Some(((0, 0), (1, 2))));
+        assert_eq!(fun.bounds(), grid.bounds());
+        assert_eq!(sparse.bounds(), Some(((0, 0), (0, 2))));
+        assert_eq!(grid.cell(2, 0), None);
+        assert_eq!(fun.cell(-1, 0), None);
+
+        let palette = |&c: &char| {
+            if c == '#' {
+                Rgb::WHITE
+            } else {
+                Rgb::BLACK
+            }
+        };
+        let bounds = ((0, 0), (1, 2));
+        let options = RenderOptions::scaled(2);
+        let frames = [
+            render_frame(&grid, bounds, &options, palette),
+            render_frame(&sparse, bounds, &options, palette),
+            render_frame(&fun, bounds, &options, palette),
+        ];
+        assert_eq!(frames[0], frames[1]);
+        assert_eq!(frames[0], frames[2]);
+        assert_eq!(frames[0].pixel(1, 3), Rgb::BLACK);
+        assert_eq!(frames[0].pixel(4, 1), Rgb::WHITE);
+
+        let unclipped: (GridBox, &dyn Fn(i64, i64) -> Option<char>) =
+            (GridBox::new_grid(), &hash);
+        assert_eq!(unclipped.bounds(), None);
+    }
+
+    #[test]
+    fn grid_lines() {
+        let diagonal: HashSet<(i64, i64)> =
+            [(0, 0), (1, 1), (2, 2)].into_iter().collect();
+        let options = RenderOptions {
+            grid_lines: Some(Rgb::GRAY),
+            ..RenderOptions::scaled(8)
+        };
+        let bounds = diagonal.bounds().unwrap();
+        let frame = render_frame(&diagonal, bounds, &options, Rgb::WHITE);
+        assert_eq!((frame.width(), frame.height()), (28, 28));
+        for i in [0, 9, 18, 27] {
+            assert_eq!(frame.pixel(i, 5), Rgb::GRAY);
+            assert_eq!(frame.pixel(14, i), Rgb::GRAY);
+        }
+        assert_eq!(frame.pixel(1, 1), Rgb::WHITE);
+        assert_eq!(frame.pixel(8, 8), Rgb::WHITE);
+        assert_eq!(frame.pixel(10, 1), Rgb::BLACK);
+        assert_eq!(frame.pixel(26, 26), Rgb::WHITE);
+
+        let empty = ((0, 0), (-1, 2));
+        let frame = render_frame(&diagonal, empty, &options, Rgb::WHITE);
+        assert_eq!(frame.height(), 0);
+    }
+}
@@ -0,0 +1,305 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Rendering maps as SVG vector images.
+//!
+//! Each run of same-colored cells in a row becomes a single
+//! rectangle, which keeps files small for typical maps.
+//! Paths, such as the result of a search, can be drawn over
+//! the map.
+//!
+//! # Examples
+//!
+//! ```
+//! # use maprender::*;
+//! # use geom::Grid;
+//! let maze = Grid::new(2, 3, "#..##.".chars().collect());
+//! let walls = |&c: &char| if c == '#' { Rgb::BLACK } else { Rgb::WHITE };
+//! let mut svg = Svg::new(&maze, walls, &SvgOptions::default());
+//! svg.draw_path([(0, 1), (0, 2), (1, 2)], Rgb::RED);
+//! let text = svg.to_string();
+//! assert!(text.contains(r#"viewBox="0 0 30 20""#));
+//! assert_eq!(text.matches("<rect").count(), 4);
+//! ```
+
+use std::fmt::{self, Write};
+
+use crate::{Paint, RenderSource, Rgb};
+
+/// Options for `render_svg()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SvgOptions {
+    /// Width and height of each cell in SVG units.
+    pub unit: usize,
+    /// Color filling the whole image behind the cells, if
+    /// any. Otherwise empty cells are transparent.
+    pub background: Option<Rgb>,
+    /// Color of an outline around each non-empty cell, if
+    /// any.
+    pub outline: Option<Rgb>,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            unit: 10,
+            background: None,
+            outline: None,
+        }
+    }
+}
+
+/// SVG color notation for `color`.
+fn svg_color(Rgb(r, g, b): Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// An SVG image of a map, which can be drawn on before
+/// being written out with `to_string()`.
+#[derive(Debug, Clone)]
+pub struct Svg {
+    /// Map coordinates of the top left cell.
+    origin: (i64, i64),
+    /// Size in SVG units.
+    width: usize,
+    height: usize,
+    unit: usize,
+    /// SVG elements so far.
+    body: String,
+}
+
+impl Svg {
+    /// SVG image of the given map, with cells colored by
+    /// `palette` and drawn as given by `options`.
+    pub fn new<S, P>(view: &S, palette: P, options: &SvgOptions) -> Self
+    where
+        S: RenderSource + ?Sized,
+        P: Paint<S::Cell>,
+    {
+        let unit = options.unit;
+        let ((r0, c0), (r1, c1)) = view.bounds().unwrap_or(((0, 0), (-1, -1)));
+        let width = (c1 - c0 + 1) as usize * unit;
+        let height = (r1 - r0 + 1) as usize * unit;
+        let mut svg = Svg {
+            origin: (r0, c0),
+            width,
+            height,
+            unit,
+            body: String::new(),
+        };
+        if let Some(color) = options.background {
+            svg.push_rect((0, 0), (width, height), color);
+        }
+
+        let mut outlines = String::new();
+        for row in r0..=r1 {
+            let y = (row - r0) as usize * unit;
+            // Current run of cells as start column and color.
+            let mut run: Option<(i64, Rgb)> = None;
+            for col in c0..=c1 + 1 {
+                let color = if col <= c1 {
+                    view.cell(row, col).map(|v| palette.paint(&v))
+                } else {
+                    None
+                };
+                if color.is_some() && options.outline.is_some() {
+                    let x = (col - c0) as usize * unit;
+                    let u = unit;
+                    write!(outlines, "M{} {}h{}v{}h-{}z", x, y, u, u, u)
+                        .unwrap();
+                }
+                if let Some((start, c)) = run {
+                    if color == Some(c) {
+                        continue;
+                    }
+                    let x = (start - c0) as usize * unit;
+                    let w = (col - start) as usize * unit;
+                    svg.push_rect((x, y), (w, unit), c);
+                }
+                run = color.map(|c| (col, c));
+            }
+        }
+        if let Some(color) = options.outline {
+            if !outlines.is_empty() {
+                writeln!(
+                    svg.body,
+                    concat!(
+                        r#"<path d="{}" fill="none" stroke="{}" "#,
+                        r#"stroke-width="{}"/>"#,
+                    ),
+                    outlines,
+                    svg_color(color),
+                    unit as f64 / 10.0,
+                )
+                .unwrap();
+            }
+        }
+        svg
+    }
+
+    /// Append a filled rectangle.
+    fn push_rect(
+        &mut self,
+        (x, y): (usize, usize),
+        (w, h): (usize, usize),
+        color: Rgb,
+    ) {
+        writeln!(
+            self.body,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+            x,
+            y,
+            w,
+            h,
+            svg_color(color),
+        )
+        .unwrap();
+    }
+
+    /// Draw a path through the centers of the given cells,
+    /// given as `(row, col)` map coordinates, in the given
+    /// color.
+    pub fn draw_path<I>(&mut self, points: I, color: Rgb)
+    where
+        I: IntoIterator<Item = (i64, i64)>,
+    {
+        let unit = self.unit as f64;
+        let (r0, c0) = self.origin;
+        let points: Vec<String> = points
+            .into_iter()
+            .map(|(r, c)| {
+                let x = (c - c0) as f64 * unit + unit / 2.0;
+                let y = (r - r0) as f64 * unit + unit / 2.0;
+                format!("{},{}", x, y)
+            })
+            .collect();
+        writeln!(
+            self.body,
+            concat!(
+                r#"<polyline points="{}" fill="none" stroke="{}" "#,
+                r#"stroke-width="{}" stroke-linecap="round" "#,
+                r#"stroke-linejoin="round"/>"#,
+            ),
+            points.join(" "),
+            svg_color(color),
+            self.unit as f64 / 4.0,
+        )
+        .unwrap();
+    }
+}
+
+impl fmt::Display for Svg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" "#,
+                r#"width="{}" height="{}" viewBox="0 0 {} {}">"#,
+            ),
+            self.width, self.height, self.width, self.height,
+        )?;
+        write!(f, "{}", self.body)?;
+        writeln!(f, "</svg>")
+    }
+}
+
+/// SVG image of the given map, with cells colored by
+/// `palette` and drawn as given by `options`. Empty cells
+/// are not drawn. To draw over the map, use `Svg` instead.
+pub fn render_svg<S, P>(view: &S, palette: P, options: &SvgOptions) -> String
+where
+    S: RenderSource + ?Sized,
+    P: Paint<S::Cell>,
+{
+    Svg::new(view, palette, options).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use std::collections::{HashMap, HashSet};
+
+    use geom::Grid;
+
+    /// Values of the named attribute of each element with
+    /// the given tag.
+    fn attrs<'a>(svg: &'a str, tag: &str, name: &str) -> Vec<&'a str> {
+        let open = format!("<{}", tag);
+        let key = format!(" {}=\"", name);
+        svg.split(&open)
+            .skip(1)
+            .map(|elt| {
+                let elt = &elt[..elt.find('>').unwrap()];
+                let start = elt.find(&key).unwrap() + key.len();
+                let len = elt[start..].find('"').unwrap();
+                &elt[start..start + len]
+            })
+            .collect()
+    }
+
+    fn maze() -> Grid<char> {
+        let rows = ["#####", "#..##", "##..#", "#####"];
+        Grid::new(4, 5, rows.iter().flat_map(|r| r.chars()).collect())
+    }
+
+    fn walls(c: &char) -> Rgb {
+        if *c == '#' {
+            Rgb::BLACK
+        } else {
+            Rgb::WHITE
+        }
+    }
+
+    #[test]
+    fn merged_rects() {
+        let options = SvgOptions {
+            unit: 4,
+            ..SvgOptions::default()
+        };
+        let svg = render_svg(&maze(), walls, &options);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(attrs(&svg, "svg", "viewBox"), ["0 0 20 16"]);
+        // One rect per run: 1 + 3 + 3 + 1.
+        assert_eq!(attrs(&svg, "rect", "width"), [
+            "20", "4", "8", "8", "8", "8", "4", "20",
+        ]);
+        assert_eq!(attrs(&svg, "rect", "x")[5], "8");
+        assert_eq!(attrs(&svg, "rect", "fill")[5], "#ffffff");
+        assert_eq!(attrs(&svg, "rect", "y")[5], "8");
+    }
+
+    #[test]
+    fn sparse_with_extras() {
+        let mut map: HashMap<(i64, i64), char> = HashMap::new();
+        map.insert((5, -2), 'a');
+        map.insert((6, -2), 'a');
+        map.insert((7, -1), 'b');
+        let options = SvgOptions {
+            unit: 2,
+            background: Some(Rgb::BLUE),
+            outline: Some(Rgb::GRAY),
+        };
+        let palette = |&c: &char| if c == 'a' { Rgb::RED } else { Rgb::GREEN };
+        let mut svg = Svg::new(&map, palette, &options);
+        svg.draw_path([(-2, 5), (-2, 7), (-1, 7)], Rgb::YELLOW);
+        let text = svg.to_string();
+        assert_eq!(attrs(&text, "svg", "viewBox"), ["0 0 6 4"]);
+        assert_eq!(attrs(&text, "rect", "fill"), [
+            "#0000ff", "#ff0000", "#00ff00",
+        ]);
+        let outlines = attrs(&text, "path", "d");
+        assert_eq!(outlines, ["M0 0h2v2h-2zM2 0h2v2h-2zM4 2h2v2h-2z"]);
+        let route = attrs(&text, "polyline", "points");
+        assert_eq!(route, ["1,1 5,1 5,3"]);
+        assert_eq!(attrs(&text, "polyline", "stroke"), ["#ffff00"]);
+
+        let empty: HashSet<(i64, i64)> = HashSet::new();
+        let svg = render_svg(&empty, Rgb::WHITE, &options);
+        assert_eq!(attrs(&svg, "svg", "viewBox"), ["0 0 0 0"]);
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Drawing text on frames, for captions such as step
+//! counters.
+//!
+//! Text is drawn in a built-in 5×7 pixel font covering
+//! digits, capital letters (lowercase letters are drawn as
+//! capitals), and some punctuation. Other characters are
+//! drawn as a hollow box.
+//!
+//! # Examples
+//!
+//! ```
+//! # use maprender::*;
+//! let mut frame = Frame::new(64, 9, Rgb::BLACK);
+//! let end = draw_text(&mut frame, (1, 1), "step 42", Rgb::WHITE);
+//! assert_eq!(end, 1 + text_width("step 42"));
+//! assert_eq!(frame.pixel(1, 2), Rgb::WHITE);
+//! ```
+
+use crate::{Frame, Rgb};
+
+/// Width of a glyph in pixels.
+pub const GLYPH_WIDTH: usize = 5;
+
+/// Height of a glyph in pixels.
+pub const GLYPH_HEIGHT: usize = 7;
+
+/// Glyph rows, top to bottom, with the leftmost pixel in
+/// the high bit. Sorted by character.
+const FONT: &[(char, [u8; GLYPH_HEIGHT])] = &[
+    ('!', [0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00100]),
+    ('#', [0b01010, 0b01010, 0b11111, 0b01010, 0b11111, 0b01010, 0b01010]),
+    ('%', [0b11000, 0b11001, 0b00010, 0b00100, 0b01000, 0b10011, 0b00011]),
+    ('(', [0b00010, 0b00100, 0b01000, 0b01000, 0b01000, 0b00100, 0b00010]),
+    (')', [0b01000, 0b00100, 0b00010, 0b00010, 0b00010, 0b00100, 0b01000]),
+    ('*', [0b00000, 0b00100, 0b10101, 0b01110, 0b10101, 0b00100, 0b00000]),
+    ('+', [0b00000, 0b00100, 0b00100, 0b11111, 0b00100, 0b00100, 0b00000]),
+    (',', [0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b00100, 0b01000]),
+    ('-', [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000]),
+    ('.', [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100]),
+    ('/', [0b00000, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b00000]),
+    ('0', [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110]),
+    ('1', [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110]),
+    ('2', [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111]),
+    ('3', [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110]),
+    ('4', [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010]),
+    ('5', [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110]),
+    ('6', [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110]),
+    ('7', [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000]),
+    ('8', [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110]),
+    ('9', [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100]),
+    (':', [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000]),
+    ('<', [0b00010, 0b00100, 0b01000, 0b10000, 0b01000, 0b00100, 0b00010]),
+    ('=', [0b00000, 0b00000, 0b11111, 0b00000, 0b11111, 0b00000, 0b00000]),
+    ('>', [0b01000, 0b00100, 0b00010, 0b00001, 0b00010, 0b00100, 0b01000]),
+    ('?', [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b00000, 0b00100]),
+    ('A', [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001]),
+    ('B', [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110]),
+    ('C', [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110]),
+    ('D', [0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100]),
+    ('E', [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111]),
+    ('F', [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000]),
+    ('G', [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111]),
+    ('H', [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001]),
+    ('I', [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110]),
+    ('J', [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100]),
+    ('K', [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001]),
+    ('L', [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111]),
+    ('M', [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001]),
+    ('N', [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001]),
+    ('O', [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110]),
+    ('P', [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000]),
+    ('Q', [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101]),
+    ('R', [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001]),
+    ('S', [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110]),
+    ('T', [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100]),
+    ('U', [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110]),
+    ('V', [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100]),
+    ('W', [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010]),
+    ('X', [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001]),
+    ('Y', [0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100, 0b00100]),
+    ('Z', [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111]),
+];
+
+/// Glyph for characters not in the font.
+const MISSING: [u8; GLYPH_HEIGHT] =
+    [0b11111, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11111];
+
+/// Glyph rows for the given character; see `FONT`.
+fn glyph(c: char) -> [u8; GLYPH_HEIGHT] {
+    if c == ' ' {
+        return [0; GLYPH_HEIGHT];
+    }
+    let c = c.to_ascii_uppercase();
+    match FONT.binary_search_by_key(&c, |&(g, _)| g) {
+        Ok(i) => FONT[i].1,
+        Err(_) => MISSING,
+    }
+}
+
+/// Width in pixels of `text` as drawn by `draw_text()`:
+/// glyphs are separated by a blank column.
+pub fn text_width(text: &str) -> usize {
+    let n = text.chars().count();
+    (n * (GLYPH_WIDTH + 1)).saturating_sub(1)
+}
+
+/// Draw `text` on the frame in the given color, with the
+/// top left of the first glyph at `(x, y)`. Pixels that
+/// would fall outside the frame are not drawn. Returns the
+/// `x` coordinate just past the text.
+pub fn draw_text(
+    frame: &mut Frame,
+    (x, y): (usize, usize),
+    text: &str,
+    color: Rgb,
+) -> usize {
+    let mut left = x;
+    for c in text.chars() {
+        for (dy, row) in glyph(c).into_iter().enumerate() {
+            for dx in 0..GLYPH_WIDTH {
+                let (px, py) = (left + dx, y + dy);
+                let on = row & (1 << (GLYPH_WIDTH - 1 - dx)) != 0;
+                if on && px < frame.width() && py < frame.height() {
+                    frame.set_pixel(px, py, color);
+                }
+            }
+        }
+        left += GLYPH_WIDTH + 1;
+    }
+    x + text_width(text)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// Read back text drawn at `(x, y)` by matching glyphs.
+    fn read_text(frame: &Frame, (x, y): (usize, usize), n: usize) -> String {
+        (0..n)
+            .map(|i| {
+                let left = x + i * (GLYPH_WIDTH + 1);
+                let mut rows = [0u8; GLYPH_HEIGHT];
+                for (dy, row) in rows.iter_mut().enumerate() {
+                    for dx in 0..GLYPH_WIDTH {
+                        let on = frame.pixel(left + dx, y + dy) != Rgb::BLACK;
+                        *row = (*row << 1) | on as u8;
+                    }
+                }
+                if rows == [0; GLYPH_HEIGHT] {
+                    return ' ';
+                }
+                FONT.iter().find(|&&(_, g)| g == rows).map_or('?', |g| g.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn font_sorted_and_distinct() {
+        assert!(FONT.windows(2).all(|w| w[0].0 < w[1].0));
+        for (i, (c, g)) in FONT.iter().enumerate() {
+            assert!(g.iter().all(|&row| row < 1 << GLYPH_WIDTH), "{}", c);
+            assert!(FONT[..i].iter().all(|h| h.1 != *g), "{}", c);
+            assert_ne!(*g, MISSING);
+        }
+    }
+
+    #[test]
+    fn caption() {
+        let text = "Step 1234: (x+y)=7!";
+        let mut frame = Frame::new(text_width(text) + 4, 11, Rgb::BLACK);
+        let end = draw_text(&mut frame, (2, 2), text, Rgb::YELLOW);
+        assert_eq!(end, frame.width() - 2);
+        let read = read_text(&frame, (2, 2), text.chars().count());
+        assert_eq!(read, text.to_uppercase());
+        for x in 0..frame.width() {
+            assert_eq!(frame.pixel(x, 1), Rgb::BLACK);
+            assert_eq!(frame.pixel(x, 9), Rgb::BLACK);
+        }
+
+        // Clipped at the edges rather than panicking.
+        let mut small = Frame::new(8, 4, Rgb::BLACK);
+        assert_eq!(draw_text(&mut small, (3, 1), "~W", Rgb::RED), 14);
+        assert_eq!(small.pixel(3, 1), Rgb::RED);
+        assert_eq!(small.pixel(4, 2), Rgb::BLACK);
+    }
+}
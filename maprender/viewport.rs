@@ -0,0 +1,112 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Rendering just part of a map.
+//!
+//! A `Viewport` picks the window of a map to draw: anything
+//! outside the window is simply not drawn. Wrap a map with
+//! `windowed()` to render it through a viewport with any
+//! renderer, or set the viewport of a `Recorder` to make an
+//! animation through it.
+//!
+//! # Examples
+//!
+//! ```
+//! # use maprender::*;
+//! # use std::collections::HashSet;
+//! let sand: HashSet<(i64, i64)> = [(500, 0), (500, 9)].into_iter().collect();
+//! let fit = windowed(&sand, Viewport::Fit { padding: 1 });
+//! assert_eq!(fit.bounds(), Some(((-1, 499), (10, 501))));
+//! let camera = Viewport::Follow {
+//!     center: (9, 500),
+//!     half_extent: (1, 2),
+//! };
+//! let text = render_ansi(&windowed(&sand, camera), AnsiStyle::new('o'));
+//! assert_eq!(text, "    \n  o \n");
+//! ```
+
+use crate::{Bounds, RenderSource};
+
+/// The window of a map to be drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Viewport {
+    /// The whole map.
+    #[default]
+    Full,
+    /// A fixed window.
+    Fixed(Bounds),
+    /// The whole map with `padding` empty cells around it.
+    Fit {
+        /// Cells of padding on each side.
+        padding: i64,
+    },
+    /// A window of fixed size centered on a point, usually
+    /// moved each frame to track something. The window is
+    /// `2 * half_extent` rows and columns, and `center`,
+    /// which is `(row, col)`, is the cell just below and
+    /// right of its middle. A window with no rows or no
+    /// columns shows nothing.
+    Follow {
+        /// Center of the window.
+        center: (i64, i64),
+        /// Half the rows and columns shown.
+        half_extent: (i64, i64),
+    },
+}
+
+impl Viewport {
+    /// The window to draw of a map with the given bounds,
+    /// or `None` for nothing. Only `Full` and `Fit` depend
+    /// on the map.
+    pub fn window(&self, content: Option<Bounds>) -> Option<Bounds> {
+        match *self {
+            Viewport::Full => content,
+            Viewport::Fixed(bounds) => Some(bounds),
+            Viewport::Fit { padding: p } => {
+                let ((r0, c0), (r1, c1)) = content?;
+                Some(((r0 - p, c0 - p), (r1 + p, c1 + p)))
+            }
+            Viewport::Follow {
+                center: (r, c),
+                half_extent: (hr, hc),
+            } => (hr > 0 && hc > 0)
+                .then_some(((r - hr, c - hc), (r + hr - 1, c + hc - 1))),
+        }
+    }
+}
+
+/// A map seen through a viewport; see `windowed()`.
+#[derive(Debug, Clone, Copy)]
+pub struct Windowed<'a, S: ?Sized> {
+    source: &'a S,
+    window: Option<Bounds>,
+}
+
+/// The given map seen through `viewport`: its bounds are
+/// the viewport window, and cells outside the window are
+/// empty. The window is fixed when this is called.
+pub fn windowed<S>(source: &S, viewport: Viewport) -> Windowed<'_, S>
+where
+    S: RenderSource + ?Sized,
+{
+    let window = viewport.window(source.bounds());
+    Windowed { source, window }
+}
+
+impl<S: RenderSource + ?Sized> RenderSource for Windowed<'_, S> {
+    type Cell = S::Cell;
+
+    fn bounds(&self) -> Option<Bounds> {
+        self.window
+    }
+
+    fn cell(&self, row: i64, col: i64) -> Option<S::Cell> {
+        let ((r0, c0), (r1, c1)) = self.window?;
+        if row < r0 || row > r1 || col < c0 || col > c1 {
+            return None;
+        }
+        self.source.cell(row, col)
+    }
+}
@@ -0,0 +1,254 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Just enough arbitrary-precision integer arithmetic for
+//! exact elimination in `solve_linear()`: the numbers there
+//! are a few hundred bits at most, so the simple schoolbook
+//! and bit-at-a-time methods here are plenty fast.
+
+use std::cmp::Ordering;
+
+/// A signed integer of any size, as a sign and a magnitude
+/// in little-endian 64-bit limbs with no high zero limbs.
+/// Zero has no limbs and is not negative.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BigInt {
+    neg: bool,
+    mag: Vec<u64>,
+}
+
+impl From<i128> for BigInt {
+    fn from(n: i128) -> Self {
+        let m = n.unsigned_abs();
+        BigInt::new(n < 0, vec![m as u64, (m >> 64) as u64])
+    }
+}
+
+impl BigInt {
+    /// Zero.
+    pub(crate) const ZERO: BigInt = BigInt {
+        neg: false,
+        mag: Vec::new(),
+    };
+
+    /// The integer with the given sign and magnitude, which
+    /// may have high zero limbs.
+    fn new(neg: bool, mut mag: Vec<u64>) -> Self {
+        trim(&mut mag);
+        let neg = neg && !mag.is_empty();
+        BigInt { neg, mag }
+    }
+
+    /// True if this is zero.
+    pub(crate) fn is_zero(&self) -> bool {
+        self.mag.is_empty()
+    }
+
+    /// The absolute value.
+    pub(crate) fn abs(&self) -> BigInt {
+        BigInt {
+            neg: false,
+            mag: self.mag.clone(),
+        }
+    }
+
+    /// Compare absolute values.
+    pub(crate) fn cmp_abs(&self, other: &BigInt) -> Ordering {
+        cmp_mag(&self.mag, &other.mag)
+    }
+
+    /// The value as an `i128`, if it fits.
+    pub(crate) fn to_i128(&self) -> Option<i128> {
+        if self.mag.len() > 2 {
+            return None;
+        }
+        let limb = |i: usize| self.mag.get(i).copied().unwrap_or(0) as u128;
+        let m = limb(1) << 64 | limb(0);
+        if self.neg {
+            0i128.checked_sub_unsigned(m)
+        } else {
+            i128::try_from(m).ok()
+        }
+    }
+
+    /// The product.
+    pub(crate) fn mul(&self, other: &BigInt) -> BigInt {
+        let mut mag = vec![0u64; self.mag.len() + other.mag.len()];
+        for (i, &a) in self.mag.iter().enumerate() {
+            // Each step is at most (2^64 - 1)^2 + 2 (2^64 - 1),
+            // which just fits.
+            let mut carry = 0u128;
+            for (j, &b) in other.mag.iter().enumerate() {
+                let t = a as u128 * b as u128 + mag[i + j] as u128 + carry;
+                mag[i + j] = t as u64;
+                carry = t >> 64;
+            }
+            mag[i + other.mag.len()] = carry as u64;
+        }
+        BigInt::new(self.neg != other.neg, mag)
+    }
+
+    /// The difference.
+    pub(crate) fn sub(&self, other: &BigInt) -> BigInt {
+        if self.neg != other.neg {
+            let mut mag = self.mag.clone();
+            add_mag(&mut mag, &other.mag);
+            return BigInt::new(self.neg, mag);
+        }
+        if cmp_mag(&self.mag, &other.mag) == Ordering::Less {
+            let mut mag = other.mag.clone();
+            sub_mag(&mut mag, &self.mag);
+            BigInt::new(!self.neg, mag)
+        } else {
+            let mut mag = self.mag.clone();
+            sub_mag(&mut mag, &other.mag);
+            BigInt::new(self.neg, mag)
+        }
+    }
+
+    /// The quotient and remainder of division rounding
+    /// toward zero, as with the primitive integers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `d` is zero.
+    pub(crate) fn div_rem(&self, d: &BigInt) -> (BigInt, BigInt) {
+        assert!(!d.is_zero(), "division by zero");
+        let mut q = vec![0u64; self.mag.len()];
+        let mut r = Vec::new();
+        let bits = self.mag.len() * 64;
+        for bit in (0..bits).rev() {
+            let (limb, shift) = (bit / 64, bit % 64);
+            shl1(&mut r, self.mag[limb] >> shift & 1);
+            if cmp_mag(&r, &d.mag) != Ordering::Less {
+                sub_mag(&mut r, &d.mag);
+                q[limb] |= 1 << shift;
+            }
+        }
+        (BigInt::new(self.neg != d.neg, q), BigInt::new(self.neg, r))
+    }
+
+    /// The greatest common divisor of the absolute values,
+    /// which is zero only if both are.
+    pub(crate) fn gcd(&self, other: &BigInt) -> BigInt {
+        let (mut a, mut b) = (self.abs(), other.abs());
+        while !b.is_zero() {
+            let r = a.div_rem(&b).1;
+            (a, b) = (b, r);
+        }
+        a
+    }
+}
+
+/// Drop high zero limbs.
+fn trim(mag: &mut Vec<u64>) {
+    while mag.last() == Some(&0) {
+        mag.pop();
+    }
+}
+
+/// Compare trimmed magnitudes.
+fn cmp_mag(a: &[u64], b: &[u64]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+/// Add `b` into `a`.
+fn add_mag(a: &mut Vec<u64>, b: &[u64]) {
+    if a.len() < b.len() {
+        a.resize(b.len(), 0);
+    }
+    let mut carry = false;
+    for (i, x) in a.iter_mut().enumerate() {
+        let y = b.get(i).copied().unwrap_or(0);
+        let (s, c1) = x.overflowing_add(y);
+        let (s, c2) = s.overflowing_add(carry as u64);
+        *x = s;
+        carry = c1 || c2;
+    }
+    if carry {
+        a.push(1);
+    }
+}
+
+/// Subtract `b` from `a`, which must be no smaller,
+/// leaving `a` trimmed.
+fn sub_mag(a: &mut Vec<u64>, b: &[u64]) {
+    let mut borrow = false;
+    for (i, x) in a.iter_mut().enumerate() {
+        let y = b.get(i).copied().unwrap_or(0);
+        let (d, b1) = x.overflowing_sub(y);
+        let (d, b2) = d.overflowing_sub(borrow as u64);
+        *x = d;
+        borrow = b1 || b2;
+    }
+    assert!(!borrow, "magnitude underflow");
+    trim(a);
+}
+
+/// Shift the trimmed magnitude `a` left one bit, shifting
+/// in `bit`.
+fn shl1(a: &mut Vec<u64>, bit: u64) {
+    let mut carry = bit;
+    for x in a.iter_mut() {
+        let top = *x >> 63;
+        *x = *x << 1 | carry;
+        carry = top;
+    }
+    if carry != 0 {
+        a.push(carry);
+    }
+}
+
+#[test]
+fn test_bigint_arithmetic() {
+    let big = |n: i128| BigInt::from(n);
+    let vals = [
+        0,
+        1,
+        -1,
+        7,
+        -12,
+        1 << 64,
+        -(1 << 70) + 3,
+        u64::MAX as i128,
+        i64::MIN as i128,
+        (1 << 100) + 12345,
+        -(1 << 90) - 1,
+    ];
+    for &x in &vals {
+        assert_eq!(big(x).to_i128(), Some(x));
+        for &y in &vals {
+            let (bx, by) = (big(x), big(y));
+            assert_eq!(bx.sub(&by).to_i128(), x.checked_sub(y));
+            assert_eq!(bx.mul(&by).to_i128(), x.checked_mul(y));
+            let order = x.unsigned_abs().cmp(&y.unsigned_abs());
+            assert_eq!(bx.cmp_abs(&by), order);
+            if y != 0 {
+                let (q, r) = bx.div_rem(&by);
+                assert_eq!(q.to_i128(), Some(x / y));
+                assert_eq!(r.to_i128(), Some(x % y));
+            }
+            let g = match (x, y) {
+                (0, _) => y.abs(),
+                (_, 0) => x.abs(),
+                _ => crate::gcd(x, y),
+            };
+            assert_eq!(bx.gcd(&by).to_i128(), Some(g));
+        }
+    }
+    // Past `i128`, and back.
+    let m = big(i128::MAX);
+    let square = m.mul(&m);
+    assert_eq!(square.to_i128(), None);
+    assert_eq!(square.div_rem(&m), (m.clone(), BigInt::ZERO));
+    assert_eq!(square.sub(&square), BigInt::ZERO);
+    assert_eq!(BigInt::ZERO.sub(&square).mul(&big(-1)), square);
+    assert_eq!(big(i128::MIN).to_i128(), Some(i128::MIN));
+    assert_eq!(big(i128::MIN).sub(&big(1)).to_i128(), None);
+    // 2^127 - 1 is prime.
+    assert_eq!(square.gcd(&m.mul(&big(6))), m);
+}
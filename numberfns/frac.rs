@@ -0,0 +1,536 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Exact fractions, and exact linear algebra with them.
+//!
+//! Puzzles with large coordinates and lines through them
+//! (2023 day 24) need exact answers that `f64` cannot be
+//! trusted to give. Fractions here are `i128` over `i128`,
+//! which holds such answers easily; `solve_linear()` works
+//! with bigger integers on the way to them.
+//!
+//! # Examples
+//!
+//! ```
+//! # use numberfns::*;
+//! let third = Frac::new(1, 3);
+//! assert_eq!(third + third + third, Frac::from(1));
+//! assert_eq!(Frac::new(6, -4).to_string(), "-3/2");
+//!
+//! // 2x + y = 5, x - y = 1.
+//! let a = [[2.into(), 1.into()], [1.into(), (-1).into()]];
+//! let x = solve_linear(a, [5.into(), 1.into()]).unwrap();
+//! assert_eq!(x, [2.into(), 1.into()]);
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, RangeInclusive, Sub};
+
+use crate::bigint::BigInt;
+use crate::gcd;
+
+/// An exact fraction, always in lowest terms with a
+/// positive denominator. Arithmetic panics on overflow; the
+/// `checked_` methods return `None` instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Frac {
+    num: i128,
+    den: i128,
+}
+
+impl Frac {
+    /// Zero.
+    pub const ZERO: Frac = Frac { num: 0, den: 1 };
+    /// One.
+    pub const ONE: Frac = Frac { num: 1, den: 1 };
+
+    /// The fraction `num / den`, reduced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `den` is zero.
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "fraction with zero denominator");
+        if num == 0 {
+            return Frac::ZERO;
+        }
+        let g = gcd(num, den) * den.signum();
+        Frac {
+            num: num / g,
+            den: den / g,
+        }
+    }
+
+    /// The numerator, in lowest terms.
+    pub fn numer(self) -> i128 {
+        self.num
+    }
+
+    /// The denominator, in lowest terms: always positive.
+    pub fn denom(self) -> i128 {
+        self.den
+    }
+
+    /// True if the fraction is zero.
+    pub fn is_zero(self) -> bool {
+        self.num == 0
+    }
+
+    /// The fraction as an integer, if it is one.
+    pub fn to_integer(self) -> Option<i128> {
+        (self.den == 1).then_some(self.num)
+    }
+
+    /// The reciprocal of the fraction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fraction is zero.
+    pub fn recip(self) -> Self {
+        Frac::new(self.den, self.num)
+    }
+
+    /// The sum of two fractions, or `None` on overflow.
+    pub fn checked_add(self, other: Frac) -> Option<Frac> {
+        let g = gcd(self.den, other.den);
+        let num = self
+            .num
+            .checked_mul(other.den / g)?
+            .checked_add(other.num.checked_mul(self.den / g)?)?;
+        Some(Frac::new(num, (self.den / g).checked_mul(other.den)?))
+    }
+
+    /// The difference of two fractions, or `None` on
+    /// overflow.
+    pub fn checked_sub(self, other: Frac) -> Option<Frac> {
+        self.checked_add(-other)
+    }
+
+    /// The product of two fractions, or `None` on overflow.
+    pub fn checked_mul(self, other: Frac) -> Option<Frac> {
+        if self.is_zero() || other.is_zero() {
+            return Some(Frac::ZERO);
+        }
+        // Cancel before multiplying, to keep the products
+        // small.
+        let g1 = gcd(self.num, other.den);
+        let g2 = gcd(other.num, self.den);
+        Some(Frac::new(
+            (self.num / g1).checked_mul(other.num / g2)?,
+            (self.den / g2).checked_mul(other.den / g1)?,
+        ))
+    }
+
+    /// The quotient of two fractions, or `None` if `other`
+    /// is zero or on overflow.
+    pub fn checked_div(self, other: Frac) -> Option<Frac> {
+        if other.is_zero() {
+            return None;
+        }
+        self.checked_mul(other.recip())
+    }
+}
+
+impl Default for Frac {
+    fn default() -> Self {
+        Frac::ZERO
+    }
+}
+
+impl From<i128> for Frac {
+    fn from(n: i128) -> Self {
+        Frac { num: n, den: 1 }
+    }
+}
+
+impl From<i64> for Frac {
+    fn from(n: i64) -> Self {
+        Frac::from(n as i128)
+    }
+}
+
+impl From<i32> for Frac {
+    fn from(n: i32) -> Self {
+        Frac::from(n as i128)
+    }
+}
+
+impl fmt::Display for Frac {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl Ord for Frac {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_ratios(self.num, self.den, other.num, other.den)
+    }
+}
+
+/// Compare `a / b` with `c / d`, where `b` and `d` are
+/// positive. Denominators are positive, so cross-multiplying
+/// keeps the order. When the products overflow, compare the
+/// integer parts instead; if those are equal, comparing the
+/// remainders is comparing the reciprocals of the
+/// fractional parts the other way round, which are the next
+/// terms of the continued fractions and smaller numbers.
+fn cmp_ratios(a: i128, b: i128, c: i128, d: i128) -> Ordering {
+    let (mut a, mut b, mut c, mut d) = (a, b, c, d);
+    loop {
+        if let (Some(ad), Some(cb)) = (a.checked_mul(d), c.checked_mul(b)) {
+            return ad.cmp(&cb);
+        }
+        let (q1, r1) = (a.div_euclid(b), a.rem_euclid(b));
+        let (q2, r2) = (c.div_euclid(d), c.rem_euclid(d));
+        if q1 != q2 {
+            return q1.cmp(&q2);
+        }
+        // Now compare r1 / b with r2 / d, both in [0, 1).
+        match (r1, r2) {
+            (0, 0) => return Ordering::Equal,
+            (0, _) => return Ordering::Less,
+            (_, 0) => return Ordering::Greater,
+            _ => (a, b, c, d) = (d, r2, b, r1),
+        }
+    }
+}
+
+impl PartialOrd for Frac {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for Frac {
+    type Output = Frac;
+
+    fn neg(self) -> Frac {
+        Frac {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl Add for Frac {
+    type Output = Frac;
+
+    fn add(self, other: Frac) -> Frac {
+        self.checked_add(other).expect("fraction overflow")
+    }
+}
+
+impl Sub for Frac {
+    type Output = Frac;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, other: Frac) -> Frac {
+        self + -other
+    }
+}
+
+impl Mul for Frac {
+    type Output = Frac;
+
+    fn mul(self, other: Frac) -> Frac {
+        self.checked_mul(other).expect("fraction overflow")
+    }
+}
+
+impl Div for Frac {
+    type Output = Frac;
+
+    /// # Panics
+    ///
+    /// Panics if `other` is zero.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Frac) -> Frac {
+        self * other.recip()
+    }
+}
+
+/// Solve the linear system `a x = b` exactly, returning
+/// `x`. Returns `None` if `a` is singular, or if some
+/// component of `x` does not fit in a `Frac`.
+///
+/// Each equation is scaled to integers and the elimination
+/// is fraction-free, with each row divided through by the
+/// GCD of its entries after every step. Even so, the
+/// numbers along the way can be far bigger than the answer,
+/// so they are kept as integers of unbounded size: the six
+/// equations for the rock in 2023 day 24, with positions
+/// around 10^14 and velocities in the hundreds, pass `i128`
+/// about one time in seven.
+pub fn solve_linear<const N: usize>(
+    a: [[Frac; N]; N],
+    b: [Frac; N],
+) -> Option<[Frac; N]> {
+    // Each equation as integers, with the right-hand side
+    // last.
+    let mut rows = Vec::with_capacity(N);
+    for (row, &rhs) in a.iter().zip(&b) {
+        let row: Vec<Frac> = row.iter().copied().chain([rhs]).collect();
+        let scale = row.iter().fold(BigInt::from(1), |scale, f| {
+            let den = BigInt::from(f.denom());
+            scale.div_rem(&scale.gcd(&den)).0.mul(&den)
+        });
+        let ints = row
+            .iter()
+            .map(|f| {
+                let den = BigInt::from(f.denom());
+                BigInt::from(f.numer()).mul(&scale.div_rem(&den).0)
+            })
+            .collect();
+        rows.push(primitive(ints));
+    }
+    for col in 0..N {
+        // The smallest pivot keeps the rows it is used on
+        // smallest.
+        let pivot = (col..N)
+            .filter(|&r| !rows[r][col].is_zero())
+            .min_by(|&r, &s| rows[r][col].cmp_abs(&rows[s][col]))?;
+        rows.swap(col, pivot);
+        for r in 0..N {
+            if r == col || rows[r][col].is_zero() {
+                continue;
+            }
+            let g = rows[col][col].gcd(&rows[r][col]);
+            let p = rows[col][col].div_rem(&g).0;
+            let q = rows[r][col].div_rem(&g).0;
+            let row = rows[r]
+                .iter()
+                .zip(&rows[col])
+                .map(|(x, y)| x.mul(&p).sub(&y.mul(&q)))
+                .collect();
+            rows[r] = primitive(row);
+        }
+    }
+    // Each row is now just its diagonal entry and its
+    // right-hand side.
+    let mut x = [Frac::ZERO; N];
+    for (i, row) in rows.iter().enumerate() {
+        x[i] = Frac::new(row[N].to_i128()?, row[i].to_i128()?);
+    }
+    Some(x)
+}
+
+/// The given integers divided through by their GCD.
+fn primitive(mut row: Vec<BigInt>) -> Vec<BigInt> {
+    let g = row.iter().fold(BigInt::ZERO, |g, x| g.gcd(x));
+    if !g.is_zero() && g != BigInt::from(1) {
+        for x in &mut row {
+            *x = x.div_rem(&g).0;
+        }
+    }
+    row
+}
+
+/// Given two points moving in the plane, starting at `p1`
+/// and `p2` with velocities `v1` and `v2`, return true if
+/// their paths cross at a point neither has yet reached
+/// (or is at now), with both coordinates in `window`. The
+/// points need not get there at the same time. Paths that
+/// are parallel are taken never to cross.
+pub fn segments_intersect_future(
+    p1: [i64; 2],
+    v1: [i64; 2],
+    p2: [i64; 2],
+    v2: [i64; 2],
+    window: RangeInclusive<i64>,
+) -> bool {
+    let cross = |a: [i128; 2], b: [i128; 2]| a[0] * b[1] - a[1] * b[0];
+    let wide = |v: [i64; 2]| [v[0] as i128, v[1] as i128];
+    let (v1, v2) = (wide(v1), wide(v2));
+    let det = cross(v1, v2);
+    if det == 0 {
+        return false;
+    }
+    // Solve p1 + t v1 = p2 + s v2 for the times t and s.
+    let d = [p2[0] as i128 - p1[0] as i128, p2[1] as i128 - p1[1] as i128];
+    let t = Frac::new(cross(d, v2), det);
+    let s = Frac::new(cross(d, v1), det);
+    if t < Frac::ZERO || s < Frac::ZERO {
+        return false;
+    }
+    let lo = Frac::from(*window.start());
+    let hi = Frac::from(*window.end());
+    (0..2).all(|i| {
+        let x = Frac::from(p1[i]) + t * Frac::from(v1[i]);
+        lo <= x && x <= hi
+    })
+}
+
+/// 2023 day 24 example hailstones, as position and
+/// velocity.
+#[cfg(test)]
+const HAILSTONES: [([i64; 3], [i64; 3]); 5] = [
+    ([19, 13, 30], [-2, 1, -2]),
+    ([18, 19, 22], [-1, -1, -2]),
+    ([20, 25, 34], [-2, -2, -4]),
+    ([12, 31, 28], [-1, -2, -1]),
+    ([20, 19, 15], [1, -5, -3]),
+];
+
+#[test]
+fn test_frac_arithmetic() {
+    let f = Frac::new;
+    assert_eq!(f(2, 4), f(1, 2));
+    assert_eq!(f(3, -6), f(-1, 2));
+    assert_eq!(f(-3, -6).denom(), 2);
+    assert_eq!(f(0, -5), Frac::ZERO);
+    assert_eq!(f(1, 2) + f(1, 3), f(5, 6));
+    assert_eq!(f(1, 2) - f(1, 3), f(1, 6));
+    assert_eq!(f(2, 3) * f(9, 4), f(3, 2));
+    assert_eq!(f(2, 3) / f(-4, 9), f(-3, 2));
+    assert!(f(-1, 2) < f(-1, 3));
+    assert!(f(7, 3) > Frac::from(2));
+    assert_eq!(f(10, 5).to_integer(), Some(2));
+    assert_eq!(f(10, 4).to_integer(), None);
+    assert_eq!(f(1, 2).checked_add(f(1, 3)), Some(f(5, 6)));
+    assert_eq!(f(1, 2).checked_sub(f(1, 2)), Some(Frac::ZERO));
+    assert_eq!(f(2, 3).checked_div(Frac::ZERO), None);
+    let huge = Frac::from(i128::MAX);
+    assert_eq!(huge.checked_add(Frac::ONE), None);
+    assert_eq!(huge.checked_mul(f(2, 3)), None);
+    assert_eq!(huge.checked_mul(f(2, 4)), Some(f(i128::MAX, 2)));
+    assert_eq!(Frac::from(5).to_string(), "5");
+    // Comparisons whose cross products overflow.
+    let n = i128::MAX;
+    assert!(f(n, n - 1) < f(n - 1, n - 2));
+    assert!(f(-n, n - 1) > f(1 - n, n - 2));
+    assert!(f(n - 2, n - 1) < f(n - 1, n));
+    assert!(f(n, n - 2) < f(n - 1, n - 3));
+    assert!(f(n - 1, n) > f(n / 2, n / 2 + 1));
+    assert_eq!(f(n, n - 1).cmp(&f(n, n - 1)), Ordering::Equal);
+    // Big values that would not survive a trip through f64.
+    let big = Frac::from(1i64 << 60) + f(1, 3);
+    assert_eq!((big - Frac::from(1i64 << 60)) * Frac::from(3), Frac::ONE);
+}
+
+#[test]
+fn test_solve_linear() {
+    let g = |n: i64| Frac::from(n);
+    let singular = [[g(1), g(2)], [g(2), g(4)]];
+    assert_eq!(solve_linear(singular, [g(1), g(2)]), None);
+    // Needs a row swap for the first pivot.
+    let a = [[g(0), g(1), g(1)], [g(2), g(0), g(1)], [g(1), g(1), g(0)]];
+    let x = solve_linear(a, [g(5), g(5), g(3)]).unwrap();
+    assert_eq!(x, [g(1), g(2), g(3)]);
+    let x = solve_linear([[g(3)]], [g(1)]).unwrap();
+    assert_eq!(x, [Frac::new(1, 3)]);
+}
+
+#[test]
+fn test_hailstones_cross() {
+    let mut crossings = 0;
+    for (i, &(p1, v1)) in HAILSTONES.iter().enumerate() {
+        for &(p2, v2) in &HAILSTONES[i + 1..] {
+            let (p1, v1) = ([p1[0], p1[1]], [v1[0], v1[1]]);
+            let (p2, v2) = ([p2[0], p2[1]], [v2[0], v2[1]]);
+            crossings += segments_intersect_future(p1, v1, p2, v2, 7..=27)
+                as usize;
+        }
+    }
+    assert_eq!(crossings, 2);
+}
+
+/// The linear system for the rock of 2023 day 24 part 2,
+/// from three of the hailstones. The rock at P with
+/// velocity V hits each hailstone, so (P - p) × (V - v) = 0
+/// for each. Subtracting these for two hailstones cancels
+/// the P × V term, leaving three linear equations. Two
+/// pairs give six equations for the six unknowns.
+#[cfg(test)]
+fn rock_system(
+    stones: [([i64; 3], [i64; 3]); 3],
+) -> ([[Frac; 6]; 6], [Frac; 6]) {
+    let g = |n: i64| Frac::from(n);
+    let cross = |a: [i64; 3], b: [i64; 3]| {
+        [
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ]
+    };
+    let sub = |a: [i64; 3], b: [i64; 3]| {
+        [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+    };
+    let (p0, v0) = stones[0];
+    let mut a = [[Frac::ZERO; 6]; 6];
+    let mut b = [Frac::ZERO; 6];
+    for (k, &(pj, vj)) in stones[1..].iter().enumerate() {
+        let [wx, wy, wz] = sub(vj, v0);
+        let [qx, qy, qz] = sub(pj, p0);
+        let rhs = sub(cross(pj, vj), cross(p0, v0));
+        let rows = [
+            [0, wz, -wy, 0, -qz, qy],
+            [-wz, 0, wx, qz, 0, -qx],
+            [wy, -wx, 0, -qy, qx, 0],
+        ];
+        for (i, row) in rows.iter().enumerate() {
+            a[3 * k + i] = row.map(g);
+            b[3 * k + i] = g(rhs[i]);
+        }
+    }
+    (a, b)
+}
+
+#[test]
+fn test_rock_throw() {
+    let stones = [HAILSTONES[0], HAILSTONES[1], HAILSTONES[3]];
+    let (a, b) = rock_system(stones);
+    let x = solve_linear(a, b).unwrap();
+    let rock: Vec<i128> = x.iter().map(|c| c.to_integer().unwrap()).collect();
+    assert_eq!(rock, [24, 13, 10, -3, 1, 2]);
+    assert_eq!(rock[..3].iter().sum::<i128>(), 47);
+}
+
+#[test]
+fn test_rock_throw_puzzle_scale() {
+    // Hailstones hit around 10^11 to 10^12 ns in, as in a
+    // puzzle input. Eliminating with `i128` fractions
+    // overflows on this one.
+    let stones = [
+        ([153062024997492, 208884823790666, 323796812227191], [210, 62, 125]),
+        ([402195495034510, 240846258032290, 218263873492337], [-77, 16, 256]),
+        ([173628523643654, 371838280587940, 383085879713655], [231, -224, -89]),
+    ];
+    let (a, b) = rock_system(stones);
+    let x = solve_linear(a, b).unwrap();
+    let rock: Vec<i128> = x.iter().map(|c| c.to_integer().unwrap()).collect();
+    let desired = [
+        225574254304646,
+        281397053097820,
+        228176290063911,
+        119,
+        -29,
+        245,
+    ];
+    assert_eq!(rock, desired);
+
+    // Here the elimination passes `i128`.
+    let stones = [
+        ([237790967864476, 172792052575916, 220680648362222], [-35, 217, 26]),
+        ([26328688224340, 158374386823160, 125106098020646], [131, -186, 39]),
+        ([222371563070614, 65944134469360, 31119642110236], [-64, -59, 175]),
+    ];
+    let (a, b) = rock_system(stones);
+    let x = solve_linear(a, b).unwrap();
+    let rock: Vec<i128> = x.iter().map(|c| c.to_integer().unwrap()).collect();
+    let desired = [
+        244536295657540,
+        230127338816960,
+        232244067436046,
+        -91,
+        -259,
+        -70,
+    ];
+    assert_eq!(rock, desired);
+}
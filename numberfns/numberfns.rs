@@ -5,21 +5,94 @@
 
 //! Number-theoretic functions for Advent of Code solutions.
 
+mod frac;
+pub use frac::*;
+
+mod poly;
+pub use poly::*;
+
+mod bigint;
+
 use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::ops::{Div, Mul, Rem};
+
+/// Primitive integer types, signed or unsigned, for the
+/// number-theoretic functions here.
+pub trait Integer:
+    Copy
+    + Debug
+    + Ord
+    + Div<Output = Self>
+    + Mul<Output = Self>
+    + Rem<Output = Self>
+{
+    /// Zero.
+    const ZERO: Self;
+
+    /// The absolute value. Unsigned types are their own
+    /// absolute values.
+    fn abs_value(self) -> Self;
+
+    /// The product, or `None` if it overflows.
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! integer_unsigned {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+
+            fn abs_value(self) -> Self {
+                self
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+        }
+    )*};
+}
+
+macro_rules! integer_signed {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: Self = 0;
+
+            fn abs_value(self) -> Self {
+                self.abs()
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+        }
+    )*};
+}
+
+integer_unsigned!(u8, u16, u32, u64, u128, usize);
+integer_signed!(i8, i16, i32, i64, i128, isize);
 
 /// The GCD is not part of standard Rust. We don't need
 /// super-efficiency, so we just use the faster form of the
 /// [Euclidean
 /// Algorithm](https://en.wikipedia.org/wiki/Euclidean_algorithm#Procedure).
+/// Signed inputs are taken by absolute value, so the GCD is
+/// always positive.
+///
+/// # Panics
+///
+/// Panics if either input is zero.
 #[allow(clippy::many_single_char_names)]
-pub fn gcd(m: u64, n: u64) -> u64 {
-    assert!(m > 0 && n > 0);
+pub fn gcd<T: Integer>(m: T, n: T) -> T {
+    assert!(m != T::ZERO && n != T::ZERO);
+    let (m, n) = (m.abs_value(), n.abs_value());
     let (mut a, mut b) = if m > n {
         (m, n)
     } else {
         (n, m)
     };
-    while b != 0 {
+    while b != T::ZERO {
         let t = b;
         b = a % b;
         a = t;
@@ -33,20 +106,39 @@ fn test_gcd() {
     assert_eq!(2, gcd(2, 4));
     assert_eq!(2, gcd(4, 2));
     assert_eq!(3, gcd(9, 12));
+    assert_eq!(3u64, gcd(9u64, 12));
+    assert_eq!(6usize, gcd(12usize, 18));
+    assert_eq!(7u32, gcd(7u32, 7));
+    assert_eq!(4i64, gcd(-8i64, 12));
+    assert_eq!(4i64, gcd(-8i64, -12));
+    assert_eq!(1u128 << 70, gcd(3u128 << 70, 1 << 71));
+    assert_eq!(5i128, gcd(-5i128, 1_000_000_000_000_000_000_005));
+}
+
+#[test]
+#[should_panic]
+fn test_gcd_zero() {
+    gcd(0i64, 5);
 }
 
 /// The LCM of a pair of numbers is computed as their
 /// product divided by their GCD.  The implementation is
 /// careful to do things in optimal order to avoid overflow
-/// when possible.
-pub fn lcm(a: u64, b: u64) -> u64 {
-    assert!(a > 0 && b > 0);
+/// when possible. Signed inputs are taken by absolute
+/// value, so the LCM is always positive.
+///
+/// # Panics
+///
+/// Panics if either input is zero.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    assert!(a != T::ZERO && b != T::ZERO);
+    let (a, b) = (a.abs_value(), b.abs_value());
     let (mut p, q) = if a > b {
         (a, b)
     } else {
         (b, a)
     };
-    p /= gcd(p, q);
+    p = p / gcd(p, q);
     p * q
 }
 
@@ -57,6 +149,73 @@ fn test_lcm() {
     assert_eq!(12, lcm(4, 6));
     assert_eq!(60, lcm(20, 6));
     assert_eq!(100, lcm(25, 4));
+    assert_eq!(60usize, lcm(20usize, 6));
+    assert_eq!(60i64, lcm(-20i64, 6));
+    assert_eq!(1u128 << 80, lcm(1u128 << 80, 1 << 79));
+}
+
+/// The GCD of all the given numbers, or `None` if there
+/// are none.
+///
+/// # Panics
+///
+/// Panics if any of the numbers is zero.
+pub fn gcd_all<T, I>(values: I) -> Option<T>
+where
+    T: Integer,
+    I: IntoIterator<Item = T>,
+{
+    let mut values = values.into_iter();
+    let first = values.next()?;
+    assert!(first != T::ZERO);
+    Some(values.fold(first.abs_value(), gcd))
+}
+
+#[test]
+fn test_gcd_all() {
+    assert_eq!(gcd_all([12u64, 18, 30]), Some(6));
+    assert_eq!(gcd_all(vec![-9i64]), Some(9));
+    assert_eq!(gcd_all(Vec::<usize>::new()), None);
+    assert_eq!(gcd_all([4, 9, 6]), Some(1));
+}
+
+/// The LCM of all the given numbers, or `None` if there are
+/// none or if the LCM overflows `T`. Use a wider `T` to
+/// get a bigger answer.
+///
+/// # Panics
+///
+/// Panics if any of the numbers is zero.
+pub fn lcm_all<T, I>(values: I) -> Option<T>
+where
+    T: Integer,
+    I: IntoIterator<Item = T>,
+{
+    let mut values = values.into_iter();
+    let first = values.next()?;
+    assert!(first != T::ZERO);
+    values.try_fold(first.abs_value(), |m, n| {
+        let n = n.abs_value();
+        m.checked_mul(n / gcd(m, n))
+    })
+}
+
+#[test]
+fn test_lcm_all() {
+    assert_eq!(lcm_all([4u64, 6, 10]), Some(60));
+    assert_eq!(lcm_all(Vec::<u64>::new()), None);
+    assert_eq!(lcm_all([7u32]), Some(7));
+    assert_eq!(lcm_all([-4i64, 6]), Some(12));
+    // Any product of two of these overflows, but the LCM
+    // does not.
+    let big = [1u64 << 40, 3 << 40, 1 << 42];
+    assert_eq!(lcm_all(big), Some(3 << 42));
+    // This LCM really does overflow `u64`, but not `u128`.
+    let primes = [4_294_967_291u64, 4_294_967_279, 65_521];
+    assert_eq!(lcm_all(primes), None);
+    let primes = primes.map(u128::from);
+    let desired = 4_294_967_291u128 * 4_294_967_279 * 65_521;
+    assert_eq!(lcm_all(primes), Some(desired));
 }
 
 /// Extended Euclidean algorithm for GCD. Suitable for
@@ -65,18 +224,36 @@ fn test_lcm() {
 /// From an unsigned-inputs [C++
 /// implementation](https://jeffhurchalla.com/2018/10/13/implementing-the-extended-euclidean-algorithm-with-unsigned-inputs/)
 /// by Jeff Hurchala.
+///
+/// The coefficients returned are no bigger than half the
+/// other input, so they fit in `i64` even for inputs near
+/// `u64::MAX`: `extended_gcd(2, u64::MAX)` needs a
+/// coefficient of `-(2^63 - 1)`, just inside the range.
+///
+/// # Panics
+///
+/// Panics rather than returning a wrapped coefficient if
+/// one should ever fail to fit.
 pub fn extended_gcd(a: u64, b: u64) -> (u64, i64, i64) {
-    let mut x1 = 1i64;
-    let mut y1 = 0i64;
+    let (g, x, y) = extended_gcd_u128(a.into(), b.into());
+    let coeff = |c| i64::try_from(c).expect("Bézout coefficient overflow");
+    (g as u64, coeff(x), coeff(y))
+}
+
+/// Extended Euclidean algorithm for GCD, as with
+/// `extended_gcd()`, for inputs less than 2^127.
+pub fn extended_gcd_u128(a: u128, b: u128) -> (u128, i128, i128) {
+    let mut x1 = 1i128;
+    let mut y1 = 0i128;
     let mut a1 = a;
-    let mut x0 = 0i64;
-    let mut y0 = 1i64;
+    let mut x0 = 0i128;
+    let mut y0 = 1i128;
     let mut a2 = b;
-    let mut q = 0u64;
+    let mut q = 0u128;
 
     while a2 != 0 {
-        let x2 = x0 - q as i64 *x1;
-        let y2 = y0 - q as i64 * y1;
+        let x2 = x0 - q as i128 *x1;
+        let y2 = y0 - q as i128 * y1;
         x0 = x1;
         y0 = y1;
         let a0 = a1;
@@ -97,6 +274,26 @@ fn test_extended_gcd() {
     assert_eq!(extended_gcd(1914, 899), (29, 8, -17));
     // http://www.math.cmu.edu/~bkell/21110-2010s/extended-euclidean.html
     assert_eq!(extended_gcd(1398, 324), (6, -19, 82));
+    assert_eq!(extended_gcd(u64::MAX, 1), (1, 0, 1));
+    // Coefficients at the edge of `i64`.
+    let edge = -i64::MAX;
+    assert_eq!(extended_gcd(2, u64::MAX), (1, edge, 1));
+    assert_eq!(extended_gcd(u64::MAX, 2), (1, 1, edge));
+    let (g, x, y) = extended_gcd(u64::MAX, u64::MAX - 1);
+    assert_eq!(g, 1);
+    let (a, b) = (u64::MAX as i128, (u64::MAX - 1) as i128);
+    assert_eq!(x as i128 * a + y as i128 * b, 1);
+}
+
+#[test]
+fn test_extended_gcd_u128() {
+    assert_eq!(extended_gcd_u128(1914, 899), (29, 8, -17));
+    let (a, b) = (1u128 << 80, 3u128.pow(25));
+    let (g, x, y) = extended_gcd_u128(a, b);
+    assert_eq!(g, 1);
+    assert_eq!(x * a as i128 + y * b as i128, 1);
+    let (g, x, y) = extended_gcd_u128(2, u128::MAX >> 1);
+    assert_eq!((g, x, y), (1, -(i128::MAX >> 1), 1));
 }
 
 /// Modular multiplicative inverse. Returns `None` if the
@@ -106,6 +303,12 @@ fn test_extended_gcd() {
 /// implementation](https://jeffhurchalla.com/2018/10/13/implementing-the-extended-euclidean-algorithm-with-unsigned-inputs/)
 /// by Jeff Hurchala.
 pub fn mod_inv(modulus: u64, value: u64) -> Option<u64> {
+    mod_inv_u128(modulus.into(), value.into()).map(|inv| inv as u64)
+}
+
+/// Modular multiplicative inverse, as with `mod_inv()`, for
+/// a modulus less than 2^127.
+pub fn mod_inv_u128(modulus: u128, value: u128) -> Option<u128> {
     // Ordinarily, operations modulo 0 are undefined.
     if modulus == 0 {
         return None;
@@ -119,14 +322,14 @@ pub fn mod_inv(modulus: u64, value: u64) -> Option<u64> {
     if modulus == 1 {
         return Some(0);
     }
-    let (gcd, _, y) = extended_gcd(modulus, value);
+    let (gcd, _, y) = extended_gcd_u128(modulus, value);
     if gcd != 1 {
         return None;
     }
     if y >= 0 {
-        Some(y as u64)
+        Some(y as u128)
     } else {
-        Some((y + i64::try_from(modulus).unwrap()) as u64)
+        Some((y + i128::try_from(modulus).unwrap()) as u128)
     }
 }
 
@@ -137,6 +340,313 @@ fn test_mod_inv() {
     assert_eq!(mod_inv(9, 12), None);
     // https://rosettacode.org/wiki/Modular_inverse
     assert_eq!(mod_inv(2017, 42), Some(1969));
+    assert_eq!(mod_inv(1, 1), Some(0));
+}
+
+#[test]
+fn test_mod_inv_u128() {
+    assert_eq!(mod_inv_u128(2017, 42), Some(1969));
+    assert_eq!(mod_inv_u128(9, 12), None);
+    // A modulus past `u64`.
+    let m = (1u128 << 89) - 1;
+    let v = 1_000_000_007u128;
+    let inv = mod_inv_u128(m, v).unwrap();
+    assert!(inv < m);
+    assert_eq!(inv * v % m, 1);
+}
+
+/// The product *a* × *b* reduced mod `modulus`, computed
+/// without overflow for any `u64` modulus.
+///
+/// # Panics
+///
+/// Panics if `modulus` is zero.
+pub fn mod_mul(a: u64, b: u64, modulus: u64) -> u64 {
+    assert!(modulus != 0);
+    (u128::from(a) * u128::from(b) % u128::from(modulus)) as u64
+}
+
+#[test]
+fn test_mod_mul() {
+    assert_eq!(mod_mul(3, 4, 5), 2);
+    assert_eq!(mod_mul(7, 8, 1), 0);
+    let m = (1 << 63) + 25;
+    assert_eq!(mod_mul(m - 1, m - 1, m), 1);
+    assert_eq!(mod_mul(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+}
+
+/// *base*^*exp* reduced mod `modulus`, by [repeated
+/// squaring](https://en.wikipedia.org/wiki/Exponentiation_by_squaring).
+/// By convention 0^0 is 1, reduced.
+///
+/// # Panics
+///
+/// Panics if `modulus` is zero.
+pub fn mod_pow(base: u64, exp: u64, modulus: u64) -> u64 {
+    assert!(modulus != 0);
+    let mut base = base % modulus;
+    let mut exp = exp;
+    let mut result = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mod_mul(result, base, modulus);
+        }
+        base = mod_mul(base, base, modulus);
+        exp >>= 1;
+    }
+    result
+}
+
+#[test]
+fn test_mod_pow() {
+    assert_eq!(mod_pow(2, 10, 1000), 24);
+    assert_eq!(mod_pow(5, 0, 7), 1);
+    assert_eq!(mod_pow(0, 0, 7), 1);
+    assert_eq!(mod_pow(5, 3, 1), 0);
+    assert_eq!(mod_pow(0, 5, 7), 0);
+    assert_eq!(mod_pow(12, 2, 5), 4);
+    // Advent of Code 2015 day 25 example: the code at
+    // row 6, column 6, which is on diagonal 11.
+    let n = 10 * 11 / 2 + 6;
+    let code = mod_mul(20151125, mod_pow(252533, n - 1, 33554393), 33554393);
+    assert_eq!(code, 27995004);
+    // Big modulus: (m-1)^odd ≡ -1.
+    let m = (1 << 63) + 25;
+    assert_eq!(mod_pow(m - 1, 12345, m), m - 1);
+}
+
+#[test]
+fn test_mod_pow_fermat() {
+    for p in [7u64, 2017, 1_000_000_007, 18_446_744_073_709_551_557] {
+        for a in [2u64, 3, 42, 1_000_003] {
+            if a % p == 0 {
+                continue;
+            }
+            assert_eq!(mod_pow(a, p - 2, p), mod_inv(p, a % p).unwrap());
+            assert_eq!(mod_pow(a, p - 1, p), 1);
+        }
+    }
+}
+
+/// True iff `n` is prime. Uses
+/// [Miller–Rabin](https://en.wikipedia.org/wiki/Miller%E2%80%93Rabin_primality_test)
+/// with the first twelve primes as witnesses, which is
+/// deterministic for all `u64`.
+pub fn is_prime(n: u64) -> bool {
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    for &p in &WITNESSES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    'witness: for &a in &WITNESSES {
+        let mut x = mod_pow(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = mod_mul(x, x, n);
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+#[test]
+fn test_is_prime() {
+    assert!(!is_prime(0));
+    assert!(!is_prime(1));
+    assert!(is_prime(2));
+    assert!(is_prime(3));
+    assert!(!is_prime(4));
+    assert!(is_prime(37));
+    assert!(is_prime(41));
+    assert!(!is_prime(41 * 41));
+    assert!(!is_prime(1_000_000_007 * 1_000_000_007));
+    // Carmichael numbers.
+    for n in [561, 1105, 1729, 2465, 2821, 6601, 8911] {
+        assert!(!is_prime(n));
+    }
+    // A strong pseudoprime to bases 2 through 11.
+    assert!(!is_prime(3_825_123_056_546_413_051));
+    assert!(is_prime(1_000_000_007));
+    assert!(is_prime(18_446_744_073_709_551_557));
+    assert!(!is_prime(u64::MAX));
+    let small: Vec<u64> = (0..50).filter(|&n| is_prime(n)).collect();
+    assert_eq!(small, [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47]);
+}
+
+/// All the primes less than or equal to `n`, in increasing
+/// order. Uses a [Sieve of
+/// Eratosthenes](https://en.wikipedia.org/wiki/Sieve_of_Eratosthenes)
+/// over the odd numbers only, one bit per number, so that
+/// `n` in the tens of millions is cheap.
+pub fn primes_upto(n: usize) -> Vec<u64> {
+    if n < 2 {
+        return Vec::new();
+    }
+    // Bit i stands for 2i + 1 and is set if composite.
+    let nodd = n.div_ceil(2);
+    let mut composite = vec![0u64; nodd.div_ceil(64)];
+    let get = |bits: &[u64], i: usize| bits[i / 64] >> (i % 64) & 1 == 1;
+    let mut i = 1;
+    while (2 * i + 1) * (2 * i + 1) <= n {
+        if !get(&composite, i) {
+            let p = 2 * i + 1;
+            let mut j = p * p / 2;
+            while j < nodd {
+                composite[j / 64] |= 1 << (j % 64);
+                j += p;
+            }
+        }
+        i += 1;
+    }
+    let mut primes = vec![2];
+    primes.extend(
+        (1..nodd)
+            .filter(|&i| !get(&composite, i))
+            .map(|i| 2 * i as u64 + 1),
+    );
+    primes
+}
+
+#[test]
+fn test_primes_upto() {
+    assert!(primes_upto(0).is_empty());
+    assert!(primes_upto(1).is_empty());
+    assert_eq!(primes_upto(2), [2]);
+    assert_eq!(primes_upto(3), [2, 3]);
+    assert_eq!(primes_upto(30), [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+    assert_eq!(primes_upto(49).last(), Some(&47));
+    assert_eq!(primes_upto(53).last(), Some(&53));
+    let primes = primes_upto(100_000);
+    assert_eq!(primes.len(), 9592);
+    let expected: Vec<u64> = (0..=100_000).filter(|&n| is_prime(n)).collect();
+    assert_eq!(primes, expected);
+}
+
+/// *n*!, or `None` if it overflows.
+pub fn factorial(n: u64) -> Option<u64> {
+    (2..=n).try_fold(1u64, |f, i| f.checked_mul(i))
+}
+
+#[test]
+fn test_factorial() {
+    assert_eq!(factorial(0), Some(1));
+    assert_eq!(factorial(1), Some(1));
+    assert_eq!(factorial(5), Some(120));
+    assert_eq!(factorial(20), Some(2_432_902_008_176_640_000));
+    assert_eq!(factorial(21), None);
+}
+
+/// The binomial coefficient *C*(*n*, *k*), or `None` if it
+/// overflows. This is 0 when *k* > *n*.
+///
+/// Uses the multiplicative formula, dividing out common
+/// factors at each step so that intermediate results are
+/// no bigger than they need to be.
+pub fn binomial(n: u64, k: u64) -> Option<u64> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k);
+    (0..k).try_fold(1u64, |c, i| {
+        // c * (n - i) / (i + 1) is exact. With the common
+        // factor of c and i + 1 divided out, what's left
+        // of i + 1 must divide n - i.
+        let g = if c == 1 { 1 } else { gcd(c, i + 1) };
+        let d = (i + 1) / g;
+        (c / g).checked_mul((n - i) / d)
+    })
+}
+
+#[test]
+fn test_binomial() {
+    assert_eq!(binomial(5, 2), Some(10));
+    assert_eq!(binomial(7, 0), Some(1));
+    assert_eq!(binomial(7, 7), Some(1));
+    assert_eq!(binomial(0, 0), Some(1));
+    assert_eq!(binomial(3, 4), Some(0));
+    assert_eq!(binomial(52, 5), Some(2_598_960));
+    assert_eq!(binomial(52, 26), Some(495_918_532_948_104));
+    assert_eq!(binomial(62, 31), Some(465_428_353_255_261_088));
+    assert_eq!(binomial(66, 33), Some(7_219_428_434_016_265_740));
+    assert_eq!(binomial(68, 34), None);
+    assert_eq!(binomial(u64::MAX, 1), Some(u64::MAX));
+    assert_eq!(binomial(u64::MAX, 2), None);
+    for n in 0..30 {
+        let row: Vec<u64> = (0..=n).map(|k| binomial(n, k).unwrap()).collect();
+        assert_eq!(row.iter().sum::<u64>(), 1 << n);
+    }
+}
+
+/// The binomial coefficient *C*(*n*, *k*) reduced mod the
+/// prime `p`. This is 0 when *k* > *n*.
+///
+/// Each base-`p` digit is handled with factorials and
+/// `mod_inv()`, and the digits are combined with [Lucas's
+/// theorem](https://en.wikipedia.org/wiki/Lucas%27s_theorem),
+/// so *n* may be larger than `p`.
+///
+/// # Panics
+///
+/// Panics if `p` is zero. The result is meaningless if `p`
+/// is not prime.
+pub fn binomial_mod(n: u64, k: u64, p: u64) -> u64 {
+    assert!(p != 0);
+    let (mut n, mut k) = (n, k);
+    let mut result = 1 % p;
+    while k > 0 {
+        let (ni, ki) = (n % p, k % p);
+        if ki > ni {
+            return 0;
+        }
+        let ki = ki.min(ni - ki);
+        let mut num = 1;
+        let mut den = 1;
+        for i in 0..ki {
+            num = mod_mul(num, ni - i, p);
+            den = mod_mul(den, i + 1, p);
+        }
+        let Some(inv) = mod_inv(p, den) else {
+            return 0;
+        };
+        result = mod_mul(result, mod_mul(num, inv, p), p);
+        n /= p;
+        k /= p;
+    }
+    result
+}
+
+#[test]
+fn test_binomial_mod() {
+    const P: u64 = 1_000_000_007;
+    assert_eq!(binomial_mod(5, 2, 7), 3);
+    assert_eq!(binomial_mod(10, 0, 7), 1);
+    assert_eq!(binomial_mod(10, 10, 7), 1);
+    assert_eq!(binomial_mod(3, 4, 7), 0);
+    assert_eq!(binomial_mod(52, 26, P), 495_918_532_948_104 % P);
+    // n ≥ p, via Lucas.
+    assert_eq!(binomial_mod(10, 3, 3), 120 % 3);
+    assert_eq!(binomial_mod(1000, 500, 13), 0);
+    for n in 0..40 {
+        for k in 0..=n {
+            let c = binomial(n, k).unwrap();
+            for p in [2, 3, 5, 7, 13, 37] {
+                assert_eq!(binomial_mod(n, k, p), c % p);
+            }
+        }
+    }
+    // Known value: C(10^6, 5×10^5) mod (10^9 + 7).
+    assert_eq!(binomial_mod(1_000_000, 500_000, P), 996_692_777);
 }
 
 /// Solution *x* to a pair of congruences
@@ -145,26 +655,38 @@ fn test_mod_inv() {
 /// > *x* ≡ *b* (mod *n*)
 ///
 /// if one exists. Returns *x* and the LCM of *m* and *n*.
+/// The moduli need not be coprime. Returns `None` if the
+/// congruences are incompatible or if the LCM of the moduli
+/// does not fit in a `u64`.
+///
+/// The arithmetic is done in `u128`, so moduli anywhere in
+/// the `u64` range are fine.
+///
+/// # Panics
+///
+/// Panics if either modulus is zero.
 ///
 /// From
 /// [Wikipedia](https://en.wikipedia.org/wiki/Chinese_remainder_theorem#Generalization_to_non-coprime_moduli).
 #[allow(clippy::many_single_char_names)]
 pub fn crt(a: u64, b: u64, m: u64, n: u64) -> Option<(u64, u64)> {
-    let (g, u, v) = extended_gcd(m, n);
-    if a % g == b % g {
-        let x = a as i64 * v * n as i64
-            + b as i64 * u * m as i64;
-        let lcm =  m * n / g;
-        let x = if x >= 0 {
-            x
-        } else {
-            x + m as i64 * n as i64
-        };
-        assert!(x >= 0);
-        Some((x as u64 / g, lcm))
-    } else {
-        None
+    let g = u128::from(gcd(m, n));
+    let (a, b, m, n) = (
+        u128::from(a) % u128::from(m),
+        u128::from(b) % u128::from(n),
+        u128::from(m),
+        u128::from(n),
+    );
+    if a % g != b % g {
+        return None;
     }
+    // Solve x = a + m*k where (m/g)*k ≡ (b - a)/g (mod n/g).
+    let ng = n / g;
+    let inv = mod_inv_u128(ng, (m / g) % ng)?;
+    let d = (b + n - a % n) % n / g;
+    let k = d * inv % ng;
+    let lcm = u64::try_from(m * ng).ok()?;
+    Some(((a + m * k) as u64, lcm))
 }
 
 #[test]
@@ -174,8 +696,302 @@ fn test_crt() {
     assert_eq!(crt(3, 4, 6, 6), None);
     assert_eq!(crt(3, 6, 9, 12), Some((30, 36)));
     assert_eq!(crt(3, 5, 9, 12), None);
+    assert_eq!(crt(12, 5, 5, 7), Some((12, 35)));
+}
+
+#[test]
+fn test_crt_large() {
+    // These used to overflow the intermediate products.
+    let (m, n) = (1_000_000_007, 998_244_353);
+    let (a, b) = (123_456_789, 987_654_321);
+    let (x, l) = crt(a, b, m, n).unwrap();
+    assert_eq!(l, m * n);
+    assert_eq!((x % m, x % n), (a, b));
+
+    let (m, n) = (2 * 999_999_937, 3 * 999_999_937);
+    let (x, l) = crt(5, 999_999_942, m, n).unwrap();
+    assert_eq!(l, 6 * 999_999_937);
+    assert_eq!((x % m, x % n), (5, 999_999_942));
+    assert_eq!(crt(5, 6, m, n), None);
+
+    // The LCM doesn't fit.
+    assert_eq!(crt(0, 0, u64::MAX, u64::MAX - 1), None);
 }
 
+/// Solution *x* to a list of congruences, each given as a
+/// pair `(a, m)` meaning *x* ≡ *a* (mod *m*). Returns *x*
+/// and the LCM of the moduli, or `None` as with `crt()`.
+/// An empty list gives `(0, 1)`.
+pub fn crt_all(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(x, l), &(a, m)| crt(x, a, l, m))
+}
+
+#[test]
+fn test_crt_all() {
+    assert_eq!(crt_all(&[]), Some((0, 1)));
+    assert_eq!(crt_all(&[(3, 5)]), Some((3, 5)));
+    assert_eq!(crt_all(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    assert_eq!(crt_all(&[(3, 6), (1, 9), (0, 2)]), None);
+
+    // Advent of Code 2020 day 13 example: buses
+    // `7,13,x,x,59,x,31,19`, where bus *b* at offset *i*
+    // gives *t* ≡ -*i* (mod *b*).
+    let buses = [(0, 7), (1, 13), (4, 59), (6, 31), (7, 19)];
+    let congruences: Vec<(u64, u64)> = buses
+        .iter()
+        .map(|&(i, b)| ((b - i % b) % b, b))
+        .collect();
+    assert_eq!(crt_all(&congruences), Some((1068781, 7 * 13 * 59 * 31 * 19)));
+
+    // Moduli near 10^9.
+    let congruences = [(17, 1_000_000_007), (42, 999_999_937)];
+    let (x, l) = crt_all(&congruences).unwrap();
+    assert_eq!(l, 1_000_000_007 * 999_999_937);
+    assert_eq!((x % 1_000_000_007, x % 999_999_937), (17, 42));
+}
+
+
+/// The integer square root of `n`: the largest *s* with
+/// *s*² ≤ `n`.
+pub fn isqrt(n: u64) -> u64 {
+    n.isqrt()
+}
+
+#[test]
+fn test_isqrt() {
+    assert_eq!(isqrt(0), 0);
+    assert_eq!(isqrt(1), 1);
+    assert_eq!(isqrt(3), 1);
+    assert_eq!(isqrt(4), 2);
+    assert_eq!(isqrt(99), 9);
+    assert_eq!(isqrt(100), 10);
+    let s = 4_294_967_295;
+    assert_eq!(isqrt(s * s), s);
+    assert_eq!(isqrt(s * s - 1), s - 1);
+    assert_eq!(isqrt(u64::MAX), s);
+    for n in 0..10_000u64 {
+        let s = isqrt(n);
+        assert!(s * s <= n && (s + 1) * (s + 1) > n);
+    }
+}
+
+/// True iff `n` is the square of an integer.
+pub fn is_perfect_square(n: u64) -> bool {
+    let s = isqrt(n);
+    s * s == n
+}
+
+#[test]
+fn test_is_perfect_square() {
+    assert!(is_perfect_square(0));
+    assert!(is_perfect_square(1));
+    assert!(!is_perfect_square(2));
+    assert!(is_perfect_square(144));
+    assert!(!is_perfect_square(145));
+    assert!(is_perfect_square(4_294_967_295 * 4_294_967_295));
+    assert!(!is_perfect_square(u64::MAX));
+}
+
+/// The number of integers *x* with *ax*² + *bx* + *c* < 0,
+/// or `None` if there are infinitely many. This is the
+/// count of integers strictly between the roots when *a* >
+/// 0: integer roots themselves are not counted.
+///
+/// # Panics
+///
+/// Panics if the discriminant or an evaluation of the
+/// quadratic overflows `i128`, which can only happen with
+/// coefficients near the `i64` limits.
+pub fn count_integer_solutions(a: i64, b: i64, c: i64) -> Option<u64> {
+    if a < 0 || (a == 0 && (b != 0 || c < 0)) {
+        return None;
+    }
+    if a == 0 {
+        return Some(0);
+    }
+    let (a, b, c) = (i128::from(a), i128::from(b), i128::from(c));
+    let overflow = "count_integer_solutions: overflow";
+    let f = |x: i128| -> i128 {
+        a.checked_mul(x)
+            .and_then(|ax| ax.checked_add(b))
+            .and_then(|axb| axb.checked_mul(x))
+            .and_then(|axbx| axbx.checked_add(c))
+            .expect(overflow)
+    };
+    // Products of two `i64`s always fit, but 4ac and the
+    // difference may not.
+    let disc = (a * c)
+        .checked_mul(4)
+        .and_then(|ac4| (b * b).checked_sub(ac4))
+        .expect(overflow);
+    if disc <= 0 {
+        return Some(0);
+    }
+    // Estimate the roots from the integer square root of the
+    // discriminant, then walk to the exact boundaries: `lo`
+    // is the last non-solution below the roots and `hi` the
+    // first non-solution above.
+    let s = disc.isqrt();
+    let mut lo = (-b - s).div_euclid(2 * a);
+    let mut hi = (-b + s).div_euclid(2 * a) + 1;
+    while f(lo) < 0 {
+        lo -= 1;
+    }
+    while lo < hi && f(lo + 1) >= 0 {
+        lo += 1;
+    }
+    while f(hi) < 0 {
+        hi += 1;
+    }
+    while hi > lo && f(hi - 1) >= 0 {
+        hi -= 1;
+    }
+    Some((hi - lo - 1).max(0) as u64)
+}
+
+#[test]
+fn test_count_integer_solutions() {
+    // Advent of Code 2023 day 6 example: t(T - t) > D is
+    // t² - Tt + D < 0.
+    assert_eq!(count_integer_solutions(1, -7, 9), Some(4));
+    assert_eq!(count_integer_solutions(1, -15, 40), Some(8));
+    // Roots 10 and 20 exactly: the ties don't count.
+    assert_eq!(count_integer_solutions(1, -30, 200), Some(9));
+    assert_eq!(count_integer_solutions(1, -71530, 940200), Some(71503));
+    // (x - 3)² < 0 never holds.
+    assert_eq!(count_integer_solutions(1, -6, 9), Some(0));
+    assert_eq!(count_integer_solutions(1, 0, 1), Some(0));
+    // Roots in (0, 1).
+    assert_eq!(count_integer_solutions(4, -4, 0), Some(0));
+    assert_eq!(count_integer_solutions(4, -4, -1), Some(2));
+    assert_eq!(count_integer_solutions(-1, 0, 0), None);
+    assert_eq!(count_integer_solutions(0, 1, 0), None);
+    assert_eq!(count_integer_solutions(0, 0, -1), None);
+    assert_eq!(count_integer_solutions(0, 0, 0), Some(0));
+    // Big roots.
+    let t = 3_000_000_000i64;
+    assert_eq!(count_integer_solutions(1, -t, 0), Some(t as u64 - 1));
+}
+
+#[test]
+fn test_count_integer_solutions_brute() {
+    for a in 1..6i64 {
+        for b in -25..=25i64 {
+            for c in -25..=25i64 {
+                let expected = (-100..=100i64)
+                    .filter(|&x| a * x * x + b * x + c < 0)
+                    .count() as u64;
+                assert_eq!(
+                    count_integer_solutions(a, b, c),
+                    Some(expected),
+                    "{a}x² + {b}x + {c}",
+                );
+            }
+        }
+    }
+}
+
+/// The digits of `n` in the given `base`, most significant
+/// first. Zero is `[0]`.
+///
+/// # Panics
+///
+/// Panics unless `base` is in `2..=256`.
+pub fn to_digits(n: u64, base: u64) -> Vec<u8> {
+    assert!((2..=256).contains(&base));
+    let mut digits = Vec::new();
+    let mut n = n;
+    loop {
+        digits.push((n % base) as u8);
+        n /= base;
+        if n == 0 {
+            break;
+        }
+    }
+    digits.reverse();
+    digits
+}
+
+#[test]
+fn test_to_digits() {
+    assert_eq!(to_digits(0, 10), [0]);
+    assert_eq!(to_digits(1234, 10), [1, 2, 3, 4]);
+    assert_eq!(to_digits(10, 2), [1, 0, 1, 0]);
+    assert_eq!(to_digits(255, 16), [15, 15]);
+    assert_eq!(to_digits(255, 256), [255]);
+    assert_eq!(to_digits(u64::MAX, 16), [15; 16]);
+}
+
+/// The number with the given `digits` in the given `base`,
+/// most significant first. Returns `None` if a digit is not
+/// less than `base` or if the number overflows. No digits
+/// is zero.
+pub fn from_digits(digits: &[u8], base: u64) -> Option<u64> {
+    digits.iter().try_fold(0u64, |n, &d| {
+        let d = u64::from(d);
+        if d >= base {
+            return None;
+        }
+        n.checked_mul(base)?.checked_add(d)
+    })
+}
+
+#[test]
+fn test_from_digits() {
+    assert_eq!(from_digits(&[], 10), Some(0));
+    assert_eq!(from_digits(&[0], 10), Some(0));
+    assert_eq!(from_digits(&[1, 2, 3, 4], 10), Some(1234));
+    assert_eq!(from_digits(&[1, 10], 10), None);
+    assert_eq!(from_digits(&[2], 2), None);
+    assert_eq!(from_digits(&[15; 16], 16), Some(u64::MAX));
+    let mut too_big = [0; 17];
+    too_big[0] = 1;
+    assert_eq!(from_digits(&too_big, 16), None);
+}
+
+#[test]
+fn test_digits_round_trip() {
+    for base in [2, 10, 16, 26] {
+        let mut n = 0u64;
+        while n < u64::MAX / 7 {
+            let digits = to_digits(n, base);
+            assert!(digits.len() == 1 || digits[0] != 0);
+            assert_eq!(from_digits(&digits, base), Some(n));
+            n = n * 7 + 3;
+        }
+        for n in 0..1000 {
+            assert_eq!(from_digits(&to_digits(n, base), base), Some(n));
+        }
+        let digits = to_digits(u64::MAX, base);
+        assert_eq!(from_digits(&digits, base), Some(u64::MAX));
+    }
+}
+
+/// The number with the given signed `digits` in the given
+/// `base`, most significant first. Digits may be negative
+/// or otherwise out of the usual range, so that [balanced
+/// bases](https://en.wikipedia.org/wiki/Balanced_ternary)
+/// work. Returns `None` if the number overflows.
+pub fn from_digits_signed(digits: &[i64], base: i64) -> Option<i64> {
+    digits
+        .iter()
+        .try_fold(0i64, |n, &d| n.checked_mul(base)?.checked_add(d))
+}
+
+#[test]
+fn test_from_digits_signed() {
+    assert_eq!(from_digits_signed(&[], 5), Some(0));
+    assert_eq!(from_digits_signed(&[1, 2, 3], 10), Some(123));
+    assert_eq!(from_digits_signed(&[1, -1], 10), Some(9));
+    // Advent of Code 2022 day 25 SNAFU: `1=-0-2` is 1747.
+    assert_eq!(from_digits_signed(&[1, -2, -1, 0, -1, 2], 5), Some(1747));
+    // Balanced ternary: `+-0` is 6.
+    assert_eq!(from_digits_signed(&[1, -1, 0], 3), Some(6));
+    assert_eq!(from_digits_signed(&[1; 64], 2), None);
+    assert_eq!(from_digits_signed(&[-1; 63], 2), Some(i64::MIN + 1));
+}
 
 /// Returns -1, 0 or 1 as the input is negative, zero or
 /// positive.
@@ -0,0 +1,174 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Extrapolating sequences that follow a polynomial.
+//!
+//! Some puzzles give sample values of a sequence and ask
+//! for more (2023 day 9). Others have an answer that grows
+//! as a polynomial in the step count, so that a few
+//! measured values pin down the rest (2023 day 21).
+//!
+//! # Examples
+//!
+//! ```
+//! # use numberfns::*;
+//! assert_eq!(extrapolate_next(&[1, 4, 9, 16]), 25);
+//! assert_eq!(extrapolate_prev(&[1, 4, 9, 16]), 0);
+//!
+//! // The quadratic through these points is x² + 1.
+//! let points = [(1, 2), (2, 5), (3, 10)];
+//! assert_eq!(lagrange_interpolate(&points, 100), Ok(10_001));
+//! ```
+
+use crate::Frac;
+
+/// The rows of the difference table of `values`: the
+/// values, their differences, their differences' differences
+/// and so on, until a row is all zeros or empty.
+fn differences(values: &[i64]) -> Vec<Vec<i128>> {
+    let row: Vec<i128> = values.iter().map(|&v| i128::from(v)).collect();
+    let mut rows = vec![row];
+    while let Some(row) = rows.last() {
+        if row.iter().all(|&v| v == 0) {
+            break;
+        }
+        let next = row.windows(2).map(|w| w[1] - w[0]).collect();
+        rows.push(next);
+    }
+    rows
+}
+
+/// Convert an extrapolated value back to `i64`.
+fn narrow(v: i128) -> i64 {
+    i64::try_from(v).unwrap_or_else(|_| {
+        panic!("extrapolated value {} does not fit in i64", v)
+    })
+}
+
+/// The value following `values`, found by the method of
+/// [finite differences](https://en.wikipedia.org/wiki/Finite_difference):
+/// difference the sequence until it is all zeros, then
+/// extend each row of differences back up by one. An empty
+/// sequence is extended with zero.
+///
+/// # Panics
+///
+/// Panics if the result does not fit in `i64`.
+pub fn extrapolate_next(values: &[i64]) -> i64 {
+    let rows = differences(values);
+    narrow(rows.iter().filter_map(|row| row.last()).sum())
+}
+
+/// The value preceding `values`, found by finite
+/// differences as with `extrapolate_next()`.
+///
+/// # Panics
+///
+/// Panics if the result does not fit in `i64`.
+pub fn extrapolate_prev(values: &[i64]) -> i64 {
+    let rows = differences(values);
+    narrow(
+        rows.iter()
+            .rev()
+            .filter_map(|row| row.first())
+            .fold(0, |below, &first| first - below),
+    )
+}
+
+/// The value at `x` of the unique polynomial of least
+/// degree through the given `points`, by [Lagrange
+/// interpolation](https://en.wikipedia.org/wiki/Lagrange_polynomial).
+/// The arithmetic is exact. If the value is not an `i64`
+/// integer it is returned exactly as the error.
+///
+/// # Panics
+///
+/// Panics if two points have the same *x*.
+pub fn lagrange_interpolate(
+    points: &[(i64, i64)],
+    x: i64,
+) -> Result<i64, Frac> {
+    let x = i128::from(x);
+    let mut total = Frac::ZERO;
+    for (i, &(xi, yi)) in points.iter().enumerate() {
+        let xi = i128::from(xi);
+        let mut term = Frac::from(yi);
+        for (j, &(xj, _)) in points.iter().enumerate() {
+            if i != j {
+                let xj = i128::from(xj);
+                assert!(xi != xj, "interpolation points with the same x");
+                term = term * Frac::new(x - xj, xi - xj);
+            }
+        }
+        total = total + term;
+    }
+    total
+        .to_integer()
+        .and_then(|v| i64::try_from(v).ok())
+        .ok_or(total)
+}
+
+#[test]
+fn test_extrapolate_next() {
+    // 2023 day 9 example.
+    let rows = [
+        [0, 3, 6, 9, 12, 15],
+        [1, 3, 6, 10, 15, 21],
+        [10, 13, 16, 21, 30, 45],
+    ];
+    let next: Vec<i64> = rows.iter().map(|r| extrapolate_next(r)).collect();
+    assert_eq!(next, [18, 28, 68]);
+    assert_eq!(next.iter().sum::<i64>(), 114);
+    assert_eq!(extrapolate_next(&[7, 7, 7]), 7);
+    assert_eq!(extrapolate_next(&[0, 0]), 0);
+    assert_eq!(extrapolate_next(&[42]), 42);
+    assert_eq!(extrapolate_next(&[]), 0);
+    assert_eq!(extrapolate_next(&[1, 8, 27, 64]), 125);
+    assert_eq!(extrapolate_next(&[-5, -3, -1]), 1);
+}
+
+#[test]
+fn test_extrapolate_prev() {
+    // 2023 day 9 part 2 example.
+    let rows = [
+        [0, 3, 6, 9, 12, 15],
+        [1, 3, 6, 10, 15, 21],
+        [10, 13, 16, 21, 30, 45],
+    ];
+    let prev: Vec<i64> = rows.iter().map(|r| extrapolate_prev(r)).collect();
+    assert_eq!(prev, [-3, 0, 5]);
+    assert_eq!(extrapolate_prev(&[7, 7, 7]), 7);
+    assert_eq!(extrapolate_prev(&[]), 0);
+    assert_eq!(extrapolate_prev(&[1, 8, 27, 64]), 0);
+    assert_eq!(extrapolate_prev(&[8, 27, 64, 125]), 1);
+}
+
+#[test]
+#[should_panic]
+fn test_extrapolate_overflow() {
+    extrapolate_next(&[0, i64::MAX]);
+}
+
+#[test]
+fn test_lagrange_interpolate() {
+    // x² + x + 1.
+    let points = [(0, 1), (1, 3), (2, 7)];
+    assert_eq!(lagrange_interpolate(&points, 10), Ok(111));
+    assert_eq!(lagrange_interpolate(&points, -3), Ok(7));
+    assert_eq!(lagrange_interpolate(&points, 1), Ok(3));
+    // Points needn't be evenly spaced or in order.
+    let points = [(5, 31), (-1, 1), (2, 7)];
+    assert_eq!(lagrange_interpolate(&points, 10), Ok(111));
+    // 2023 day 21 style: a quadratic sampled at 65, 196 and
+    // 327, evaluated far away.
+    let f = |x: i64| 3 * x * x - 2 * x + 11;
+    let points = [65, 196, 327].map(|x| (x, f(x)));
+    assert_eq!(lagrange_interpolate(&points, 26_501_365), Ok(f(26_501_365)));
+    // Halfway along a line from 0 to 1.
+    let points = [(0, 0), (2, 1)];
+    assert_eq!(lagrange_interpolate(&points, 1), Err(Frac::new(1, 2)));
+    assert_eq!(lagrange_interpolate(&[], 5), Ok(0));
+    assert_eq!(lagrange_interpolate(&[(3, 9)], 5), Ok(9));
+}
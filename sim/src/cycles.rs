@@ -0,0 +1,384 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Running a simulation for an absurd number of steps by
+//! finding where it repeats.
+//!
+//! # Examples
+//!
+//! A walk around a loop of seven positions, adding up the
+//! positions visited. Only the position matters for where
+//! the walk goes next, so the total is left out of equality
+//! and hashing.
+//!
+//! ```
+//! # use sim::*;
+//! # use std::hash::{Hash, Hasher};
+//! #[derive(Clone)]
+//! struct Walk {
+//!     pos: u64,
+//!     total: u64,
+//! }
+//!
+//! impl PartialEq for Walk {
+//!     fn eq(&self, other: &Self) -> bool {
+//!         self.pos == other.pos
+//!     }
+//! }
+//!
+//! impl Eq for Walk {}
+//!
+//! impl Hash for Walk {
+//!     fn hash<H: Hasher>(&self, state: &mut H) {
+//!         self.pos.hash(state);
+//!     }
+//! }
+//!
+//! let start = Walk { pos: 0, total: 0 };
+//! let step = |w: &Walk| {
+//!     let pos = (w.pos + 3) % 7;
+//!     Walk { pos, total: w.total + pos }
+//! };
+//! let total = simulate_with_cycles(start, step, 1_000_000, |w| w.total);
+//! assert_eq!(total, 3_000_000);
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Add, Mul, Sub};
+
+/// Run `step` from `state` for `n` steps and return
+/// `metric` of the resulting state.
+///
+/// The metric is something that accumulates as the
+/// simulation runs, such as the height of a tower, and so
+/// may keep changing after the states start repeating. The
+/// `Eq` and `Hash` of the state must leave it out: two
+/// states should be equal when they behave the same from
+/// then on, apart from the metric. Then once some state
+/// repeats, the metric changes by the same amount each time
+/// around the cycle, and the rest of the way to `n` is
+/// counted rather than simulated.
+///
+/// The metric must not decrease around a cycle unless `M`
+/// is a signed type.
+///
+/// # Panics
+///
+/// Panics if the number of cycles skipped does not fit in
+/// `M`.
+pub fn simulate_with_cycles<S, F, E, M>(
+    state: S,
+    mut step: F,
+    n: u64,
+    metric: E,
+) -> M
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+    E: Fn(&S) -> M,
+    M: Copy + Add<Output = M> + Sub<Output = M> + Mul<Output = M>,
+    M: TryFrom<u64>,
+{
+    // Step at which each state was first seen.
+    let mut seen: HashMap<S, u64> = HashMap::new();
+    // Metric at each step.
+    let mut metrics: Vec<M> = Vec::new();
+    let mut state = state;
+    for i in 0.. {
+        let m = metric(&state);
+        if i == n {
+            return m;
+        }
+        if let Some(&j) = seen.get(&state) {
+            // Steps `j..i` repeat forever. Go around as many
+            // times as fit, then the rest of the way.
+            let period = i - j;
+            let cycles = (n - i) / period;
+            let tail = (n - i) % period;
+            let delta = m - metrics[j as usize];
+            let laps = M::try_from(cycles + 1).unwrap_or_else(|_| {
+                panic!("{} cycles do not fit in metric", cycles + 1)
+            });
+            return metrics[(j + tail) as usize] + laps * delta;
+        }
+        seen.insert(state.clone(), i);
+        metrics.push(m);
+        state = step(&state);
+    }
+    unreachable!()
+}
+
+/// Where a sequence of states starts repeating: the states
+/// at steps `start..start + period` repeat forever after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    /// Steps before the first state on the cycle (μ).
+    pub start: u64,
+    /// Length of the cycle (λ).
+    pub period: u64,
+}
+
+/// Find the cycle reached by running `step` from `initial`.
+/// Every state up to the first repeat is kept, so this is
+/// for states that are cheap to store. `find_cycle_brent()`
+/// keeps only two.
+///
+/// Runs forever if no state ever repeats.
+pub fn find_cycle<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    // Step at which each state was first seen.
+    let mut seen: HashMap<S, u64> = HashMap::new();
+    let mut state = initial;
+    for i in 0.. {
+        if let Some(&j) = seen.get(&state) {
+            return Cycle { start: j, period: i - j };
+        }
+        let next = step(&state);
+        seen.insert(state, i);
+        state = next;
+    }
+    unreachable!()
+}
+
+/// Find the cycle reached by running `step` from `initial`,
+/// as with `find_cycle()`, using [Brent's
+/// algorithm](https://en.wikipedia.org/wiki/Cycle_detection#Brent's_algorithm).
+/// Only two states are kept at a time and states need not
+/// be hashable, at the cost of stepping about three times
+/// as often.
+///
+/// Runs forever if no state ever repeats.
+pub fn find_cycle_brent<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    // Find the period: the hare runs ahead in doubling
+    // stretches until it meets the tortoise parked at the
+    // start of the stretch.
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    // Find the start: with the hare a period ahead, they
+    // first meet on the cycle.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, period }
+}
+
+/// The state after running `step` from `initial` for `n`
+/// steps. Once some state repeats, the rest of the way to
+/// `n` is skipped by going around the cycle. See
+/// `simulate_with_cycles()` for a version that also tracks
+/// an accumulating metric.
+pub fn state_after_n<S, F>(initial: S, mut step: F, n: u64) -> S
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    // Step at which each state was first seen.
+    let mut seen: HashMap<S, u64> = HashMap::new();
+    // State at each step.
+    let mut states: Vec<S> = Vec::new();
+    let mut state = initial;
+    for i in 0.. {
+        if i == n {
+            return state;
+        }
+        if let Some(&j) = seen.get(&state) {
+            let period = i - j;
+            return states[(j + (n - j) % period) as usize].clone();
+        }
+        seen.insert(state.clone(), i);
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use std::hash::Hasher;
+
+    /// Rocks as rows of bits from the bottom up, bit `i`
+    /// being column `i` from the left, starting two columns
+    /// from the left wall.
+    const ROCKS: [&[u8]; 5] = [
+        &[0x3c],
+        &[0x08, 0x1c, 0x08],
+        &[0x1c, 0x10, 0x10],
+        &[0x04, 0x04, 0x04, 0x04],
+        &[0x0c, 0x0c],
+    ];
+
+    /// Rows of the chamber kept below the top.
+    const KEEP: usize = 100;
+
+    /// The falling-rock chamber of 2022 day 17. Only the top
+    /// `KEEP` rows are kept, counting the rest in `trimmed`.
+    #[derive(Clone)]
+    struct Chamber {
+        rows: Vec<u8>,
+        trimmed: u64,
+        rock: usize,
+        jet: usize,
+    }
+
+    impl PartialEq for Chamber {
+        fn eq(&self, other: &Self) -> bool {
+            (&self.rows, self.rock, self.jet)
+                == (&other.rows, other.rock, other.jet)
+        }
+    }
+
+    impl Eq for Chamber {}
+
+    impl Hash for Chamber {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            (&self.rows, self.rock, self.jet).hash(state);
+        }
+    }
+
+    impl Chamber {
+        fn height(&self) -> u64 {
+            self.trimmed + self.rows.len() as u64
+        }
+
+        fn collides(&self, shape: &[u8], y: usize) -> bool {
+            shape.iter().enumerate().any(|(k, &bits)| {
+                self.rows.get(y + k).is_some_and(|&row| row & bits != 0)
+            })
+        }
+
+        /// Drop the next rock, pushed by `jets`.
+        fn drop_rock(&self, jets: &[u8]) -> Chamber {
+            let mut next = self.clone();
+            let mut shape = ROCKS[next.rock].to_vec();
+            next.rock = (next.rock + 1) % ROCKS.len();
+            let mut y = next.rows.len() + 3;
+            loop {
+                let jet = jets[next.jet];
+                next.jet = (next.jet + 1) % jets.len();
+                let pushed: Vec<u8> = if jet == b'<' {
+                    if shape.iter().any(|&bits| bits & 0x01 != 0) {
+                        shape.clone()
+                    } else {
+                        shape.iter().map(|&bits| bits >> 1).collect()
+                    }
+                } else if shape.iter().any(|&bits| bits & 0x40 != 0) {
+                    shape.clone()
+                } else {
+                    shape.iter().map(|&bits| bits << 1).collect()
+                };
+                if !next.collides(&pushed, y) {
+                    shape = pushed;
+                }
+                if y == 0 || next.collides(&shape, y - 1) {
+                    break;
+                }
+                y -= 1;
+            }
+            for (k, bits) in shape.into_iter().enumerate() {
+                if y + k == next.rows.len() {
+                    next.rows.push(0);
+                }
+                next.rows[y + k] |= bits;
+            }
+            if next.rows.len() > KEEP {
+                let cut = next.rows.len() - KEEP;
+                next.rows.drain(..cut);
+                next.trimmed += cut as u64;
+            }
+            next
+        }
+    }
+
+    #[test]
+    fn tower_height() {
+        let jets = b">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+        let chamber = Chamber {
+            rows: Vec::new(),
+            trimmed: 0,
+            rock: 0,
+            jet: 0,
+        };
+        let step = |c: &Chamber| c.drop_rock(jets);
+        for (n, height) in [(2022, 3068), (1_000_000_000_000, 1514285714288)] {
+            let h = simulate_with_cycles(chamber.clone(), step, n, |c| {
+                c.height()
+            });
+            assert_eq!(h, height);
+        }
+
+        // Check the fast-forward against simply stepping.
+        let mut c = chamber.clone();
+        for n in 0..400 {
+            let h = simulate_with_cycles(chamber.clone(), step, n, |c| {
+                c.height()
+            });
+            assert_eq!(h, c.height(), "{}", n);
+            c = step(&c);
+        }
+    }
+
+    fn squaring(x: &u64) -> u64 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn cycle() {
+        // 3, 10, then 101, 2, 5, 26, 167, 95 forever.
+        let expected = Cycle { start: 2, period: 6 };
+        assert_eq!(find_cycle(3, squaring), expected);
+        assert_eq!(find_cycle_brent(3, squaring), expected);
+        let expected = Cycle { start: 0, period: 1 };
+        assert_eq!(find_cycle(0u8, |&x| x), expected);
+        assert_eq!(find_cycle_brent(0u8, |&x| x), expected);
+        for x0 in 0..255 {
+            let brent = find_cycle_brent(x0, squaring);
+            assert_eq!(find_cycle(x0, squaring), brent);
+        }
+    }
+
+    #[test]
+    fn after_n() {
+        assert_eq!(state_after_n(3, squaring, 0), 3);
+        assert_eq!(state_after_n(3, squaring, 1), 10);
+        assert_eq!(state_after_n(3, squaring, 1_000_000_000), 5);
+        let mut x = 3;
+        for n in 0..100 {
+            assert_eq!(state_after_n(3, squaring, n), x, "{}", n);
+            x = squaring(&x);
+        }
+    }
+}
@@ -0,0 +1,351 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Discrete-event simulation.
+//!
+//! Events are handled in order of time, with events at the
+//! same time handled in the order they were scheduled, so a
+//! simulation always runs the same way. Handling an event
+//! may change the simulation state and schedule more
+//! events.
+//!
+//! # Examples
+//!
+//! The reindeer race (2015 day 14). Each reindeer's
+//! distance is brought up to date whenever it starts or
+//! stops flying, and once more at the end.
+//!
+//! ```
+//! # use sim::*;
+//! struct Reindeer {
+//!     speed: u64,
+//!     fly: u64,
+//!     rest: u64,
+//!     distance: u64,
+//!     flying_since: Option<Time>,
+//! }
+//!
+//! impl Reindeer {
+//!     fn catch_up(&mut self, now: Time) {
+//!         if let Some(since) = self.flying_since {
+//!             self.distance += self.speed * (now - since);
+//!             self.flying_since = Some(now);
+//!         }
+//!     }
+//! }
+//!
+//! enum Event {
+//!     Fly(usize),
+//!     Rest(usize),
+//! }
+//!
+//! let reindeer = |speed, fly, rest| Reindeer {
+//!     speed,
+//!     fly,
+//!     rest,
+//!     distance: 0,
+//!     flying_since: None,
+//! };
+//! // Comet and Dancer.
+//! let mut herd = vec![reindeer(14, 10, 127), reindeer(16, 11, 162)];
+//! let mut sim = Simulation::new();
+//! for i in 0..herd.len() {
+//!     sim.schedule(0, Event::Fly(i));
+//! }
+//! sim.run_until(&mut herd, 1000, |herd, now, event| match event {
+//!     Event::Fly(i) => {
+//!         herd[i].flying_since = Some(now);
+//!         vec![(now + herd[i].fly, Event::Rest(i))]
+//!     }
+//!     Event::Rest(i) => {
+//!         herd[i].catch_up(now);
+//!         herd[i].flying_since = None;
+//!         vec![(now + herd[i].rest, Event::Fly(i))]
+//!     }
+//! });
+//! for reindeer in &mut herd {
+//!     reindeer.catch_up(sim.now());
+//! }
+//! let distances: Vec<u64> = herd.iter().map(|r| r.distance).collect();
+//! assert_eq!(distances, [1120, 1056]);
+//! ```
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::fmt;
+
+/// Simulation time.
+pub type Time = u64;
+
+/// Hook called with each event before it is handled.
+type TraceFn<E> = Box<dyn FnMut(Time, &E)>;
+
+/// An event waiting in the queue.
+struct Scheduled<E> {
+    at: Time,
+    /// Order of scheduling, to break ties.
+    seq: u64,
+    event: E,
+}
+
+impl<E> Scheduled<E> {
+    fn key(&self) -> (Time, u64) {
+        (self.at, self.seq)
+    }
+}
+
+impl<E> PartialEq for Scheduled<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl<E> Eq for Scheduled<E> {}
+
+impl<E> PartialOrd for Scheduled<E> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<E> Ord for Scheduled<E> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// A queue of events of type `E` to be handled in time
+/// order, and the current time.
+///
+/// The `run` methods take a handler that is given the
+/// simulation state, the current time and the event, and
+/// returns follow-up events to schedule, as `(time, event)`
+/// pairs.
+pub struct Simulation<E> {
+    queue: BinaryHeap<Reverse<Scheduled<E>>>,
+    now: Time,
+    seq: u64,
+    handled: u64,
+    trace: Option<TraceFn<E>>,
+}
+
+impl<E> Default for Simulation<E> {
+    fn default() -> Self {
+        Simulation {
+            queue: BinaryHeap::new(),
+            now: 0,
+            seq: 0,
+            handled: 0,
+            trace: None,
+        }
+    }
+}
+
+impl<E> fmt::Debug for Simulation<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Simulation")
+            .field("now", &self.now)
+            .field("pending", &self.queue.len())
+            .field("handled", &self.handled)
+            .finish()
+    }
+}
+
+impl<E> Simulation<E> {
+    /// Make a simulation at time 0 with no events.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The current time: the time of the event last
+    /// handled, or the time last run to.
+    pub fn now(&self) -> Time {
+        self.now
+    }
+
+    /// Number of events waiting to be handled.
+    pub fn pending(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// True if no events are waiting.
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Number of events handled so far.
+    pub fn handled(&self) -> u64 {
+        self.handled
+    }
+
+    /// Time of the next event, if any.
+    pub fn next_time(&self) -> Option<Time> {
+        self.queue.peek().map(|Reverse(s)| s.at)
+    }
+
+    /// Call `trace` with the time and event just before each
+    /// event is handled: for instance, to print it with
+    /// `aoc::trace!`.
+    pub fn set_trace<F>(&mut self, trace: F)
+    where
+        F: FnMut(Time, &E) + 'static,
+    {
+        self.trace = Some(Box::new(trace));
+    }
+
+    /// Schedule `event` to happen at time `at`, after any
+    /// events already scheduled for then.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at` is before the current time.
+    pub fn schedule(&mut self, at: Time, event: E) {
+        assert!(
+            at >= self.now,
+            "event scheduled at {} in the past of {}",
+            at,
+            self.now,
+        );
+        let seq = self.seq;
+        self.seq += 1;
+        self.queue.push(Reverse(Scheduled { at, seq, event }));
+    }
+
+    /// Handle the next event, if any, returning its time.
+    pub fn step<S, H>(&mut self, state: &mut S, mut handler: H) -> Option<Time>
+    where
+        H: FnMut(&mut S, Time, E) -> Vec<(Time, E)>,
+    {
+        let Reverse(Scheduled { at, event, .. }) = self.queue.pop()?;
+        self.now = at;
+        if let Some(trace) = &mut self.trace {
+            trace(at, &event);
+        }
+        self.handled += 1;
+        for (next, event) in handler(state, at, event) {
+            self.schedule(next, event);
+        }
+        Some(at)
+    }
+
+    /// Handle events until there are none left. Returns the
+    /// final time.
+    pub fn run<S, H>(&mut self, state: &mut S, mut handler: H) -> Time
+    where
+        H: FnMut(&mut S, Time, E) -> Vec<(Time, E)>,
+    {
+        while self.step(state, &mut handler).is_some() {}
+        self.now
+    }
+
+    /// Handle every event at or before time `end`, then move
+    /// the current time to `end`.
+    pub fn run_until<S, H>(&mut self, state: &mut S, end: Time, mut handler: H)
+    where
+        H: FnMut(&mut S, Time, E) -> Vec<(Time, E)>,
+    {
+        while self.next_time().is_some_and(|t| t <= end) {
+            self.step(state, &mut handler);
+        }
+        self.now = self.now.max(end);
+    }
+
+    /// Handle events until `stop` is true of the state after
+    /// handling one. Returns the time at which it stopped,
+    /// or `None` if the events ran out first. The state is
+    /// not checked before the first event.
+    pub fn run_until_state<S, H, P>(
+        &mut self,
+        state: &mut S,
+        mut handler: H,
+        mut stop: P,
+    ) -> Option<Time>
+    where
+        H: FnMut(&mut S, Time, E) -> Vec<(Time, E)>,
+        P: FnMut(&S) -> bool,
+    {
+        while let Some(at) = self.step(state, &mut handler) {
+            if stop(state) {
+                return Some(at);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn simultaneous_events() {
+        // Each event at time 5 schedules a follow-up at the
+        // same time, which must come after everything
+        // already waiting then.
+        let mut sim = Simulation::new();
+        for name in ["a", "b", "c"] {
+            sim.schedule(5, name.to_string());
+        }
+        sim.schedule(3, "first".to_string());
+        sim.schedule(9, "last".to_string());
+        let mut log = Vec::new();
+        let end = sim.run(&mut log, |log, now, event| {
+            log.push((now, event.clone()));
+            if event.len() == 1 && event != event.to_uppercase() {
+                vec![(now, event.to_uppercase())]
+            } else {
+                Vec::new()
+            }
+        });
+        let order: Vec<&str> = log.iter().map(|(_, e)| e.as_str()).collect();
+        assert_eq!(order, ["first", "a", "b", "c", "A", "B", "C", "last"]);
+        assert_eq!(end, 9);
+        assert_eq!(sim.handled(), 8);
+        assert!(sim.is_empty());
+    }
+
+    #[test]
+    fn stopping() {
+        // A counter ticking every 3 time units.
+        let tick = |count: &mut u32, now: Time, ()| {
+            *count += 1;
+            vec![(now + 3, ())]
+        };
+        let mut sim = Simulation::new();
+        sim.schedule(0, ());
+        let mut count = 0;
+        sim.run_until(&mut count, 10, tick);
+        assert_eq!((count, sim.now()), (4, 10));
+        assert_eq!(sim.next_time(), Some(12));
+        let stopped = sim.run_until_state(&mut count, tick, |&c| c == 7);
+        assert_eq!(stopped, Some(18));
+        assert_eq!(sim.pending(), 1);
+
+        let mut sim = Simulation::new();
+        sim.schedule(2, ());
+        let trace = Rc::new(RefCell::new(Vec::new()));
+        let log = trace.clone();
+        sim.set_trace(move |now, _| log.borrow_mut().push(now));
+        let mut count = 0;
+        let once = |count: &mut u32, _, ()| {
+            *count += 1;
+            Vec::new()
+        };
+        assert_eq!(sim.run_until_state(&mut count, once, |_| false), None);
+        assert_eq!(*trace.borrow(), [2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "in the past")]
+    fn past_event() {
+        let mut sim = Simulation::new();
+        sim.run_until(&mut (), 10, |_, _, ()| Vec::new());
+        sim.schedule(5, ());
+    }
+}
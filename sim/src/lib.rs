@@ -0,0 +1,18 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Simulation drivers for Advent of Code solutions.
+//!
+//! Many puzzles describe a process one step at a time and
+//! then ask about a step far too late to reach by stepping.
+//! The drivers here do the bookkeeping for getting there
+//! anyway, or for stepping from one event to the next
+//! rather than one tick at a time.
+
+mod cycles;
+pub use cycles::*;
+
+mod events;
+pub use events::*;
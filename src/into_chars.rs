@@ -79,7 +79,7 @@ impl CharSource {
 impl Iterator for CharSource {
     type Item = char;
     fn next(&mut self) -> Option<Self::Item> {
-        let suffix = &self.s[self.i..].as_bytes();
+        let suffix = &self.s.as_bytes()[self.i..];
         utf8_char(suffix).map(|(i, ch)| {
             self.i += i;
             ch
@@ -21,17 +21,23 @@ pub mod lines;
 pub use self::lines::*;
 
 pub mod trace;
-pub use self::trace::*;
 
 pub mod into_chars;
 pub use self::into_chars::*;
 
+pub mod strings;
+pub use self::strings::*;
+
 #[cfg(feature = "astar")]
 pub extern crate astar;
+#[cfg(feature = "collections")]
+pub extern crate collections;
 #[cfg(feature = "comb")]
 pub extern crate comb;
 #[cfg(feature = "geom")]
 pub extern crate geom;
+#[cfg(feature = "graph")]
+pub extern crate graph;
 #[cfg(feature = "hexstring")]
 pub extern crate hexstring;
 #[cfg(feature = "maprender")]
@@ -40,3 +46,7 @@ pub extern crate maprender;
 pub extern crate numberfns;
 #[cfg(feature = "reparse")]
 pub extern crate reparse;
+#[cfg(feature = "sim")]
+pub extern crate sim;
+#[cfg(feature = "vm")]
+pub extern crate vm;
@@ -0,0 +1,172 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Small string operations that keep coming up: splitting
+//! in half, sets of letters, and comparing strings position
+//! by position.
+//!
+//! Sets of letters are `u64` bitmasks, so that intersecting
+//! them is just `&`. Bits 1 through 26 are `a` through `z`,
+//! and bits 27 through 52 are `A` through `Z`; so the bit
+//! of a letter is its rucksack priority (2022 day 3).
+//!
+//! # Examples
+//!
+//! ```
+//! # use aoc::*;
+//! let (left, right) = halves("vJrwpWtwJgWrhcsFMMfFFhFp").unwrap();
+//! let common = char_set(left) & char_set(right);
+//! assert_eq!(set_chars(common).collect::<String>(), "p");
+//! assert_eq!(common.trailing_zeros(), 16);
+//! ```
+
+use std::fmt;
+
+/// Error from `halves()`: the string has the given odd
+/// length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OddLengthError(pub usize);
+
+impl fmt::Display for OddLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cannot halve string of odd length {}", self.0)
+    }
+}
+
+impl std::error::Error for OddLengthError {}
+
+/// Split `s` into two halves of equal length in bytes.
+///
+/// # Panics
+///
+/// Panics if the middle of `s` is inside a character.
+pub fn halves(s: &str) -> Result<(&str, &str), OddLengthError> {
+    if !s.len().is_multiple_of(2) {
+        return Err(OddLengthError(s.len()));
+    }
+    Ok(s.split_at(s.len() / 2))
+}
+
+/// Bit of an ASCII letter in a `char_set()`, if `c` is one.
+pub fn char_bit(c: char) -> Option<u32> {
+    match c {
+        'a'..='z' => Some(c as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Some(c as u32 - 'A' as u32 + 27),
+        _ => None,
+    }
+}
+
+/// Set of the ASCII letters in `s`, as a bitmask with the
+/// layout in the module documentation. Other characters
+/// are ignored.
+pub fn char_set(s: &str) -> u64 {
+    s.chars()
+        .filter_map(char_bit)
+        .fold(0, |set, bit| set | 1 << bit)
+}
+
+/// Iterator over the letters of a `char_set()`, `a` through
+/// `z` then `A` through `Z`.
+pub fn set_chars(set: u64) -> impl Iterator<Item = char> {
+    ('a'..='z')
+        .chain('A'..='Z')
+        .filter(move |&c| set & 1 << char_bit(c).unwrap() != 0)
+}
+
+/// Iterator over the ASCII letters in both `a` and `b`,
+/// each once, in the order of `set_chars()`.
+pub fn common_letters(a: &str, b: &str) -> impl Iterator<Item = char> {
+    set_chars(char_set(a) & char_set(b))
+}
+
+/// The position, in characters, of the only character that
+/// differs between `a` and `b`. Returns `None` if the
+/// strings are not the same length, or they differ in no
+/// position or more than one.
+pub fn single_diff_position(a: &str, b: &str) -> Option<usize> {
+    if a.chars().count() != b.chars().count() {
+        return None;
+    }
+    let mut diffs = a
+        .chars()
+        .zip(b.chars())
+        .enumerate()
+        .filter(|(_, (x, y))| x != y)
+        .map(|(i, _)| i);
+    match (diffs.next(), diffs.next()) {
+        (Some(i), None) => Some(i),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const RUCKSACKS: [&str; 6] = [
+        "vJrwpWtwJgWrhcsFMMfFFhFp",
+        "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+        "PmmdzqPrVvPwwTWBwg",
+        "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+        "ttgJtRGJQctTZtZT",
+        "CrZsJsPPZsGzwwsLwLmpwMDw",
+    ];
+
+    #[test]
+    fn rucksacks() {
+        let priorities: u32 = RUCKSACKS
+            .iter()
+            .map(|sack| {
+                let (left, right) = halves(sack).unwrap();
+                (char_set(left) & char_set(right)).trailing_zeros()
+            })
+            .sum();
+        assert_eq!(priorities, 157);
+
+        let badges: u32 = RUCKSACKS
+            .chunks(3)
+            .map(|group| {
+                let common = group.iter().fold(!0, |s, &g| s & char_set(g));
+                assert_eq!(common.count_ones(), 1);
+                common.trailing_zeros()
+            })
+            .sum();
+        assert_eq!(badges, 70);
+    }
+
+    #[test]
+    fn letters() {
+        assert_eq!(char_bit('a'), Some(1));
+        assert_eq!(char_bit('Z'), Some(52));
+        assert_eq!(char_bit('7'), None);
+        assert_eq!(char_set("a-a!"), 0b10);
+        let all: String = set_chars(!0).collect();
+        assert_eq!(all.len(), 52);
+        assert_eq!(char_set(&all), ((1 << 52) - 1) << 1);
+        let common: String = common_letters("Hello", "yellow").collect();
+        assert_eq!(common, "elo");
+        assert_eq!(halves("abc"), Err(OddLengthError(3)));
+        assert_eq!(halves(""), Ok(("", "")));
+    }
+
+    #[test]
+    fn box_ids() {
+        // 2018 day 2.
+        let ids = ["abcde", "fghij", "klmno", "pqrst", "fguij", "axcye"];
+        let mut found = Vec::new();
+        for (i, a) in ids.iter().enumerate() {
+            for b in &ids[i + 1..] {
+                if let Some(p) = single_diff_position(a, b) {
+                    found.push((p, format!("{}{}", &a[..p], &a[p + 1..])));
+                }
+            }
+        }
+        assert_eq!(found, [(2, "fgij".to_string())]);
+        assert_eq!(single_diff_position("abc", "abc"), None);
+        assert_eq!(single_diff_position("abc", "abcd"), None);
+        assert_eq!(single_diff_position("αβγ", "αδγ"), Some(1));
+    }
+}
@@ -0,0 +1,344 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! The Intcode computer of Advent of Code 2019.
+//!
+//! This supports everything through day 9: parameter modes,
+//! the relative base, and memory past the end of the
+//! program. A machine never blocks: `run()` stops to ask for
+//! input it does not have and to hand over each output, so
+//! several machines can be run together.
+//!
+//! # Examples
+//!
+//! Amplifiers in a feedback loop (2019 day 7).
+//!
+//! ```
+//! # use vm::*;
+//! let program: Intcode = "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,\
+//!     27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5"
+//!     .parse()
+//!     .unwrap();
+//! let mut amps: Vec<Intcode> = [9, 8, 7, 6, 5]
+//!     .into_iter()
+//!     .map(|phase| {
+//!         let mut amp = program.clone();
+//!         amp.push_input(phase);
+//!         amp
+//!     })
+//!     .collect();
+//! let mut signal = 0;
+//! 'feedback: loop {
+//!     for amp in &mut amps {
+//!         amp.push_input(signal);
+//!         match amp.run() {
+//!             Event::Output(v) => signal = v,
+//!             Event::Halted => break 'feedback,
+//!             Event::NeedsInput => panic!("amplifier starved"),
+//!         }
+//!     }
+//! }
+//! assert_eq!(signal, 139629729);
+//! ```
+
+use std::collections::VecDeque;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+/// What stopped an Intcode machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// An input instruction found no input waiting. Run the
+    /// machine again after giving it some.
+    NeedsInput,
+    /// The machine output the given value.
+    Output(i64),
+    /// The machine halted. Running it again does nothing.
+    Halted,
+}
+
+/// An Intcode machine: memory, instruction pointer,
+/// relative base, and input waiting to be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Intcode {
+    memory: Vec<i64>,
+    ip: usize,
+    base: i64,
+    input: VecDeque<i64>,
+    halted: bool,
+}
+
+impl Intcode {
+    /// Make a machine ready to run `program` from the start.
+    pub fn new(program: Vec<i64>) -> Self {
+        Intcode {
+            memory: program,
+            ip: 0,
+            base: 0,
+            input: VecDeque::new(),
+            halted: false,
+        }
+    }
+
+    /// Parse a program of comma-separated integers.
+    /// Whitespace around the integers is ignored.
+    pub fn parse(s: &str) -> Result<Self, ParseIntError> {
+        let program = s
+            .trim()
+            .split(',')
+            .map(|v| v.trim().parse())
+            .collect::<Result<Vec<i64>, _>>()?;
+        Ok(Intcode::new(program))
+    }
+
+    /// Give the machine a value to read, after any already
+    /// waiting.
+    pub fn push_input(&mut self, value: i64) {
+        self.input.push_back(value);
+    }
+
+    /// The value at `addr`. Memory past the end of the
+    /// program reads as 0.
+    pub fn peek(&self, addr: usize) -> i64 {
+        self.memory.get(addr).copied().unwrap_or(0)
+    }
+
+    /// Store `value` at `addr`, growing memory if needed.
+    pub fn poke(&mut self, addr: usize, value: i64) {
+        if addr >= self.memory.len() {
+            self.memory.resize(addr + 1, 0);
+        }
+        self.memory[addr] = value;
+    }
+
+    /// True if the machine has halted.
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Mode of parameter `n` (from 1) of the current
+    /// instruction.
+    fn mode(&self, n: usize) -> i64 {
+        self.peek(self.ip) / 10i64.pow(n as u32 + 1) % 10
+    }
+
+    /// Address of parameter `n` of the current instruction,
+    /// given its mode.
+    fn address(&self, n: usize) -> usize {
+        let mode = self.mode(n);
+        let param = self.ip + n;
+        let addr = match mode {
+            0 => self.peek(param),
+            1 => return param,
+            2 => self.base + self.peek(param),
+            _ => panic!("bad parameter mode {} at {}", mode, self.ip),
+        };
+        usize::try_from(addr)
+            .unwrap_or_else(|_| panic!("negative address {}", addr))
+    }
+
+    /// Value of parameter `n` of the current instruction.
+    fn param(&self, n: usize) -> i64 {
+        self.peek(self.address(n))
+    }
+
+    /// Store to the address of parameter `n` of the current
+    /// instruction.
+    fn store(&mut self, n: usize, value: i64) {
+        assert!(self.mode(n) != 1, "write in immediate mode at {}", self.ip);
+        self.poke(self.address(n), value);
+    }
+
+    /// Run until the machine halts, needs input, or outputs
+    /// a value.
+    ///
+    /// # Panics
+    ///
+    /// Panics on a bad opcode or parameter mode, a write in
+    /// immediate mode, or an address that is negative.
+    pub fn run(&mut self) -> Event {
+        if self.halted {
+            return Event::Halted;
+        }
+        loop {
+            let opcode = self.peek(self.ip) % 100;
+            match opcode {
+                1 | 2 | 7 | 8 => {
+                    let (a, b) = (self.param(1), self.param(2));
+                    let value = match opcode {
+                        1 => a + b,
+                        2 => a * b,
+                        7 => (a < b) as i64,
+                        _ => (a == b) as i64,
+                    };
+                    self.store(3, value);
+                    self.ip += 4;
+                }
+                3 => {
+                    let Some(value) = self.input.pop_front() else {
+                        return Event::NeedsInput;
+                    };
+                    self.store(1, value);
+                    self.ip += 2;
+                }
+                4 => {
+                    let value = self.param(1);
+                    self.ip += 2;
+                    return Event::Output(value);
+                }
+                5 | 6 => {
+                    if (self.param(1) != 0) == (opcode == 5) {
+                        let target = self.param(2);
+                        self.ip = usize::try_from(target).unwrap_or_else(
+                            |_| panic!("negative jump to {}", target),
+                        );
+                    } else {
+                        self.ip += 3;
+                    }
+                }
+                9 => {
+                    self.base += self.param(1);
+                    self.ip += 2;
+                }
+                99 => {
+                    self.halted = true;
+                    return Event::Halted;
+                }
+                _ => panic!("bad opcode {} at {}", opcode, self.ip),
+            }
+        }
+    }
+
+    /// Give the machine `inputs`, then run it until it halts
+    /// or needs more input, returning everything it output.
+    pub fn run_with<I>(&mut self, inputs: I) -> Vec<i64>
+    where
+        I: IntoIterator<Item = i64>,
+    {
+        self.input.extend(inputs);
+        let mut output = Vec::new();
+        while let Event::Output(v) = self.run() {
+            output.push(v);
+        }
+        output
+    }
+}
+
+impl FromStr for Intcode {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, ParseIntError> {
+        Intcode::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn run(program: &str, inputs: &[i64]) -> Vec<i64> {
+        Intcode::parse(program).unwrap().run_with(inputs.iter().copied())
+    }
+
+    #[test]
+    fn day2() {
+        let mut machine = Intcode::parse("1,9,10,3,2,3,11,0,99,30,40,50")
+            .unwrap();
+        assert_eq!(machine.run(), Event::Halted);
+        assert_eq!(machine.peek(0), 3500);
+        assert_eq!(machine.peek(3), 70);
+        assert!(machine.is_halted());
+        assert_eq!(machine.run(), Event::Halted);
+
+        for (program, addr, value) in [
+            ("1,0,0,0,99", 0, 2),
+            ("2,3,0,3,99", 3, 6),
+            ("2,4,4,5,99,0", 5, 9801),
+            ("1,1,1,4,99,5,6,0,99", 0, 30),
+        ] {
+            let mut machine = Intcode::parse(program).unwrap();
+            machine.run();
+            assert_eq!(machine.peek(addr), value, "{}", program);
+        }
+    }
+
+    #[test]
+    fn day5() {
+        let mut machine = Intcode::parse("1002,4,3,4,33").unwrap();
+        assert_eq!(machine.run(), Event::Halted);
+        assert_eq!(machine.peek(4), 99);
+        assert_eq!(run("3,0,4,0,99", &[-17]), [-17]);
+
+        // Compare with 8 in each mode.
+        for program in [
+            "3,9,8,9,10,9,4,9,99,-1,8",
+            "3,3,1108,-1,8,3,4,3,99",
+        ] {
+            assert_eq!(run(program, &[8]), [1]);
+            assert_eq!(run(program, &[7]), [0]);
+        }
+        for program in [
+            "3,9,7,9,10,9,4,9,99,-1,8",
+            "3,3,1107,-1,8,3,4,3,99",
+        ] {
+            assert_eq!(run(program, &[7]), [1]);
+            assert_eq!(run(program, &[8]), [0]);
+        }
+        // Jumps.
+        for program in [
+            "3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9",
+            "3,3,1105,-1,9,1101,0,0,12,4,12,99,1",
+        ] {
+            assert_eq!(run(program, &[0]), [0]);
+            assert_eq!(run(program, &[5]), [1]);
+        }
+        let larger = "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,\
+                      1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,\
+                      999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99";
+        assert_eq!(run(larger, &[7]), [999]);
+        assert_eq!(run(larger, &[8]), [1000]);
+        assert_eq!(run(larger, &[9]), [1001]);
+    }
+
+    #[test]
+    fn day9() {
+        let quine =
+            "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99";
+        let expected: Vec<i64> =
+            quine.split(',').map(|v| v.parse().unwrap()).collect();
+        assert_eq!(run(quine, &[]), expected);
+
+        let out = run("1102,34915192,34915192,7,4,7,99,0", &[]);
+        assert_eq!(out[0].to_string().len(), 16);
+        assert_eq!(run("104,1125899906842624,99", &[]), [1125899906842624]);
+
+        // Relative-mode writes and reads.
+        assert_eq!(run("109,203,203,-3,204,-3,99", &[55]), [55]);
+        let mut machine = Intcode::parse("109,203,203,-3,99").unwrap();
+        assert_eq!(machine.run(), Event::NeedsInput);
+        machine.push_input(-4);
+        assert_eq!(machine.run(), Event::Halted);
+        assert_eq!(machine.peek(200), -4);
+        assert_eq!(machine.peek(10_000), 0);
+    }
+
+    #[test]
+    fn cloned_state() {
+        // Count up from an input, forever.
+        let mut machine: Intcode =
+            "3,100,4,100,1001,100,1,100,1105,1,2".parse().unwrap();
+        machine.push_input(10);
+        assert_eq!(machine.run(), Event::Output(10));
+        let mut copy = machine.clone();
+        assert_eq!(machine.run(), Event::Output(11));
+        assert_eq!(machine.run(), Event::Output(12));
+        assert_eq!(copy.run(), Event::Output(11));
+        copy.poke(100, -1);
+        assert_eq!(copy.run(), Event::Output(0));
+        assert_ne!(copy, machine);
+        assert!(Intcode::parse("1,x,3").is_err());
+    }
+}
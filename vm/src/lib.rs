@@ -0,0 +1,19 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! Virtual machines for Advent of Code solutions.
+//!
+//! Many puzzles ask for an interpreter for some tiny
+//! assembly language. The interpreter loop, registers and
+//! operand parsing are much the same each time, so they are
+//! here, leaving only the instructions to write. The
+//! Intcode computer of 2019, used on many days, is here
+//! whole.
+
+mod intcode;
+pub use intcode::*;
+
+mod machine;
+pub use machine::*;
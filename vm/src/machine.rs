@@ -0,0 +1,480 @@
+// Copyright © 2026 Bart Massey
+// This program is licensed under the "MIT License".
+// Please see the file LICENSE in this distribution
+// for license terms.
+
+//! A register machine for running puzzle programs.
+//!
+//! Implement `Instruction` for the puzzle's instructions,
+//! saying how each changes the machine and where control
+//! goes next, and a `Machine` does the rest: program
+//! counter, registers, output, step limits and loop
+//! detection.
+//!
+//! # Examples
+//!
+//! Assembunny (2016 day 12).
+//!
+//! ```
+//! # use vm::*;
+//! #[derive(Clone)]
+//! enum Insn {
+//!     Cpy(Operand, Reg),
+//!     Inc(Reg),
+//!     Dec(Reg),
+//!     Jnz(Operand, Operand),
+//! }
+//!
+//! impl Instruction for Insn {
+//!     fn execute(&self, m: &mut Machine<Self>) -> Flow {
+//!         match *self {
+//!             Insn::Cpy(x, r) => m.set_reg(r, m.value(x)),
+//!             Insn::Inc(r) => *m.reg_mut(r) += 1,
+//!             Insn::Dec(r) => *m.reg_mut(r) -= 1,
+//!             Insn::Jnz(x, y) if m.value(x) != 0 => {
+//!                 return Flow::Jump(m.value(y));
+//!             }
+//!             Insn::Jnz(..) => (),
+//!         }
+//!         Flow::Continue
+//!     }
+//! }
+//!
+//! let source = "cpy 41 a\ninc a\ninc a\ndec a\njnz a 2\ndec a";
+//! let program = source
+//!     .lines()
+//!     .map(|line| {
+//!         let words: Vec<&str> = line.split(' ').collect();
+//!         let operand = |i: usize| Operand::parse(words[i]).unwrap();
+//!         let reg = |i: usize| operand(i).reg().unwrap();
+//!         match words[0] {
+//!             "cpy" => Insn::Cpy(operand(1), reg(2)),
+//!             "inc" => Insn::Inc(reg(1)),
+//!             "dec" => Insn::Dec(reg(1)),
+//!             "jnz" => Insn::Jnz(operand(1), operand(2)),
+//!             w => panic!("unknown instruction {}", w),
+//!         }
+//!     })
+//!     .collect();
+//! let mut machine = Machine::new(program);
+//! assert_eq!(machine.run(1000), Stop::Halted);
+//! assert_eq!(machine.reg('a'), 42);
+//! ```
+
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::Hash;
+use std::str::FromStr;
+
+/// A register, by number. Registers named by lowercase
+/// letters are numbered from 0 for `a`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Reg(pub usize);
+
+impl Reg {
+    /// The register named by `name`, if it is a lowercase
+    /// letter.
+    pub fn named(name: char) -> Option<Reg> {
+        name.is_ascii_lowercase()
+            .then(|| Reg((name as u8 - b'a') as usize))
+    }
+}
+
+impl From<usize> for Reg {
+    fn from(n: usize) -> Reg {
+        Reg(n)
+    }
+}
+
+/// # Panics
+///
+/// Panics if the name is not a lowercase letter.
+impl From<char> for Reg {
+    fn from(name: char) -> Reg {
+        Reg::named(name).expect("register name not a lowercase letter")
+    }
+}
+
+/// An instruction operand: a register or an immediate
+/// value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operand {
+    /// The value of a register.
+    Reg(Reg),
+    /// The given value.
+    Imm(i64),
+}
+
+/// Error in parsing an operand: the text that could not be
+/// parsed is given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperandError(pub String);
+
+impl fmt::Display for OperandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "bad operand {:?}", self.0)
+    }
+}
+
+impl std::error::Error for OperandError {}
+
+impl Operand {
+    /// Parse an operand: a lowercase letter naming a
+    /// register, or a decimal integer, possibly signed.
+    pub fn parse(s: &str) -> Result<Operand, OperandError> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            if let Some(r) = Reg::named(c) {
+                return Ok(Operand::Reg(r));
+            }
+        }
+        s.parse()
+            .map(Operand::Imm)
+            .map_err(|_| OperandError(s.to_string()))
+    }
+
+    /// The register of a register operand.
+    pub fn reg(self) -> Option<Reg> {
+        match self {
+            Operand::Reg(r) => Some(r),
+            Operand::Imm(_) => None,
+        }
+    }
+}
+
+impl FromStr for Operand {
+    type Err = OperandError;
+
+    fn from_str(s: &str) -> Result<Operand, OperandError> {
+        Operand::parse(s)
+    }
+}
+
+/// Where control goes after an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    /// On to the next instruction.
+    Continue,
+    /// Jump by the given offset from this instruction.
+    Jump(i64),
+    /// Stop the machine.
+    Halt,
+    /// Output the given value, then on to the next
+    /// instruction.
+    Output(i64),
+}
+
+/// Why a run of the machine stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stop {
+    /// An instruction halted, or control left the program.
+    Halted,
+    /// The limit on the number of steps was reached.
+    StepLimit,
+    /// The machine was about to repeat a state.
+    Loop,
+}
+
+/// An instruction of a machine. Instructions are cloned
+/// to execute them, so that they may change the program.
+pub trait Instruction: Clone {
+    /// Carry out this instruction on `machine`, saying where
+    /// control goes next. The program counter still points
+    /// at this instruction.
+    fn execute(&self, machine: &mut Machine<Self>) -> Flow;
+}
+
+/// A machine running a program of instructions `I`, with
+/// registers holding `i64`s.
+#[derive(Debug, Clone)]
+pub struct Machine<I> {
+    program: Vec<I>,
+    pc: i64,
+    regs: Vec<i64>,
+    output: Vec<i64>,
+    steps: usize,
+    halted: bool,
+}
+
+impl<I> Machine<I> {
+    /// Make a machine ready to run `program` from the start,
+    /// with all registers 0.
+    pub fn new(program: Vec<I>) -> Self {
+        Machine {
+            program,
+            pc: 0,
+            regs: Vec::new(),
+            output: Vec::new(),
+            steps: 0,
+            halted: false,
+        }
+    }
+
+    /// The program counter.
+    pub fn pc(&self) -> i64 {
+        self.pc
+    }
+
+    /// Move the program counter.
+    pub fn set_pc(&mut self, pc: i64) {
+        self.pc = pc;
+    }
+
+    /// The program.
+    pub fn program(&self) -> &[I] {
+        &self.program
+    }
+
+    /// The program, for changing.
+    pub fn program_mut(&mut self) -> &mut [I] {
+        &mut self.program
+    }
+
+    /// The value of a register. Registers not yet set are
+    /// 0.
+    pub fn reg<R: Into<Reg>>(&self, r: R) -> i64 {
+        self.regs.get(r.into().0).copied().unwrap_or(0)
+    }
+
+    /// Set a register.
+    pub fn set_reg<R: Into<Reg>>(&mut self, r: R, value: i64) {
+        *self.reg_mut(r) = value;
+    }
+
+    /// A register, for changing.
+    pub fn reg_mut<R: Into<Reg>>(&mut self, r: R) -> &mut i64 {
+        let Reg(i) = r.into();
+        if i >= self.regs.len() {
+            self.regs.resize(i + 1, 0);
+        }
+        &mut self.regs[i]
+    }
+
+    /// The value of an operand.
+    pub fn value(&self, op: Operand) -> i64 {
+        match op {
+            Operand::Reg(r) => self.reg(r),
+            Operand::Imm(v) => v,
+        }
+    }
+
+    /// The values output so far.
+    pub fn output(&self) -> &[i64] {
+        &self.output
+    }
+
+    /// Remove and return the values output so far.
+    pub fn take_output(&mut self) -> Vec<i64> {
+        std::mem::take(&mut self.output)
+    }
+
+    /// Number of instructions executed.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// True if an instruction has halted the machine or
+    /// control has left the program.
+    pub fn is_halted(&self) -> bool {
+        self.halted || self.pc < 0 || self.pc >= self.program.len() as i64
+    }
+}
+
+impl<I: Instruction> Machine<I> {
+    /// Execute one instruction, returning where control went.
+    /// A halted machine does nothing and returns
+    /// `Flow::Halt`.
+    pub fn step(&mut self) -> Flow {
+        if self.is_halted() {
+            return Flow::Halt;
+        }
+        let insn = self.program[self.pc as usize].clone();
+        let flow = insn.execute(self);
+        self.steps += 1;
+        match flow {
+            Flow::Continue => self.pc += 1,
+            Flow::Jump(offset) => self.pc += offset,
+            Flow::Halt => self.halted = true,
+            Flow::Output(v) => {
+                self.output.push(v);
+                self.pc += 1;
+            }
+        }
+        flow
+    }
+
+    /// Run until the machine halts, or `limit` instructions
+    /// have been executed.
+    pub fn run(&mut self, limit: usize) -> Stop {
+        for _ in 0..limit {
+            if self.step() == Flow::Halt {
+                return Stop::Halted;
+            }
+        }
+        if self.is_halted() {
+            Stop::Halted
+        } else {
+            Stop::StepLimit
+        }
+    }
+
+    /// Run as for `run()`, but stop with `Stop::Loop` before
+    /// executing an instruction when the machine's state, as
+    /// given by `state`, has been seen before. The state
+    /// might be just the program counter, or that and some
+    /// registers.
+    pub fn run_until_loop<K, F>(&mut self, limit: usize, state: F) -> Stop
+    where
+        K: Eq + Hash,
+        F: Fn(&Machine<I>) -> K,
+    {
+        let mut seen = HashSet::new();
+        for _ in 0..limit {
+            if self.is_halted() {
+                return Stop::Halted;
+            }
+            if !seen.insert(state(self)) {
+                return Stop::Loop;
+            }
+            self.step();
+        }
+        if self.is_halted() {
+            Stop::Halted
+        } else {
+            Stop::StepLimit
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    /// The handheld console (2020 day 8).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Console {
+        Acc(i64),
+        Jmp(i64),
+        Nop(i64),
+    }
+
+    impl Instruction for Console {
+        fn execute(&self, m: &mut Machine<Self>) -> Flow {
+            match *self {
+                Console::Acc(n) => *m.reg_mut('a') += n,
+                Console::Jmp(n) => return Flow::Jump(n),
+                Console::Nop(_) => (),
+            }
+            Flow::Continue
+        }
+    }
+
+    fn console(source: &str) -> Vec<Console> {
+        source
+            .lines()
+            .map(|line| {
+                let (op, arg) = line.split_once(' ').unwrap();
+                let Operand::Imm(n) = Operand::parse(arg).unwrap() else {
+                    panic!("register operand");
+                };
+                match op {
+                    "acc" => Console::Acc(n),
+                    "jmp" => Console::Jmp(n),
+                    "nop" => Console::Nop(n),
+                    _ => panic!("unknown instruction {}", op),
+                }
+            })
+            .collect()
+    }
+
+    const BOOT: &str = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\n\
+                        acc -99\nacc +1\njmp -4\nacc +6";
+
+    #[test]
+    fn boot_loop() {
+        let program = console(BOOT);
+        let mut machine = Machine::new(program.clone());
+        let stop = machine.run_until_loop(1000, Machine::pc);
+        assert_eq!(stop, Stop::Loop);
+        assert_eq!(machine.reg('a'), 5);
+        assert_eq!(machine.pc(), 1);
+
+        // Swap one jmp or nop so that the program ends.
+        let fixed: Vec<(usize, i64)> = (0..program.len())
+            .filter_map(|i| {
+                let mut machine = Machine::new(program.clone());
+                let insn = &mut machine.program_mut()[i];
+                *insn = match *insn {
+                    Console::Jmp(n) => Console::Nop(n),
+                    Console::Nop(n) => Console::Jmp(n),
+                    Console::Acc(_) => return None,
+                };
+                let stop = machine.run_until_loop(1000, Machine::pc);
+                (stop == Stop::Halted).then(|| (i, machine.reg('a')))
+            })
+            .collect();
+        assert_eq!(fixed, [(7, 8)]);
+
+        let mut machine = Machine::new(program);
+        assert_eq!(machine.run(10), Stop::StepLimit);
+        assert_eq!(machine.steps(), 10);
+    }
+
+    #[derive(Clone)]
+    enum Out {
+        Emit(Operand),
+        Add(Reg, Operand),
+        Stop,
+    }
+
+    impl Instruction for Out {
+        fn execute(&self, m: &mut Machine<Self>) -> Flow {
+            match *self {
+                Out::Emit(x) => Flow::Output(m.value(x)),
+                Out::Add(r, x) => {
+                    *m.reg_mut(r) += m.value(x);
+                    Flow::Continue
+                }
+                Out::Stop => Flow::Halt,
+            }
+        }
+    }
+
+    #[test]
+    fn output_and_halt() {
+        let b = Operand::Reg(Reg(1));
+        let program = vec![
+            Out::Add(Reg(1), Operand::Imm(3)),
+            Out::Emit(b),
+            Out::Emit(Operand::Imm(-7)),
+            Out::Stop,
+            Out::Emit(b),
+        ];
+        let mut machine = Machine::new(program);
+        assert_eq!(machine.step(), Flow::Continue);
+        assert_eq!(machine.step(), Flow::Output(3));
+        assert_eq!(machine.run(100), Stop::Halted);
+        assert_eq!(machine.take_output(), [3, -7]);
+        assert!(machine.output().is_empty());
+        assert_eq!(machine.step(), Flow::Halt);
+        assert_eq!(machine.steps(), 4);
+        assert_eq!(machine.reg('b'), 3);
+        assert_eq!(machine.reg(Reg(25)), 0);
+    }
+
+    #[test]
+    fn operands() {
+        assert_eq!(Operand::parse("c"), Ok(Operand::Reg(Reg(2))));
+        assert_eq!("-12".parse(), Ok(Operand::Imm(-12)));
+        assert_eq!(Operand::parse("+4"), Ok(Operand::Imm(4)));
+        assert_eq!(
+            Operand::parse("ab"),
+            Err(OperandError("ab".to_string())),
+        );
+        assert!(Operand::parse("A").is_err());
+        assert!(Operand::parse("").is_err());
+        assert_eq!(Reg::named('z'), Some(Reg(25)));
+        assert_eq!(Reg::named('!'), None);
+    }
+}